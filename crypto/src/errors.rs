@@ -10,23 +10,7 @@ pub enum SrsFromFileError {
 
 impl From<lambdaworks_math::errors::DeserializationError> for SrsFromFileError {
     fn from(err: DeserializationError) -> SrsFromFileError {
-        match err {
-            DeserializationError::InvalidAmountOfBytes => {
-                SrsFromFileError::DeserializationError(DeserializationError::InvalidAmountOfBytes)
-            }
-
-            DeserializationError::FieldFromBytesError => {
-                SrsFromFileError::DeserializationError(DeserializationError::FieldFromBytesError)
-            }
-
-            DeserializationError::PointerSizeError => {
-                SrsFromFileError::DeserializationError(DeserializationError::PointerSizeError)
-            }
-
-            DeserializationError::InvalidValue => {
-                SrsFromFileError::DeserializationError(DeserializationError::InvalidValue)
-            }
-        }
+        SrsFromFileError::DeserializationError(err)
     }
 }
 
@@ -0,0 +1,185 @@
+use lambdaworks_math::{
+    errors::ByteConversionError,
+    field::{
+        element::FieldElement,
+        fields::fft_friendly::{
+            babybear::Babybear31PrimeField, stark_252_prime_field::Stark252PrimeField,
+        },
+        traits::IsPrimeField,
+    },
+    traits::ByteConversion,
+};
+
+use super::{parameters::Parameters, Poseidon};
+
+/// Prime fields with a Poseidon instance suitable for Merkle trees and Fiat-Shamir
+/// transcripts. A digest is the output of `Poseidon::hash_elements`, that is `capacity`
+/// field elements, and is encoded in 32 bytes.
+pub trait IsPoseidonField: IsPrimeField {
+    fn poseidon() -> Poseidon<Self>;
+
+    fn digest_to_bytes(digest: &[FieldElement<Self>]) -> [u8; 32];
+
+    /// Returns an error if `bytes` is not the canonical encoding of a digest, so that every
+    /// digest has a single encoding.
+    fn digest_from_bytes(bytes: &[u8; 32]) -> Result<Vec<FieldElement<Self>>, ByteConversionError>;
+}
+
+/// Starknet's Poseidon, with width 3 and rate 2. Digests are a single element.
+impl IsPoseidonField for Stark252PrimeField {
+    fn poseidon() -> Poseidon<Self> {
+        Poseidon::new_with_params(
+            Parameters::with_starknet_t3()
+                .expect("Error loading parameters for Poseidon Stark252 hasher"),
+        )
+    }
+
+    fn digest_to_bytes(digest: &[FieldElement<Self>]) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&digest[0].to_bytes_be());
+        bytes
+    }
+
+    fn digest_from_bytes(bytes: &[u8; 32]) -> Result<Vec<FieldElement<Self>>, ByteConversionError> {
+        let element = FieldElement::<Self>::from_bytes_be(bytes)?;
+        // `from_bytes_be` reduces the value, so encodings of values above p do not round trip.
+        if &element.to_bytes_be() != bytes {
+            return Err(ByteConversionError::InvalidValue);
+        }
+        Ok(vec![element])
+    }
+}
+
+/// Plonky3's Poseidon2 with width 16 and rate 8. Digests are 8 elements, each encoded
+/// in 4 bytes.
+impl IsPoseidonField for Babybear31PrimeField {
+    fn poseidon() -> Poseidon<Self> {
+        Poseidon::new_with_params(
+            Parameters::with_poseidon2_t16()
+                .expect("Error loading parameters for Poseidon2 Babybear hasher"),
+        )
+    }
+
+    fn digest_to_bytes(digest: &[FieldElement<Self>]) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        for (chunk, element) in bytes.chunks_mut(4).zip(digest) {
+            let value = element.representative().limbs[0] as u32;
+            chunk.copy_from_slice(&value.to_be_bytes());
+        }
+        bytes
+    }
+
+    fn digest_from_bytes(bytes: &[u8; 32]) -> Result<Vec<FieldElement<Self>>, ByteConversionError> {
+        bytes
+            .chunks(4)
+            .map(|chunk| {
+                let mut word = [0u8; 4];
+                word.copy_from_slice(chunk);
+                let value = u32::from_be_bytes(word) as u64;
+                let element = FieldElement::<Self>::from(value);
+                if element.representative().limbs[0] != value {
+                    return Err(ByteConversionError::InvalidValue);
+                }
+                Ok(element)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test vector of `hades_permutation` in cairo-lang.
+    #[test]
+    fn stark252_permutation_matches_starknet() {
+        let mut state = [9, 11, 2].map(FieldElement::<Stark252PrimeField>::from);
+        Stark252PrimeField::poseidon().permute(&mut state);
+
+        let expected = [
+            "0x510f3a3faf4084e3b1e95fd44c30746271b48723f7ea9c8be6a9b6b5408e7e6",
+            "0x4f511749bd4101266904288021211333fb0a514cb15381af087462fa46e6bd9",
+            "0x186f6dd1a6e79cb1b66d505574c349272cd35c07c223351a0990410798bb9d8",
+        ]
+        .map(|hex| FieldElement::from_hex(hex).unwrap());
+        assert_eq!(state, expected);
+    }
+
+    #[test]
+    fn stark252_hash_elements_is_starknet_poseidon_hash_many() {
+        let poseidon = Stark252PrimeField::poseidon();
+        let inputs = [1, 2, 3].map(FieldElement::<Stark252PrimeField>::from);
+
+        let mut state = [inputs[0], inputs[1], FieldElement::zero()];
+        poseidon.permute(&mut state);
+        state[0] += inputs[2];
+        state[1] += FieldElement::one();
+        poseidon.permute(&mut state);
+
+        assert_eq!(poseidon.hash_elements(&inputs), vec![state[0]]);
+    }
+
+    // Test vector of `test_default_babybear_poseidon2_width_16` in p3-baby-bear 0.8.0.
+    #[test]
+    fn babybear_permutation_matches_plonky3_poseidon2() {
+        let mut state = [
+            894848333, 1437655012, 1200606629, 1690012884, 71131202, 1749206695, 1717947831,
+            120589055, 19776022, 42382981, 1831865506, 724844064, 171220207, 1299207443, 227047920,
+            1783754913,
+        ]
+        .map(FieldElement::<Babybear31PrimeField>::from);
+        Babybear31PrimeField::poseidon().permute(&mut state);
+
+        let expected = [
+            516096821, 90309867, 1101817252, 1660784290, 360715097, 1789519026, 1788910906,
+            563338433, 319524748, 1741414159, 1650859320, 894311162, 1121347488, 1692793758,
+            1052633829, 1344246938,
+        ]
+        .map(FieldElement::from);
+        assert_eq!(state, expected);
+    }
+
+    #[test]
+    fn stark252_digest_round_trips_through_bytes() {
+        let digest = Stark252PrimeField::poseidon()
+            .hash_elements(&[FieldElement::from(1), FieldElement::from(2)]);
+        assert_eq!(digest.len(), 1);
+        let bytes = Stark252PrimeField::digest_to_bytes(&digest);
+        assert_eq!(
+            Stark252PrimeField::digest_from_bytes(&bytes).unwrap(),
+            digest
+        );
+    }
+
+    #[test]
+    fn babybear_digest_round_trips_through_bytes() {
+        let inputs: Vec<_> = (0..11).map(FieldElement::from).collect();
+        let digest = Babybear31PrimeField::poseidon().hash_elements(&inputs);
+        assert_eq!(digest.len(), 8);
+        let bytes = Babybear31PrimeField::digest_to_bytes(&digest);
+        assert_eq!(
+            Babybear31PrimeField::digest_from_bytes(&bytes).unwrap(),
+            digest
+        );
+    }
+
+    #[test]
+    fn inputs_differing_in_trailing_zeros_have_different_digests() {
+        let poseidon = Babybear31PrimeField::poseidon();
+        let digest = poseidon.hash_elements(&[FieldElement::from(5)]);
+        let padded_digest = poseidon.hash_elements(&[FieldElement::from(5), FieldElement::zero()]);
+        assert_ne!(digest, padded_digest);
+    }
+
+    #[test]
+    fn non_canonical_digests_are_rejected() {
+        assert_eq!(
+            Stark252PrimeField::digest_from_bytes(&[0xff; 32]),
+            Err(ByteConversionError::InvalidValue)
+        );
+        assert_eq!(
+            Babybear31PrimeField::digest_from_bytes(&[0xff; 32]),
+            Err(ByteConversionError::InvalidValue)
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use crate::merkle_tree::traits::IsMerkleTreeBackend;
 
 /// Poseidon implementation for curve BLS12381
-use self::parameters::Parameters;
+use self::parameters::{LinearLayer, Parameters};
 
 use lambdaworks_math::{
     elliptic_curve::short_weierstrass::curves::bls12_381::field_extension::BLS12381PrimeField,
@@ -9,8 +9,10 @@ use lambdaworks_math::{
 };
 use std::ops::{Add, Mul};
 
-mod parameters;
+pub mod instances;
+pub mod parameters;
 
+#[derive(Clone)]
 pub struct Poseidon<F: IsField> {
     params: Parameters<F>,
 }
@@ -76,40 +78,106 @@ where
         }
     }
 
-    pub fn sbox(&self, state: &mut [FieldElement<F>], round_number: usize) {
-        let is_full_round = round_number < self.params.n_full_rounds / 2
-            || round_number >= self.params.n_full_rounds / 2 + self.params.n_partial_rounds;
+    fn is_full_round(&self, round_number: usize) -> bool {
+        round_number < self.params.n_full_rounds / 2
+            || round_number >= self.params.n_full_rounds / 2 + self.params.n_partial_rounds
+    }
 
-        if is_full_round {
+    pub fn sbox(&self, state: &mut [FieldElement<F>], round_number: usize) {
+        if self.is_full_round(round_number) {
             // full s-box
             for current_state in state.iter_mut() {
                 *current_state = current_state.pow(self.params.alpha);
             }
         } else {
             // partial s-box
-            let last_state_index = state.len() - 1;
-            state[last_state_index] = state[last_state_index].pow(self.params.alpha);
+            let index = match self.params.linear_layer {
+                LinearLayer::Mds(_) => state.len() - 1,
+                LinearLayer::Poseidon2 { .. } => 0,
+            };
+            state[index] = state[index].pow(self.params.alpha);
         }
     }
 
+    /// Applies the linear layer of the full rounds.
     pub fn mix(&self, state: &mut [FieldElement<F>]) {
-        let mut new_state: Vec<FieldElement<F>> = Vec::with_capacity(state.len());
-        for i in 0..state.len() {
-            new_state.push(FieldElement::zero());
-            for (j, current_state) in state.iter().enumerate() {
-                let mut mij = self.params.mds_matrix[i][j].clone();
-                mij = mij.mul(current_state);
-                new_state[i] = new_state[i].clone().add(&mij);
+        match &self.params.linear_layer {
+            LinearLayer::Mds(mds_matrix) => {
+                let mut new_state: Vec<FieldElement<F>> = Vec::with_capacity(state.len());
+                for i in 0..state.len() {
+                    new_state.push(FieldElement::zero());
+                    for (j, current_state) in state.iter().enumerate() {
+                        let mut mij = mds_matrix[i][j].clone();
+                        mij = mij.mul(current_state);
+                        new_state[i] = new_state[i].clone().add(&mij);
+                    }
+                }
+                state.clone_from_slice(&new_state[0..state.len()]);
             }
+            LinearLayer::Poseidon2 { .. } => Self::external_mix(state),
         }
-        state.clone_from_slice(&new_state[0..state.len()]);
     }
 
-    fn permute(&self, state: &mut [FieldElement<F>]) {
-        for i in 0..(self.params.n_full_rounds + self.params.n_partial_rounds) {
-            self.ark(state, i);
-            self.sbox(state, i);
-            self.mix(state);
+    /// Multiplies each chunk of 4 elements by circ(2, 3, 1, 1), whose `i`-th row is the
+    /// sum of the chunk plus `x_i + 2 x_{i + 1}`, and then adds to each element the sum of
+    /// the elements at the same position of all the chunks.
+    fn external_mix(state: &mut [FieldElement<F>]) {
+        for chunk in state.chunks_exact_mut(4) {
+            let sum = &chunk[0] + &chunk[1] + &chunk[2] + &chunk[3];
+            let new_chunk: Vec<_> = (0..4)
+                .map(|i| {
+                    let next = &chunk[(i + 1) % 4];
+                    &sum + &chunk[i] + next + next
+                })
+                .collect();
+            chunk.clone_from_slice(&new_chunk);
+        }
+        let sums: Vec<FieldElement<F>> = (0..4)
+            .map(|i| {
+                state
+                    .iter()
+                    .skip(i)
+                    .step_by(4)
+                    .fold(FieldElement::zero(), |acc, x| acc + x)
+            })
+            .collect();
+        for (i, element) in state.iter_mut().enumerate() {
+            *element += sums[i % 4].clone();
+        }
+    }
+
+    /// Multiplies the state by `J + diag(internal_diagonal)`.
+    fn internal_mix(state: &mut [FieldElement<F>], internal_diagonal: &[FieldElement<F>]) {
+        let sum = state
+            .iter()
+            .fold(FieldElement::<F>::zero(), |acc, x| acc + x);
+        for (element, diagonal) in state.iter_mut().zip(internal_diagonal) {
+            *element = &*element * diagonal + &sum;
+        }
+    }
+
+    pub fn permute(&self, state: &mut [FieldElement<F>]) {
+        let n_rounds = self.params.n_full_rounds + self.params.n_partial_rounds;
+        match &self.params.linear_layer {
+            LinearLayer::Mds(_) => {
+                for i in 0..n_rounds {
+                    self.ark(state, i);
+                    self.sbox(state, i);
+                    self.mix(state);
+                }
+            }
+            LinearLayer::Poseidon2 { internal_diagonal } => {
+                self.mix(state);
+                for i in 0..n_rounds {
+                    self.ark(state, i);
+                    self.sbox(state, i);
+                    if self.is_full_round(i) {
+                        self.mix(state);
+                    } else {
+                        Self::internal_mix(state, internal_diagonal);
+                    }
+                }
+            }
         }
     }
 
@@ -159,6 +227,32 @@ where
 
         Ok(result)
     }
+
+    /// Sponge hash of any number of field elements, returning the first `capacity`
+    /// elements of the state. The input is padded with a one and then zeros up to a
+    /// multiple of `rate`, and absorbed by adding it `rate` elements at a time to the
+    /// state. With the Starknet parameters, this is Starknet's `poseidon_hash_many`.
+    pub fn hash_elements(&self, inputs: &[FieldElement<F>]) -> Vec<FieldElement<F>> {
+        let rate = self.params.rate;
+        let mut state = vec![FieldElement::zero(); rate + self.params.capacity];
+
+        let mut padded_inputs = inputs.to_vec();
+        padded_inputs.push(FieldElement::one());
+        padded_inputs.resize(
+            padded_inputs.len().next_multiple_of(rate),
+            FieldElement::zero(),
+        );
+
+        for chunk in padded_inputs.chunks(rate) {
+            for (state_element, input) in state.iter_mut().zip(chunk) {
+                *state_element += input.clone();
+            }
+            self.permute(&mut state);
+        }
+
+        state.truncate(self.params.capacity);
+        state
+    }
 }
 
 // Test values and parameters are taken from https://github.com/keep-starknet-strange/poseidon-rs/blob/f01ff35ab4dca63a9d6feb7ff3f46c9b04b28b04/src/permutation.rs#L136
@@ -211,7 +305,7 @@ mod tests {
             n_full_rounds: 8,
             n_partial_rounds: 83,
             round_constants,
-            mds_matrix,
+            linear_layer: LinearLayer::Mds(mds_matrix),
         })
     }
 
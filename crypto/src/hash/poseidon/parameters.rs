@@ -1,10 +1,32 @@
 use lambdaworks_math::{
     elliptic_curve::short_weierstrass::curves::bls12_381::field_extension::BLS12381PrimeField,
-    field::{element::FieldElement, traits::IsField},
+    field::{
+        element::FieldElement,
+        fields::fft_friendly::{
+            babybear::Babybear31PrimeField, stark_252_prime_field::Stark252PrimeField,
+        },
+        traits::{IsField, IsPrimeField},
+    },
 };
 
 type PoseidonConstants<F> = (Vec<FieldElement<F>>, Vec<Vec<FieldElement<F>>>);
 
+/// The linear layers of the permutation.
+#[derive(Clone)]
+pub enum LinearLayer<F: IsField> {
+    /// Poseidon: every round multiplies the state by this MDS matrix, and the S-box of the
+    /// partial rounds is applied to the last element.
+    Mds(Vec<Vec<FieldElement<F>>>),
+    /// Poseidon2 (https://eprint.iacr.org/2023/323), for widths multiple of 4: the full
+    /// rounds, and the permutation beforehand, multiply the state by the matrix built from
+    /// circ(2, 3, 1, 1), the partial rounds by `J + diag(internal_diagonal)`, with `J` the
+    /// all ones matrix. The S-box of the partial rounds is applied to the first element.
+    Poseidon2 {
+        internal_diagonal: Vec<FieldElement<F>>,
+    },
+}
+
+#[derive(Clone)]
 pub struct Parameters<F: IsField> {
     pub rate: usize,
     pub capacity: usize,
@@ -12,7 +34,7 @@ pub struct Parameters<F: IsField> {
     pub n_full_rounds: usize,
     pub n_partial_rounds: usize,
     pub round_constants: Vec<FieldElement<F>>,
-    pub mds_matrix: Vec<Vec<FieldElement<F>>>,
+    pub linear_layer: LinearLayer<F>,
 }
 
 /// Implements hashing for BLS 12381's field.
@@ -33,7 +55,7 @@ impl Parameters<BLS12381PrimeField> {
             n_full_rounds: 8,
             n_partial_rounds: 56,
             round_constants,
-            mds_matrix,
+            linear_layer: LinearLayer::Mds(mds_matrix),
         })
     }
 
@@ -53,30 +75,114 @@ impl Parameters<BLS12381PrimeField> {
             n_full_rounds: 8,
             n_partial_rounds: 56,
             round_constants,
-            mds_matrix,
+            linear_layer: LinearLayer::Mds(mds_matrix),
         })
     }
+}
 
-    pub fn parse(
-        round_constants_csv: &str,
-        mds_constants_csv: &str,
-    ) -> Result<PoseidonConstants<BLS12381PrimeField>, String> {
-        let round_constants = round_constants_csv
-            .split(',')
-            .map(|c| FieldElement::<BLS12381PrimeField>::new_base(c.trim()))
-            .collect();
+/// Starknet's Poseidon, with `alpha = 3`, 8 full rounds and 83 partial rounds.
+/// The round constants are `sha256("Hades{i}") mod p`, as generated by
+/// `poseidon_params.py` in cairo-lang, and the MDS matrix is
+/// [[3, 1, 1], [1, -1, 1], [1, 1, -2]].
+impl Parameters<Stark252PrimeField> {
+    pub fn with_starknet_t3() -> Result<Self, String> {
+        let round_constants_csv = include_str!("stark252/t3/round_constants.csv");
+        let mds_constants_csv = include_str!("stark252/t3/mds_matrix.csv");
+
+        let (round_constants, mds_matrix) = Self::parse(round_constants_csv, mds_constants_csv)?;
+
+        Ok(Parameters {
+            rate: 2,
+            capacity: 1,
+            alpha: 3,
+            n_full_rounds: 8,
+            n_partial_rounds: 83,
+            round_constants,
+            linear_layer: LinearLayer::Mds(mds_matrix),
+        })
+    }
+}
 
-        let mut mds_matrix = vec![];
+/// Plonky3's Poseidon2 for BabyBear with width 16, `alpha = 7`, 8 full rounds and 13
+/// partial rounds. The constants are `BABYBEAR_POSEIDON2_RC_16_EXTERNAL_INITIAL`,
+/// `BABYBEAR_POSEIDON2_RC_16_EXTERNAL_FINAL` and `BABYBEAR_POSEIDON2_RC_16_INTERNAL` of
+/// `p3-baby-bear` 0.8.0.
+impl Parameters<Babybear31PrimeField> {
+    pub fn with_poseidon2_t16() -> Result<Self, String> {
+        let external_constants_csv = include_str!("babybear/t16/external_round_constants.csv");
+        let internal_constants_csv = include_str!("babybear/t16/internal_round_constants.csv");
 
-        for line in mds_constants_csv.lines() {
-            let matrix_line = line
-                .split(',')
-                .map(|c| FieldElement::<BLS12381PrimeField>::new_base(c.trim()))
-                .collect();
+        let external_constants = Self::parse_line(&external_constants_csv.replace('\n', ","))?;
+        let internal_constants = Self::parse_line(internal_constants_csv)?;
+        let width = 16;
+        let half_full_rounds = external_constants.len() / (2 * width);
 
-            mds_matrix.push(matrix_line);
+        // The partial rounds only add a constant to the first element, so the other
+        // constants of those rounds are zero.
+        let (initial_constants, final_constants) =
+            external_constants.split_at(half_full_rounds * width);
+        let mut round_constants = initial_constants.to_vec();
+        for constant in internal_constants.iter() {
+            round_constants.push(constant.clone());
+            round_constants.extend(vec![FieldElement::zero(); width - 1]);
         }
+        round_constants.extend_from_slice(final_constants);
+
+        // [-2, 1, 2, 1/2, 3, 4, -1/2, -3, -4, 1/2^8, 1/4, 1/8, 1/2^27, -1/2^8, -1/16, -1/2^27]
+        let two_inv = FieldElement::<Babybear31PrimeField>::from(2).inv().unwrap();
+        let internal_diagonal = vec![
+            -FieldElement::from(2),
+            FieldElement::one(),
+            FieldElement::from(2),
+            two_inv.clone(),
+            FieldElement::from(3),
+            FieldElement::from(4),
+            -two_inv.clone(),
+            -FieldElement::from(3),
+            -FieldElement::from(4),
+            two_inv.pow(8u32),
+            two_inv.pow(2u32),
+            two_inv.pow(3u32),
+            two_inv.pow(27u32),
+            -two_inv.pow(8u32),
+            -two_inv.pow(4u32),
+            -two_inv.pow(27u32),
+        ];
+
+        Ok(Parameters {
+            rate: 8,
+            capacity: 8,
+            alpha: 7,
+            n_full_rounds: 2 * half_full_rounds,
+            n_partial_rounds: internal_constants.len(),
+            round_constants,
+            linear_layer: LinearLayer::Poseidon2 { internal_diagonal },
+        })
+    }
+}
+
+impl<F: IsPrimeField> Parameters<F> {
+    /// Parses round constants written as comma separated hexadecimal values, and an MDS
+    /// matrix written one row per line.
+    pub fn parse(
+        round_constants_csv: &str,
+        mds_constants_csv: &str,
+    ) -> Result<PoseidonConstants<F>, String> {
+        let round_constants = Self::parse_line(round_constants_csv)?;
+        let mds_matrix = mds_constants_csv
+            .lines()
+            .map(Self::parse_line)
+            .collect::<Result<_, _>>()?;
 
         Ok((round_constants, mds_matrix))
     }
+
+    fn parse_line(line: &str) -> Result<Vec<FieldElement<F>>, String> {
+        line.split(',')
+            .map(|c| {
+                FieldElement::from_hex(c.trim())
+                    .map_err(|_| format!("Invalid Poseidon constant {}", c.trim()))
+            })
+            .collect()
+    }
 }
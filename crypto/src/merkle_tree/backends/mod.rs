@@ -1,5 +1,6 @@
 pub mod field_element;
 pub mod field_element_vector;
+pub mod poseidon;
 /// Configurations for merkle trees
 /// Setting generics to some value
 pub mod types;
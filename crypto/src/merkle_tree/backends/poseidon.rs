@@ -0,0 +1,126 @@
+use std::marker::PhantomData;
+
+use crate::{
+    hash::poseidon::{instances::IsPoseidonField, Poseidon},
+    merkle_tree::traits::IsMerkleTreeBackend,
+};
+use lambdaworks_math::field::{
+    element::FieldElement,
+    traits::{IsField, IsSubFieldOf},
+};
+
+/// Backend for Merkle trees whose leaves are vectors of elements of `T`, hashed with the
+/// Poseidon instance of the prime field `F`. `T` can be `F` itself or an extension of it,
+/// whose elements are absorbed as their coordinates over `F`.
+#[derive(Clone)]
+pub struct BatchPoseidonBackend<F: IsPoseidonField, T = F> {
+    poseidon: Poseidon<F>,
+    phantom: PhantomData<T>,
+}
+
+impl<F: IsPoseidonField, T> Default for BatchPoseidonBackend<F, T> {
+    fn default() -> Self {
+        Self {
+            poseidon: F::poseidon(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<F, T> IsMerkleTreeBackend for BatchPoseidonBackend<F, T>
+where
+    F: IsPoseidonField + IsSubFieldOf<T>,
+    T: IsField,
+{
+    type Node = [u8; 32];
+    type Data = Vec<FieldElement<T>>;
+
+    fn hash_data(&self, input: &Vec<FieldElement<T>>) -> [u8; 32] {
+        let elements: Vec<FieldElement<F>> = input
+            .iter()
+            .flat_map(|element| F::to_coordinates(element.value()))
+            .map(|coordinate| FieldElement::from_raw(&coordinate))
+            .collect();
+        F::digest_to_bytes(&self.poseidon.hash_elements(&elements))
+    }
+
+    fn hash_new_parent(&self, left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+        let mut elements =
+            F::digest_from_bytes(left).expect("nodes are checked with `is_valid_node`");
+        elements
+            .extend(F::digest_from_bytes(right).expect("nodes are checked with `is_valid_node`"));
+        F::digest_to_bytes(&self.poseidon.hash_elements(&elements))
+    }
+
+    fn is_valid_node(&self, node: &[u8; 32]) -> bool {
+        F::digest_from_bytes(node).is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lambdaworks_math::field::{
+        element::FieldElement,
+        fields::fft_friendly::{
+            babybear::{Babybear31PrimeField, Degree4Babybear31ExtensionField},
+            stark_252_prime_field::Stark252PrimeField,
+        },
+    };
+
+    use crate::merkle_tree::{backends::poseidon::BatchPoseidonBackend, merkle::MerkleTree};
+
+    #[test]
+    fn batch_poseidon_backend_works_with_stark252() {
+        type FE = FieldElement<Stark252PrimeField>;
+        type Backend = BatchPoseidonBackend<Stark252PrimeField>;
+
+        let values: Vec<_> = (0..6)
+            .map(|i| vec![FE::from(i), FE::from(2 * i + 1)])
+            .collect();
+        let merkle_tree = MerkleTree::<Backend>::build(&values);
+        let proof = merkle_tree.get_proof_by_pos(3).unwrap();
+        assert!(proof.verify::<Backend>(&merkle_tree.root, 3, &values[3]));
+        assert!(!proof.verify::<Backend>(&merkle_tree.root, 3, &values[2]));
+    }
+
+    #[test]
+    fn batch_poseidon_backend_works_with_the_degree_4_extension_of_babybear() {
+        type FE = FieldElement<Babybear31PrimeField>;
+        type Backend = BatchPoseidonBackend<Babybear31PrimeField, Degree4Babybear31ExtensionField>;
+
+        let values: Vec<_> = (0..8)
+            .map(|i| vec![FE::from(i).to_extension(), FE::from(3 * i).to_extension()])
+            .collect();
+        let merkle_tree = MerkleTree::<Backend>::build(&values);
+        let proof = merkle_tree.get_proof_by_pos(5).unwrap();
+        assert!(proof.verify::<Backend>(&merkle_tree.root, 5, &values[5]));
+        assert!(!proof.verify::<Backend>(&merkle_tree.root, 4, &values[5]));
+    }
+
+    #[test]
+    fn batch_poseidon_backend_rejects_proofs_with_non_canonical_nodes() {
+        type FE = FieldElement<Babybear31PrimeField>;
+        type Backend = BatchPoseidonBackend<Babybear31PrimeField>;
+        const MODULUS: u32 = 0x78000001;
+
+        let values: Vec<_> = (0..8).map(|i| vec![FE::from(i)]).collect();
+        let merkle_tree = MerkleTree::<Backend>::build(&values);
+        let mut proof = merkle_tree.get_proof_by_pos(1).unwrap();
+
+        // Adds the modulus to a word of a sibling, which encodes the same element.
+        let (node, offset) = (0..proof.merkle_path.len())
+            .flat_map(|node| (0..32).step_by(4).map(move |offset| (node, offset)))
+            .find(|(node, offset)| {
+                let word = &proof.merkle_path[*node][*offset..*offset + 4];
+                u32::from_be_bytes(word.try_into().unwrap())
+                    .checked_add(MODULUS)
+                    .is_some()
+            })
+            .unwrap();
+        let word = &mut proof.merkle_path[node][offset..offset + 4];
+        let value = u32::from_be_bytes((&*word).try_into().unwrap()) + MODULUS;
+        word.copy_from_slice(&value.to_be_bytes());
+
+        assert!(!proof.verify::<Backend>(&merkle_tree.root, 1, &values[1]));
+    }
+}
@@ -1,7 +1,14 @@
 use sha2::{Sha256, Sha512};
 use sha3::{Keccak256, Keccak512, Sha3_256, Sha3_512};
 
-use super::{field_element::FieldElementBackend, field_element_vector::FieldElementVectorBackend};
+use lambdaworks_math::field::fields::fft_friendly::{
+    babybear::Babybear31PrimeField, stark_252_prime_field::Stark252PrimeField,
+};
+
+use super::{
+    field_element::FieldElementBackend, field_element_vector::FieldElementVectorBackend,
+    poseidon::BatchPoseidonBackend,
+};
 
 // Field element backend definitions
 
@@ -26,3 +33,9 @@ pub type BatchSha2_256Backend<F> = FieldElementVectorBackend<F, Sha256, 32>;
 pub type BatchSha3_512Backend<F> = FieldElementVectorBackend<F, Sha3_512, 64>;
 pub type BatchKeccak512Backend<F> = FieldElementVectorBackend<F, Keccak512, 64>;
 pub type BatchSha2_512Backend<F> = FieldElementVectorBackend<F, Sha512, 64>;
+
+// Algebraic hash backend definitions
+
+pub type BatchPoseidonStark252Backend = BatchPoseidonBackend<Stark252PrimeField>;
+pub type BatchPoseidonBabybearBackend<T = Babybear31PrimeField> =
+    BatchPoseidonBackend<Babybear31PrimeField, T>;
@@ -1,4 +1,8 @@
-use super::{proof::Proof, traits::IsMerkleTreeBackend, utils::*};
+use super::{
+    proof::{BatchProof, Proof},
+    traits::IsMerkleTreeBackend,
+    utils::*,
+};
 
 #[derive(Clone)]
 pub struct MerkleTree<B: IsMerkleTreeBackend> {
@@ -14,7 +18,13 @@ where
 {
     pub fn build(unhashed_leaves: &[B::Data]) -> Self {
         let hasher = B::default();
-        let mut hashed_leaves: Vec<B::Node> = hasher.hash_leaves(unhashed_leaves);
+        Self::build_from_hashed_leaves(hasher.hash_leaves(unhashed_leaves))
+    }
+
+    /// Builds the tree from the hashes of its leaves, so that the leaves do not need to be
+    /// held in memory all at once.
+    pub fn build_from_hashed_leaves(mut hashed_leaves: Vec<B::Node>) -> Self {
+        let hasher = B::default();
 
         //The leaf must be a power of 2 set
         hashed_leaves = complete_until_power_of_two(&mut hashed_leaves);
@@ -56,13 +66,53 @@ where
 
         merkle_path
     }
+
+    /// Returns a single proof for all the leaves at the given positions. Nodes shared by
+    /// several authentication paths, or that can be computed from the opened leaves, are
+    /// only included once. Returns `None` if any of the positions is out of range.
+    pub fn get_batch_proof(&self, positions: &[usize]) -> Option<BatchProof<B::Node>> {
+        let first_leaf = self.nodes.len() / 2;
+        if positions.is_empty()
+            || positions
+                .iter()
+                .any(|pos| first_leaf + pos >= self.nodes.len())
+        {
+            return None;
+        }
+
+        let mut level: Vec<usize> = positions.iter().map(|pos| first_leaf + pos).collect();
+        level.sort_unstable();
+        level.dedup();
+
+        let mut auth_nodes = Vec::new();
+        while level[0] != ROOT {
+            let mut next_level = Vec::with_capacity(level.len());
+            let mut i = 0;
+            while i < level.len() {
+                let sibling = sibling_index(level[i]);
+                if level.get(i + 1) == Some(&sibling) {
+                    // The sibling is also known by the verifier
+                    i += 1;
+                } else {
+                    auth_nodes.push(self.nodes[sibling].clone());
+                }
+                next_level.push(parent_index(level[i]));
+                i += 1;
+            }
+            level = next_level;
+        }
+
+        Some(BatchProof { auth_nodes })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use lambdaworks_math::field::{element::FieldElement, fields::u64_prime_field::U64PrimeField};
 
-    use crate::merkle_tree::{merkle::MerkleTree, test_merkle::TestBackend};
+    use crate::merkle_tree::{
+        merkle::MerkleTree, test_merkle::TestBackend, traits::IsMerkleTreeBackend,
+    };
 
     const MODULUS: u64 = 13;
     type U64PF = U64PrimeField<MODULUS>;
@@ -86,4 +136,15 @@ mod tests {
         let merkle_tree = MerkleTree::<TestBackend<U64PF>>::build(&values);
         assert_eq!(merkle_tree.root, FE::new(8));
     }
+
+    #[test]
+    fn build_merkle_tree_from_hashed_leaves_matches_build() {
+        let values: Vec<FE> = (1..6).map(FE::new).collect();
+        let hashed_leaves = TestBackend::<U64PF>::default().hash_leaves(&values);
+        let merkle_tree = MerkleTree::<TestBackend<U64PF>>::build_from_hashed_leaves(hashed_leaves);
+        assert_eq!(
+            merkle_tree.root,
+            MerkleTree::<TestBackend<U64PF>>::build(&values).root
+        );
+    }
 }
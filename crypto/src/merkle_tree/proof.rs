@@ -21,6 +21,13 @@ impl<T: PartialEq + Eq> Proof<T> {
         B: IsMerkleTreeBackend<Node = T>,
     {
         let hasher = B::default();
+        if !self
+            .merkle_path
+            .iter()
+            .all(|node| hasher.is_valid_node(node))
+        {
+            return false;
+        }
         let mut hashed_value = hasher.hash_data(value);
 
         for sibling_node in self.merkle_path.iter() {
@@ -37,6 +44,93 @@ impl<T: PartialEq + Eq> Proof<T> {
     }
 }
 
+/// Stores the authentication paths of several leaves of the same merkle tree, as
+/// returned by `MerkleTree::get_batch_proof`. Only the nodes that cannot be computed
+/// from the opened leaves are stored, level by level from the leaves to the root and,
+/// within a level, from left to right.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BatchProof<T: PartialEq + Eq> {
+    pub auth_nodes: Vec<T>,
+}
+
+impl<T: PartialEq + Eq> BatchProof<T> {
+    /// Verifies that `values` are the leaves at `positions` of the merkle tree with root
+    /// `root_hash` and `number_of_leaves` leaves. Positions may be repeated, as long as
+    /// they are opened with the same value.
+    pub fn verify<B>(
+        &self,
+        root_hash: &B::Node,
+        number_of_leaves: usize,
+        positions: &[usize],
+        values: &[B::Data],
+    ) -> bool
+    where
+        B: IsMerkleTreeBackend<Node = T>,
+    {
+        if !number_of_leaves.is_power_of_two()
+            || positions.len() != values.len()
+            || positions.iter().any(|pos| *pos >= number_of_leaves)
+        {
+            return false;
+        }
+        if positions.is_empty() {
+            return self.auth_nodes.is_empty();
+        }
+
+        let hasher = B::default();
+        if !self
+            .auth_nodes
+            .iter()
+            .all(|node| hasher.is_valid_node(node))
+        {
+            return false;
+        }
+        let mut level: Vec<(usize, T)> = positions
+            .iter()
+            .zip(values)
+            .map(|(pos, value)| (*pos, hasher.hash_data(value)))
+            .collect();
+        level.sort_by_key(|(pos, _)| *pos);
+        if level
+            .windows(2)
+            .any(|pair| pair[0].0 == pair[1].0 && pair[0].1 != pair[1].1)
+        {
+            return false;
+        }
+        level.dedup_by_key(|(pos, _)| *pos);
+
+        let mut auth_nodes = self.auth_nodes.iter();
+        let mut level_size = number_of_leaves;
+        while level_size > 1 {
+            let mut next_level = Vec::with_capacity(level.len());
+            let mut i = 0;
+            while i < level.len() {
+                let (index, node) = &level[i];
+                let parent =
+                    if index % 2 == 0 && level.get(i + 1).map(|(pos, _)| *pos) == Some(index + 1) {
+                        i += 1;
+                        hasher.hash_new_parent(node, &level[i].1)
+                    } else {
+                        let Some(sibling_node) = auth_nodes.next() else {
+                            return false;
+                        };
+                        if index % 2 == 0 {
+                            hasher.hash_new_parent(node, sibling_node)
+                        } else {
+                            hasher.hash_new_parent(sibling_node, node)
+                        }
+                    };
+                next_level.push((index >> 1, parent));
+                i += 1;
+            }
+            level = next_level;
+            level_size >>= 1;
+        }
+
+        auth_nodes.next().is_none() && root_hash == &level[0].1
+    }
+}
+
 impl<T> Serializable for Proof<T>
 where
     T: Serializable + PartialEq + Eq,
@@ -141,6 +235,63 @@ mod tests {
         assert!(proof.verify::<TestBackend<Ecgfp5>>(&merkle_tree.root, 9349, &Ecgfp5FE::new(9350)));
     }
 
+    #[test]
+    fn batch_proof_of_several_leaves_verifies() {
+        let values: Vec<Ecgfp5FE> = (1..17).map(Ecgfp5FE::new).collect();
+        let merkle_tree = TestMerkleTreeEcgfp::build(&values);
+        let positions = [9, 2, 3, 14, 9];
+        let leaves: Vec<_> = positions.iter().map(|pos| values[*pos]).collect();
+
+        let proof = merkle_tree.get_batch_proof(&positions).unwrap();
+        assert!(proof.verify::<TestBackend<Ecgfp5>>(&merkle_tree.root, 16, &positions, &leaves));
+    }
+
+    #[test]
+    fn batch_proof_of_a_single_leaf_is_its_merkle_path() {
+        let values: Vec<Ecgfp5FE> = (1..17).map(Ecgfp5FE::new).collect();
+        let merkle_tree = TestMerkleTreeEcgfp::build(&values);
+
+        let proof = merkle_tree.get_batch_proof(&[5]).unwrap();
+        let path = merkle_tree.get_proof_by_pos(5).unwrap();
+        assert_eq!(proof.auth_nodes, path.merkle_path);
+    }
+
+    #[test]
+    fn batch_proof_does_not_repeat_shared_nodes() {
+        let values: Vec<Ecgfp5FE> = (1..17).map(Ecgfp5FE::new).collect();
+        let merkle_tree = TestMerkleTreeEcgfp::build(&values);
+
+        // Siblings 4 and 5 share their whole path, 6 only needs the node of 7
+        let proof = merkle_tree.get_batch_proof(&[4, 5, 6]).unwrap();
+        assert_eq!(proof.auth_nodes.len(), 3);
+    }
+
+    #[test]
+    fn batch_proof_with_a_wrong_leaf_does_not_verify() {
+        let values: Vec<Ecgfp5FE> = (1..17).map(Ecgfp5FE::new).collect();
+        let merkle_tree = TestMerkleTreeEcgfp::build(&values);
+        let positions = [1, 8];
+        let proof = merkle_tree.get_batch_proof(&positions).unwrap();
+
+        let leaves = [values[1], values[9]];
+        assert!(!proof.verify::<TestBackend<Ecgfp5>>(&merkle_tree.root, 16, &positions, &leaves));
+    }
+
+    #[test]
+    fn batch_proof_with_extra_nodes_does_not_verify() {
+        let values: Vec<Ecgfp5FE> = (1..17).map(Ecgfp5FE::new).collect();
+        let merkle_tree = TestMerkleTreeEcgfp::build(&values);
+        let mut proof = merkle_tree.get_batch_proof(&[3]).unwrap();
+        proof.auth_nodes.push(Ecgfp5FE::new(1));
+
+        assert!(!proof.verify::<TestBackend<Ecgfp5>>(
+            &merkle_tree.root,
+            16,
+            &[3],
+            &[Ecgfp5FE::new(4)]
+        ));
+    }
+
     fn assert_merkle_path(values: &[FE], expected_values: &[FE]) {
         for (node, expected_node) in values.iter().zip(expected_values) {
             assert_eq!(node, expected_node);
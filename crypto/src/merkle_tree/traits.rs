@@ -20,4 +20,10 @@ pub trait IsMerkleTreeBackend: Default {
     /// This function takes to children nodes and builds a new parent node.
     /// It will be used in the construction of the Merkle tree.
     fn hash_new_parent(&self, child_1: &Self::Node, child_2: &Self::Node) -> Self::Node;
+
+    /// Returns whether `node` is a valid encoding of a node. Proofs with invalid nodes are
+    /// rejected before hashing them, so `hash_new_parent` only gets valid nodes.
+    fn is_valid_node(&self, _node: &Self::Node) -> bool {
+        true
+    }
 }
@@ -4,7 +4,9 @@ use lambdaworks_math::{
     elliptic_curve::{
         short_weierstrass::{
             curves::bls12_381::{
-                curve::BLS12381Curve, pairing::BLS12381AtePairing, twist::BLS12381TwistCurve,
+                curve::BLS12381Curve,
+                pairing::{BLS12381AtePairing, G2Prepared},
+                twist::BLS12381TwistCurve,
             },
             point::ShortWeierstrassProjectivePoint,
         },
@@ -33,6 +35,13 @@ pub fn bls12381_elliptic_curve_benchmarks(c: &mut Criterion) {
     c.bench_function("BLS12381 Ate pairing", |b| {
         b.iter(|| BLS12381AtePairing::compute(black_box(&p), black_box(&q)))
     });
+
+    let prepared_q = G2Prepared::new(&q);
+    c.bench_function("BLS12381 Ate pairing with prepared G2", |b| {
+        b.iter(|| {
+            BLS12381AtePairing::compute_batch_prepared(&[(black_box(&p), black_box(&prepared_q))])
+        })
+    });
 }
 
 criterion_group!(bls12381, bls12381_elliptic_curve_benchmarks);
@@ -1,2 +1,9 @@
+/// Compression of the points of G1, as for BLS12 381. Points of G2 are not compressed.
+pub mod compression;
 pub mod curve;
+pub mod default_types;
 pub mod field_extension;
+pub mod twist;
+
+#[cfg(feature = "std")]
+pub mod pairing;
@@ -0,0 +1,253 @@
+use super::field_extension::{
+    Degree12ExtensionField, Degree2ExtensionField, Degree6ExtensionField, LevelTwoResidue,
+};
+use super::{curve::BLS12377Curve, twist::BLS12377TwistCurve};
+use crate::{
+    cyclic_group::IsGroup,
+    elliptic_curve::short_weierstrass::line_function::{
+        self, add_step, double_step, miller_loop_bits, LineFunction, TwistType,
+    },
+    elliptic_curve::short_weierstrass::point::ShortWeierstrassProjectivePoint,
+    elliptic_curve::traits::IsPairing,
+    field::element::FieldElement,
+    unsigned_integer::element::UnsignedInteger,
+};
+
+type Fp2E = FieldElement<Degree2ExtensionField>;
+type Fp12E = FieldElement<Degree12ExtensionField>;
+type G1Point = ShortWeierstrassProjectivePoint<BLS12377Curve>;
+type G2Point = ShortWeierstrassProjectivePoint<BLS12377TwistCurve>;
+
+#[derive(Clone)]
+pub struct BLS12377AtePairing;
+impl IsPairing for BLS12377AtePairing {
+    type G1Point = G1Point;
+    type G2Point = G2Point;
+    type OutputField = Degree12ExtensionField;
+
+    /// Compute the product of the ate pairings for a list of point pairs.
+    fn compute_batch(pairs: &[(&Self::G1Point, &Self::G2Point)]) -> Fp12E {
+        let mut result = FieldElement::one();
+        for (p, q) in pairs {
+            if !p.is_neutral_element() && !q.is_neutral_element() {
+                let p = p.to_affine();
+                let q = q.to_affine();
+                result = result * miller(&q, &p);
+            }
+        }
+        final_exponentiation(&result)
+    }
+}
+
+/// This is equal to the frobenius trace of the BLS12 377 curve minus one.
+const MILLER_LOOP_CONSTANT: u64 = 0x8508c00000000001;
+
+fn double_accumulate_line(t: &mut G2Point, p: &G1Point, accumulator: &mut Fp12E) {
+    let line = double_step(t);
+    *accumulator = mul_by_line(&accumulator.square(), &line, p);
+}
+
+fn add_accumulate_line(t: &mut G2Point, q: &G2Point, p: &G1Point, accumulator: &mut Fp12E) {
+    let line = add_step(t, q);
+    *accumulator = mul_by_line(accumulator, &line, p);
+}
+
+fn mul_by_line(f: &Fp12E, line: &LineFunction<Degree2ExtensionField>, p: &G1Point) -> Fp12E {
+    line_function::mul_by_line::<LevelTwoResidue, _, _>(f, line, p, TwistType::D)
+}
+
+/// Implements the miller loop for the ate pairing of the BLS12 377 curve.
+/// Based on algorithm 9.2, page 212 of the book
+/// "Topics in computational number theory" by W. Bons and K. Lenstra
+/// Since the parameter of the curve is positive, the result doesn't need to be inverted
+/// as for BLS12 381.
+fn miller(q: &G2Point, p: &G1Point) -> Fp12E {
+    let mut r = q.clone();
+    let mut f = Fp12E::one();
+    for bit in miller_loop_bits(MILLER_LOOP_CONSTANT) {
+        double_accumulate_line(&mut r, p, &mut f);
+        if bit {
+            add_accumulate_line(&mut r, q, p, &mut f);
+        }
+    }
+    f
+}
+
+/// Auxiliary function for the final exponentiation of the ate pairing.
+fn frobenius_square(f: &Fp12E) -> Fp12E {
+    let [a, b] = f.value();
+    let w_raised_to_p_squared_minus_one = FieldElement::<Degree6ExtensionField>::new_base(
+        "9b3af05dd14f6ec619aaf7d34594aabc5ed1347970dec00452217cc900000008508c00000000002",
+    );
+    let omega_3 = Fp2E::new_base(
+        "9b3af05dd14f6ec619aaf7d34594aabc5ed1347970dec00452217cc900000008508c00000000001",
+    );
+    let omega_3_squared = Fp2E::new_base("1ae3a4617c510eabc8756ba8f8c524eb8882a75cc9bc8e359064ee822fb5bffd1e945779fffffffffffffffffffffff");
+
+    let [a0, a1, a2] = a.value();
+    let [b0, b1, b2] = b.value();
+
+    let f0 = FieldElement::new([a0.clone(), a1 * &omega_3, a2 * &omega_3_squared]);
+    let f1 = FieldElement::new([b0.clone(), b1 * omega_3, b2 * omega_3_squared]);
+
+    FieldElement::new([f0, f1 * w_raised_to_p_squared_minus_one])
+}
+
+// The easy part of the final exponentiation raises to (p^6 - 1)(p^2 + 1), and the hard
+// part to (p^4 - p^2 + 1) / r.
+fn final_exponentiation(base: &Fp12E) -> Fp12E {
+    const PHI_DIVIDED_BY_R: UnsignedInteger<20> = UnsignedInteger::from_hex_unchecked("6d616e43720774d7d810d5cbdf0576728e56efc3bf3b4074a5448da5cfbef98d9c2cce3b25c548afd84225b34ccc65eca9c9678a845497a9781d8129911a8d889828282015fcd1c3fa1470f8b2d1eefd89535f9b5aaae0551dffcf72fb0bd948d5f4548283abcaf63f0a34fcb827dc8f4db069bf65f4f6974b4ff0fa27719b834b6904468768c0eaeea22e68002e16ba88600000000000000000000001");
+
+    let f1 = base.conjugate() * base.inv().unwrap();
+    let f2 = frobenius_square(&f1) * f1;
+    f2.pow(PHI_DIVIDED_BY_R)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        elliptic_curve::traits::IsEllipticCurve,
+        unsigned_integer::element::{U256, U384},
+    };
+
+    use super::super::field_extension::BLS12377PrimeField;
+    use super::*;
+
+    #[test]
+    fn test_double_accumulate_line_doubles_point_correctly() {
+        let g1 = BLS12377Curve::generator();
+        let g2 = BLS12377TwistCurve::generator();
+        let mut r = g2.clone();
+        let mut f = FieldElement::one();
+        double_accumulate_line(&mut r, &g1, &mut f);
+        assert_eq!(r, g2.operate_with(&g2));
+    }
+
+    #[test]
+    fn test_add_accumulate_line_adds_points_correctly() {
+        let g1 = BLS12377Curve::generator();
+        let g = BLS12377TwistCurve::generator();
+        let a: u64 = 12;
+        let b: u64 = 23;
+        let g2 = g.operate_with_self(a).to_affine();
+        let g3 = g.operate_with_self(b).to_affine();
+        let expected = g.operate_with_self(a + b);
+        let mut r = g2;
+        let mut f = FieldElement::one();
+        add_accumulate_line(&mut r, &g3, &g1, &mut f);
+        assert_eq!(r, expected);
+    }
+
+    #[test]
+    fn frobenius_square_is_the_power_to_p_squared() {
+        let f = BLS12377AtePairing::compute(
+            &BLS12377Curve::generator(),
+            &BLS12377TwistCurve::generator(),
+        );
+        let p = U384::from_hex_unchecked("1ae3a4617c510eac63b05c06ca1493b1a22d9f300f5138f1ef3622fba094800170b5d44300000008508c00000000001");
+        assert_eq!(frobenius_square(&f), f.pow(p).pow(p));
+    }
+
+    #[test]
+    fn pairing_of_the_generators_has_order_r() {
+        let r = U256::from_hex_unchecked(
+            "12ab655e9a2ca55660b44d1e5c37b00159aa76fed00000010a11800000000001",
+        );
+        let result = BLS12377AtePairing::compute(
+            &BLS12377Curve::generator(),
+            &BLS12377TwistCurve::generator(),
+        );
+        assert_ne!(result, FieldElement::one());
+        assert_eq!(result.pow(r), FieldElement::one());
+    }
+
+    #[test]
+    fn batch_ate_pairing_bilinearity() {
+        let p = BLS12377Curve::generator();
+        let q = BLS12377TwistCurve::generator();
+        let a = U384::from_u64(11);
+        let b = U384::from_u64(93);
+
+        let result = BLS12377AtePairing::compute_batch(&[
+            (
+                &p.operate_with_self(a).to_affine(),
+                &q.operate_with_self(b).to_affine(),
+            ),
+            (
+                &p.operate_with_self(a * b).to_affine(),
+                &q.neg().to_affine(),
+            ),
+        ]);
+        assert_eq!(result, FieldElement::one());
+    }
+
+    #[test]
+    fn ate_pairing_is_bilinear_in_each_argument() {
+        let p = BLS12377Curve::generator();
+        let q = BLS12377TwistCurve::generator();
+        let base = BLS12377AtePairing::compute(&p, &q);
+        assert_eq!(
+            BLS12377AtePairing::compute(&p.operate_with_self(6_u64), &q.operate_with_self(5_u64)),
+            base.pow(30_u64)
+        );
+    }
+
+    #[test]
+    fn ate_pairing_returns_one_when_one_element_is_the_neutral_element() {
+        let p = BLS12377Curve::generator().to_affine();
+        let q = ShortWeierstrassProjectivePoint::neutral_element();
+        let result = BLS12377AtePairing::compute_batch(&[(&p.to_affine(), &q)]);
+        assert_eq!(result, FieldElement::one());
+
+        let p = ShortWeierstrassProjectivePoint::neutral_element();
+        let q = BLS12377TwistCurve::generator();
+        let result = BLS12377AtePairing::compute_batch(&[(&p, &q.to_affine())]);
+        assert_eq!(result, FieldElement::one());
+    }
+
+    /// The reduced ate pairing of the generators, `f^((p^12 - 1) / r)` for the Miller
+    /// function `f` of length x. The expected value was computed with an independent
+    /// implementation that untwists the point of G2 to the curve over Fp12 and uses affine
+    /// lines and a plain exponentiation, with the same tower of extensions.
+    #[test]
+    fn ate_pairing_of_the_generators_matches_known_answer() {
+        type FpE = FieldElement<BLS12377PrimeField>;
+        type Fp6E = FieldElement<Degree6ExtensionField>;
+        let fp2 = |a0: &str, a1: &str| Fp2E::new([FpE::new_base(a0), FpE::new_base(a1)]);
+        let expected = Fp12E::new([
+            Fp6E::new([
+                fp2(
+                    "c2f1f0fd153fbe3107a2d435c0ab6398b7927d865f75e20c0791c0f792bcc075c963d2eaf1200025500dfe2d23063b",
+                    "1a01bf0ea164000331b7574f9b93ac7220b6c1a2e0a4f06f57831a60ac99bc04d9c750eaee5937f54be403f4562962c",
+                ),
+                fp2(
+                    "3aa5da7f8e7475e6408cee591dfe05436e106cc6e1dc1f3bd5c18fc1790382ccb284871405c584a7793fc504cb1a3",
+                    "9e8ba46b104f901a7b27f84dd69bed1cb1850afd57067dcb7fa7ec886b2918a843de86a9cd1c562ddd1af02d006863",
+                ),
+                fp2(
+                    "21e5a0962b85ba14e07917446a248d9ae30401838ad671c29bdee9fc9dd0b511e8ce8cc463f428c4babc99597cb78c",
+                    "b09446f49449d3b527577065a070e5650c56882ac4077a85b917c919dff4be03a3b8f6036e806c7e6f37d91d0ee127",
+                ),
+            ]),
+            Fp6E::new([
+                fp2(
+                    "1d2622c9d3418710fb07a57ea5d5551f6f3e6409173b144b75b61f16be7508fa4f8c8024472139ec2e003dbdbb0362",
+                    "47d6d2eb2f1a0d3a7cf059a092b377a93f167534e5a5bab1fb6caa75b37cbc9a522acfe33304ec3285dfe1a2f97eda",
+                ),
+                fp2(
+                    "59c9e2fb0b6b969c0fe202ab2d80c59c2ca890b0f00bd41c52284ecbb6c493efc397f049af384274cbab546e7d7bbb",
+                    "136b71884823cb7d1a667c60722cbd0b228b916695b523c821c30da768678e894aeb7e5d067a10f9aaf67e320939941",
+                ),
+                fp2(
+                    "8b425f3ca5248287eadc755db70764d1a6ac9339108e4249fb935554dcec541cea08d442f232d4816cda1d7a33d41a",
+                    "10993bdef24baee66bde6c04c3dea8170f0fe584517bc7b614f3cf83ed0da9ec9c28654692b7d6c6bc02d1a30be7ebd",
+                ),
+            ]),
+        ]);
+        let result = BLS12377AtePairing::compute(
+            &BLS12377Curve::generator(),
+            &BLS12377TwistCurve::generator(),
+        );
+        assert_eq!(result, expected);
+    }
+}
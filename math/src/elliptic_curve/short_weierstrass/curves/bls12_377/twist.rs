@@ -0,0 +1,128 @@
+use crate::elliptic_curve::short_weierstrass::point::ShortWeierstrassProjectivePoint;
+use crate::elliptic_curve::traits::IsEllipticCurve;
+use crate::unsigned_integer::element::U384;
+use crate::{
+    elliptic_curve::short_weierstrass::traits::IsShortWeierstrass, field::element::FieldElement,
+};
+
+use super::field_extension::Degree2ExtensionField;
+
+const GENERATOR_X_0: U384 = U384::from_hex_unchecked("018480be71c785fec89630a2a3841d01c565f071203e50317ea501f557db6b9b71889f52bb53540274e3e48f7c005196");
+const GENERATOR_X_1: U384 = U384::from_hex_unchecked("00ea6040e700403170dc5a51b1b140d5532777ee6651cecbe7223ece0799c9de5cf89984bff76fe6b26bfefa6ea16afe");
+const GENERATOR_Y_0: U384 = U384::from_hex_unchecked("00690d665d446f7bd960736bcbb2efb4de03ed7274b49a58e458c282f832d204f2cf88886d8c7c2ef094094409fd4ddf");
+const GENERATOR_Y_1: U384 = U384::from_hex_unchecked("00f8169fd28355189e549da3151a70aa61ef11ac3d591bf12463b01acee304c24279b83f5e52270bd9a1cdd185eb8f93");
+
+/// The description of the D-type sextic twist y^2 = x^3 + 1 / u of the curve, whose
+/// subgroup of order r is G2. A point (x, y) of the twist maps to the point
+/// (x w^2, y w^3) of the curve over Fp12.
+#[derive(Clone, Debug)]
+pub struct BLS12377TwistCurve;
+
+impl IsEllipticCurve for BLS12377TwistCurve {
+    type BaseField = Degree2ExtensionField;
+    type PointRepresentation = ShortWeierstrassProjectivePoint<Self>;
+
+    fn generator() -> Self::PointRepresentation {
+        Self::PointRepresentation::new([
+            FieldElement::new([
+                FieldElement::new(GENERATOR_X_0),
+                FieldElement::new(GENERATOR_X_1),
+            ]),
+            FieldElement::new([
+                FieldElement::new(GENERATOR_Y_0),
+                FieldElement::new(GENERATOR_Y_1),
+            ]),
+            FieldElement::one(),
+        ])
+    }
+}
+
+impl IsShortWeierstrass for BLS12377TwistCurve {
+    fn a() -> FieldElement<Self::BaseField> {
+        FieldElement::zero()
+    }
+
+    fn b() -> FieldElement<Self::BaseField> {
+        FieldElement::new([
+            FieldElement::zero(),
+            FieldElement::new(U384::from_hex_unchecked("10222f6db0fd6f343bd03737460c589dc7b4f91cd5fd889129207b63c6bf8000dd39e5c1ccccccd1c9ed9999999999a")),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        cyclic_group::IsGroup,
+        elliptic_curve::{
+            short_weierstrass::{
+                curves::bls12_377::field_extension::{
+                    BLS12377PrimeField, Degree2ExtensionField, LevelTwoResidue,
+                },
+                traits::IsShortWeierstrass,
+            },
+            traits::IsEllipticCurve,
+        },
+        field::{element::FieldElement, extensions::cubic::HasCubicNonResidue},
+        unsigned_integer::element::U256,
+    };
+
+    use super::BLS12377TwistCurve;
+    type Level0FE = FieldElement<BLS12377PrimeField>;
+    type Level1FE = FieldElement<Degree2ExtensionField>;
+
+    #[cfg(feature = "std")]
+    use crate::elliptic_curve::short_weierstrass::point::{
+        Endianness, PointFormat, ShortWeierstrassProjectivePoint,
+    };
+
+    #[test]
+    fn b_is_the_inverse_of_the_level_two_residue() {
+        assert_eq!(
+            BLS12377TwistCurve::b() * LevelTwoResidue::residue(),
+            Level1FE::one()
+        );
+    }
+
+    #[test]
+    fn create_generator() {
+        let g = BLS12377TwistCurve::generator();
+        let [x, y, _] = g.coordinates();
+        assert_eq!(
+            BLS12377TwistCurve::defining_equation(x, y),
+            Level1FE::zero()
+        );
+    }
+
+    #[test]
+    fn generator_has_order_r() {
+        let r = U256::from_hex_unchecked(
+            "12ab655e9a2ca55660b44d1e5c37b00159aa76fed00000010a11800000000001",
+        );
+        let g = BLS12377TwistCurve::generator();
+        assert!(g.operate_with_self(r).is_neutral_element());
+        assert!(!g.is_neutral_element());
+    }
+
+    #[test]
+    fn create_invalid_points_returns_an_error() {
+        let x = Level1FE::new([Level0FE::from(1), Level0FE::from(2)]);
+        assert!(BLS12377TwistCurve::create_point_from_affine(x.clone(), x).is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn serialize_deserialize_generator() {
+        let g = BLS12377TwistCurve::generator();
+        let bytes = g.serialize(PointFormat::Projective, Endianness::LittleEndian);
+
+        let deserialized = ShortWeierstrassProjectivePoint::<BLS12377TwistCurve>::deserialize(
+            &bytes,
+            PointFormat::Projective,
+            Endianness::LittleEndian,
+        )
+        .unwrap();
+
+        assert_eq!(deserialized, g);
+    }
+}
@@ -105,6 +105,14 @@ impl IsField for Degree2ExtensionField {
     }
 }
 
+impl FieldElement<Degree2ExtensionField> {
+    /// Returns `a0 - a1 * u` for `self = a0 + a1 * u`, which is also `self` raised to p.
+    pub fn conjugate(&self) -> Self {
+        let [a0, a1] = self.value();
+        Self::new([a0.clone(), -a1])
+    }
+}
+
 #[cfg(feature = "std")]
 impl ByteConversion for FieldElement<Degree2ExtensionField> {
     fn to_bytes_be(&self) -> Vec<u8> {
@@ -245,6 +253,15 @@ mod tests {
     use super::*;
     type Fp12E = FieldElement<Degree12ExtensionField>;
 
+    #[test]
+    fn conjugate_is_the_frobenius_map() {
+        let a = FieldElement::<Degree2ExtensionField>::new([
+            FieldElement::from(3),
+            FieldElement::from(5),
+        ]);
+        assert_eq!(a.conjugate(), a.pow(BLS12381_PRIME_FIELD_ORDER));
+    }
+
     #[test]
     fn element_squared_1() {
         // base = 1 + u + (1 + u)v + (1 + u)v^2 + ((1+u) + (1 + u)v + (1+ u)v^2)w
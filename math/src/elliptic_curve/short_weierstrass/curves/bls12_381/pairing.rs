@@ -1,23 +1,31 @@
-use super::field_extension::{Degree12ExtensionField, Degree2ExtensionField};
-use crate::{
-    elliptic_curve::short_weierstrass::curves::bls12_381::field_extension::Degree6ExtensionField,
-    field::element::FieldElement, unsigned_integer::element::UnsignedInteger,
+use super::field_extension::{
+    BLS12381PrimeField, Degree12ExtensionField, Degree2ExtensionField, Degree6ExtensionField,
+    LevelTwoResidue,
 };
+use crate::field::element::FieldElement;
 
 use super::{curve::BLS12381Curve, twist::BLS12381TwistCurve};
 use crate::{
     cyclic_group::IsGroup,
-    elliptic_curve::short_weierstrass::curves::bls12_381::field_extension::LevelTwoResidue,
+    elliptic_curve::short_weierstrass::line_function::{
+        self, add_step, double_step, miller_loop_bits, LineFunction, TwistType,
+    },
     elliptic_curve::short_weierstrass::point::ShortWeierstrassProjectivePoint,
-    elliptic_curve::short_weierstrass::traits::IsShortWeierstrass,
-    elliptic_curve::traits::IsPairing, field::extensions::cubic::HasCubicNonResidue,
+    elliptic_curve::traits::IsPairing,
 };
 
+type FpE = FieldElement<BLS12381PrimeField>;
+type Fp2E = FieldElement<Degree2ExtensionField>;
+type Fp6E = FieldElement<Degree6ExtensionField>;
+type Fp12E = FieldElement<Degree12ExtensionField>;
+type G1Point = ShortWeierstrassProjectivePoint<BLS12381Curve>;
+type G2Point = ShortWeierstrassProjectivePoint<BLS12381TwistCurve>;
+
 #[derive(Clone)]
 pub struct BLS12381AtePairing;
 impl IsPairing for BLS12381AtePairing {
-    type G1Point = ShortWeierstrassProjectivePoint<BLS12381Curve>;
-    type G2Point = ShortWeierstrassProjectivePoint<BLS12381TwistCurve>;
+    type G1Point = G1Point;
+    type G2Point = G2Point;
     type OutputField = Degree12ExtensionField;
 
     /// Compute the product of the ate pairings for a list of point pairs.
@@ -36,147 +44,125 @@ impl IsPairing for BLS12381AtePairing {
     }
 }
 
+impl BLS12381AtePairing {
+    /// Compute the product of the ate pairings for a list of pairs whose G2 points
+    /// were prepared beforehand. The Miller loops of all the pairs share the squarings
+    /// of the accumulator, so this is also faster than `compute_batch` for a single use.
+    pub fn compute_batch_prepared(pairs: &[(&G1Point, &G2Prepared)]) -> Fp12E {
+        let pairs: Vec<_> = pairs
+            .iter()
+            .filter(|(p, q)| !p.is_neutral_element() && !q.lines.is_empty())
+            .map(|(p, q)| (p.to_affine(), *q))
+            .collect();
+        final_exponentiation(&miller_prepared(&pairs))
+    }
+}
+
+/// A point of G2 together with the lines of its Miller loop. The lines do not depend
+/// on the point of G1, so preparing a G2 point that is paired repeatedly, such as the
+/// fixed points of a KZG verifier, saves all the arithmetic on the twist.
+#[derive(Clone, Debug)]
+pub struct G2Prepared {
+    // Lines in the order the Miller loop consumes them. Empty for the neutral element.
+    lines: Vec<LineFunction<Degree2ExtensionField>>,
+}
+
+impl G2Prepared {
+    pub fn new(q: &G2Point) -> Self {
+        if q.is_neutral_element() {
+            return Self { lines: Vec::new() };
+        }
+        let q = q.to_affine();
+        let mut t = q.clone();
+        let mut lines = Vec::new();
+        for bit in miller_loop_bits(MILLER_LOOP_CONSTANT) {
+            lines.push(double_step(&mut t));
+            if bit {
+                lines.push(add_step(&mut t, &q));
+            }
+        }
+        Self { lines }
+    }
+}
+
+impl From<&G2Point> for G2Prepared {
+    fn from(q: &G2Point) -> Self {
+        Self::new(q)
+    }
+}
+
 /// This is equal to the frobenius trace of the BLS12 381 curve minus one.
+/// It is the absolute value of the curve parameter x, which is negative.
 const MILLER_LOOP_CONSTANT: u64 = 0xd201000000010000;
-fn double_accumulate_line(
-    t: &mut ShortWeierstrassProjectivePoint<BLS12381TwistCurve>,
-    p: &ShortWeierstrassProjectivePoint<BLS12381Curve>,
-    accumulator: &mut FieldElement<Degree12ExtensionField>,
-) {
-    let [x1, y1, z1] = t.coordinates();
-    let [px, py, _] = p.coordinates();
-    let residue = LevelTwoResidue::residue();
-    let two_inv = FieldElement::<Degree2ExtensionField>::new_base("d0088f51cbff34d258dd3db21a5d66bb23ba5c279c2895fb39869507b587b120f55ffff58a9ffffdcff7fffffffd556");
-
-    let a = &two_inv * x1 * y1;
-    let b = y1.square();
-    let c = z1.square();
-    let d = FieldElement::from(3) * &c;
-    let e = BLS12381TwistCurve::b() * d;
-    let f = FieldElement::from(3) * &e;
-    let g = two_inv * (&b + &f);
-    let h = (y1 + z1).square() - (&b + &c);
-
-    let x3 = &a * (&b - &f);
-    let y3 = g.square() - (FieldElement::from(3) * e.square());
-    let z3 = &b * &h;
-
-    let [h0, h1] = h.value();
-    let x1_sq_3 = FieldElement::from(3) * x1.square();
-    let [x1_sq_30, x1_sq_31] = x1_sq_3.value();
-
-    t.0.value = [x3, y3, z3];
-
-    // (a0 + a2w2 + a4w4 + a1w + a3w3 + a5w5) * (b0 + b2 w2 + b3 w3) =
-    // (a0b0 + r (a3b3 + a4b2)) w0 + (a1b0 + r (a4b3 + a5b2)) w
-    // (a2b0 + r  a5b3 + a0b2 ) w2 + (a3b0 + a0b3 + a1b2    ) w3
-    // (a4b0 +    a1b3 + a2b2 ) w4 + (a5b0 + a2b3 + a3b2    ) w5
-    let accumulator_sq = accumulator.square();
-    let [x, y] = accumulator_sq.value();
-    let [a0, a2, a4] = x.value();
-    let [a1, a3, a5] = y.value();
-    let b0 = e - b;
-    let b2 = FieldElement::new([x1_sq_30 * px, x1_sq_31 * px]);
-    let b3 = FieldElement::new([-h0 * py, -h1 * py]);
-    *accumulator = FieldElement::new([
-        FieldElement::new([
-            a0 * &b0 + &residue * (a3 * &b3 + a4 * &b2), // w0
-            a2 * &b0 + &residue * a5 * &b3 + a0 * &b2,   // w2
-            a4 * &b0 + a1 * &b3 + a2 * &b2,              // w4
-        ]),
-        FieldElement::new([
-            a1 * &b0 + &residue * (a4 * &b3 + a5 * &b2), // w1
-            a3 * &b0 + a0 * &b3 + a1 * &b2,              // w3
-            a5 * &b0 + a2 * &b3 + a3 * &b2,              // w5
-        ]),
-    ]);
+
+/// Equal to `(MILLER_LOOP_CONSTANT + 1) / 3`, used by the hard part of the final exponentiation.
+const MILLER_LOOP_CONSTANT_PLUS_ONE_DIV_THREE: u64 = 0x460055555555aaab;
+
+fn double_accumulate_line(t: &mut G2Point, p: &G1Point, accumulator: &mut Fp12E) {
+    let line = double_step(t);
+    *accumulator = mul_by_line(&accumulator.square(), &line, p);
 }
-fn add_accumulate_line(
-    t: &mut ShortWeierstrassProjectivePoint<BLS12381TwistCurve>,
-    q: &ShortWeierstrassProjectivePoint<BLS12381TwistCurve>,
-    p: &ShortWeierstrassProjectivePoint<BLS12381Curve>,
-    accumulator: &mut FieldElement<Degree12ExtensionField>,
-) {
-    let [x1, y1, z1] = t.coordinates();
-    let [x2, y2, _] = q.coordinates();
-    let [px, py, _] = p.coordinates();
-    let residue = LevelTwoResidue::residue();
-
-    let a = y2 * z1;
-    let b = x2 * z1;
-    let theta = y1 - a;
-    let lambda = x1 - b;
-    let c = theta.square();
-    let d = lambda.square();
-    let e = &lambda * &d;
-    let f = z1 * c;
-    let g = x1 * d;
-    let h = &e + f - FieldElement::from(2) * &g;
-    let i = y1 * &e;
-
-    let x3 = &lambda * &h;
-    let y3 = &theta * (g - h) - i;
-    let z3 = z1 * e;
-
-    t.0.value = [x3, y3, z3];
-
-    let [lambda0, lambda1] = lambda.value();
-    let [theta0, theta1] = theta.value();
-
-    let [x, y] = accumulator.value();
-    let [a0, a2, a4] = x.value();
-    let [a1, a3, a5] = y.value();
-    let b0 = -lambda.clone() * y2 + theta.clone() * x2;
-    let b2 = FieldElement::new([-theta0 * px, -theta1 * px]);
-    let b3 = FieldElement::new([lambda0 * py, lambda1 * py]);
-    *accumulator = FieldElement::new([
-        FieldElement::new([
-            a0 * &b0 + &residue * (a3 * &b3 + a4 * &b2), // w0
-            a2 * &b0 + &residue * a5 * &b3 + a0 * &b2,   // w2
-            a4 * &b0 + a1 * &b3 + a2 * &b2,              // w4
-        ]),
-        FieldElement::new([
-            a1 * &b0 + &residue * (a4 * &b3 + a5 * &b2), // w1
-            a3 * &b0 + a0 * &b3 + a1 * &b2,              // w3
-            a5 * &b0 + a2 * &b3 + a3 * &b2,              // w5
-        ]),
-    ]);
+
+fn add_accumulate_line(t: &mut G2Point, q: &G2Point, p: &G1Point, accumulator: &mut Fp12E) {
+    let line = add_step(t, q);
+    *accumulator = mul_by_line(accumulator, &line, p);
 }
+
+/// Returns the product of `a` by the residue `1 + u` of Fp6.
+fn mul_fp2_by_residue(a: &Fp2E) -> Fp2E {
+    let [a0, a1] = a.value();
+    Fp2E::new([a0 - a1, a0 + a1])
+}
+
+fn mul_by_line(f: &Fp12E, line: &LineFunction<Degree2ExtensionField>, p: &G1Point) -> Fp12E {
+    line_function::mul_by_line::<LevelTwoResidue, _, _>(f, line, p, TwistType::M)
+}
+
 /// Implements the miller loop for the ate pairing of the BLS12 381 curve.
 /// Based on algorithm 9.2, page 212 of the book
 /// "Topics in computational number theory" by W. Bons and K. Lenstra
-#[allow(unused)]
-fn miller(
-    q: &ShortWeierstrassProjectivePoint<BLS12381TwistCurve>,
-    p: &ShortWeierstrassProjectivePoint<BLS12381Curve>,
-) -> FieldElement<Degree12ExtensionField> {
+fn miller(q: &G2Point, p: &G1Point) -> Fp12E {
     let mut r = q.clone();
-    let mut f = FieldElement::<Degree12ExtensionField>::one();
-    let mut miller_loop_constant = MILLER_LOOP_CONSTANT;
-    let mut miller_loop_constant_bits: Vec<bool> = vec![];
-
-    while miller_loop_constant > 0 {
-        miller_loop_constant_bits.insert(0, (miller_loop_constant & 1) == 1);
-        miller_loop_constant >>= 1;
-    }
-
-    for bit in miller_loop_constant_bits[1..].iter() {
+    let mut f = Fp12E::one();
+    for bit in miller_loop_bits(MILLER_LOOP_CONSTANT) {
         double_accumulate_line(&mut r, p, &mut f);
-        if *bit {
+        if bit {
             add_accumulate_line(&mut r, q, p, &mut f);
         }
     }
-    f.inv().unwrap()
+    // The curve parameter is negative, so the result has to be inverted. The conjugate
+    // differs from the inverse by a factor of Fp6, which the final exponentiation sends to one.
+    f.conjugate()
+}
+
+/// The miller loop of several pairs of affine points of G1 and prepared points of G2,
+/// sharing the squarings of the accumulator.
+fn miller_prepared(pairs: &[(G1Point, &G2Prepared)]) -> Fp12E {
+    let mut f = Fp12E::one();
+    let mut step = 0;
+    for bit in miller_loop_bits(MILLER_LOOP_CONSTANT) {
+        f = f.square();
+        for (p, q) in pairs {
+            f = mul_by_line(&f, &q.lines[step], p);
+        }
+        step += 1;
+        if bit {
+            for (p, q) in pairs {
+                f = mul_by_line(&f, &q.lines[step], p);
+            }
+            step += 1;
+        }
+    }
+    f.conjugate()
 }
 
 /// Auxiliary function for the final exponentiation of the ate pairing.
-fn frobenius_square(
-    f: &FieldElement<Degree12ExtensionField>,
-) -> FieldElement<Degree12ExtensionField> {
+/// Returns `f` raised to p^10, the inverse of the square of the frobenius map.
+fn frobenius_square(f: &Fp12E) -> Fp12E {
     let [a, b] = f.value();
-    let w_raised_to_p_squared_minus_one = FieldElement::<Degree6ExtensionField>::new_base("1a0111ea397fe699ec02408663d4de85aa0d857d89759ad4897d29650fb85f9b409427eb4f49fffd8bfd00000000aaad");
-    let omega_3 = FieldElement::<Degree2ExtensionField>::new_base("1a0111ea397fe699ec02408663d4de85aa0d857d89759ad4897d29650fb85f9b409427eb4f49fffd8bfd00000000aaac");
-    let omega_3_squared = FieldElement::<Degree2ExtensionField>::new_base(
+    let w_raised_to_p_squared_minus_one = Fp6E::new_base("1a0111ea397fe699ec02408663d4de85aa0d857d89759ad4897d29650fb85f9b409427eb4f49fffd8bfd00000000aaad");
+    let omega_3 = Fp2E::new_base("1a0111ea397fe699ec02408663d4de85aa0d857d89759ad4897d29650fb85f9b409427eb4f49fffd8bfd00000000aaac");
+    let omega_3_squared = Fp2E::new_base(
         "5f19672fdf76ce51ba69c6076a0f77eaddb3a93be6f89688de17d813620a00022e01fffffffefffe",
     );
 
@@ -189,31 +175,139 @@ fn frobenius_square(
     FieldElement::new([f0, f1 * w_raised_to_p_squared_minus_one])
 }
 
+/// Returns `f` raised to p. Writing `f = sum(c_i w^i)`, this is `sum(conj(c_i) gamma_i w^i)`
+/// where `gamma_i = (1 + u)^(i (p - 1) / 6)`.
+fn frobenius(f: &Fp12E) -> Fp12E {
+    let gamma = |c0: &str, c1: &str| Fp2E::new([FpE::new_base(c0), FpE::new_base(c1)]);
+    let gamma_1 = gamma("1904d3bf02bb0667c231beb4202c0d1f0fd603fd3cbd5f4f7b2443d784bab9c4f67ea53d63e7813d8d0775ed92235fb8", "fc3e2b36c4e03288e9e902231f9fb854a14787b6c7b36fec0c8ec971f63c5f282d5ac14d6c7ec22cf78a126ddc4af3");
+    let gamma_2 = gamma("0", "1a0111ea397fe699ec02408663d4de85aa0d857d89759ad4897d29650fb85f9b409427eb4f49fffd8bfd00000000aaac");
+    let gamma_3 = gamma("6af0e0437ff400b6831e36d6bd17ffe48395dabc2d3435e77f76e17009241c5ee67992f72ec05f4c81084fbede3cc09", "6af0e0437ff400b6831e36d6bd17ffe48395dabc2d3435e77f76e17009241c5ee67992f72ec05f4c81084fbede3cc09");
+    let gamma_4 = gamma("1a0111ea397fe699ec02408663d4de85aa0d857d89759ad4897d29650fb85f9b409427eb4f49fffd8bfd00000000aaad", "0");
+    let gamma_5 = gamma("5b2cfd9013a5fd8df47fa6b48b1e045f39816240c0b8fee8beadf4d8e9c0566c63a3e6e257f87329b18fae980078116", "144e4211384586c16bd3ad4afa99cc9170df3560e77982d0db45f3536814f0bd5871c1908bd478cd1ee605167ff82995");
+
+    let [x, y] = f.value();
+    let [c0, c2, c4] = x.value();
+    let [c1, c3, c5] = y.value();
+    Fp12E::new([
+        Fp6E::new([
+            c0.conjugate(),
+            c2.conjugate() * gamma_2,
+            c4.conjugate() * gamma_4,
+        ]),
+        Fp6E::new([
+            c1.conjugate() * gamma_1,
+            c3.conjugate() * gamma_3,
+            c5.conjugate() * gamma_5,
+        ]),
+    ])
+}
+
+/// Returns the square of `(a0 + a1 s)` in Fp4 = Fp2[s] / (s^2 - (1 + u)).
+fn fp4_square(a0: &Fp2E, a1: &Fp2E) -> (Fp2E, Fp2E) {
+    let t0 = a0.square();
+    let t1 = a1.square();
+    let c1 = (a0 + a1).square() - &t0 - &t1;
+    (t0 + mul_fp2_by_residue(&t1), c1)
+}
+
+/// Squares an element of the cyclotomic subgroup of Fp12, which contains the result
+/// of the easy part of the final exponentiation. Taken from "Faster Squaring in the
+/// Cyclotomic Subgroup of Sixth Degree Extensions" by Granger and Scott
+/// (https://eprint.iacr.org/2009/565.pdf). Seeing Fp12 as Fp4[w] / (w^3 - s) with
+/// `s = w^3`, the square of `f = A + B w + C w^2` is
+/// `(3 A^2 - 2 conj(A)) + (3 s C^2 + 2 conj(B)) w + (3 B^2 - 2 conj(C)) w^2`.
+fn cyclotomic_square(f: &Fp12E) -> Fp12E {
+    let [x, y] = f.value();
+    let [c0, c2, c4] = x.value();
+    let [c1, c3, c5] = y.value();
+    let three_times_minus_twice = |a: Fp2E, b: &Fp2E| {
+        let t = &a - b;
+        &t + &t + a
+    };
+    let three_times_plus_twice = |a: Fp2E, b: &Fp2E| {
+        let t = &a + b;
+        &t + &t + a
+    };
+
+    let (a0, a1) = fp4_square(c0, c3);
+    let (b0, b1) = fp4_square(c1, c4);
+    let (d0, d1) = fp4_square(c2, c5);
+
+    Fp12E::new([
+        Fp6E::new([
+            three_times_minus_twice(a0, c0),
+            three_times_minus_twice(b0, c2),
+            three_times_minus_twice(d0, c4),
+        ]),
+        Fp6E::new([
+            three_times_plus_twice(mul_fp2_by_residue(&d1), c1),
+            three_times_plus_twice(a1, c3),
+            three_times_plus_twice(b1, c5),
+        ]),
+    ])
+}
+
+/// Raises an element of the cyclotomic subgroup to a nonzero `exponent`.
+fn cyclotomic_pow(f: &Fp12E, exponent: u64) -> Fp12E {
+    let length = u64::BITS - exponent.leading_zeros();
+    let mut result = f.clone();
+    for i in (0..length - 1).rev() {
+        result = cyclotomic_square(&result);
+        if (exponent >> i) & 1 == 1 {
+            result = result * f;
+        }
+    }
+    result
+}
+
 // To understand more about how to reduce the final exponentiation
 // read "Efficient Final Exponentiation via Cyclotomic Structure for
 // Pairings over Families of Elliptic Curves" (https://eprint.iacr.org/2020/875.pdf)
-//
-// TODO: implement optimizations for the hard part of the final exponentiation.
-#[allow(unused)]
-fn final_exponentiation(
-    base: &FieldElement<Degree12ExtensionField>,
-) -> FieldElement<Degree12ExtensionField> {
-    const PHI_DIVIDED_BY_R: UnsignedInteger<20> = UnsignedInteger::from_hex_unchecked("f686b3d807d01c0bd38c3195c899ed3cde88eeb996ca394506632528d6a9a2f230063cf081517f68f7764c28b6f8ae5a72bce8d63cb9f827eca0ba621315b2076995003fc77a17988f8761bdc51dc2378b9039096d1b767f17fcbde783765915c97f36c6f18212ed0b283ed237db421d160aeb6a1e79983774940996754c8c71a2629b0dea236905ce937335d5b68fa9912aae208ccf1e516c3f438e3ba79");
-
+fn final_exponentiation(base: &Fp12E) -> Fp12E {
+    // Easy part: raise to (p^6 - 1)(p^2 + 1).
     let f1 = base.conjugate() * base.inv().unwrap();
-    let f2 = frobenius_square(&f1) * f1;
-    f2.pow(PHI_DIVIDED_BY_R)
+    let f2 = frobenius_square(&f1) * &f1;
+
+    // Hard part: raise to (p^4 - p^2 + 1) / r, which equals
+    // (x - 1)^2 / 3 * (x + p) * (x^2 + p^2 - 1) + 1 for the curve parameter x.
+    // Since x = -MILLER_LOOP_CONSTANT, (x - 1)^2 / 3 = (MILLER_LOOP_CONSTANT + 1)^2 / 3
+    // and raising to x is raising to MILLER_LOOP_CONSTANT and conjugating. In the
+    // cyclotomic subgroup p^6 acts as -1 and p^4 as p^2 - 1, so conjugating the result
+    // of `frobenius_square` raises to p^4 = p^2 - 1.
+    let a = cyclotomic_pow(&f2, MILLER_LOOP_CONSTANT_PLUS_ONE_DIV_THREE);
+    let a = cyclotomic_pow(&a, MILLER_LOOP_CONSTANT) * &a;
+    let b = cyclotomic_pow(&a, MILLER_LOOP_CONSTANT).conjugate() * frobenius(&a);
+    let c = cyclotomic_pow(
+        &cyclotomic_pow(&b, MILLER_LOOP_CONSTANT),
+        MILLER_LOOP_CONSTANT,
+    ) * frobenius_square(&b).conjugate();
+    c * f2
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        cyclic_group::IsGroup, elliptic_curve::traits::IsEllipticCurve,
-        unsigned_integer::element::U384,
+        cyclic_group::IsGroup,
+        elliptic_curve::{
+            short_weierstrass::curves::bls12_381::field_extension::BLS12381_PRIME_FIELD_ORDER,
+            traits::IsEllipticCurve,
+        },
+        unsigned_integer::element::{UnsignedInteger, U384},
     };
 
     use super::*;
 
+    /// An element of the cyclotomic subgroup, the output of the easy part of the final
+    /// exponentiation for the Miller loop of the generators.
+    fn cyclotomic_element() -> Fp12E {
+        let f = miller(
+            &BLS12381TwistCurve::generator(),
+            &BLS12381Curve::generator(),
+        );
+        let f1 = f.conjugate() * f.inv().unwrap();
+        frobenius_square(&f1) * f1
+    }
+
     #[test]
     fn test_double_accumulate_line_doubles_point_correctly() {
         let g1 = BLS12381Curve::generator();
@@ -239,6 +333,67 @@ mod tests {
         assert_eq!(r, expected);
     }
 
+    #[test]
+    fn mul_by_line_matches_multiplication_by_the_full_line() {
+        let p = BLS12381Curve::generator()
+            .operate_with_self(5_u64)
+            .to_affine();
+        let mut t = BLS12381TwistCurve::generator();
+        let line = double_step(&mut t);
+        let [px, py, _] = p.coordinates();
+        let full_line = Fp12E::new([
+            Fp6E::new([
+                line.c.clone(),
+                &line.cx * Fp2E::new([px.clone(), FpE::zero()]),
+                Fp2E::zero(),
+            ]),
+            Fp6E::new([
+                Fp2E::zero(),
+                &line.cy * Fp2E::new([py.clone(), FpE::zero()]),
+                Fp2E::zero(),
+            ]),
+        ]);
+        let f = miller(&BLS12381TwistCurve::generator(), &p);
+        assert_eq!(mul_by_line(&f, &line, &p), f * full_line);
+    }
+
+    #[test]
+    fn cyclotomic_square_equals_square_in_the_cyclotomic_subgroup() {
+        let f = cyclotomic_element();
+        assert_eq!(cyclotomic_square(&f), f.square());
+    }
+
+    #[test]
+    fn cyclotomic_pow_equals_pow_in_the_cyclotomic_subgroup() {
+        let f = cyclotomic_element();
+        assert_eq!(
+            cyclotomic_pow(&f, MILLER_LOOP_CONSTANT),
+            f.pow(MILLER_LOOP_CONSTANT)
+        );
+    }
+
+    #[test]
+    fn frobenius_is_raising_to_p() {
+        let f = miller(
+            &BLS12381TwistCurve::generator(),
+            &BLS12381Curve::generator(),
+        );
+        assert_eq!(frobenius(&f), f.pow(BLS12381_PRIME_FIELD_ORDER));
+    }
+
+    #[test]
+    fn final_exponentiation_matches_the_generic_exponentiation() {
+        const PHI_DIVIDED_BY_R: UnsignedInteger<20> = UnsignedInteger::from_hex_unchecked("f686b3d807d01c0bd38c3195c899ed3cde88eeb996ca394506632528d6a9a2f230063cf081517f68f7764c28b6f8ae5a72bce8d63cb9f827eca0ba621315b2076995003fc77a17988f8761bdc51dc2378b9039096d1b767f17fcbde783765915c97f36c6f18212ed0b283ed237db421d160aeb6a1e79983774940996754c8c71a2629b0dea236905ce937335d5b68fa9912aae208ccf1e516c3f438e3ba79");
+
+        let f = miller(
+            &BLS12381TwistCurve::generator(),
+            &BLS12381Curve::generator(),
+        );
+        let f1 = f.conjugate() * f.inv().unwrap();
+        let f2 = frobenius_square(&f1) * f1;
+        assert_eq!(final_exponentiation(&f), f2.pow(PHI_DIVIDED_BY_R));
+    }
+
     #[test]
     fn batch_ate_pairing_bilinearity() {
         let p = BLS12381Curve::generator();
@@ -259,6 +414,53 @@ mod tests {
         assert_eq!(result, FieldElement::one());
     }
 
+    #[test]
+    fn ate_pairing_is_bilinear_and_non_degenerate() {
+        let p = BLS12381Curve::generator();
+        let q = BLS12381TwistCurve::generator();
+        let e = BLS12381AtePairing::compute(&p, &q);
+        let r = U384::from_hex_unchecked(
+            "73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001",
+        );
+        assert_ne!(e, FieldElement::one());
+        assert_eq!(e.pow(r), FieldElement::one());
+        assert_eq!(
+            BLS12381AtePairing::compute(&p.operate_with_self(6_u64), &q.operate_with_self(5_u64)),
+            e.pow(30_u64)
+        );
+    }
+
+    #[test]
+    fn prepared_pairing_matches_compute_batch() {
+        let p = BLS12381Curve::generator();
+        let q = BLS12381TwistCurve::generator();
+        let (p1, q1) = (p.operate_with_self(7_u64), q.operate_with_self(3_u64));
+        let (p2, q2) = (p.operate_with_self(11_u64), q.neg());
+        let (prepared_q1, prepared_q2) = (G2Prepared::new(&q1), G2Prepared::from(&q2));
+
+        assert_eq!(
+            BLS12381AtePairing::compute_batch_prepared(&[(&p1, &prepared_q1)]),
+            BLS12381AtePairing::compute(&p1, &q1)
+        );
+        assert_eq!(
+            BLS12381AtePairing::compute_batch_prepared(&[(&p1, &prepared_q1), (&p2, &prepared_q2)]),
+            BLS12381AtePairing::compute_batch(&[(&p1, &q1), (&p2, &q2)])
+        );
+    }
+
+    #[test]
+    fn prepared_batch_ate_pairing_bilinearity() {
+        let p = BLS12381Curve::generator();
+        let q = G2Prepared::new(&BLS12381TwistCurve::generator().neg());
+        let q_times_b = G2Prepared::new(&BLS12381TwistCurve::generator().operate_with_self(93_u64));
+
+        let result = BLS12381AtePairing::compute_batch_prepared(&[
+            (&p.operate_with_self(11_u64), &q_times_b),
+            (&p.operate_with_self(11_u64 * 93), &q),
+        ]);
+        assert_eq!(result, FieldElement::one());
+    }
+
     #[test]
     fn ate_pairing_returns_one_when_one_element_is_the_neutral_element() {
         let p = BLS12381Curve::generator().to_affine();
@@ -271,4 +473,21 @@ mod tests {
         let result = BLS12381AtePairing::compute_batch(&[(&p, &q.to_affine())]);
         assert_eq!(result, FieldElement::one());
     }
+
+    #[test]
+    fn prepared_pairing_returns_one_when_one_element_is_the_neutral_element() {
+        let p = BLS12381Curve::generator();
+        let neutral_q = G2Prepared::new(&ShortWeierstrassProjectivePoint::neutral_element());
+        assert_eq!(
+            BLS12381AtePairing::compute_batch_prepared(&[(&p, &neutral_q)]),
+            FieldElement::one()
+        );
+
+        let q = G2Prepared::new(&BLS12381TwistCurve::generator());
+        let neutral_p = ShortWeierstrassProjectivePoint::neutral_element();
+        assert_eq!(
+            BLS12381AtePairing::compute_batch_prepared(&[(&neutral_p, &q)]),
+            FieldElement::one()
+        );
+    }
 }
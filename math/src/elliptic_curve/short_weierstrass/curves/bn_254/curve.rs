@@ -0,0 +1,112 @@
+use super::field_extension::{BN254PrimeField, Degree2ExtensionField};
+use crate::elliptic_curve::short_weierstrass::point::ShortWeierstrassProjectivePoint;
+use crate::elliptic_curve::traits::IsEllipticCurve;
+use crate::{
+    elliptic_curve::short_weierstrass::traits::IsShortWeierstrass, field::element::FieldElement,
+};
+
+pub type BN254FieldElement = FieldElement<BN254PrimeField>;
+pub type BN254TwistCurveFieldElement = FieldElement<Degree2ExtensionField>;
+
+/// The description of the curve, also known as alt_bn128.
+#[derive(Clone, Debug)]
+pub struct BN254Curve;
+
+impl IsEllipticCurve for BN254Curve {
+    type BaseField = BN254PrimeField;
+    type PointRepresentation = ShortWeierstrassProjectivePoint<Self>;
+
+    fn generator() -> Self::PointRepresentation {
+        Self::PointRepresentation::new([
+            FieldElement::<Self::BaseField>::new_base("1"),
+            FieldElement::<Self::BaseField>::new_base("2"),
+            FieldElement::one(),
+        ])
+    }
+}
+
+impl IsShortWeierstrass for BN254Curve {
+    fn a() -> FieldElement<Self::BaseField> {
+        FieldElement::from(0)
+    }
+
+    fn b() -> FieldElement<Self::BaseField> {
+        FieldElement::from(3)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        cyclic_group::IsGroup, elliptic_curve::traits::EllipticCurveError,
+        field::element::FieldElement, unsigned_integer::element::U256,
+    };
+
+    use super::BN254Curve;
+
+    #[allow(clippy::upper_case_acronyms)]
+    type FEE = FieldElement<BN254PrimeField>;
+
+    fn point(x: &str, y: &str) -> ShortWeierstrassProjectivePoint<BN254Curve> {
+        BN254Curve::create_point_from_affine(FEE::new_base(x), FEE::new_base(y)).unwrap()
+    }
+
+    // The following vectors are the ones of the tests of the ecAdd and ecMul precompiles
+    // of Ethereum.
+    #[test]
+    fn add_points_matches_ethereum_test_vector() {
+        let p = point(
+            "18b18acfb4c2c30276db5411368e7185b311dd124691610c5d3b74034e093dc9",
+            "063c909c4720840cb5134cb9f59fa749755796819658d32efc0d288198f37266",
+        );
+        let q = point(
+            "07c2b7f58a84bd6145f00c9c2bc0bb1a187f20ff2c92963a88019e7c6a014eed",
+            "06614e20c147e940f2d70da3f74c9a17df361706a4485c742bd6788478fa17d7",
+        );
+        let expected = point(
+            "2243525c5efd4b9c3d3c45ac0ca3fe4dd85e830a4ce6b65fa1eeaee202839703",
+            "301d1d33be6da8e509df21cc35964723180eed7532537db9ae5e7d48f195c915",
+        );
+        assert_eq!(p.operate_with(&q), expected);
+    }
+
+    #[test]
+    fn scalar_multiplication_matches_ethereum_test_vector() {
+        let p = point(
+            "2bd3e6d0f3b142924f5ca7b49ce5b9d54c4703d7ae5648e61d02268b1a0a9fb7",
+            "21611ce0a6af85915e2f1d70300909ce2e49dfad4a4619c8390cae66cefdb204",
+        );
+        let expected = point(
+            "070a8d6a982153cae4be29d434e8faef8a47b274a053f5a4ee2a6c9c13c31e5c",
+            "031b8ce914eba3a9ffb989f9cdd5b0f01943074bf4f0f315690ec3cec6981afc",
+        );
+        assert_eq!(p.operate_with_self(0x11138ce750fa15c2_u64), expected);
+    }
+
+    #[test]
+    fn generator_has_order_r() {
+        let r = U256::from_hex_unchecked(
+            "30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001",
+        );
+        let g = BN254Curve::generator();
+        assert!(g.operate_with_self(r).is_neutral_element());
+    }
+
+    #[test]
+    fn create_invalid_points_returns_an_error() {
+        assert_eq!(
+            BN254Curve::create_point_from_affine(FEE::from(1), FEE::from(1)),
+            Err(EllipticCurveError::InvalidPoint)
+        );
+    }
+
+    #[test]
+    fn operate_with_self_works_1() {
+        let g = BN254Curve::generator();
+        assert_eq!(
+            g.operate_with(&g).operate_with(&g),
+            g.operate_with_self(3_u16)
+        );
+    }
+}
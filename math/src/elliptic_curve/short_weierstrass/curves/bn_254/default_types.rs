@@ -0,0 +1,44 @@
+use crate::{
+    field::{
+        element::FieldElement,
+        fields::montgomery_backed_prime_fields::{IsModulus, MontgomeryBackendPrimeField},
+        traits::IsFFTField,
+    },
+    unsigned_integer::element::{UnsignedInteger, U256},
+};
+
+#[derive(Clone, Debug)]
+pub struct FrConfig;
+
+/// Modulus of bn 254 subgroup
+impl IsModulus<U256> for FrConfig {
+    const MODULUS: U256 = U256::from_hex_unchecked(
+        "30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001",
+    );
+}
+
+/// FrField using MontgomeryBackend for bn 254
+pub type FrField = MontgomeryBackendPrimeField<FrConfig, 4>;
+/// FrElement using MontgomeryBackend for bn 254
+pub type FrElement = FieldElement<FrField>;
+
+/// The root of unity is 5 raised to (r - 1) / 2^28, where 5 generates the multiplicative
+/// group of Fr.
+impl IsFFTField for FrField {
+    const TWO_ADICITY: u64 = 28;
+    const TWO_ADIC_PRIMITVE_ROOT_OF_UNITY: Self::BaseType = UnsignedInteger::from_hex_unchecked(
+        "2a3c09f0a58a7e8500e0a7eb8ef62abc402d111e41112ed49bd61b6e725b19f0",
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn two_adic_primitive_root_of_unity_has_order_two_to_the_28() {
+        let root = FrElement::new(FrField::TWO_ADIC_PRIMITVE_ROOT_OF_UNITY);
+        assert_eq!(root.pow(1_u64 << 27), -FrElement::one());
+        assert_eq!(root.pow(1_u64 << 28), FrElement::one());
+    }
+}
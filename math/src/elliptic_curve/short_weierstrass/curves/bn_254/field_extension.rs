@@ -0,0 +1,275 @@
+use crate::field::{
+    element::FieldElement,
+    errors::FieldError,
+    extensions::{
+        cubic::{CubicExtensionField, HasCubicNonResidue},
+        quadratic::{HasQuadraticNonResidue, QuadraticExtensionField},
+    },
+    fields::montgomery_backed_prime_fields::{IsModulus, MontgomeryBackendPrimeField},
+    traits::IsField,
+};
+use crate::unsigned_integer::element::U256;
+
+#[cfg(feature = "std")]
+use crate::traits::ByteConversion;
+
+pub const BN254_PRIME_FIELD_ORDER: U256 =
+    U256::from_hex_unchecked("30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47");
+
+// FPBN254
+#[derive(Clone, Debug)]
+pub struct BN254FieldModulus;
+impl IsModulus<U256> for BN254FieldModulus {
+    const MODULUS: U256 = BN254_PRIME_FIELD_ORDER;
+}
+
+pub type BN254PrimeField = MontgomeryBackendPrimeField<BN254FieldModulus, 4>;
+
+//////////////////
+/// Quadratic extension Fp[u] / (u^2 + 1). Since p = 3 (mod 4), -1 is not a square.
+#[derive(Clone, Debug)]
+pub struct Degree2ExtensionField;
+
+impl IsField for Degree2ExtensionField {
+    type BaseType = [FieldElement<BN254PrimeField>; 2];
+
+    /// Returns the component wise addition of `a` and `b`
+    fn add(a: &Self::BaseType, b: &Self::BaseType) -> Self::BaseType {
+        [&a[0] + &b[0], &a[1] + &b[1]]
+    }
+
+    /// Returns the multiplication of `a` and `b` using the following
+    /// equation:
+    /// (a0 + a1 * u) * (b0 + b1 * u) = a0 * b0 - a1 * b1 + (a0 * b1 + a1 * b0) * u
+    /// where `u.pow(2)` equals -1.
+    fn mul(a: &Self::BaseType, b: &Self::BaseType) -> Self::BaseType {
+        let a0b0 = &a[0] * &b[0];
+        let a1b1 = &a[1] * &b[1];
+        let z = (&a[0] + &a[1]) * (&b[0] + &b[1]);
+        [&a0b0 - &a1b1, z - a0b0 - a1b1]
+    }
+
+    fn square(a: &Self::BaseType) -> Self::BaseType {
+        let [a0, a1] = a;
+        let v0 = a0 * a1;
+        let c0 = (a0 + a1) * (a0 - a1);
+        let c1 = &v0 + &v0;
+        [c0, c1]
+    }
+
+    /// Returns the component wise subtraction of `a` and `b`
+    fn sub(a: &Self::BaseType, b: &Self::BaseType) -> Self::BaseType {
+        [&a[0] - &b[0], &a[1] - &b[1]]
+    }
+
+    /// Returns the component wise negation of `a`
+    fn neg(a: &Self::BaseType) -> Self::BaseType {
+        [-&a[0], -&a[1]]
+    }
+
+    /// Returns the multiplicative inverse of `a`
+    /// This uses the equality `(a0 + a1 * u) * (a0 - a1 * u) = a0.pow(2) + a1.pow(2)`
+    fn inv(a: &Self::BaseType) -> Result<Self::BaseType, FieldError> {
+        let inv_norm = (a[0].square() + a[1].square()).inv()?;
+        Ok([&a[0] * &inv_norm, -&a[1] * inv_norm])
+    }
+
+    /// Returns the division of `a` and `b`
+    fn div(a: &Self::BaseType, b: &Self::BaseType) -> Self::BaseType {
+        Self::mul(a, &Self::inv(b).unwrap())
+    }
+
+    /// Returns a boolean indicating whether `a` and `b` are equal component wise.
+    fn eq(a: &Self::BaseType, b: &Self::BaseType) -> bool {
+        a[0] == b[0] && a[1] == b[1]
+    }
+
+    /// Returns the additive neutral element of the field extension.
+    fn zero() -> Self::BaseType {
+        [FieldElement::zero(), FieldElement::zero()]
+    }
+
+    /// Returns the multiplicative neutral element of the field extension.
+    fn one() -> Self::BaseType {
+        [FieldElement::one(), FieldElement::zero()]
+    }
+
+    /// Returns the element `x * 1` where 1 is the multiplicative neutral element.
+    fn from_u64(x: u64) -> Self::BaseType {
+        [FieldElement::from(x), FieldElement::zero()]
+    }
+
+    /// Takes as input an element of BaseType and returns the internal representation
+    /// of that element in the field.
+    /// Note: for this case this is simply the identity, because the components
+    /// already have correct representations.
+    fn from_base_type(x: Self::BaseType) -> Self::BaseType {
+        x
+    }
+}
+
+impl FieldElement<Degree2ExtensionField> {
+    /// Returns `a0 - a1 * u` for `self = a0 + a1 * u`, which is also `self` raised to p.
+    pub fn conjugate(&self) -> Self {
+        let [a0, a1] = self.value();
+        Self::new([a0.clone(), -a1])
+    }
+}
+
+#[cfg(feature = "std")]
+impl ByteConversion for FieldElement<Degree2ExtensionField> {
+    fn to_bytes_be(&self) -> Vec<u8> {
+        let mut byte_slice = ByteConversion::to_bytes_be(&self.value()[0]);
+        byte_slice.extend(ByteConversion::to_bytes_be(&self.value()[1]));
+        byte_slice
+    }
+
+    fn to_bytes_le(&self) -> Vec<u8> {
+        let mut byte_slice = ByteConversion::to_bytes_le(&self.value()[0]);
+        byte_slice.extend(ByteConversion::to_bytes_le(&self.value()[1]));
+        byte_slice
+    }
+
+    fn from_bytes_be(bytes: &[u8]) -> Result<Self, crate::errors::ByteConversionError>
+    where
+        Self: std::marker::Sized,
+    {
+        const BYTES_PER_FIELD: usize = 32;
+        let x0 = FieldElement::from_bytes_be(&bytes[0..BYTES_PER_FIELD])?;
+        let x1 = FieldElement::from_bytes_be(&bytes[BYTES_PER_FIELD..BYTES_PER_FIELD * 2])?;
+        Ok(Self::new([x0, x1]))
+    }
+
+    fn from_bytes_le(bytes: &[u8]) -> Result<Self, crate::errors::ByteConversionError>
+    where
+        Self: std::marker::Sized,
+    {
+        const BYTES_PER_FIELD: usize = 32;
+        let x0 = FieldElement::from_bytes_le(&bytes[0..BYTES_PER_FIELD])?;
+        let x1 = FieldElement::from_bytes_le(&bytes[BYTES_PER_FIELD..BYTES_PER_FIELD * 2])?;
+        Ok(Self::new([x0, x1]))
+    }
+}
+
+///////////////
+/// Cubic non residue `9 + u` of Fp2, used to build Fp6 = Fp2[v] / (v^3 - (9 + u)).
+#[derive(Debug, Clone)]
+pub struct LevelTwoResidue;
+impl HasCubicNonResidue for LevelTwoResidue {
+    type BaseField = Degree2ExtensionField;
+
+    fn residue() -> FieldElement<Degree2ExtensionField> {
+        FieldElement::new([FieldElement::from(9), FieldElement::one()])
+    }
+}
+
+pub type Degree6ExtensionField = CubicExtensionField<LevelTwoResidue>;
+
+/// Quadratic non residue `v` of Fp6, used to build Fp12 = Fp6[w] / (w^2 - v).
+#[derive(Debug, Clone)]
+pub struct LevelThreeResidue;
+impl HasQuadraticNonResidue for LevelThreeResidue {
+    type BaseField = Degree6ExtensionField;
+
+    fn residue() -> FieldElement<Degree6ExtensionField> {
+        FieldElement::new([
+            FieldElement::zero(),
+            FieldElement::one(),
+            FieldElement::zero(),
+        ])
+    }
+}
+
+pub type Degree12ExtensionField = QuadraticExtensionField<LevelThreeResidue>;
+
+impl FieldElement<BN254PrimeField> {
+    pub fn new_base(a_hex: &str) -> Self {
+        Self::new(U256::from(a_hex))
+    }
+}
+
+impl FieldElement<Degree2ExtensionField> {
+    pub fn new_base(a_hex: &str) -> Self {
+        Self::new([FieldElement::new(U256::from(a_hex)), FieldElement::zero()])
+    }
+}
+
+impl FieldElement<Degree6ExtensionField> {
+    pub fn new_base(a_hex: &str) -> Self {
+        Self::new([
+            FieldElement::new([FieldElement::new(U256::from(a_hex)), FieldElement::zero()]),
+            FieldElement::zero(),
+            FieldElement::zero(),
+        ])
+    }
+}
+
+impl FieldElement<Degree12ExtensionField> {
+    pub fn new_base(a_hex: &str) -> Self {
+        Self::new([
+            FieldElement::<Degree6ExtensionField>::new_base(a_hex),
+            FieldElement::zero(),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    type FpE = FieldElement<BN254PrimeField>;
+    type Fp2E = FieldElement<Degree2ExtensionField>;
+    type Fp6E = FieldElement<Degree6ExtensionField>;
+    type Fp12E = FieldElement<Degree12ExtensionField>;
+
+    fn sample_fp12() -> Fp12E {
+        let coefficients: Vec<_> = (1..=12_u64).map(|i| FpE::from(i * i * 7919 + 13)).collect();
+        let fp2 = |i: usize| Fp2E::new([coefficients[i].clone(), coefficients[i + 1].clone()]);
+        Fp12E::new([
+            Fp6E::new([fp2(0), fp2(2), fp2(4)]),
+            Fp6E::new([fp2(6), fp2(8), fp2(10)]),
+        ])
+    }
+
+    #[test]
+    fn square_of_u_is_minus_one() {
+        let u = Fp2E::new([FpE::zero(), FpE::one()]);
+        assert_eq!(u.square(), -Fp2E::one());
+    }
+
+    #[test]
+    fn cube_of_v_is_the_level_two_residue() {
+        let v = Fp6E::new([Fp2E::zero(), Fp2E::one(), Fp2E::zero()]);
+        assert_eq!(
+            v.pow(3_u64),
+            Fp6E::new([LevelTwoResidue::residue(), Fp2E::zero(), Fp2E::zero()])
+        );
+    }
+
+    #[test]
+    fn conjugate_is_the_frobenius_map() {
+        let a = Fp2E::new([FpE::from(3), FpE::from(5)]);
+        assert_eq!(a.conjugate(), a.pow(BN254_PRIME_FIELD_ORDER));
+    }
+
+    #[test]
+    fn square_matches_multiplication() {
+        let a = sample_fp12();
+        assert_eq!(a.square(), &a * &a);
+    }
+
+    #[test]
+    fn inverse_times_element_is_one() {
+        let a = sample_fp12();
+        assert_eq!(&a * a.inv().unwrap(), Fp12E::one());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn byte_conversion_of_degree_two_elements_roundtrips() {
+        let a = Fp2E::new([FpE::from(0x1234), FpE::from(0x5678)]);
+        let bytes = a.to_bytes_be();
+        assert_eq!(bytes.len(), 64);
+        assert_eq!(Fp2E::from_bytes_be(&bytes).unwrap(), a);
+        assert_eq!(Fp2E::from_bytes_le(&a.to_bytes_le()).unwrap(), a);
+    }
+}
@@ -0,0 +1,7 @@
+pub mod curve;
+pub mod default_types;
+pub mod field_extension;
+pub mod twist;
+
+#[cfg(feature = "std")]
+pub mod pairing;
@@ -0,0 +1,314 @@
+use super::field_extension::{
+    BN254PrimeField, Degree12ExtensionField, Degree2ExtensionField, Degree6ExtensionField,
+    LevelTwoResidue,
+};
+use super::{curve::BN254Curve, twist::BN254TwistCurve};
+use crate::{
+    cyclic_group::IsGroup,
+    elliptic_curve::short_weierstrass::line_function::{
+        self, add_step, double_step, LineFunction, TwistType,
+    },
+    elliptic_curve::short_weierstrass::point::ShortWeierstrassProjectivePoint,
+    elliptic_curve::traits::IsPairing,
+    field::element::FieldElement,
+    unsigned_integer::element::UnsignedInteger,
+};
+
+type FpE = FieldElement<BN254PrimeField>;
+type Fp2E = FieldElement<Degree2ExtensionField>;
+type Fp12E = FieldElement<Degree12ExtensionField>;
+type G1Point = ShortWeierstrassProjectivePoint<BN254Curve>;
+type G2Point = ShortWeierstrassProjectivePoint<BN254TwistCurve>;
+
+#[derive(Clone)]
+pub struct BN254AtePairing;
+impl IsPairing for BN254AtePairing {
+    type G1Point = G1Point;
+    type G2Point = G2Point;
+    type OutputField = Degree12ExtensionField;
+
+    /// Compute the product of the optimal ate pairings for a list of point pairs.
+    fn compute_batch(pairs: &[(&Self::G1Point, &Self::G2Point)]) -> Fp12E {
+        let mut result = FieldElement::one();
+        for (p, q) in pairs {
+            if !p.is_neutral_element() && !q.is_neutral_element() {
+                let p = p.to_affine();
+                let q = q.to_affine();
+                result = result * miller(&q, &p);
+            }
+        }
+        final_exponentiation(&result)
+    }
+}
+
+/// Digits of 6x + 2 in non adjacent form, from the least significant one,
+/// where x = 4965661367192848881 is the parameter of the BN254 curve.
+const MILLER_LOOP_NAF: [i8; 66] = [
+    0, 0, 0, 1, 0, 1, 0, -1, 0, 0, -1, 0, 0, 0, 1, 0, 0, -1, 0, -1, 0, 0, 0, 1, 0, -1, 0, 0, 0, 0,
+    -1, 0, 0, 1, 0, -1, 0, 0, 1, 0, 0, 0, 0, 0, -1, 0, 0, -1, 0, 1, 0, -1, 0, 0, 0, -1, 0, -1, 0,
+    0, 0, 1, 0, -1, 0, 1,
+];
+
+/// Doubles `t` and multiplies the accumulator, squared, by the tangent line at `t`
+/// evaluated at `p`.
+fn double_accumulate_line(t: &mut G2Point, p: &G1Point, accumulator: &mut Fp12E) {
+    let line = double_step(t);
+    *accumulator = mul_by_line(&accumulator.square(), &line, p);
+}
+
+/// Adds the affine point `q` to `t` and multiplies the accumulator by the line through
+/// them evaluated at `p`.
+fn add_accumulate_line(t: &mut G2Point, q: &G2Point, p: &G1Point, accumulator: &mut Fp12E) {
+    let line = add_step(t, q);
+    *accumulator = mul_by_line(accumulator, &line, p);
+}
+
+fn mul_by_line(f: &Fp12E, line: &LineFunction<Degree2ExtensionField>, p: &G1Point) -> Fp12E {
+    line_function::mul_by_line::<LevelTwoResidue, _, _>(f, line, p, TwistType::D)
+}
+
+/// Returns the image of `q` by the Frobenius endomorphism of the curve over Fp12, taken
+/// back to the twist. Its action on the coordinates is the conjugation followed by the
+/// product by (9 + u)^((p - 1) / 3) and (9 + u)^((p - 1) / 2).
+fn frobenius_twist(q: &G2Point) -> G2Point {
+    let gamma_x = Fp2E::new([
+        FpE::new_base("2fb347984f7911f74c0bec3cf559b143b78cc310c2c3330c99e39557176f553d"),
+        FpE::new_base("16c9e55061ebae204ba4cc8bd75a079432ae2a1d0b7c9dce1665d51c640fcba2"),
+    ]);
+    let gamma_y = Fp2E::new([
+        FpE::new_base("063cf305489af5dcdc5ec698b6e2f9b9dbaae0eda9c95998dc54014671a0135a"),
+        FpE::new_base("07c03cbcac41049a0704b5a7ec796f2b21807dc98fa25bd282d37f632623b0e3"),
+    ]);
+    let [x, y, z] = q.coordinates();
+    G2Point::new([
+        x.conjugate() * gamma_x,
+        y.conjugate() * gamma_y,
+        z.conjugate(),
+    ])
+}
+
+/// Implements the Miller loop of the optimal ate pairing of the BN254 curve, of
+/// length 6x + 2, followed by the lines through π(q) and -π^2(q), where π is the
+/// Frobenius endomorphism.
+/// Based on algorithm 1 of "High-Speed Software Implementation of the Optimal Ate
+/// Pairing over Barreto-Naehrig Curves" (https://eprint.iacr.org/2010/354.pdf).
+fn miller(q: &G2Point, p: &G1Point) -> Fp12E {
+    let mut t = q.clone();
+    let mut f = Fp12E::one();
+    let q_neg = q.neg();
+
+    for digit in MILLER_LOOP_NAF.iter().rev().skip(1) {
+        double_accumulate_line(&mut t, p, &mut f);
+        match digit {
+            1 => add_accumulate_line(&mut t, q, p, &mut f),
+            -1 => add_accumulate_line(&mut t, &q_neg, p, &mut f),
+            _ => {}
+        }
+    }
+
+    let q1 = frobenius_twist(q);
+    let q2 = frobenius_twist(&q1).neg();
+    add_accumulate_line(&mut t, &q1, p, &mut f);
+    add_accumulate_line(&mut t, &q2, p, &mut f);
+    f
+}
+
+/// Auxiliary function for the final exponentiation of the ate pairing.
+fn frobenius_square(f: &Fp12E) -> Fp12E {
+    let [a, b] = f.value();
+    let w_raised_to_p_squared_minus_one = FieldElement::<Degree6ExtensionField>::new_base(
+        "30644e72e131a0295e6dd9e7e0acccb0c28f069fbb966e3de4bd44e5607cfd49",
+    );
+    let omega_3 =
+        Fp2E::new_base("30644e72e131a0295e6dd9e7e0acccb0c28f069fbb966e3de4bd44e5607cfd48");
+    let omega_3_squared = Fp2E::new_base("59e26bcea0d48bacd4f263f1acdb5c4f5763473177fffffe");
+
+    let [a0, a1, a2] = a.value();
+    let [b0, b1, b2] = b.value();
+
+    let f0 = FieldElement::new([a0.clone(), a1 * &omega_3, a2 * &omega_3_squared]);
+    let f1 = FieldElement::new([b0.clone(), b1 * omega_3, b2 * omega_3_squared]);
+
+    FieldElement::new([f0, f1 * w_raised_to_p_squared_minus_one])
+}
+
+// The easy part of the final exponentiation raises to (p^6 - 1)(p^2 + 1), and the hard
+// part to (p^4 - p^2 + 1) / r.
+fn final_exponentiation(base: &Fp12E) -> Fp12E {
+    const PHI_DIVIDED_BY_R: UnsignedInteger<12> = UnsignedInteger::from_hex_unchecked("1baaa710b0759ad331ec15183177faf6c0eb522d5b122784e529a5861876f6b3b1b1355d189227d79581e16f3fd90c66b887d56d5095f23aaa441e3954bcf8adcc7b44c87cdbacff1154e7e1da014fd5abf5cc4f49c36d4e81bb482ccdf42b1");
+
+    let f1 = base.conjugate() * base.inv().unwrap();
+    let f2 = frobenius_square(&f1) * f1;
+    f2.pow(PHI_DIVIDED_BY_R)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        elliptic_curve::traits::IsEllipticCurve,
+        unsigned_integer::element::{U256, U384},
+    };
+
+    use super::*;
+
+    const SUBGROUP_ORDER: U256 = U256::from_hex_unchecked(
+        "30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001",
+    );
+
+    #[test]
+    fn test_double_accumulate_line_doubles_point_correctly() {
+        let g1 = BN254Curve::generator();
+        let g2 = BN254TwistCurve::generator();
+        let mut r = g2.clone();
+        let mut f = FieldElement::one();
+        double_accumulate_line(&mut r, &g1, &mut f);
+        assert_eq!(r, g2.operate_with(&g2));
+    }
+
+    #[test]
+    fn test_add_accumulate_line_adds_points_correctly() {
+        let g1 = BN254Curve::generator();
+        let g = BN254TwistCurve::generator();
+        let a: u64 = 12;
+        let b: u64 = 23;
+        let g2 = g.operate_with_self(a).to_affine();
+        let g3 = g.operate_with_self(b).to_affine();
+        let expected = g.operate_with_self(a + b);
+        let mut r = g2;
+        let mut f = FieldElement::one();
+        add_accumulate_line(&mut r, &g3, &g1, &mut f);
+        assert_eq!(r, expected);
+    }
+
+    #[test]
+    fn mul_by_line_matches_multiplication_by_the_full_line() {
+        type Fp6E = FieldElement<Degree6ExtensionField>;
+        let p = BN254Curve::generator().operate_with_self(5_u64).to_affine();
+        let mut t = BN254TwistCurve::generator();
+        let line = double_step(&mut t);
+        let [px, py, _] = p.coordinates();
+        let full_line = Fp12E::new([
+            Fp6E::new([
+                &line.cy * Fp2E::new([py.clone(), FpE::zero()]),
+                Fp2E::zero(),
+                Fp2E::zero(),
+            ]),
+            Fp6E::new([
+                &line.cx * Fp2E::new([px.clone(), FpE::zero()]),
+                line.c.clone(),
+                Fp2E::zero(),
+            ]),
+        ]);
+        let f = miller(&BN254TwistCurve::generator(), &p);
+        assert_eq!(mul_by_line(&f, &line, &p), f * full_line);
+    }
+
+    #[test]
+    fn frobenius_twist_is_the_multiplication_by_p_on_g2() {
+        let q = BN254TwistCurve::generator().operate_with_self(5_u64);
+        let p = super::super::field_extension::BN254_PRIME_FIELD_ORDER;
+        assert_eq!(frobenius_twist(&q), q.operate_with_self(p));
+    }
+
+    #[test]
+    fn frobenius_square_is_the_power_to_p_squared() {
+        let f = BN254AtePairing::compute(&BN254Curve::generator(), &BN254TwistCurve::generator());
+        let p = U384::from_hex_unchecked(
+            "30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47",
+        );
+        assert_eq!(frobenius_square(&f), f.pow(p).pow(p));
+    }
+
+    #[test]
+    fn pairing_of_the_generators_has_order_r() {
+        let result =
+            BN254AtePairing::compute(&BN254Curve::generator(), &BN254TwistCurve::generator());
+        assert_ne!(result, FieldElement::one());
+        assert_eq!(result.pow(SUBGROUP_ORDER), FieldElement::one());
+    }
+
+    #[test]
+    fn batch_ate_pairing_bilinearity() {
+        let p = BN254Curve::generator();
+        let q = BN254TwistCurve::generator();
+        let a = U256::from_u64(11);
+        let b = U256::from_u64(93);
+
+        let result = BN254AtePairing::compute_batch(&[
+            (
+                &p.operate_with_self(a).to_affine(),
+                &q.operate_with_self(b).to_affine(),
+            ),
+            (
+                &p.operate_with_self(a * b).to_affine(),
+                &q.neg().to_affine(),
+            ),
+        ]);
+        assert_eq!(result, FieldElement::one());
+    }
+
+    #[test]
+    fn ate_pairing_is_bilinear_in_each_argument() {
+        let p = BN254Curve::generator();
+        let q = BN254TwistCurve::generator();
+        let base = BN254AtePairing::compute(&p, &q);
+        assert_eq!(
+            BN254AtePairing::compute(&p.operate_with_self(6_u64), &q.operate_with_self(5_u64)),
+            base.pow(30_u64)
+        );
+    }
+
+    #[test]
+    fn ate_pairing_returns_one_when_one_element_is_the_neutral_element() {
+        let p = BN254Curve::generator().to_affine();
+        let q = ShortWeierstrassProjectivePoint::neutral_element();
+        let result = BN254AtePairing::compute_batch(&[(&p.to_affine(), &q)]);
+        assert_eq!(result, FieldElement::one());
+
+        let p = ShortWeierstrassProjectivePoint::neutral_element();
+        let q = BN254TwistCurve::generator();
+        let result = BN254AtePairing::compute_batch(&[(&p, &q.to_affine())]);
+        assert_eq!(result, FieldElement::one());
+    }
+
+    /// Test vector of the ecPairing precompile of Ethereum, whose input encodes the
+    /// points of G2 with the coefficient of u first.
+    #[test]
+    fn ate_pairing_matches_ethereum_test_vector() {
+        let g1_point = |x: &str, y: &str| {
+            BN254Curve::create_point_from_affine(FpE::new_base(x), FpE::new_base(y)).unwrap()
+        };
+        let g2_point = |x_1: &str, x_0: &str, y_1: &str, y_0: &str| {
+            BN254TwistCurve::create_point_from_affine(
+                Fp2E::new([FpE::new_base(x_0), FpE::new_base(x_1)]),
+                Fp2E::new([FpE::new_base(y_0), FpE::new_base(y_1)]),
+            )
+            .unwrap()
+        };
+        let p1 = g1_point(
+            "1c76476f4def4bb94541d57ebba1193381ffa7aa76ada664dd31c16024c43f59",
+            "3034dd2920f673e204fee2811c678745fc819b55d3e9d294e45c9b03a76aef41",
+        );
+        let q1 = g2_point(
+            "209dd15ebff5d46c4bd888e51a93cf99a7329636c63514396b4a452003a35bf7",
+            "04bf11ca01483bfa8b34b43561848d28905960114c8ac04049af4b6315a41678",
+            "2bb8324af6cfc93537a2ad1a445cfd0ca2a71acd7ac41fadbf933c2a51be344d",
+            "120a2a4cf30c1bf9845f20c6fe39e07ea2cce61f0c9bb048165fe5e4de877550",
+        );
+        let p2 = g1_point(
+            "111e129f1cf1097710d41c4ac70fcdfa5ba2023c6ff1cbeac322de49d1b6df7c",
+            "2032c61a830e3c17286de9462bf242fca2883585b93870a73853face6a6bf411",
+        );
+        let q2 = g2_point(
+            "198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2",
+            "1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed",
+            "090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b",
+            "12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa",
+        );
+        assert_eq!(q2, BN254TwistCurve::generator());
+
+        let result = BN254AtePairing::compute_batch(&[(&p1, &q1), (&p2, &q2)]);
+        assert_eq!(result, FieldElement::one());
+        assert_ne!(BN254AtePairing::compute(&p1, &q1), FieldElement::one());
+    }
+}
@@ -0,0 +1,129 @@
+use crate::elliptic_curve::short_weierstrass::point::ShortWeierstrassProjectivePoint;
+use crate::elliptic_curve::traits::IsEllipticCurve;
+use crate::unsigned_integer::element::U256;
+use crate::{
+    elliptic_curve::short_weierstrass::traits::IsShortWeierstrass, field::element::FieldElement,
+};
+
+use super::{curve::BN254FieldElement as FpE, field_extension::Degree2ExtensionField};
+
+const GENERATOR_X_0: U256 =
+    U256::from_hex_unchecked("1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed");
+const GENERATOR_X_1: U256 =
+    U256::from_hex_unchecked("198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2");
+const GENERATOR_Y_0: U256 =
+    U256::from_hex_unchecked("12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa");
+const GENERATOR_Y_1: U256 =
+    U256::from_hex_unchecked("090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b");
+
+/// The description of the D-type sextic twist y^2 = x^3 + 3 / (9 + u) of the curve,
+/// whose subgroup of order r is G2. A point (x, y) of the twist maps to the point
+/// (x w^2, y w^3) of the curve over Fp12.
+#[derive(Clone, Debug)]
+pub struct BN254TwistCurve;
+
+impl IsEllipticCurve for BN254TwistCurve {
+    type BaseField = Degree2ExtensionField;
+    type PointRepresentation = ShortWeierstrassProjectivePoint<Self>;
+
+    fn generator() -> Self::PointRepresentation {
+        Self::PointRepresentation::new([
+            FieldElement::new([
+                FieldElement::new(GENERATOR_X_0),
+                FieldElement::new(GENERATOR_X_1),
+            ]),
+            FieldElement::new([
+                FieldElement::new(GENERATOR_Y_0),
+                FieldElement::new(GENERATOR_Y_1),
+            ]),
+            FieldElement::one(),
+        ])
+    }
+}
+
+impl IsShortWeierstrass for BN254TwistCurve {
+    fn a() -> FieldElement<Self::BaseField> {
+        FieldElement::zero()
+    }
+
+    fn b() -> FieldElement<Self::BaseField> {
+        FieldElement::new([
+            FpE::new_base("2b149d40ceb8aaae81be18991be06ac3b5b4c5e559dbefa33267e6dc24a138e5"),
+            FpE::new_base("9713b03af0fed4cd2cafadeed8fdf4a74fa084e52d1852e4a2bd0685c315d2"),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        cyclic_group::IsGroup,
+        elliptic_curve::{
+            short_weierstrass::{
+                curves::bn_254::field_extension::{
+                    BN254PrimeField, Degree2ExtensionField, LevelTwoResidue,
+                },
+                traits::IsShortWeierstrass,
+            },
+            traits::IsEllipticCurve,
+        },
+        field::{element::FieldElement, extensions::cubic::HasCubicNonResidue},
+        unsigned_integer::element::U256,
+    };
+
+    use super::BN254TwistCurve;
+    type Level0FE = FieldElement<BN254PrimeField>;
+    type Level1FE = FieldElement<Degree2ExtensionField>;
+
+    #[cfg(feature = "std")]
+    use crate::elliptic_curve::short_weierstrass::point::{
+        Endianness, PointFormat, ShortWeierstrassProjectivePoint,
+    };
+
+    #[test]
+    fn b_is_three_divided_by_the_level_two_residue() {
+        assert_eq!(
+            BN254TwistCurve::b() * LevelTwoResidue::residue(),
+            Level1FE::from(3)
+        );
+    }
+
+    #[test]
+    fn create_generator() {
+        let g = BN254TwistCurve::generator();
+        let [x, y, _] = g.coordinates();
+        assert_eq!(BN254TwistCurve::defining_equation(x, y), Level1FE::zero());
+    }
+
+    #[test]
+    fn generator_has_order_r() {
+        let r = U256::from_hex_unchecked(
+            "30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001",
+        );
+        let g = BN254TwistCurve::generator();
+        assert!(g.operate_with_self(r).is_neutral_element());
+        assert!(!g.is_neutral_element());
+    }
+
+    #[test]
+    fn create_invalid_points_returns_an_error() {
+        let x = Level1FE::new([Level0FE::from(1), Level0FE::from(2)]);
+        assert!(BN254TwistCurve::create_point_from_affine(x.clone(), x).is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn serialize_deserialize_generator() {
+        let g = BN254TwistCurve::generator();
+        let bytes = g.serialize(PointFormat::Projective, Endianness::LittleEndian);
+
+        let deserialized = ShortWeierstrassProjectivePoint::<BN254TwistCurve>::deserialize(
+            &bytes,
+            PointFormat::Projective,
+            Endianness::LittleEndian,
+        )
+        .unwrap();
+
+        assert_eq!(deserialized, g);
+    }
+}
@@ -1,5 +1,6 @@
 pub mod bls12_377;
 pub mod bls12_381;
+pub mod bn_254;
 pub mod stark_curve;
 pub mod test_curve_1;
 pub mod test_curve_2;
@@ -1,5 +1,8 @@
 /// Implementation of particular cases of elliptic curves.
 pub mod curves;
+/// Line functions and loop bits of the Miller loops of the pairings whose G2 lies on a
+/// sextic twist.
+pub(crate) mod line_function;
 /// Structs for points
 pub mod point;
 /// Common behaviour for Elliptic curves.
@@ -20,6 +20,18 @@ pub enum DeserializationError {
     FieldFromBytesError,
     PointerSizeError,
     InvalidValue,
+    /// The bytes do not start with the magic bytes of the expected format.
+    InvalidMagicBytes,
+    /// The bytes are encoded with a version of the format that is not supported.
+    UnsupportedVersion,
+    /// The bytes encode elements of a different field than the expected one.
+    FieldMismatch,
+    /// The bytes encode commitments of a different hash function than the expected one.
+    HashMismatch,
+    /// There are bytes left after the end of the encoded value.
+    TrailingBytes,
+    /// The bytes encode a proof generated with other options than the expected ones.
+    OptionsMismatch,
 }
 
 impl From<ByteConversionError> for DeserializationError {
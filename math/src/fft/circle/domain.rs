@@ -0,0 +1,193 @@
+use crate::{cyclic_group::IsGroup, field::fields::mersenne31::Mersenne31Field};
+
+use super::point::CirclePoint;
+
+type Point = CirclePoint<Mersenne31Field>;
+
+/// Coset `initial * <step>` of the subgroup of order 2^`log_size` of the circle group of
+/// Mersenne 31. Its points are indexed by `i -> initial * step^i`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Coset {
+    pub initial: Point,
+    pub step: Point,
+    pub log_size: u32,
+}
+
+impl Coset {
+    /// Returns the coset of the subgroup of order 2^`log_size` that contains `initial`.
+    pub fn new(initial: Point, log_size: u32) -> Self {
+        Self {
+            initial,
+            step: Point::subgroup_generator(log_size),
+            log_size,
+        }
+    }
+
+    /// Returns the subgroup of order 2^`log_size`.
+    pub fn subgroup(log_size: u32) -> Self {
+        Self::new(Point::neutral_element(), log_size)
+    }
+
+    /// Returns the coset of the subgroup of order 2^`log_size` whose points are the odd
+    /// powers of a generator of the subgroup of order 2^(`log_size` + 1).
+    pub fn odds(log_size: u32) -> Self {
+        Self::new(Point::subgroup_generator(log_size + 1), log_size)
+    }
+
+    pub fn size(&self) -> usize {
+        1 << self.log_size
+    }
+
+    /// Returns the point of index `i`.
+    pub fn at(&self, i: usize) -> Point {
+        self.initial
+            .operate_with(&self.step.operate_with_self(i as u64))
+    }
+
+    /// Returns the points of the coset in order of their index.
+    pub fn points(&self) -> Vec<Point> {
+        core::iter::successors(Some(self.initial.clone()), |point| {
+            Some(point.operate_with(&self.step))
+        })
+        .take(self.size())
+        .collect()
+    }
+
+    /// Returns the image of the coset by the squaring map, a coset of half its size.
+    pub fn double(&self) -> Self {
+        Self {
+            initial: self.initial.double(),
+            step: self.step.double(),
+            log_size: self.log_size.saturating_sub(1),
+        }
+    }
+
+    /// Returns the coset of the conjugates of the points of `self`.
+    pub fn conjugate(&self) -> Self {
+        Self {
+            initial: self.initial.neg(),
+            step: self.step.neg(),
+            log_size: self.log_size,
+        }
+    }
+}
+
+/// Domain of the circle FFT: the union of a coset `H` of size N / 2 and its conjugate,
+/// where `H` and its conjugate are disjoint. Its points are indexed by `i -> H[i]` for
+/// `i < N / 2` and `i -> conjugate(H[i - N / 2])` otherwise.
+///
+/// The x coordinates of `H` are closed under negation, and squaring maps them two to one
+/// onto the x coordinates of the doubled coset, which makes the domain suitable for the
+/// recursive halving of the FFT.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CircleDomain {
+    half_coset: Coset,
+}
+
+impl CircleDomain {
+    /// Returns the domain of `half_coset` and its conjugate, or `None` if they are not
+    /// disjoint, which happens when the square of `half_coset.initial` lies in the subgroup
+    /// of `half_coset`.
+    pub fn new(half_coset: Coset) -> Option<Self> {
+        if half_coset
+            .initial
+            .double()
+            .is_in_subgroup(half_coset.log_size)
+        {
+            None
+        } else {
+            Some(Self { half_coset })
+        }
+    }
+
+    /// Returns the canonic coset of size 2^`log_size`, the odd powers of a generator of
+    /// the subgroup of order 2^(`log_size` + 1). It is the domain of `Coset::odds`.
+    /// # Panics
+    /// Panics if `log_size` is zero.
+    pub fn canonic(log_size: u32) -> Self {
+        assert!(log_size > 0, "A circle domain has at least two points");
+        Self {
+            half_coset: Coset::new(Point::subgroup_generator(log_size + 1), log_size - 1),
+        }
+    }
+
+    pub fn half_coset(&self) -> &Coset {
+        &self.half_coset
+    }
+
+    pub fn log_size(&self) -> u32 {
+        self.half_coset.log_size + 1
+    }
+
+    pub fn size(&self) -> usize {
+        1 << self.log_size()
+    }
+
+    /// Returns the point of index `i`.
+    pub fn at(&self, i: usize) -> Point {
+        let half_size = self.half_coset.size();
+        if i < half_size {
+            self.half_coset.at(i)
+        } else {
+            self.half_coset.at(i - half_size).neg()
+        }
+    }
+
+    /// Returns the points of the domain in order of their index.
+    pub fn points(&self) -> Vec<Point> {
+        let half_coset_points = self.half_coset.points();
+        let conjugates = half_coset_points.iter().map(|point| point.neg()).collect();
+        [half_coset_points, conjugates].concat()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn points_of_a_coset_match_its_indexing() {
+        let coset = Coset::new(Point::GENERATOR.operate_with_self(3_u64), 4);
+        let points = coset.points();
+        assert_eq!(points.len(), 16);
+        for (i, point) in points.iter().enumerate() {
+            assert_eq!(*point, coset.at(i));
+        }
+        assert_eq!(coset.at(16), coset.initial);
+    }
+
+    #[test]
+    fn canonic_domain_is_the_coset_of_odd_powers() {
+        let domain = CircleDomain::canonic(5);
+        let mut points = domain.points();
+        let mut odds = Coset::odds(5).points();
+        let key = |point: &Point| (*point.x.value(), *point.y.value());
+        points.sort_by_key(key);
+        odds.sort_by_key(key);
+        assert_eq!(points, odds);
+    }
+
+    #[test]
+    fn domain_points_match_their_indexing() {
+        let domain = CircleDomain::canonic(4);
+        for (i, point) in domain.points().iter().enumerate() {
+            assert_eq!(*point, domain.at(i));
+        }
+    }
+
+    #[test]
+    fn domain_of_a_subgroup_is_rejected() {
+        assert_eq!(CircleDomain::new(Coset::subgroup(3)), None);
+        let shift = Point::subgroup_generator(4);
+        assert_eq!(CircleDomain::new(Coset::new(shift, 3)), None);
+        assert!(CircleDomain::new(Coset::new(Point::GENERATOR, 3)).is_some());
+    }
+
+    #[test]
+    fn double_of_a_coset_is_its_image_by_squaring() {
+        let coset = Coset::new(Point::GENERATOR.operate_with_self(7_u64), 3);
+        let doubled: Vec<_> = coset.points().iter().map(|point| point.double()).collect();
+        assert_eq!(doubled[..4], coset.double().points()[..]);
+        assert_eq!(doubled[4..], coset.double().points()[..]);
+    }
+}
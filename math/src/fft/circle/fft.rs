@@ -0,0 +1,250 @@
+use crate::{fft::errors::FFTError, field::fields::mersenne31::M31};
+
+use super::domain::{CircleDomain, Coset};
+
+/// Executes the circle FFT: returns the evaluations on `domain`, in the order of its
+/// points, of the polynomial with the given coefficients in the FFT basis.
+///
+/// The basis function of index `j` with bits `j_0, j_1, ...` is
+/// `y^j_0 * x^j_1 * π(x)^j_2 * π(π(x))^j_3 * ...`, where `π(x) = 2x^2 - 1` is the
+/// x coordinate of the square of a point. Coefficients beyond the ones given are zero,
+/// so a polynomial can be evaluated on a domain larger than its number of coefficients.
+pub fn evaluate(coefficients: &[M31], domain: &CircleDomain) -> Result<Vec<M31>, FFTError> {
+    if coefficients.len() > domain.size() {
+        return Err(FFTError::DomainSizeError(domain.size(), coefficients.len()));
+    }
+    let mut coefficients = coefficients.to_vec();
+    coefficients.resize(domain.size(), M31::zero());
+
+    // f(x, y) = f_0(x) + y f_1(x), and the conjugate points only differ in the sign of y.
+    let (even, odd) = deinterleave(&coefficients);
+    let half_coset = domain.half_coset();
+    let even = evaluate_on_line(&even, half_coset);
+    let odd = evaluate_on_line(&odd, half_coset);
+    let odd: Vec<_> = half_coset
+        .points()
+        .iter()
+        .zip(odd)
+        .map(|(point, value)| point.y * value)
+        .collect();
+    let evaluations = even.iter().zip(&odd).map(|(e, o)| e + o);
+    let conjugate_evaluations = even.iter().zip(&odd).map(|(e, o)| e - o);
+    Ok(evaluations.chain(conjugate_evaluations).collect())
+}
+
+/// Executes the inverse circle FFT: returns the coefficients in the FFT basis of the
+/// polynomial with the given evaluations on `domain`, in the order of its points.
+/// See `evaluate` for the basis.
+pub fn interpolate(evaluations: &[M31], domain: &CircleDomain) -> Result<Vec<M31>, FFTError> {
+    if evaluations.len() != domain.size() {
+        return Err(FFTError::DomainSizeError(domain.size(), evaluations.len()));
+    }
+    let half_coset = domain.half_coset();
+    let (values, conjugate_values) = evaluations.split_at(half_coset.size());
+
+    // f_0(x) = (f(x, y) + f(x, -y)) / 2 and f_1(x) = (f(x, y) - f(x, -y)) / 2y.
+    let mut inverses: Vec<_> = half_coset
+        .points()
+        .iter()
+        .map(|point| point.y + point.y)
+        .collect();
+    M31::inplace_batch_inverse(&mut inverses)?;
+    let two_inv = M31::from(2).inv()?;
+    let even: Vec<_> = values
+        .iter()
+        .zip(conjugate_values)
+        .map(|(a, b)| (a + b) * two_inv)
+        .collect();
+    let odd: Vec<_> = values
+        .iter()
+        .zip(conjugate_values)
+        .zip(&inverses)
+        .map(|((a, b), inverse)| (a - b) * inverse)
+        .collect();
+    let even = interpolate_on_line(&even, half_coset, &two_inv)?;
+    let odd = interpolate_on_line(&odd, half_coset, &two_inv)?;
+    Ok(interleave(&even, &odd))
+}
+
+/// Evaluates the polynomial `g(x) = g_0(π(x)) + x g_1(π(x))` on the x coordinates of
+/// `coset`, whose negations are the x coordinates of the second half of the coset.
+fn evaluate_on_line(coefficients: &[M31], coset: &Coset) -> Vec<M31> {
+    if coefficients.len() == 1 {
+        return coefficients.to_vec();
+    }
+    let (even, odd) = deinterleave(coefficients);
+    let doubled_coset = coset.double();
+    let even = evaluate_on_line(&even, &doubled_coset);
+    let odd = evaluate_on_line(&odd, &doubled_coset);
+    let odd: Vec<_> = coset
+        .points()
+        .iter()
+        .zip(odd)
+        .map(|(point, value)| point.x * value)
+        .collect();
+    let evaluations = even.iter().zip(&odd).map(|(e, o)| e + o);
+    let negated_evaluations = even.iter().zip(&odd).map(|(e, o)| e - o);
+    evaluations.chain(negated_evaluations).collect()
+}
+
+/// Inverse of `evaluate_on_line`, with `g_0(π(x)) = (g(x) + g(-x)) / 2` and
+/// `g_1(π(x)) = (g(x) - g(-x)) / 2x`.
+fn interpolate_on_line(
+    evaluations: &[M31],
+    coset: &Coset,
+    two_inv: &M31,
+) -> Result<Vec<M31>, FFTError> {
+    if evaluations.len() == 1 {
+        return Ok(evaluations.to_vec());
+    }
+    let (values, negated_values) = evaluations.split_at(evaluations.len() / 2);
+    let mut inverses: Vec<_> = coset
+        .points()
+        .iter()
+        .take(values.len())
+        .map(|point| point.x + point.x)
+        .collect();
+    M31::inplace_batch_inverse(&mut inverses)?;
+    let even: Vec<_> = values
+        .iter()
+        .zip(negated_values)
+        .map(|(a, b)| (a + b) * two_inv)
+        .collect();
+    let odd: Vec<_> = values
+        .iter()
+        .zip(negated_values)
+        .zip(&inverses)
+        .map(|((a, b), inverse)| (a - b) * inverse)
+        .collect();
+    let doubled_coset = coset.double();
+    let even = interpolate_on_line(&even, &doubled_coset, two_inv)?;
+    let odd = interpolate_on_line(&odd, &doubled_coset, two_inv)?;
+    Ok(interleave(&even, &odd))
+}
+
+fn deinterleave(values: &[M31]) -> (Vec<M31>, Vec<M31>) {
+    let even = values.iter().step_by(2).copied().collect();
+    let odd = values.iter().skip(1).step_by(2).copied().collect();
+    (even, odd)
+}
+
+fn interleave(even: &[M31], odd: &[M31]) -> Vec<M31> {
+    even.iter().zip(odd).flat_map(|(e, o)| [*e, *o]).collect()
+}
+
+/// Returns the value at `point` of the basis function of index `j`, see `evaluate`.
+#[cfg(test)]
+pub(crate) fn basis_function(
+    j: usize,
+    point: &super::point::CirclePoint<crate::field::fields::mersenne31::Mersenne31Field>,
+) -> M31 {
+    let mut result = if j & 1 == 1 { point.y } else { M31::one() };
+    let mut x = point.x;
+    let mut j = j >> 1;
+    while j > 0 {
+        if j & 1 == 1 {
+            result = result * x;
+        }
+        x = super::point::CirclePoint::double_x(&x);
+        j >>= 1;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cyclic_group::IsGroup, fft::circle::point::CirclePoint};
+
+    fn naive_evaluate(coefficients: &[M31], domain: &CircleDomain) -> Vec<M31> {
+        domain
+            .points()
+            .iter()
+            .map(|point| {
+                coefficients
+                    .iter()
+                    .enumerate()
+                    .map(|(j, c)| c * basis_function(j, point))
+                    .fold(M31::zero(), |acc, term| acc + term)
+            })
+            .collect()
+    }
+
+    fn sample_coefficients(len: usize) -> Vec<M31> {
+        (0..len as u64)
+            .map(|i| M31::from(i * i * 7919 + 13))
+            .collect()
+    }
+
+    fn shifted_domain(log_size: u32) -> CircleDomain {
+        let shift = CirclePoint::GENERATOR.operate_with_self(12345_u64);
+        CircleDomain::new(Coset::new(shift, log_size - 1)).unwrap()
+    }
+
+    #[test]
+    fn evaluate_matches_naive_evaluation_on_canonic_domains() {
+        for log_size in 1..=6 {
+            let domain = CircleDomain::canonic(log_size);
+            let coefficients = sample_coefficients(domain.size());
+            assert_eq!(
+                evaluate(&coefficients, &domain).unwrap(),
+                naive_evaluate(&coefficients, &domain)
+            );
+        }
+    }
+
+    #[test]
+    fn evaluate_matches_naive_evaluation_on_a_shifted_domain() {
+        let domain = shifted_domain(5);
+        let coefficients = sample_coefficients(domain.size());
+        assert_eq!(
+            evaluate(&coefficients, &domain).unwrap(),
+            naive_evaluate(&coefficients, &domain)
+        );
+    }
+
+    #[test]
+    fn evaluate_pads_the_coefficients_with_zeros() {
+        let domain = CircleDomain::canonic(6);
+        let coefficients = sample_coefficients(8);
+        assert_eq!(
+            evaluate(&coefficients, &domain).unwrap(),
+            naive_evaluate(&coefficients, &domain)
+        );
+    }
+
+    #[test]
+    fn interpolate_is_the_inverse_of_evaluate() {
+        for domain in [
+            CircleDomain::canonic(1),
+            CircleDomain::canonic(7),
+            shifted_domain(4),
+        ] {
+            let coefficients = sample_coefficients(domain.size());
+            let evaluations = evaluate(&coefficients, &domain).unwrap();
+            assert_eq!(interpolate(&evaluations, &domain).unwrap(), coefficients);
+        }
+    }
+
+    #[test]
+    fn interpolate_of_a_constant_is_a_constant() {
+        let domain = CircleDomain::canonic(4);
+        let evaluations = vec![M31::from(5); domain.size()];
+        let mut expected = vec![M31::zero(); domain.size()];
+        expected[0] = M31::from(5);
+        assert_eq!(interpolate(&evaluations, &domain).unwrap(), expected);
+    }
+
+    #[test]
+    fn wrong_number_of_values_is_rejected() {
+        let domain = CircleDomain::canonic(3);
+        assert!(matches!(
+            evaluate(&sample_coefficients(16), &domain),
+            Err(FFTError::DomainSizeError(8, 16))
+        ));
+        assert!(matches!(
+            interpolate(&sample_coefficients(4), &domain),
+            Err(FFTError::DomainSizeError(8, 4))
+        ));
+    }
+}
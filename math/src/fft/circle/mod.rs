@@ -0,0 +1,11 @@
+//! FFT over the circle group of the Mersenne 31 field.
+//!
+//! Mersenne 31 has no large multiplicative subgroups of order a power of two, but the
+//! points of the circle x^2 + y^2 = 1 over it form a group of order 2^31. The circle FFT
+//! evaluates and interpolates polynomials on cosets of its subgroups, halving the domain
+//! first with the projection onto the x axis and then with the squaring map x -> 2x^2 - 1,
+//! as in "Circle STARKs" by Haböck, Levit and Papini.
+pub mod domain;
+pub mod fft;
+pub mod point;
+pub mod polynomial;
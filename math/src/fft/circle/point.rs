@@ -0,0 +1,194 @@
+use crate::{
+    cyclic_group::IsGroup,
+    field::{
+        element::FieldElement,
+        fields::mersenne31::Mersenne31Field,
+        traits::{IsField, IsSubFieldOf},
+    },
+};
+
+/// Logarithm of the order of the circle group of Mersenne 31, which is p + 1 = 2^31.
+pub const CIRCLE_LOG_ORDER: u32 = 31;
+
+/// Point of the circle x^2 + y^2 = 1 over the field `F`.
+///
+/// The points form a group with the law `(x0, y0) * (x1, y1) = (x0 x1 - y0 y1, x0 y1 + y0 x1)`,
+/// the multiplication of the complex numbers `x + iy` of norm one. The neutral element is
+/// `(1, 0)`, the inverse of a point is its conjugate `(x, -y)`, and the square of `(x, y)`
+/// is `(2x^2 - 1, 2xy)`. Over a field where -1 is not a square, as Mersenne 31, the
+/// group is cyclic of order p + 1.
+#[derive(Debug, Clone)]
+pub struct CirclePoint<F: IsField> {
+    pub x: FieldElement<F>,
+    pub y: FieldElement<F>,
+}
+
+impl<F: IsField> CirclePoint<F> {
+    /// Returns the point `(x, y)`, or `None` if it is not on the circle.
+    pub fn new(x: FieldElement<F>, y: FieldElement<F>) -> Option<Self> {
+        if x.square() + y.square() == FieldElement::one() {
+            Some(Self { x, y })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the x coordinate of the square of a point with x coordinate `x`, 2x^2 - 1,
+    /// which only depends on `x`.
+    pub fn double_x(x: &FieldElement<F>) -> FieldElement<F> {
+        let x_squared = x.square();
+        &x_squared + &x_squared - FieldElement::one()
+    }
+
+    /// Returns the square of `self`.
+    pub fn double(&self) -> Self {
+        let xy = &self.x * &self.y;
+        Self {
+            x: Self::double_x(&self.x),
+            y: &xy + &xy,
+        }
+    }
+
+    /// Returns `self` squared `n` times, that is, raised to 2^n.
+    pub fn repeated_double(&self, n: u32) -> Self {
+        (0..n).fold(self.clone(), |point, _| point.double())
+    }
+
+    /// Returns the point `(-x, -y)`, which is `self` times the element of order two.
+    pub fn antipode(&self) -> Self {
+        Self {
+            x: -&self.x,
+            y: -&self.y,
+        }
+    }
+
+    /// Returns the embedding of `self` into the circle over an extension `L` of `F`.
+    pub fn to_extension<L: IsField>(&self) -> CirclePoint<L>
+    where
+        F: IsSubFieldOf<L>,
+    {
+        CirclePoint {
+            x: self.x.clone().to_extension(),
+            y: self.y.clone().to_extension(),
+        }
+    }
+}
+
+impl<F: IsField> PartialEq for CirclePoint<F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.x == other.x && self.y == other.y
+    }
+}
+
+impl<F: IsField> Eq for CirclePoint<F> {}
+
+impl<F: IsField> IsGroup for CirclePoint<F> {
+    fn neutral_element() -> Self {
+        Self {
+            x: FieldElement::one(),
+            y: FieldElement::zero(),
+        }
+    }
+
+    fn operate_with(&self, other: &Self) -> Self {
+        Self {
+            x: &self.x * &other.x - &self.y * &other.y,
+            y: &self.x * &other.y + &self.y * &other.x,
+        }
+    }
+
+    /// Returns the conjugate `(x, -y)` of `self`.
+    fn neg(&self) -> Self {
+        Self {
+            x: self.x.clone(),
+            y: -&self.y,
+        }
+    }
+}
+
+impl CirclePoint<Mersenne31Field> {
+    /// Generator of the circle group of Mersenne 31, of order 2^31.
+    pub const GENERATOR: Self = Self {
+        x: FieldElement::const_from_raw(2),
+        y: FieldElement::const_from_raw(1268011823),
+    };
+
+    /// Returns a generator of the subgroup of order 2^`log_order`.
+    /// # Panics
+    /// Panics if `log_order` is larger than `CIRCLE_LOG_ORDER`.
+    pub fn subgroup_generator(log_order: u32) -> Self {
+        assert!(
+            log_order <= CIRCLE_LOG_ORDER,
+            "The circle group has no subgroup of order 2^{log_order}"
+        );
+        Self::GENERATOR.repeated_double(CIRCLE_LOG_ORDER - log_order)
+    }
+
+    /// Returns whether `self` belongs to the subgroup of order 2^`log_order`.
+    pub fn is_in_subgroup(&self, log_order: u32) -> bool {
+        self.repeated_double(log_order).is_neutral_element()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::fields::mersenne31::{Mersenne31ComplexField, M31};
+
+    type Point = CirclePoint<Mersenne31Field>;
+
+    #[test]
+    fn generator_is_on_the_circle() {
+        let generator = Point::GENERATOR;
+        assert_eq!(Point::new(generator.x, generator.y), Some(generator));
+        assert_eq!(Point::new(M31::from(2), M31::from(2)), None);
+    }
+
+    #[test]
+    fn generator_has_order_two_to_the_31() {
+        let generator = Point::GENERATOR;
+        assert!(generator.is_in_subgroup(CIRCLE_LOG_ORDER));
+        assert!(!generator.is_in_subgroup(CIRCLE_LOG_ORDER - 1));
+    }
+
+    #[test]
+    fn subgroup_generator_of_order_two_is_minus_one() {
+        assert_eq!(
+            Point::subgroup_generator(1),
+            Point::new(-M31::one(), M31::zero()).unwrap()
+        );
+    }
+
+    #[test]
+    fn double_matches_operate_with_self() {
+        let point = Point::subgroup_generator(10).operate_with_self(123_u64);
+        assert_eq!(point.double(), point.operate_with(&point));
+        assert_eq!(point.repeated_double(3), point.operate_with_self(8_u64));
+    }
+
+    #[test]
+    fn point_times_conjugate_is_the_neutral_element() {
+        let point = Point::GENERATOR.operate_with_self(12345_u64);
+        assert!(point.operate_with(&point.neg()).is_neutral_element());
+    }
+
+    #[test]
+    fn antipode_is_the_product_by_the_element_of_order_two() {
+        let point = Point::GENERATOR.operate_with_self(777_u64);
+        assert_eq!(
+            point.antipode(),
+            point.operate_with(&Point::subgroup_generator(1))
+        );
+    }
+
+    #[test]
+    fn embedding_into_the_complex_extension_commutes_with_the_group_law() {
+        let a = Point::GENERATOR.operate_with_self(5_u64);
+        let b = Point::GENERATOR.operate_with_self(11_u64);
+        assert_eq!(
+            a.to_extension::<Mersenne31ComplexField>()
+                .operate_with(&b.to_extension()),
+            a.operate_with(&b).to_extension()
+        );
+    }
+}
@@ -0,0 +1,206 @@
+use crate::{
+    fft::errors::FFTError,
+    field::{
+        element::FieldElement,
+        fields::mersenne31::{Mersenne31Field, M31},
+        traits::{IsField, IsSubFieldOf},
+    },
+};
+
+use super::{domain::CircleDomain, fft, point::CirclePoint};
+
+/// Polynomial on the circle over Mersenne 31, given by its coefficients in the FFT basis
+/// described in `fft::evaluate`. A polynomial with 2^n coefficients is determined by
+/// its evaluations on any circle domain of size 2^n.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CirclePoly {
+    coefficients: Vec<M31>,
+}
+
+impl CirclePoly {
+    /// Returns the polynomial with the given coefficients, whose number must be a power
+    /// of two.
+    pub fn new(coefficients: &[M31]) -> Result<Self, FFTError> {
+        if !coefficients.len().is_power_of_two() {
+            return Err(FFTError::InputError(coefficients.len()));
+        }
+        Ok(Self {
+            coefficients: coefficients.to_vec(),
+        })
+    }
+
+    /// Returns the polynomial with the given evaluations on `domain`, in the order of
+    /// its points.
+    pub fn interpolate(domain: &CircleDomain, evaluations: &[M31]) -> Result<Self, FFTError> {
+        Ok(Self {
+            coefficients: fft::interpolate(evaluations, domain)?,
+        })
+    }
+
+    pub fn coefficients(&self) -> &[M31] {
+        &self.coefficients
+    }
+
+    pub fn log_size(&self) -> u32 {
+        self.coefficients.len().trailing_zeros()
+    }
+
+    /// Returns the evaluations of the polynomial on `domain`, in the order of its points.
+    /// The domain can be larger than the number of coefficients, e.g. to extend the
+    /// evaluations of the polynomial to a blowup of the domain they were given on.
+    pub fn evaluate(&self, domain: &CircleDomain) -> Result<Vec<M31>, FFTError> {
+        fft::evaluate(&self.coefficients, domain)
+    }
+
+    /// Returns the value of the polynomial at `point`, which can be a point of the circle
+    /// over an extension of Mersenne 31, e.g. to evaluate it out of the domain.
+    pub fn eval_at_point<E: IsField>(&self, point: &CirclePoint<E>) -> FieldElement<E>
+    where
+        Mersenne31Field: IsSubFieldOf<E>,
+    {
+        // Folds the coefficients with the factors of the basis, one bit of the index at
+        // a time: y for the first one, then x, π(x), π(π(x)), ...
+        let factors = core::iter::once(point.y.clone())
+            .chain(core::iter::successors(Some(point.x.clone()), |x| {
+                Some(CirclePoint::double_x(x))
+            }));
+        let mut values: Vec<FieldElement<E>> =
+            self.coefficients.iter().map(|c| c.to_extension()).collect();
+        for factor in factors.take(self.log_size() as usize) {
+            values = values
+                .chunks(2)
+                .map(|pair| &pair[0] + &factor * &pair[1])
+                .collect();
+        }
+        values.swap_remove(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        cyclic_group::IsGroup,
+        fft::circle::{domain::Coset, fft::basis_function},
+        field::fields::mersenne31::{Mersenne31ComplexField, CM31},
+    };
+
+    fn sample_evaluations(len: usize) -> Vec<M31> {
+        (0..len as u64).map(|i| M31::from(i * 31 + 5)).collect()
+    }
+
+    #[test]
+    fn new_requires_a_power_of_two_of_coefficients() {
+        assert!(matches!(
+            CirclePoly::new(&sample_evaluations(6)),
+            Err(FFTError::InputError(6))
+        ));
+        assert_eq!(
+            CirclePoly::new(&sample_evaluations(8)).unwrap().log_size(),
+            3
+        );
+    }
+
+    #[test]
+    fn interpolation_evaluates_to_the_given_values() {
+        let domain = CircleDomain::canonic(5);
+        let evaluations = sample_evaluations(domain.size());
+        let poly = CirclePoly::interpolate(&domain, &evaluations).unwrap();
+        assert_eq!(poly.evaluate(&domain).unwrap(), evaluations);
+        for (point, value) in domain.points().iter().zip(&evaluations) {
+            assert_eq!(poly.eval_at_point(point), *value);
+        }
+    }
+
+    #[test]
+    fn eval_at_point_matches_naive_evaluation() {
+        let poly = CirclePoly::new(&sample_evaluations(16)).unwrap();
+        let point = CirclePoint::GENERATOR.operate_with_self(987654321_u64);
+        let expected = poly
+            .coefficients()
+            .iter()
+            .enumerate()
+            .map(|(j, c)| c * basis_function(j, &point))
+            .fold(M31::zero(), |acc, term| acc + term);
+        assert_eq!(poly.eval_at_point(&point), expected);
+    }
+
+    #[test]
+    fn eval_at_point_of_small_polynomials() {
+        let point = CirclePoint::GENERATOR.operate_with_self(3_u64);
+        let constant = CirclePoly::new(&[M31::from(7)]).unwrap();
+        assert_eq!(constant.eval_at_point(&point), M31::from(7));
+        let linear = CirclePoly::new(&[M31::from(7), M31::from(2)]).unwrap();
+        assert_eq!(
+            linear.eval_at_point(&point),
+            M31::from(7) + point.y * M31::from(2)
+        );
+    }
+
+    #[test]
+    fn evaluations_extend_to_a_larger_domain() {
+        let domain = CircleDomain::canonic(3);
+        let extended_domain = CircleDomain::canonic(6);
+        let poly = CirclePoly::interpolate(&domain, &sample_evaluations(domain.size())).unwrap();
+        let extended_evaluations = poly.evaluate(&extended_domain).unwrap();
+        for (point, value) in extended_domain.points().iter().zip(&extended_evaluations) {
+            assert_eq!(poly.eval_at_point(point), *value);
+        }
+        let extended_poly =
+            CirclePoly::interpolate(&extended_domain, &extended_evaluations).unwrap();
+        assert_eq!(&extended_poly.coefficients()[..8], poly.coefficients());
+        assert!(extended_poly.coefficients()[8..]
+            .iter()
+            .all(|c| *c == M31::zero()));
+    }
+
+    #[test]
+    fn interpolation_on_a_shifted_domain() {
+        let shift = CirclePoint::GENERATOR.operate_with_self(1_u64 << 20);
+        let domain = CircleDomain::new(Coset::new(shift, 4)).unwrap();
+        let evaluations = sample_evaluations(domain.size());
+        let poly = CirclePoly::interpolate(&domain, &evaluations).unwrap();
+        for (point, value) in domain.points().iter().zip(&evaluations) {
+            assert_eq!(poly.eval_at_point(point), *value);
+        }
+    }
+
+    #[test]
+    fn eval_at_a_point_over_the_complex_extension() {
+        let poly = CirclePoly::new(&sample_evaluations(8)).unwrap();
+        let point = CirclePoint::GENERATOR.operate_with_self(42_u64);
+        assert_eq!(
+            poly.eval_at_point(&point.to_extension::<Mersenne31ComplexField>()),
+            poly.eval_at_point(&point).to_extension()
+        );
+        // A point of the circle over CM31 that is not over M31: (x, y) with
+        // x = (z + 1/z) / 2 and y = (z - 1/z) / 2i for z = 2 + 3i.
+        let z = CM31::new([M31::from(2), M31::from(3)]);
+        let z_inv = z.inv().unwrap();
+        let i = CM31::new([M31::zero(), M31::one()]);
+        let two_inv = CM31::from(2).inv().unwrap();
+        let x = (&z + &z_inv) * &two_inv;
+        let y = (&z - &z_inv) * two_inv * i.inv().unwrap();
+        let point = CirclePoint::new(x, y).unwrap();
+        let expected = poly
+            .coefficients()
+            .iter()
+            .enumerate()
+            .map(|(j, c)| {
+                let mut term = c.to_extension::<Mersenne31ComplexField>();
+                let mut x = point.x.clone();
+                if j & 1 == 1 {
+                    term = term * &point.y;
+                }
+                for bit in 1..3 {
+                    if (j >> bit) & 1 == 1 {
+                        term = term * &x;
+                    }
+                    x = CirclePoint::double_x(&x);
+                }
+                term
+            })
+            .fold(CM31::zero(), |acc, term| acc + term);
+        assert_eq!(poly.eval_at_point(&point), expected);
+    }
+}
@@ -15,6 +15,8 @@ pub enum FFTError {
     InputError(usize),
     #[error("Order should be less than or equal to 63, but is {0}")]
     OrderError(u64),
+    #[error("Got {1} values for a domain of size {0}")]
+    DomainSizeError(usize, usize),
     #[cfg(feature = "metal")]
     #[error("A Metal related error has ocurred")]
     MetalError(#[from] MetalError),
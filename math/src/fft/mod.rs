@@ -1,3 +1,4 @@
+pub mod circle;
 pub mod cpu;
 pub mod errors;
 pub mod gpu;
@@ -1,9 +1,12 @@
 use crate::{
     field::{
         element::FieldElement,
+        extensions::quadratic::{HasQuadraticNonResidue, QuadraticExtensionField},
         fields::montgomery_backed_prime_fields::{IsModulus, MontgomeryBackendPrimeField},
+        traits::{IsFFTField, IsField, IsSubFieldOf},
     },
-    unsigned_integer::element::U64,
+    traits::Serializable,
+    unsigned_integer::element::{UnsignedInteger, U64},
 };
 
 pub type U64MontgomeryBackendPrimeField<T> = MontgomeryBackendPrimeField<T, 1>;
@@ -18,6 +21,125 @@ impl IsModulus<U64> for MontgomeryConfigBabybear31PrimeField {
 pub type Babybear31PrimeField =
     U64MontgomeryBackendPrimeField<MontgomeryConfigBabybear31PrimeField>;
 
+impl IsFFTField for Babybear31PrimeField {
+    // p - 1 = 2^27 * 15
+    const TWO_ADICITY: u64 = 27;
+    // 31^15, where 31 is a generator of the multiplicative group.
+    const TWO_ADIC_PRIMITVE_ROOT_OF_UNITY: U64 = UnsignedInteger::from_hex_unchecked("1a427a41");
+
+    fn field_name() -> &'static str {
+        "babybear31"
+    }
+}
+
+/// Quadratic non residue used to build the degree 2 extension of Babybear,
+/// Fp2 = Fp[u] / (u^2 - 11).
+#[derive(Debug, Clone)]
+pub struct Babybear31Degree2Residue;
+impl HasQuadraticNonResidue for Babybear31Degree2Residue {
+    type BaseField = Babybear31PrimeField;
+
+    fn residue() -> FieldElement<Babybear31PrimeField> {
+        FieldElement::from(11)
+    }
+}
+
+pub type Degree2Babybear31ExtensionField = QuadraticExtensionField<Babybear31Degree2Residue>;
+
+/// Quadratic non residue used to build the degree 4 extension of Babybear on top of
+/// the degree 2 one, Fp4 = Fp2[v] / (v^2 - u). This is the same field as Fp[v] / (v^4 - 11).
+#[derive(Debug, Clone)]
+pub struct Babybear31Degree4Residue;
+impl HasQuadraticNonResidue for Babybear31Degree4Residue {
+    type BaseField = Degree2Babybear31ExtensionField;
+
+    fn residue() -> FieldElement<Degree2Babybear31ExtensionField> {
+        FieldElement::new([FieldElement::zero(), FieldElement::one()])
+    }
+}
+
+pub type Degree4Babybear31ExtensionField = QuadraticExtensionField<Babybear31Degree4Residue>;
+
+/// The roots of unity of the extension are the ones of Babybear, so FFTs over it use
+/// the same domains as the base field.
+impl IsFFTField for Degree4Babybear31ExtensionField {
+    const TWO_ADICITY: u64 = 27;
+    const TWO_ADIC_PRIMITVE_ROOT_OF_UNITY: Self::BaseType = [
+        FieldElement::const_from_raw([
+            FieldElement::from_hex_unchecked("1a427a41"),
+            FieldElement::from_hex_unchecked("0"),
+        ]),
+        FieldElement::const_from_raw([
+            FieldElement::from_hex_unchecked("0"),
+            FieldElement::from_hex_unchecked("0"),
+        ]),
+    ];
+
+    fn field_name() -> &'static str {
+        "babybear31_degree4"
+    }
+}
+
+impl IsSubFieldOf<Degree4Babybear31ExtensionField> for Babybear31PrimeField {
+    const EXTENSION_DEGREE: usize = 4;
+
+    fn mul(
+        a: &Self::BaseType,
+        b: &<Degree4Babybear31ExtensionField as IsField>::BaseType,
+    ) -> <Degree4Babybear31ExtensionField as IsField>::BaseType {
+        let a = FieldElement::<Self>::from_raw(a);
+        let [b0, b1] = b;
+        let [b00, b01] = b0.value();
+        let [b10, b11] = b1.value();
+        [
+            FieldElement::new([&a * b00, &a * b01]),
+            FieldElement::new([&a * b10, &a * b11]),
+        ]
+    }
+
+    fn embed(a: Self::BaseType) -> <Degree4Babybear31ExtensionField as IsField>::BaseType {
+        [
+            FieldElement::new([FieldElement::from_raw(&a), FieldElement::zero()]),
+            FieldElement::zero(),
+        ]
+    }
+
+    /// Coordinates in the basis `1, u, v, uv`.
+    fn to_coordinates(
+        b: &<Degree4Babybear31ExtensionField as IsField>::BaseType,
+    ) -> Vec<Self::BaseType> {
+        b.iter()
+            .flat_map(|c| c.value().iter().map(|x| *x.value()))
+            .collect()
+    }
+
+    fn from_coordinates(
+        coordinates: &[Self::BaseType],
+    ) -> <Degree4Babybear31ExtensionField as IsField>::BaseType {
+        let c = |i: usize| FieldElement::<Self>::from_raw(&coordinates[i]);
+        [
+            FieldElement::new([c(0), c(1)]),
+            FieldElement::new([c(2), c(3)]),
+        ]
+    }
+}
+
+impl Serializable for FieldElement<Degree2Babybear31ExtensionField> {
+    /// Returns the concatenation of the serialization of each component.
+    #[cfg(feature = "std")]
+    fn serialize(&self) -> Vec<u8> {
+        self.value().iter().flat_map(|c| c.serialize()).collect()
+    }
+}
+
+impl Serializable for FieldElement<Degree4Babybear31ExtensionField> {
+    /// Returns the concatenation of the serialization of each component.
+    #[cfg(feature = "std")]
+    fn serialize(&self) -> Vec<u8> {
+        self.value().iter().flat_map(|c| c.serialize()).collect()
+    }
+}
+
 impl FieldElement<Babybear31PrimeField> {
     pub fn to_bytes_le(&self) -> [u8; 8] {
         let limbs = self.representative().limbs;
@@ -87,3 +209,92 @@ mod test_babybear_31_bytes_ops {
         assert_eq!(element, from_bytes);
     }
 }
+
+#[cfg(test)]
+mod test_babybear_31_extensions {
+    use super::*;
+    use crate::fft::polynomial::FFTPoly;
+    use crate::polynomial::Polynomial;
+
+    type FE = FieldElement<Babybear31PrimeField>;
+    type FE4 = FieldElement<Degree4Babybear31ExtensionField>;
+
+    fn fe4(values: [u64; 4]) -> FE4 {
+        FE4::new([
+            FieldElement::new([FE::from(values[0]), FE::from(values[1])]),
+            FieldElement::new([FE::from(values[2]), FE::from(values[3])]),
+        ])
+    }
+
+    #[test]
+    fn two_adic_primitive_root_of_unity_has_the_right_order() {
+        let root = FE::new(Babybear31PrimeField::TWO_ADIC_PRIMITVE_ROOT_OF_UNITY);
+        assert_eq!(root.pow(1_u64 << 27), FE::one());
+        assert_ne!(root.pow(1_u64 << 26), FE::one());
+    }
+
+    #[test]
+    fn roots_of_unity_of_the_extension_are_the_embedded_roots_of_the_base_field() {
+        let root =
+            Babybear31PrimeField::get_primitive_root_of_unity::<Babybear31PrimeField>(10).unwrap();
+        let root_extension = Degree4Babybear31ExtensionField::get_primitive_root_of_unity::<
+            Degree4Babybear31ExtensionField,
+        >(10)
+        .unwrap();
+        assert_eq!(root.to_extension(), root_extension);
+    }
+
+    #[test]
+    fn generator_of_the_degree_4_extension_is_a_fourth_root_of_11() {
+        let v = fe4([0, 0, 1, 0]);
+        assert_eq!(v.pow(4_u64), FE::from(11).to_extension());
+    }
+
+    #[test]
+    fn mul_by_subfield_element_matches_mul_by_its_embedding() {
+        let a = FE::from(123456789);
+        let b = fe4([3, 1415, 92, 65358]);
+        let result = FE4::new(<Babybear31PrimeField as IsSubFieldOf<
+            Degree4Babybear31ExtensionField,
+        >>::mul(a.value(), b.value()));
+        assert_eq!(result, a.to_extension() * b);
+    }
+
+    #[test]
+    fn coordinates_in_degree_4_extension_round_trip() {
+        let b = fe4([3, 1415, 92, 65358]);
+        let coordinates =
+            <Babybear31PrimeField as IsSubFieldOf<Degree4Babybear31ExtensionField>>::to_coordinates(
+                b.value(),
+            );
+        assert_eq!(coordinates.len(), 4);
+        assert_eq!(FE::from_raw(&coordinates[1]), FE::from(1415));
+        let result = FE4::new(<Babybear31PrimeField as IsSubFieldOf<
+            Degree4Babybear31ExtensionField,
+        >>::from_coordinates(&coordinates));
+        assert_eq!(result, b);
+    }
+
+    #[test]
+    fn inverse_in_degree_4_extension_works() {
+        let a = fe4([1, 2, 3, 4]);
+        assert_eq!(&a * a.inv().unwrap(), FE4::one());
+    }
+
+    #[test]
+    fn fft_over_degree_4_extension_matches_naive_evaluation() {
+        let poly = Polynomial::new(&[
+            fe4([1, 2, 3, 4]),
+            fe4([5, 6, 7, 8]),
+            fe4([9, 10, 11, 12]),
+            fe4([13, 14, 15, 16]),
+        ]);
+        let evaluations = poly.evaluate_fft(2, None).unwrap();
+        let root = Degree4Babybear31ExtensionField::get_primitive_root_of_unity::<
+            Degree4Babybear31ExtensionField,
+        >(3)
+        .unwrap();
+        let expected: Vec<FE4> = (0..8_u64).map(|i| poly.evaluate(&root.pow(i))).collect();
+        assert_eq!(evaluations, expected);
+    }
+}
@@ -0,0 +1,555 @@
+use crate::{
+    errors::CreationError,
+    field::{
+        element::FieldElement,
+        errors::FieldError,
+        extensions::{
+            cubic::{CubicExtensionField, HasCubicNonResidue},
+            quadratic::{HasQuadraticNonResidue, QuadraticExtensionField},
+        },
+        traits::{IsFFTField, IsField, IsPrimeField, IsSubFieldOf},
+    },
+    traits::Serializable,
+    unsigned_integer::element::U64,
+};
+#[cfg(feature = "std")]
+use crate::{
+    errors::{
+        ByteConversionError::{self, FromBEBytesError, FromLEBytesError},
+        DeserializationError,
+    },
+    traits::{ByteConversion, Deserializable},
+};
+
+/// Goldilocks Prime p = 2^64 - 2^32 + 1
+pub const GOLDILOCKS_PRIME: u64 = 0xffff_ffff_0000_0001;
+
+/// 2^64 - p = 2^32 - 1, which is congruent to 2^64 modulo p.
+const EPSILON: u64 = 0xffff_ffff;
+
+/// Prime field of the Goldilocks prime. Elements are kept as their canonical
+/// representatives in `[0, p)`, and products are reduced using the special form of the
+/// modulus, 2^64 = 2^32 - 1 (mod p), instead of a generic division.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Goldilocks64Field;
+
+impl Goldilocks64Field {
+    /// Reduces a 128 bit integer `x = x_lo + 2^64 * x_hi_lo + 2^96 * x_hi_hi` modulo p,
+    /// using that 2^64 = 2^32 - 1 and 2^96 = -1 (mod p).
+    #[inline(always)]
+    fn reduce_128(x: u128) -> u64 {
+        let x_lo = x as u64;
+        let x_hi = (x >> 64) as u64;
+        let x_hi_hi = x_hi >> 32;
+        let x_hi_lo = x_hi & EPSILON;
+
+        // x_lo - x_hi_hi. On a borrow the result is off by 2^64 = EPSILON (mod p), and
+        // subtracting EPSILON can not borrow again since x_hi_hi < 2^32.
+        let (mut t0, borrow) = x_lo.overflowing_sub(x_hi_hi);
+        if borrow {
+            t0 = t0.wrapping_sub(EPSILON);
+        }
+        // x_hi_lo * (2^32 - 1) fits in 64 bits.
+        let t1 = x_hi_lo * EPSILON;
+        Self::canonical_add(t0, t1)
+    }
+
+    /// Returns `a + b` modulo p for any two 64 bit integers, as a canonical representative.
+    #[inline(always)]
+    fn canonical_add(a: u64, b: u64) -> u64 {
+        let (sum, carry) = a.overflowing_add(b);
+        // On a carry the sum is off by 2^64 = EPSILON (mod p). Adding EPSILON back can not
+        // overflow, since the truncated sum is at most 2^64 - 2.
+        let sum = if carry { sum + EPSILON } else { sum };
+        if sum >= GOLDILOCKS_PRIME {
+            sum - GOLDILOCKS_PRIME
+        } else {
+            sum
+        }
+    }
+}
+
+impl IsField for Goldilocks64Field {
+    type BaseType = u64;
+
+    fn add(a: &u64, b: &u64) -> u64 {
+        Self::canonical_add(*a, *b)
+    }
+
+    fn sub(a: &u64, b: &u64) -> u64 {
+        let (diff, borrow) = a.overflowing_sub(*b);
+        // On a borrow the difference is `a - b + 2^64`, and `a - b + p` is obtained by
+        // subtracting EPSILON, which can not borrow again since `a - b > -p`.
+        if borrow {
+            diff.wrapping_sub(EPSILON)
+        } else {
+            diff
+        }
+    }
+
+    fn neg(a: &u64) -> u64 {
+        if *a == 0 {
+            0
+        } else {
+            GOLDILOCKS_PRIME - a
+        }
+    }
+
+    fn mul(a: &u64, b: &u64) -> u64 {
+        Self::reduce_128(*a as u128 * *b as u128)
+    }
+
+    fn square(a: &u64) -> u64 {
+        Self::reduce_128(*a as u128 * *a as u128)
+    }
+
+    fn div(a: &u64, b: &u64) -> u64 {
+        <Self as IsField>::mul(a, &Self::inv(b).unwrap())
+    }
+
+    fn inv(a: &u64) -> Result<u64, FieldError> {
+        if *a == 0 {
+            return Err(FieldError::InvZeroError);
+        }
+        Ok(Self::pow(a, GOLDILOCKS_PRIME - 2))
+    }
+
+    fn eq(a: &u64, b: &u64) -> bool {
+        a == b
+    }
+
+    fn zero() -> u64 {
+        0
+    }
+
+    fn one() -> u64 {
+        1
+    }
+
+    fn from_u64(x: u64) -> u64 {
+        if x >= GOLDILOCKS_PRIME {
+            x - GOLDILOCKS_PRIME
+        } else {
+            x
+        }
+    }
+
+    fn from_base_type(x: u64) -> u64 {
+        Self::from_u64(x)
+    }
+}
+
+impl Copy for FieldElement<Goldilocks64Field> {}
+
+impl IsPrimeField for Goldilocks64Field {
+    type RepresentativeType = U64;
+
+    fn representative(x: &u64) -> U64 {
+        U64::from_u64(*x)
+    }
+
+    fn field_bit_size() -> usize {
+        64
+    }
+
+    fn from_hex(hex_string: &str) -> Result<u64, CreationError> {
+        let hex_string = hex_string.strip_prefix("0x").unwrap_or(hex_string);
+        u64::from_str_radix(hex_string, 16)
+            .map(Self::from_u64)
+            .map_err(|_| CreationError::InvalidHexString)
+    }
+}
+
+impl IsFFTField for Goldilocks64Field {
+    // p - 1 = 2^32 * (2^32 - 1)
+    const TWO_ADICITY: u64 = 32;
+    // 7^(2^32 - 1), where 7 is a generator of the multiplicative group.
+    const TWO_ADIC_PRIMITVE_ROOT_OF_UNITY: u64 = 0x185629dcda58878c;
+
+    fn field_name() -> &'static str {
+        "goldilocks64"
+    }
+}
+
+#[cfg(feature = "std")]
+impl ByteConversion for FieldElement<Goldilocks64Field> {
+    fn to_bytes_be(&self) -> Vec<u8> {
+        self.value().to_be_bytes().into()
+    }
+
+    fn to_bytes_le(&self) -> Vec<u8> {
+        self.value().to_le_bytes().into()
+    }
+
+    fn from_bytes_be(bytes: &[u8]) -> Result<Self, ByteConversionError> {
+        let bytes: [u8; 8] = bytes.try_into().map_err(|_| FromBEBytesError)?;
+        Ok(Self::from(u64::from_be_bytes(bytes)))
+    }
+
+    fn from_bytes_le(bytes: &[u8]) -> Result<Self, ByteConversionError> {
+        let bytes: [u8; 8] = bytes.try_into().map_err(|_| FromLEBytesError)?;
+        Ok(Self::from(u64::from_le_bytes(bytes)))
+    }
+}
+
+#[cfg(feature = "std")]
+impl Serializable for FieldElement<Goldilocks64Field> {
+    fn serialize(&self) -> Vec<u8> {
+        self.to_bytes_be()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Deserializable for FieldElement<Goldilocks64Field> {
+    fn deserialize(bytes: &[u8]) -> Result<Self, DeserializationError>
+    where
+        Self: Sized,
+    {
+        Self::from_bytes_be(bytes).map_err(|x| x.into())
+    }
+}
+
+/// Quadratic non residue used to build the degree 2 extension of Goldilocks,
+/// Fp2 = Fp[u] / (u^2 - 7).
+#[derive(Debug, Clone)]
+pub struct Goldilocks64Degree2Residue;
+impl HasQuadraticNonResidue for Goldilocks64Degree2Residue {
+    type BaseField = Goldilocks64Field;
+
+    fn residue() -> FieldElement<Goldilocks64Field> {
+        FieldElement::from(7)
+    }
+}
+
+pub type Degree2Goldilocks64ExtensionField = QuadraticExtensionField<Goldilocks64Degree2Residue>;
+
+/// Cubic non residue used to build the degree 3 extension of Goldilocks,
+/// Fp3 = Fp[v] / (v^3 - 7). Since 7 generates the multiplicative group, it is neither
+/// a square nor a cube.
+#[derive(Debug, Clone)]
+pub struct Goldilocks64Degree3Residue;
+impl HasCubicNonResidue for Goldilocks64Degree3Residue {
+    type BaseField = Goldilocks64Field;
+
+    fn residue() -> FieldElement<Goldilocks64Field> {
+        FieldElement::from(7)
+    }
+}
+
+pub type Degree3Goldilocks64ExtensionField = CubicExtensionField<Goldilocks64Degree3Residue>;
+
+const ROOT_OF_UNITY: FieldElement<Goldilocks64Field> = FieldElement::const_from_raw(
+    <Goldilocks64Field as IsFFTField>::TWO_ADIC_PRIMITVE_ROOT_OF_UNITY,
+);
+const ZERO: FieldElement<Goldilocks64Field> = FieldElement::const_from_raw(0);
+
+/// The extensions use the roots of unity of Goldilocks, so FFTs over them use the same
+/// domains as the base field.
+impl IsFFTField for Degree2Goldilocks64ExtensionField {
+    const TWO_ADICITY: u64 = 32;
+    const TWO_ADIC_PRIMITVE_ROOT_OF_UNITY: Self::BaseType = [ROOT_OF_UNITY, ZERO];
+
+    fn field_name() -> &'static str {
+        "goldilocks64_degree2"
+    }
+}
+
+impl IsFFTField for Degree3Goldilocks64ExtensionField {
+    const TWO_ADICITY: u64 = 32;
+    const TWO_ADIC_PRIMITVE_ROOT_OF_UNITY: Self::BaseType = [ROOT_OF_UNITY, ZERO, ZERO];
+
+    fn field_name() -> &'static str {
+        "goldilocks64_degree3"
+    }
+}
+
+impl IsSubFieldOf<Degree2Goldilocks64ExtensionField> for Goldilocks64Field {
+    const EXTENSION_DEGREE: usize = 2;
+
+    fn mul(
+        a: &Self::BaseType,
+        b: &<Degree2Goldilocks64ExtensionField as IsField>::BaseType,
+    ) -> <Degree2Goldilocks64ExtensionField as IsField>::BaseType {
+        let a = FieldElement::<Self>::from_raw(a);
+        [a * b[0], a * b[1]]
+    }
+
+    fn embed(a: Self::BaseType) -> <Degree2Goldilocks64ExtensionField as IsField>::BaseType {
+        [FieldElement::from_raw(&a), FieldElement::zero()]
+    }
+
+    /// Coordinates in the basis `1, u`.
+    fn to_coordinates(
+        b: &<Degree2Goldilocks64ExtensionField as IsField>::BaseType,
+    ) -> Vec<Self::BaseType> {
+        b.iter().map(|x| *x.value()).collect()
+    }
+
+    fn from_coordinates(
+        coordinates: &[Self::BaseType],
+    ) -> <Degree2Goldilocks64ExtensionField as IsField>::BaseType {
+        [
+            FieldElement::from_raw(&coordinates[0]),
+            FieldElement::from_raw(&coordinates[1]),
+        ]
+    }
+}
+
+impl IsSubFieldOf<Degree3Goldilocks64ExtensionField> for Goldilocks64Field {
+    const EXTENSION_DEGREE: usize = 3;
+
+    fn mul(
+        a: &Self::BaseType,
+        b: &<Degree3Goldilocks64ExtensionField as IsField>::BaseType,
+    ) -> <Degree3Goldilocks64ExtensionField as IsField>::BaseType {
+        let a = FieldElement::<Self>::from_raw(a);
+        [a * b[0], a * b[1], a * b[2]]
+    }
+
+    fn embed(a: Self::BaseType) -> <Degree3Goldilocks64ExtensionField as IsField>::BaseType {
+        [
+            FieldElement::from_raw(&a),
+            FieldElement::zero(),
+            FieldElement::zero(),
+        ]
+    }
+
+    /// Coordinates in the basis `1, v, v^2`.
+    fn to_coordinates(
+        b: &<Degree3Goldilocks64ExtensionField as IsField>::BaseType,
+    ) -> Vec<Self::BaseType> {
+        b.iter().map(|x| *x.value()).collect()
+    }
+
+    fn from_coordinates(
+        coordinates: &[Self::BaseType],
+    ) -> <Degree3Goldilocks64ExtensionField as IsField>::BaseType {
+        [
+            FieldElement::from_raw(&coordinates[0]),
+            FieldElement::from_raw(&coordinates[1]),
+            FieldElement::from_raw(&coordinates[2]),
+        ]
+    }
+}
+
+impl Serializable for FieldElement<Degree2Goldilocks64ExtensionField> {
+    /// Returns the concatenation of the serialization of each component.
+    #[cfg(feature = "std")]
+    fn serialize(&self) -> Vec<u8> {
+        self.value().iter().flat_map(|c| c.serialize()).collect()
+    }
+}
+
+impl Serializable for FieldElement<Degree3Goldilocks64ExtensionField> {
+    /// Returns the concatenation of the serialization of each component.
+    #[cfg(feature = "std")]
+    fn serialize(&self) -> Vec<u8> {
+        self.value().iter().flat_map(|c| c.serialize()).collect()
+    }
+}
+
+#[cfg(test)]
+mod test_goldilocks_64_field {
+    use super::*;
+    use crate::field::fields::u64_prime_field::U64PrimeField;
+
+    type FE = FieldElement<Goldilocks64Field>;
+    /// The same field with the generic `%` reduction, used as a reference.
+    type ReferenceFE = FieldElement<U64PrimeField<GOLDILOCKS_PRIME>>;
+
+    /// The generic field may leave `p` as the representative of zero.
+    fn canonical(x: ReferenceFE) -> u64 {
+        x.value() % GOLDILOCKS_PRIME
+    }
+
+    fn sample_values() -> Vec<u64> {
+        let mut values = vec![
+            0,
+            1,
+            2,
+            EPSILON - 1,
+            EPSILON,
+            EPSILON + 1,
+            1 << 32,
+            1 << 63,
+            GOLDILOCKS_PRIME - 2,
+            GOLDILOCKS_PRIME - 1,
+        ];
+        let mut state = 0x0123_4567_89ab_cdef_u64;
+        for _ in 0..50 {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            values.push(state % GOLDILOCKS_PRIME);
+        }
+        values
+    }
+
+    #[test]
+    fn operations_match_the_generic_reduction() {
+        let values = sample_values();
+        for a in &values {
+            for b in &values {
+                let (x, y) = (FE::from(*a), FE::from(*b));
+                let (x_ref, y_ref) = (ReferenceFE::from(*a), ReferenceFE::from(*b));
+                assert_eq!(*(x + y).value(), canonical(x_ref + y_ref));
+                assert_eq!(*(x - y).value(), canonical(x_ref - y_ref));
+                assert_eq!(*(x * y).value(), canonical(x_ref * y_ref));
+            }
+            assert_eq!(*(-FE::from(*a)).value(), canonical(-ReferenceFE::from(*a)));
+        }
+    }
+
+    #[test]
+    fn from_u64_reduces_values_above_the_modulus() {
+        assert_eq!(FE::from(GOLDILOCKS_PRIME), FE::zero());
+        assert_eq!(FE::from(u64::MAX), FE::from(EPSILON - 1));
+    }
+
+    #[test]
+    fn product_of_largest_elements_is_one() {
+        let minus_one = -FE::one();
+        assert_eq!(minus_one * minus_one, FE::one());
+    }
+
+    #[test]
+    fn inverse_times_element_is_one() {
+        for a in sample_values().into_iter().skip(1) {
+            let a = FE::from(a);
+            assert_eq!(a * a.inv().unwrap(), FE::one());
+        }
+        assert!(FE::zero().inv().is_err());
+    }
+
+    #[test]
+    fn from_hex_reads_the_representative() {
+        assert_eq!(
+            FE::from_hex("0x185629dcda58878c").unwrap().representative(),
+            U64::from_u64(0x185629dcda58878c)
+        );
+        assert_eq!(FE::from_hex("ffffffff00000002").unwrap(), FE::one());
+    }
+
+    #[test]
+    fn two_adic_primitive_root_of_unity_has_order_two_to_the_32() {
+        let root = FE::from(Goldilocks64Field::TWO_ADIC_PRIMITVE_ROOT_OF_UNITY);
+        assert_eq!(root.pow(1_u64 << 32), FE::one());
+        assert_eq!(root.pow(1_u64 << 31), -FE::one());
+    }
+
+    #[test]
+    fn get_primitive_root_of_unity_of_small_order() {
+        let root = Goldilocks64Field::get_primitive_root_of_unity::<Goldilocks64Field>(2).unwrap();
+        assert_eq!(root.square(), -FE::one());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn byte_serialization_and_deserialization_works() {
+        let element = FE::from(0x0123_4567_89ab_cdef);
+        let bytes_le = element.to_bytes_le();
+        let bytes_be = element.to_bytes_be();
+        assert_eq!(FE::from_bytes_le(&bytes_le).unwrap(), element);
+        assert_eq!(FE::from_bytes_be(&bytes_be).unwrap(), element);
+        assert_eq!(FE::deserialize(&element.serialize()).unwrap(), element);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn from_bytes_rejects_wrong_lengths() {
+        assert!(FE::from_bytes_be(&[1, 2, 3]).is_err());
+        assert!(FE::from_bytes_le(&[0; 9]).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "lambdaworks-serde")]
+    fn serde_serialization_and_deserialization_works() {
+        let element = FE::from(0x0123_4567_89ab_cdef);
+        let serialized = serde_json::to_string(&element).unwrap();
+        let deserialized: FE = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(serialized, "{\"value\":\"0x123456789abcdef\"}");
+        assert_eq!(deserialized, element);
+    }
+}
+
+#[cfg(test)]
+mod test_goldilocks_64_extensions {
+    use super::*;
+
+    type FE = FieldElement<Goldilocks64Field>;
+    type FE2 = FieldElement<Degree2Goldilocks64ExtensionField>;
+    type FE3 = FieldElement<Degree3Goldilocks64ExtensionField>;
+
+    #[test]
+    fn residue_is_not_a_square_nor_a_cube() {
+        let seven = FE::from(7);
+        assert_eq!(seven.pow((GOLDILOCKS_PRIME - 1) / 2), -FE::one());
+        assert_ne!(seven.pow((GOLDILOCKS_PRIME - 1) / 3), FE::one());
+    }
+
+    #[test]
+    fn square_of_u_is_the_residue() {
+        let u = FE2::new([FE::zero(), FE::one()]);
+        assert_eq!(u.square(), FE2::new([FE::from(7), FE::zero()]));
+    }
+
+    #[test]
+    fn cube_of_v_is_the_residue() {
+        let v = FE3::new([FE::zero(), FE::one(), FE::zero()]);
+        assert_eq!(
+            v.pow(3_u64),
+            FE3::new([FE::from(7), FE::zero(), FE::zero()])
+        );
+    }
+
+    #[test]
+    fn inverse_in_the_extensions() {
+        let a = FE2::new([FE::from(3), FE::from(0x0123_4567_89ab_cdef)]);
+        assert_eq!(&a * a.inv().unwrap(), FE2::one());
+        let b = FE3::new([FE::from(3), FE::from(5), -FE::from(11)]);
+        assert_eq!(&b * b.inv().unwrap(), FE3::one());
+    }
+
+    #[test]
+    fn mul_by_subfield_element_matches_embedding() {
+        let a = FE::from(0x0123_4567_89ab_cdef);
+        let b = FE2::new([FE::from(3), FE::from(5)]);
+        let result = FE2::new(<Goldilocks64Field as IsSubFieldOf<
+            Degree2Goldilocks64ExtensionField,
+        >>::mul(a.value(), b.value()));
+        assert_eq!(result, a.to_extension() * b);
+        let c = FE3::new([FE::from(3), FE::from(5), -FE::from(11)]);
+        let result = FE3::new(<Goldilocks64Field as IsSubFieldOf<
+            Degree3Goldilocks64ExtensionField,
+        >>::mul(a.value(), c.value()));
+        assert_eq!(result, a.to_extension() * c);
+    }
+
+    #[test]
+    fn coordinates_round_trip() {
+        let b = FE3::new([FE::from(3), FE::from(5), -FE::from(11)]);
+        let coordinates =
+            <Goldilocks64Field as IsSubFieldOf<Degree3Goldilocks64ExtensionField>>::to_coordinates(
+                b.value(),
+            );
+        assert_eq!(coordinates, vec![3, 5, GOLDILOCKS_PRIME - 11]);
+        let result = FE3::new(<Goldilocks64Field as IsSubFieldOf<
+            Degree3Goldilocks64ExtensionField,
+        >>::from_coordinates(&coordinates));
+        assert_eq!(result, b);
+    }
+
+    #[test]
+    fn roots_of_unity_of_the_extensions_are_embedded() {
+        let root = Degree2Goldilocks64ExtensionField::get_primitive_root_of_unity::<
+            Degree2Goldilocks64ExtensionField,
+        >(32)
+        .unwrap();
+        assert_eq!(root.pow(1_u64 << 31), -FE2::one());
+        let root = Degree3Goldilocks64ExtensionField::get_primitive_root_of_unity::<
+            Degree3Goldilocks64ExtensionField,
+        >(32)
+        .unwrap();
+        assert_eq!(root.pow(1_u64 << 31), -FE3::one());
+    }
+}
@@ -1,4 +1,6 @@
 /// Implemenation of the Babybear Prime field p = 2^31 - 2^27 + 1
 pub mod babybear;
+/// Implementation of the Goldilocks Prime field p = 2^64 - 2^32 + 1
+pub mod goldilocks;
 /// Implementation of two-adic prime field over 256 bit unsigned integers.
 pub mod stark_252_prime_field;
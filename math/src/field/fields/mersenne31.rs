@@ -0,0 +1,348 @@
+use crate::{
+    errors::CreationError,
+    field::{
+        element::FieldElement,
+        errors::FieldError,
+        extensions::quadratic::{HasQuadraticNonResidue, QuadraticExtensionField},
+        traits::{IsField, IsPrimeField, IsSubFieldOf},
+    },
+    traits::Serializable,
+    unsigned_integer::element::U64,
+};
+#[cfg(feature = "std")]
+use crate::{
+    errors::{
+        ByteConversionError::{self, FromBEBytesError, FromLEBytesError},
+        DeserializationError,
+    },
+    traits::{ByteConversion, Deserializable},
+};
+
+/// Mersenne prime p = 2^31 - 1
+pub const MERSENNE_31_PRIME: u32 = (1 << 31) - 1;
+
+/// Prime field of the Mersenne prime 2^31 - 1. Elements are kept as their canonical
+/// representatives in `[0, p)`, and products are reduced using that 2^31 = 1 (mod p).
+///
+/// Since p - 1 = 2 * (2^30 - 1), the field has no large multiplicative subgroups of
+/// order a power of two and does not implement `IsFFTField`. FFTs over it use the
+/// circle group instead, see `fft::circle`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Mersenne31Field;
+
+pub type M31 = FieldElement<Mersenne31Field>;
+
+impl Mersenne31Field {
+    /// Reduces a 64 bit integer modulo p, adding its high and low 31 bits.
+    #[inline(always)]
+    fn reduce_64(x: u64) -> u32 {
+        let p = MERSENNE_31_PRIME as u64;
+        // Both sums are congruent to `x`, the first is below 2^34 and the second below 2p.
+        let x = (x & p) + (x >> 31);
+        let x = ((x & p) + (x >> 31)) as u32;
+        Self::reduce_once(x)
+    }
+
+    /// Reduces an integer smaller than 2p modulo p.
+    #[inline(always)]
+    fn reduce_once(x: u32) -> u32 {
+        if x >= MERSENNE_31_PRIME {
+            x - MERSENNE_31_PRIME
+        } else {
+            x
+        }
+    }
+}
+
+impl IsField for Mersenne31Field {
+    type BaseType = u32;
+
+    fn add(a: &u32, b: &u32) -> u32 {
+        Self::reduce_once(a + b)
+    }
+
+    fn sub(a: &u32, b: &u32) -> u32 {
+        if a >= b {
+            a - b
+        } else {
+            a + MERSENNE_31_PRIME - b
+        }
+    }
+
+    fn neg(a: &u32) -> u32 {
+        if *a == 0 {
+            0
+        } else {
+            MERSENNE_31_PRIME - a
+        }
+    }
+
+    fn mul(a: &u32, b: &u32) -> u32 {
+        Self::reduce_64(*a as u64 * *b as u64)
+    }
+
+    fn div(a: &u32, b: &u32) -> u32 {
+        <Self as IsField>::mul(a, &Self::inv(b).unwrap())
+    }
+
+    fn inv(a: &u32) -> Result<u32, FieldError> {
+        if *a == 0 {
+            return Err(FieldError::InvZeroError);
+        }
+        Ok(Self::pow(a, MERSENNE_31_PRIME - 2))
+    }
+
+    fn eq(a: &u32, b: &u32) -> bool {
+        a == b
+    }
+
+    fn zero() -> u32 {
+        0
+    }
+
+    fn one() -> u32 {
+        1
+    }
+
+    fn from_u64(x: u64) -> u32 {
+        Self::reduce_64(x)
+    }
+
+    fn from_base_type(x: u32) -> u32 {
+        Self::reduce_64(x as u64)
+    }
+}
+
+impl Copy for M31 {}
+
+impl IsPrimeField for Mersenne31Field {
+    type RepresentativeType = U64;
+
+    fn representative(x: &u32) -> U64 {
+        U64::from_u64(*x as u64)
+    }
+
+    fn field_bit_size() -> usize {
+        31
+    }
+
+    fn from_hex(hex_string: &str) -> Result<u32, CreationError> {
+        let hex_string = hex_string.strip_prefix("0x").unwrap_or(hex_string);
+        u64::from_str_radix(hex_string, 16)
+            .map(Self::from_u64)
+            .map_err(|_| CreationError::InvalidHexString)
+    }
+}
+
+#[cfg(feature = "std")]
+impl ByteConversion for M31 {
+    fn to_bytes_be(&self) -> Vec<u8> {
+        self.value().to_be_bytes().into()
+    }
+
+    fn to_bytes_le(&self) -> Vec<u8> {
+        self.value().to_le_bytes().into()
+    }
+
+    fn from_bytes_be(bytes: &[u8]) -> Result<Self, ByteConversionError> {
+        let bytes: [u8; 4] = bytes.try_into().map_err(|_| FromBEBytesError)?;
+        Ok(Self::from(u32::from_be_bytes(bytes) as u64))
+    }
+
+    fn from_bytes_le(bytes: &[u8]) -> Result<Self, ByteConversionError> {
+        let bytes: [u8; 4] = bytes.try_into().map_err(|_| FromLEBytesError)?;
+        Ok(Self::from(u32::from_le_bytes(bytes) as u64))
+    }
+}
+
+#[cfg(feature = "std")]
+impl Serializable for M31 {
+    fn serialize(&self) -> Vec<u8> {
+        self.to_bytes_be()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Deserializable for M31 {
+    fn deserialize(bytes: &[u8]) -> Result<Self, DeserializationError>
+    where
+        Self: Sized,
+    {
+        Self::from_bytes_be(bytes).map_err(|x| x.into())
+    }
+}
+
+/// Quadratic non residue used to build the complex extension of Mersenne 31,
+/// CM31 = Fp[i] / (i^2 + 1). Since p = 3 (mod 4), -1 is not a square.
+#[derive(Debug, Clone)]
+pub struct Mersenne31ComplexResidue;
+impl HasQuadraticNonResidue for Mersenne31ComplexResidue {
+    type BaseField = Mersenne31Field;
+
+    fn residue() -> M31 {
+        -M31::one()
+    }
+}
+
+pub type Mersenne31ComplexField = QuadraticExtensionField<Mersenne31ComplexResidue>;
+
+pub type CM31 = FieldElement<Mersenne31ComplexField>;
+
+impl IsSubFieldOf<Mersenne31ComplexField> for Mersenne31Field {
+    const EXTENSION_DEGREE: usize = 2;
+
+    fn mul(
+        a: &Self::BaseType,
+        b: &<Mersenne31ComplexField as IsField>::BaseType,
+    ) -> <Mersenne31ComplexField as IsField>::BaseType {
+        let a = M31::from_raw(a);
+        [a * b[0], a * b[1]]
+    }
+
+    fn embed(a: Self::BaseType) -> <Mersenne31ComplexField as IsField>::BaseType {
+        [M31::from_raw(&a), M31::zero()]
+    }
+
+    /// Coordinates in the basis `1, i`.
+    fn to_coordinates(b: &<Mersenne31ComplexField as IsField>::BaseType) -> Vec<Self::BaseType> {
+        b.iter().map(|x| *x.value()).collect()
+    }
+
+    fn from_coordinates(
+        coordinates: &[Self::BaseType],
+    ) -> <Mersenne31ComplexField as IsField>::BaseType {
+        [
+            M31::from_raw(&coordinates[0]),
+            M31::from_raw(&coordinates[1]),
+        ]
+    }
+}
+
+impl Serializable for CM31 {
+    /// Returns the concatenation of the serialization of each component.
+    #[cfg(feature = "std")]
+    fn serialize(&self) -> Vec<u8> {
+        self.value().iter().flat_map(|c| c.serialize()).collect()
+    }
+}
+
+#[cfg(test)]
+mod test_mersenne_31_field {
+    use super::*;
+    use crate::field::fields::u64_prime_field::U64PrimeField;
+
+    /// The same field with the generic `%` reduction, used as a reference.
+    type ReferenceFE = FieldElement<U64PrimeField<{ MERSENNE_31_PRIME as u64 }>>;
+
+    fn canonical(x: ReferenceFE) -> u32 {
+        (x.value() % MERSENNE_31_PRIME as u64) as u32
+    }
+
+    fn sample_values() -> Vec<u64> {
+        let p = MERSENNE_31_PRIME as u64;
+        let mut values = vec![0, 1, 2, p - 2, p - 1, 1 << 30];
+        let mut state = 0x0123_4567_89ab_cdef_u64;
+        for _ in 0..50 {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            values.push(state % p);
+        }
+        values
+    }
+
+    #[test]
+    fn operations_match_the_generic_reduction() {
+        let values = sample_values();
+        for a in &values {
+            for b in &values {
+                let (x, y) = (M31::from(*a), M31::from(*b));
+                let (x_ref, y_ref) = (ReferenceFE::from(*a), ReferenceFE::from(*b));
+                assert_eq!(*(x + y).value(), canonical(x_ref + y_ref));
+                assert_eq!(*(x - y).value(), canonical(x_ref - y_ref));
+                assert_eq!(*(x * y).value(), canonical(x_ref * y_ref));
+            }
+            assert_eq!(*(-M31::from(*a)).value(), canonical(-ReferenceFE::from(*a)));
+        }
+    }
+
+    #[test]
+    fn from_u64_reduces_any_integer() {
+        let p = MERSENNE_31_PRIME as u64;
+        for x in [
+            p,
+            p + 1,
+            2 * p + 5,
+            1 << 62,
+            (1 << 62) + 3,
+            u64::MAX - 1,
+            u64::MAX,
+        ] {
+            assert_eq!(*M31::from(x).value() as u64, x % p);
+        }
+    }
+
+    #[test]
+    fn inverse_times_element_is_one() {
+        for a in sample_values().into_iter().skip(1) {
+            let a = M31::from(a);
+            assert_eq!(a * a.inv().unwrap(), M31::one());
+        }
+        assert!(M31::zero().inv().is_err());
+    }
+
+    #[test]
+    fn from_hex_reduces_the_value() {
+        assert_eq!(M31::from_hex("0x7fffffff").unwrap(), M31::zero());
+        assert_eq!(M31::from_hex("80000000").unwrap(), M31::one());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn byte_serialization_and_deserialization_works() {
+        let element = M31::from(0x1234_5678);
+        assert_eq!(element.to_bytes_be(), vec![0x12, 0x34, 0x56, 0x78]);
+        assert_eq!(M31::from_bytes_le(&element.to_bytes_le()).unwrap(), element);
+        assert_eq!(M31::deserialize(&element.serialize()).unwrap(), element);
+        assert!(M31::from_bytes_be(&[0; 8]).is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_mersenne_31_complex_field {
+    use super::*;
+
+    #[test]
+    fn minus_one_is_not_a_square() {
+        let exponent = (MERSENNE_31_PRIME - 1) / 2;
+        assert_eq!((-M31::one()).pow(exponent), -M31::one());
+    }
+
+    #[test]
+    fn square_of_i_is_minus_one() {
+        let i = CM31::new([M31::zero(), M31::one()]);
+        assert_eq!(i.square(), -CM31::one());
+    }
+
+    #[test]
+    fn product_with_conjugate_is_the_norm() {
+        let z = CM31::new([M31::from(3), M31::from(5)]);
+        assert_eq!(&z * z.conjugate(), CM31::from(34));
+    }
+
+    #[test]
+    fn inverse_times_element_is_one() {
+        let z = CM31::new([M31::from(0x1234_5678), -M31::from(11)]);
+        assert_eq!(&z * z.inv().unwrap(), CM31::one());
+    }
+
+    #[test]
+    fn mul_by_subfield_element_matches_embedding() {
+        let a = M31::from(0x1234_5678);
+        let z = CM31::new([M31::from(3), M31::from(5)]);
+        let result = CM31::new(
+            <Mersenne31Field as IsSubFieldOf<Mersenne31ComplexField>>::mul(a.value(), z.value()),
+        );
+        assert_eq!(result, a.to_extension() * z);
+    }
+}
@@ -1,5 +1,7 @@
 /// Implementation of two-adic prime fields to use with the Fast Fourier Transform (FFT).
 pub mod fft_friendly;
+/// Implementation of the Mersenne prime field (p = 2^31 - 1) and its complex extension
+pub mod mersenne31;
 pub mod montgomery_backed_prime_fields;
 /// Implementation of the Goldilocks Prime field (p = 2^448 - 2^224 - 1)
 pub mod p448_goldilocks_prime_field;
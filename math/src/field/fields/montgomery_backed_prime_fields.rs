@@ -287,8 +287,8 @@ where
 
     fn field_bit_size() -> usize {
         let mut evaluated_bit = NUM_LIMBS * 64 - 1;
-        let max_element = M::MODULUS - UnsignedInteger::<NUM_LIMBS>::from_u128(1);
-        let one = UnsignedInteger::from_u128(1);
+        let max_element = M::MODULUS - UnsignedInteger::<NUM_LIMBS>::from_u64(1);
+        let one = UnsignedInteger::from_u64(1);
 
         while ((max_element >> evaluated_bit) & one) != one {
             evaluated_bit -= 1;
@@ -20,7 +20,7 @@ pub enum RootsConfig {
 /// A two-adic primitive root of unity is a number w that satisfies w^(2^n) = 1
 /// and w^(j) != 1 for every j below 2^n. With this primitive root we can generate
 /// any other root of unity we need to perform FFT.
-pub trait IsFFTField: IsPrimeField {
+pub trait IsFFTField: IsField {
     const TWO_ADICITY: u64;
     const TWO_ADIC_PRIMITVE_ROOT_OF_UNITY: Self::BaseType;
 
@@ -132,6 +132,48 @@ pub trait IsField: Debug + Clone {
     fn from_base_type(x: Self::BaseType) -> Self::BaseType;
 }
 
+/// Trait to embed a field `Self` into a field `F` that contains it, e.g. a prime
+/// field into one of its extensions. Every field is trivially a subfield of itself.
+pub trait IsSubFieldOf<F: IsField>: IsField {
+    /// Degree of `F` as a vector space over `Self`.
+    const EXTENSION_DEGREE: usize;
+
+    /// Returns the multiplication of `a`, an element of the subfield, and `b`, an
+    /// element of `F`.
+    fn mul(a: &Self::BaseType, b: &F::BaseType) -> F::BaseType {
+        F::mul(&Self::embed(a.clone()), b)
+    }
+
+    /// Returns the representation of `a` as an element of `F`.
+    fn embed(a: Self::BaseType) -> F::BaseType;
+
+    /// Returns the `EXTENSION_DEGREE` coordinates of `b` in a basis of `F` over `Self`.
+    fn to_coordinates(b: &F::BaseType) -> Vec<Self::BaseType>;
+
+    /// Returns the element of `F` with the given coordinates. Inverse of `to_coordinates`.
+    fn from_coordinates(coordinates: &[Self::BaseType]) -> F::BaseType;
+}
+
+impl<F: IsField> IsSubFieldOf<F> for F {
+    const EXTENSION_DEGREE: usize = 1;
+
+    fn mul(a: &Self::BaseType, b: &F::BaseType) -> F::BaseType {
+        F::mul(a, b)
+    }
+
+    fn embed(a: Self::BaseType) -> F::BaseType {
+        a
+    }
+
+    fn to_coordinates(b: &F::BaseType) -> Vec<Self::BaseType> {
+        vec![b.clone()]
+    }
+
+    fn from_coordinates(coordinates: &[Self::BaseType]) -> F::BaseType {
+        coordinates[0].clone()
+    }
+}
+
 #[derive(PartialEq)]
 pub enum LegendreSymbol {
     MinusOne,
@@ -1,5 +1,5 @@
 use super::field::element::FieldElement;
-use crate::field::traits::IsField;
+use crate::field::traits::{IsField, IsSubFieldOf};
 use std::ops;
 
 /// Represents the polynomial c_0 + c_1 * X + c_2 * X^2 + ... + c_n * X^n
@@ -246,6 +246,21 @@ impl<F: IsField> Polynomial<FieldElement<F>> {
         }
         parts
     }
+
+    /// Returns the same polynomial with its coefficients embedded into the field `L`
+    /// containing `F`.
+    pub fn to_extension<L: IsField>(self) -> Polynomial<FieldElement<L>>
+    where
+        F: IsSubFieldOf<L>,
+    {
+        Polynomial {
+            coefficients: self
+                .coefficients
+                .into_iter()
+                .map(|coeff| coeff.to_extension())
+                .collect(),
+        }
+    }
 }
 
 pub fn compose<F>(
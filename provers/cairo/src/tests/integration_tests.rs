@@ -46,17 +46,15 @@ fn test_verifier_rejects_wrong_authentication_paths() {
     let mut proof = generate_cairo_proof(&main_trace, &pub_inputs, &proof_options).unwrap();
 
     // Change order of authentication path hashes
-    let query = 0;
     let merkle_tree = 0;
-    let mut original_path = proof.deep_poly_openings[query].lde_trace_merkle_proofs[merkle_tree]
-        .merkle_path
+    let mut original_path = proof.lde_trace_merkle_proofs[merkle_tree]
+        .auth_nodes
         .clone();
     original_path.swap(0, 1);
     // For the test to make sense, we have to make sure
     // that the two hashes are different.
     assert_ne!(original_path[0], original_path[1]);
-    proof.deep_poly_openings[query].lde_trace_merkle_proofs[merkle_tree].merkle_path =
-        original_path;
+    proof.lde_trace_merkle_proofs[merkle_tree].auth_nodes = original_path;
 
     // Verifier should reject the proof
     assert!(!verify_cairo_proof(&proof, &pub_inputs, &proof_options));
@@ -3,6 +3,7 @@ use crate::air::MemorySegment;
 use lambdaworks_math::field::element::FieldElement;
 use lambdaworks_math::field::fields::fft_friendly::stark_252_prime_field::Stark252PrimeField;
 use serde::{Deserialize, Serialize};
+use stark_platinum_prover::grinding::GrindingHash;
 use stark_platinum_prover::proof::options::ProofOptions;
 use stark_platinum_prover::proof::stark::StarkProof;
 use stark_platinum_prover::verifier::verify;
@@ -52,5 +53,9 @@ pub fn new_proof_options(
         fri_number_of_queries,
         coset_offset: coset_offset as u64,
         grinding_factor,
+        zero_knowledge: false,
+        fri_folding_factor: 2,
+        fri_max_remainder_degree: 0,
+        grinding_hash: GrindingHash::Keccak256,
     }
 }
@@ -1,20 +1,27 @@
 use lambdaworks_crypto::merkle_tree::{
-    backends::types::{BatchKeccak256Backend, Keccak256Backend},
-    merkle::MerkleTree,
+    backends::types::BatchKeccak256Backend, merkle::MerkleTree, traits::IsMerkleTreeBackend,
 };
+use lambdaworks_math::field::{element::FieldElement, traits::IsField};
 
-// Merkle Trees configuration
-
-// Security of both hashes should match
-
-pub type FriMerkleTreeBackend<F> = Keccak256Backend<F>;
-pub type FriMerkleTree<F> = MerkleTree<FriMerkleTreeBackend<F>>;
-
-// If using hashes with 256-bit security, commitment size should be 32
-// If using hashes with 512-bit security, commitment size should be 64
-// TODO: Commitment type should be obtained from MerkleTrees
+/// Commitment of the default backend. Other backends may have nodes of other sizes,
+/// e.g. 64 bytes for `Sha3_512Backend`.
 pub const COMMITMENT_SIZE: usize = 32;
 pub type Commitment = [u8; COMMITMENT_SIZE];
 
+/// Default backend of the prover and the verifier.
 pub type BatchedMerkleTreeBackend<F> = BatchKeccak256Backend<F>;
 pub type BatchedMerkleTree<F> = MerkleTree<BatchedMerkleTreeBackend<F>>;
+
+/// Backends that can commit to the trace, the composition polynomial and the FRI layers:
+/// leaves are rows of elements of `F`. Their nodes are the commitments of the proof.
+pub trait IsStarkMerkleTreeBackend<F: IsField>:
+    IsMerkleTreeBackend<Data = Vec<FieldElement<F>>>
+{
+}
+
+impl<F, B> IsStarkMerkleTreeBackend<F> for B
+where
+    F: IsField,
+    B: IsMerkleTreeBackend<Data = Vec<FieldElement<F>>>,
+{
+}
@@ -0,0 +1,336 @@
+use std::collections::BTreeSet;
+
+use itertools::Itertools;
+use lambdaworks_math::field::{
+    element::FieldElement,
+    traits::{IsField, IsSubFieldOf},
+};
+
+use crate::{context::AirContext, frame::Frame, proof::options::ProofOptions};
+
+use super::{
+    dag::ExpressionDag,
+    expression::{Column, ColumnKind, Expr, Variable},
+};
+
+/// Declares the columns of an AIR and its transition constraints as expressions over them,
+/// so that the `AirContext` is derived from the constraints instead of being written by
+/// hand:
+///
+/// ```
+/// # use lambdaworks_math::field::fields::fft_friendly::stark_252_prime_field::Stark252PrimeField;
+/// # use stark_platinum_prover::{constraints::builder::ConstraintBuilder, proof::options::ProofOptions};
+/// let mut builder = ConstraintBuilder::<Stark252PrimeField>::new();
+/// let a = builder.main_column("a");
+/// let b = builder.main_column("b");
+/// builder.transition(a.next() - b.curr());
+/// builder.transition(b.next() - a.curr() - b.curr());
+/// let constraints = builder.build();
+///
+/// let context = constraints.air_context(&ProofOptions::default_test_options());
+/// assert_eq!(context.transition_offsets, vec![0, 1]);
+/// assert_eq!(context.transition_exemptions, vec![1, 1]);
+/// ```
+pub struct ConstraintBuilder<F: IsField> {
+    main_columns: Vec<String>,
+    auxiliary_columns: Vec<String>,
+    preprocessed_columns: Vec<String>,
+    periodic_columns: Vec<(String, Vec<FieldElement<F>>)>,
+    challenges: Vec<String>,
+    transitions: Vec<(Expr<F>, usize)>,
+}
+
+impl<F: IsField> Default for ConstraintBuilder<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: IsField> ConstraintBuilder<F> {
+    pub fn new() -> Self {
+        Self {
+            main_columns: Vec::new(),
+            auxiliary_columns: Vec::new(),
+            preprocessed_columns: Vec::new(),
+            periodic_columns: Vec::new(),
+            challenges: Vec::new(),
+            transitions: Vec::new(),
+        }
+    }
+
+    /// Declares the next column of the main trace.
+    pub fn main_column(&mut self, name: &str) -> Column {
+        Self::declare(&mut self.main_columns, ColumnKind::Main, name)
+    }
+
+    /// Declares the next column of the auxiliary trace.
+    pub fn auxiliary_column(&mut self, name: &str) -> Column {
+        Self::declare(&mut self.auxiliary_columns, ColumnKind::Auxiliary, name)
+    }
+
+    /// Declares the next preprocessed column. See `AIR::get_preprocessed_columns`.
+    pub fn preprocessed_column(&mut self, name: &str) -> Column {
+        Self::declare(
+            &mut self.preprocessed_columns,
+            ColumnKind::Preprocessed,
+            name,
+        )
+    }
+
+    fn declare(columns: &mut Vec<String>, kind: ColumnKind, name: &str) -> Column {
+        columns.push(name.to_string());
+        Column {
+            kind,
+            index: columns.len() - 1,
+        }
+    }
+
+    /// Declares the next periodic column, with its values over one period, and returns its
+    /// value at the current row. See `AIR::get_periodic_column_values`.
+    pub fn periodic_column(&mut self, name: &str, values: Vec<FieldElement<F>>) -> Expr<F> {
+        self.periodic_columns.push((name.to_string(), values));
+        Expr::Variable(Variable::Periodic(self.periodic_columns.len() - 1))
+    }
+
+    /// Declares the next RAP challenge. Their values are given, in order of declaration,
+    /// to `TransitionConstraints::evaluate`.
+    pub fn challenge(&mut self, name: &str) -> Expr<F> {
+        self.challenges.push(name.to_string());
+        Expr::Variable(Variable::Challenge(self.challenges.len() - 1))
+    }
+
+    /// Adds a transition constraint that must vanish on every row whose frame lies within
+    /// the trace: it is exempted from the last rows, as many as the largest offset it
+    /// reads.
+    pub fn transition(&mut self, constraint: Expr<F>) {
+        let exemptions = constraint.max_offset();
+        self.transition_with_exemptions(constraint, exemptions);
+    }
+
+    /// Adds a transition constraint that must vanish on every row but the last
+    /// `exemptions` ones.
+    pub fn transition_with_exemptions(&mut self, constraint: Expr<F>, exemptions: usize) {
+        self.transitions.push((constraint, exemptions));
+    }
+
+    pub fn build(self) -> TransitionConstraints<F> {
+        let mut offsets = BTreeSet::from([0]);
+        for (constraint, _) in &self.transitions {
+            constraint.for_each_variable(&mut |variable| {
+                if let Variable::Trace { offset, .. } = variable {
+                    offsets.insert(*offset);
+                }
+            });
+        }
+        let (periodic_column_names, periodic_column_values) =
+            self.periodic_columns.into_iter().unzip();
+        let (constraints, exemptions): (Vec<_>, _) = self.transitions.into_iter().unzip();
+        let simplified: Vec<_> = constraints.iter().map(Expr::simplify).collect();
+        let degrees = simplified.iter().map(Expr::exact_degree).collect();
+
+        TransitionConstraints {
+            main_columns: self.main_columns,
+            auxiliary_columns: self.auxiliary_columns,
+            preprocessed_columns: self.preprocessed_columns,
+            periodic_column_names,
+            periodic_column_values,
+            challenges: self.challenges,
+            dag: ExpressionDag::new(&simplified),
+            constraints,
+            degrees,
+            exemptions,
+            offsets: offsets.into_iter().collect(),
+        }
+    }
+}
+
+/// Transition constraints of an AIR built with a `ConstraintBuilder`, together with the
+/// layout of the columns they read. They are evaluated in their simplified form, as an
+/// `ExpressionDag`, and their degrees are the exact ones.
+#[derive(Clone, Debug)]
+pub struct TransitionConstraints<F: IsField> {
+    main_columns: Vec<String>,
+    auxiliary_columns: Vec<String>,
+    preprocessed_columns: Vec<String>,
+    periodic_column_names: Vec<String>,
+    periodic_column_values: Vec<Vec<FieldElement<F>>>,
+    challenges: Vec<String>,
+    constraints: Vec<Expr<F>>,
+    dag: ExpressionDag<F>,
+    degrees: Vec<usize>,
+    exemptions: Vec<usize>,
+    offsets: Vec<usize>,
+}
+
+impl<F: IsField> TransitionConstraints<F> {
+    /// The constraints as they were added to the builder.
+    pub fn constraints(&self) -> &[Expr<F>] {
+        &self.constraints
+    }
+
+    /// Graph of the simplified constraints, whose outputs are the constraints in order.
+    pub fn dag(&self) -> &ExpressionDag<F> {
+        &self.dag
+    }
+
+    pub fn number_of_main_columns(&self) -> usize {
+        self.main_columns.len()
+    }
+
+    pub fn number_of_auxiliary_columns(&self) -> usize {
+        self.auxiliary_columns.len()
+    }
+
+    pub fn number_of_preprocessed_columns(&self) -> usize {
+        self.preprocessed_columns.len()
+    }
+
+    pub fn number_of_challenges(&self) -> usize {
+        self.challenges.len()
+    }
+
+    /// Names of the columns in the order of the frame: main, auxiliary and preprocessed.
+    pub fn column_names(&self) -> impl Iterator<Item = &str> {
+        self.main_columns
+            .iter()
+            .chain(&self.auxiliary_columns)
+            .chain(&self.preprocessed_columns)
+            .map(String::as_str)
+    }
+
+    /// Position in the frame of the column called `name`, which is also its column index in
+    /// boundary constraints.
+    pub fn column_index(&self, name: &str) -> Option<usize> {
+        self.column_names().position(|column| column == name)
+    }
+
+    pub fn periodic_column_names(&self) -> &[String] {
+        &self.periodic_column_names
+    }
+
+    /// Values of the periodic columns, as expected by `AIR::get_periodic_column_values`.
+    pub fn periodic_column_values(&self) -> &[Vec<FieldElement<F>>] {
+        &self.periodic_column_values
+    }
+
+    /// Exact degree of each constraint. See `Expr::exact_degree`.
+    pub fn transition_degrees(&self) -> &[usize] {
+        &self.degrees
+    }
+
+    pub fn transition_exemptions(&self) -> &[usize] {
+        &self.exemptions
+    }
+
+    /// Offsets of the rows read by the constraints, in increasing order. The first one is
+    /// always zero.
+    pub fn transition_offsets(&self) -> &[usize] {
+        &self.offsets
+    }
+
+    /// Context of an AIR with these constraints. The trace columns are the main and the
+    /// auxiliary ones.
+    pub fn air_context(&self, proof_options: &ProofOptions) -> AirContext {
+        AirContext {
+            proof_options: proof_options.clone(),
+            trace_columns: self.number_of_main_columns() + self.number_of_auxiliary_columns(),
+            transition_degrees: self.degrees.clone(),
+            transition_offsets: self.offsets.clone(),
+            transition_exemptions: self.exemptions.clone(),
+            num_transition_constraints: self.constraints.len(),
+            num_transition_exemptions: self
+                .exemptions
+                .iter()
+                .filter(|exemptions| **exemptions > 0)
+                .unique()
+                .count(),
+        }
+    }
+
+    /// Position in the frame of `column`.
+    fn frame_column(&self, column: &Column) -> usize {
+        match column.kind {
+            ColumnKind::Main => column.index,
+            ColumnKind::Auxiliary => self.main_columns.len() + column.index,
+            ColumnKind::Preprocessed => {
+                self.main_columns.len() + self.auxiliary_columns.len() + column.index
+            }
+        }
+    }
+
+    /// Evaluates the constraints over a frame read at `transition_offsets`, as expected by
+    /// `AIR::compute_transition`. `challenges` holds the values of the challenges in order
+    /// of declaration.
+    pub fn evaluate<E>(
+        &self,
+        frame: &Frame<E>,
+        challenges: &[FieldElement<E>],
+    ) -> Vec<FieldElement<E>>
+    where
+        F: IsSubFieldOf<E>,
+        E: IsField,
+    {
+        let value = |variable: &Variable| match variable {
+            Variable::Trace { column, offset } => {
+                let row = self.offsets.binary_search(offset).unwrap();
+                frame.get_row(row)[self.frame_column(column)].clone()
+            }
+            Variable::Periodic(index) => frame.get_periodic_values()[*index].clone(),
+            Variable::Challenge(index) => challenges[*index].clone(),
+        };
+        self.dag.evaluate(&value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lambdaworks_math::field::{
+        element::FieldElement, fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
+    };
+
+    use super::ConstraintBuilder;
+    use crate::{frame::Frame, proof::options::ProofOptions};
+
+    type FE = FieldElement<Stark252PrimeField>;
+
+    #[test]
+    fn the_context_is_derived_from_the_constraints() {
+        let mut builder = ConstraintBuilder::<Stark252PrimeField>::new();
+        let a = builder.main_column("a");
+        let z = builder.auxiliary_column("z");
+        let k = builder.preprocessed_column("k");
+        let gamma = builder.challenge("gamma");
+        builder.transition(a.at(3) - a.curr() * a.curr() * k.curr());
+        builder.transition_with_exemptions(z.next() * (a.curr() + &gamma) - z.curr(), 4);
+        let constraints = builder.build();
+
+        let context = constraints.air_context(&ProofOptions::default_test_options());
+        assert_eq!(context.trace_columns, 2);
+        assert_eq!(context.transition_offsets, vec![0, 1, 3]);
+        assert_eq!(context.transition_degrees, vec![3, 2]);
+        assert_eq!(context.transition_exemptions, vec![3, 4]);
+        assert_eq!(context.num_transition_constraints, 2);
+        assert_eq!(context.num_transition_exemptions, 2);
+        assert_eq!(constraints.column_index("k"), Some(2));
+    }
+
+    #[test]
+    fn constraints_read_the_rows_of_their_offsets() {
+        let mut builder = ConstraintBuilder::<Stark252PrimeField>::new();
+        let a = builder.main_column("a");
+        let b = builder.main_column("b");
+        let c = builder.periodic_column("c", vec![FE::one(), FE::zero()]);
+        let gamma = builder.challenge("gamma");
+        builder.transition(b.at(2) - a.curr());
+        builder.transition(a.curr().pow(3) + c * gamma);
+        let constraints = builder.build();
+
+        // Rows 0 and 2 of the trace, which are the rows at offsets 0 and 2.
+        let frame =
+            Frame::new((1..=4).map(FE::from).collect(), 2).with_periodic_values(vec![FE::from(5)]);
+        assert_eq!(
+            constraints.evaluate(&frame, &[FE::from(7)]),
+            vec![FE::from(4 - 1), FE::from(1 + 5 * 7)]
+        );
+    }
+}
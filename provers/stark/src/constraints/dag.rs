@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+
+use lambdaworks_math::field::{
+    element::FieldElement,
+    traits::{IsField, IsSubFieldOf},
+};
+
+use super::expression::{Expr, Variable};
+
+/// Node of an `ExpressionDag`. Operands are indexes of previous nodes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Node {
+    /// Index of the value in `ExpressionDag::constants`.
+    Constant(usize),
+    Variable(Variable),
+    Neg(usize),
+    Add(usize, usize),
+    Sub(usize, usize),
+    Mul(usize, usize),
+}
+
+/// Indexes of the nodes already in the graph, by node and by address of the expression
+/// they were built from.
+struct Indexes<F: IsField> {
+    by_node: HashMap<Node, usize>,
+    by_address: HashMap<*const Expr<F>, usize>,
+}
+
+impl<F: IsField> Default for Indexes<F> {
+    fn default() -> Self {
+        Self {
+            by_node: HashMap::new(),
+            by_address: HashMap::new(),
+        }
+    }
+}
+
+/// Expressions flattened into a directed acyclic graph where equal subexpressions are
+/// merged into a single node. The nodes are sorted so that the operands of a node come
+/// before it, so they can be evaluated, or translated into code, one after the other,
+/// computing each subexpression once.
+#[derive(Clone, Debug)]
+pub struct ExpressionDag<F: IsField> {
+    nodes: Vec<Node>,
+    constants: Vec<FieldElement<F>>,
+    outputs: Vec<usize>,
+}
+
+impl<F: IsField> ExpressionDag<F> {
+    /// Builds the graph of `expressions`, whose values are the outputs of the graph.
+    pub fn new(expressions: &[Expr<F>]) -> Self {
+        let mut dag = Self {
+            nodes: Vec::new(),
+            constants: Vec::new(),
+            outputs: Vec::new(),
+        };
+        let mut indexes = Indexes::default();
+        dag.outputs = expressions
+            .iter()
+            .map(|expression| dag.insert(expression, &mut indexes))
+            .collect();
+        dag
+    }
+
+    /// Returns the index of the node of `expression`, adding it to the graph if there is no
+    /// equal node yet.
+    fn insert(&mut self, expression: &Expr<F>, indexes: &mut Indexes<F>) -> usize {
+        // Shared subexpressions are only visited once.
+        let address = expression as *const Expr<F>;
+        if let Some(index) = indexes.by_address.get(&address) {
+            return *index;
+        }
+        let node = match expression {
+            Expr::Constant(constant) => {
+                let position = self.constants.iter().position(|c| c == constant);
+                Node::Constant(position.unwrap_or_else(|| {
+                    self.constants.push(constant.clone());
+                    self.constants.len() - 1
+                }))
+            }
+            Expr::Variable(variable) => Node::Variable(*variable),
+            Expr::Neg(expr) => Node::Neg(self.insert(expr, indexes)),
+            Expr::Add(lhs, rhs) => Node::Add(self.insert(lhs, indexes), self.insert(rhs, indexes)),
+            Expr::Sub(lhs, rhs) => Node::Sub(self.insert(lhs, indexes), self.insert(rhs, indexes)),
+            Expr::Mul(lhs, rhs) => Node::Mul(self.insert(lhs, indexes), self.insert(rhs, indexes)),
+        };
+        let index = *indexes.by_node.entry(node).or_insert_with(|| {
+            self.nodes.push(node);
+            self.nodes.len() - 1
+        });
+        indexes.by_address.insert(address, index);
+        index
+    }
+
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    pub fn constants(&self) -> &[FieldElement<F>] {
+        &self.constants
+    }
+
+    /// Indexes of the nodes of the expressions the graph was built from, in order.
+    pub fn outputs(&self) -> &[usize] {
+        &self.outputs
+    }
+
+    /// Evaluates the expressions over `E`, taking the values of the variables from `value`.
+    pub fn evaluate<E>(&self, value: &impl Fn(&Variable) -> FieldElement<E>) -> Vec<FieldElement<E>>
+    where
+        F: IsSubFieldOf<E>,
+        E: IsField,
+    {
+        let mut evaluations: Vec<FieldElement<E>> = Vec::with_capacity(self.nodes.len());
+        for node in &self.nodes {
+            let evaluation = match node {
+                Node::Constant(index) => self.constants[*index].clone().to_extension(),
+                Node::Variable(variable) => value(variable),
+                Node::Neg(operand) => -&evaluations[*operand],
+                Node::Add(lhs, rhs) => &evaluations[*lhs] + &evaluations[*rhs],
+                Node::Sub(lhs, rhs) => &evaluations[*lhs] - &evaluations[*rhs],
+                Node::Mul(lhs, rhs) => &evaluations[*lhs] * &evaluations[*rhs],
+            };
+            evaluations.push(evaluation);
+        }
+        self.outputs
+            .iter()
+            .map(|output| evaluations[*output].clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lambdaworks_math::field::{
+        element::FieldElement, fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
+    };
+
+    use super::{ExpressionDag, Node};
+    use crate::constraints::builder::ConstraintBuilder;
+
+    type FE = FieldElement<Stark252PrimeField>;
+
+    #[test]
+    fn equal_subexpressions_are_merged() {
+        let mut builder = ConstraintBuilder::<Stark252PrimeField>::new();
+        let a = builder.main_column("a");
+        let b = builder.main_column("b");
+        // a + b is built twice, and a³² shares its factors.
+        let expressions = [
+            (a.curr() + b.curr()) * (a.curr() + b.curr()),
+            a.curr().pow(32) - FE::from(2),
+            a.curr() + b.curr() + FE::from(2),
+        ];
+        let dag = ExpressionDag::new(&expressions);
+
+        // a, b, a + b, (a + b)², five squares up to a³², 2, a³² - 2 and a + b + 2
+        assert_eq!(dag.nodes().len(), 12);
+        assert_eq!(dag.constants(), &[FE::from(2)]);
+        assert_eq!(dag.nodes()[dag.outputs()[0]], Node::Mul(2, 2));
+    }
+
+    #[test]
+    fn the_graph_evaluates_as_the_expressions() {
+        let mut builder = ConstraintBuilder::<Stark252PrimeField>::new();
+        let a = builder.main_column("a");
+        let gamma = builder.challenge("gamma");
+        let expressions = [
+            a.next() * (a.curr() + &gamma) - a.curr().pow(3),
+            -(a.curr() - FE::from(7)),
+        ];
+        let value = |variable: &super::Variable| match variable {
+            super::Variable::Trace { offset, .. } => FE::from(*offset as u64 + 2),
+            _ => FE::from(11),
+        };
+        assert_eq!(
+            ExpressionDag::new(&expressions).evaluate(&value),
+            expressions
+                .iter()
+                .map(|expression| expression.evaluate(&value))
+                .collect::<Vec<_>>()
+        );
+    }
+}
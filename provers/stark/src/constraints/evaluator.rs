@@ -1,7 +1,10 @@
 use itertools::Itertools;
 use lambdaworks_math::{
     fft::cpu::roots_of_unity::get_powers_of_primitive_root_coset,
-    field::{element::FieldElement, traits::IsFFTField},
+    field::{
+        element::FieldElement,
+        traits::{IsFFTField, IsField, IsSubFieldOf},
+    },
     polynomial::Polynomial,
     traits::Serializable,
 };
@@ -17,13 +20,28 @@ use crate::debug::check_boundary_polys_divisibility;
 use crate::domain::Domain;
 use crate::trace::TraceTable;
 use crate::traits::AIR;
-use crate::{frame::Frame, prover::evaluate_polynomial_on_lde_domain};
+use crate::{
+    frame::Frame,
+    prover::{evaluate_polynomial_on_coset, evaluate_polynomial_on_lde_domain},
+};
+
+/// Rows of the LDE of the trace on which the composition polynomial is evaluated, together
+/// with the evaluations of the transition exemptions and periodic columns on the same
+/// points. Row `i` holds the point of index `first_index + i * index_step` of the LDE
+/// domain.
+struct LdeSegment<'a, E: IsField> {
+    trace: &'a TraceTable<E>,
+    transition_exemptions_evaluations: Vec<Vec<FieldElement<E>>>,
+    periodic_values_evaluations: Vec<Vec<FieldElement<E>>>,
+    first_index: usize,
+    index_step: usize,
+}
 
-pub struct ConstraintEvaluator<F: IsFFTField, A: AIR> {
+pub struct ConstraintEvaluator<A: AIR> {
     air: A,
-    boundary_constraints: BoundaryConstraints<F>,
+    boundary_constraints: BoundaryConstraints<A::FieldExtension>,
 }
-impl<F: IsFFTField, A: AIR + AIR<Field = F>> ConstraintEvaluator<F, A> {
+impl<A: AIR> ConstraintEvaluator<A> {
     pub fn new(air: &A, rap_challenges: &A::RAPChallenges) -> Self {
         let boundary_constraints = air.boundary_constraints(rap_challenges);
 
@@ -33,44 +51,159 @@ impl<F: IsFFTField, A: AIR + AIR<Field = F>> ConstraintEvaluator<F, A> {
         }
     }
 
+    /// Evaluates the composition polynomial over the LDE domain. The values of the main
+    /// trace in `lde_trace` must already be embedded into the extension field.
     pub fn evaluate(
         &self,
-        lde_trace: &TraceTable<F>,
-        domain: &Domain<F>,
-        transition_coefficients: &[FieldElement<F>],
-        boundary_coefficients: &[FieldElement<F>],
+        lde_trace: &TraceTable<A::FieldExtension>,
+        domain: &Domain<A::Field>,
+        transition_coefficients: &[FieldElement<A::FieldExtension>],
+        boundary_coefficients: &[FieldElement<A::FieldExtension>],
+        rap_challenges: &A::RAPChallenges,
+    ) -> Vec<FieldElement<A::FieldExtension>>
+    where
+        FieldElement<A::Field>: Serializable + Send + Sync,
+        FieldElement<A::FieldExtension>: Serializable + Send + Sync,
+        A: Send + Sync,
+        A::RAPChallenges: Send + Sync,
+    {
+        let segment = LdeSegment {
+            trace: lde_trace,
+            transition_exemptions_evaluations: evaluate_polynomials_on_lde_domain(
+                self.air.transition_exemptions(),
+                domain,
+            ),
+            periodic_values_evaluations: evaluate_polynomials_on_lde_domain(
+                self.air.get_periodic_column_polynomials(),
+                domain,
+            ),
+            first_index: 0,
+            index_step: 1,
+        };
+
+        self.evaluate_segment(
+            &segment,
+            domain,
+            transition_coefficients,
+            boundary_coefficients,
+            rap_challenges,
+        )
+    }
+
+    /// Evaluates the composition polynomial over the LDE domain one coset of the trace
+    /// domain at a time, so that only the evaluations of the trace polynomials on a single
+    /// coset are kept in memory. The result is the same as the one of `evaluate` on the LDE
+    /// of `trace_polys`, which must hold the main trace polynomials embedded into the
+    /// extension followed by the auxiliary ones.
+    pub fn evaluate_by_cosets(
+        &self,
+        trace_polys: &[Polynomial<FieldElement<A::FieldExtension>>],
+        domain: &Domain<A::Field>,
+        transition_coefficients: &[FieldElement<A::FieldExtension>],
+        boundary_coefficients: &[FieldElement<A::FieldExtension>],
+        rap_challenges: &A::RAPChallenges,
+    ) -> Vec<FieldElement<A::FieldExtension>>
+    where
+        FieldElement<A::Field>: Serializable + Send + Sync,
+        FieldElement<A::FieldExtension>: Serializable + Send + Sync,
+        A: Send + Sync,
+        A::RAPChallenges: Send + Sync,
+    {
+        let blowup_factor = domain.blowup_factor;
+        let trace_length = domain.interpolation_domain_size;
+        let transition_exemptions = self.air.transition_exemptions();
+        let periodic_column_polynomials = self.air.get_periodic_column_polynomials();
+
+        let mut evaluations = vec![FieldElement::zero(); blowup_factor * trace_length];
+        for coset in 0..blowup_factor {
+            // The points of the LDE domain whose index is `coset` modulo the blowup factor
+            // form the coset `h ω^coset <g>` of the trace domain.
+            let offset = &domain.lde_roots_of_unity_coset[coset];
+            let columns: Vec<_> = trace_polys
+                .iter()
+                .map(|poly| {
+                    let offset = offset.clone().to_extension();
+                    evaluate_polynomial_on_coset(poly, trace_length, &offset).unwrap()
+                })
+                .collect();
+            let segment = LdeSegment {
+                trace: &TraceTable::from_columns(&columns),
+                transition_exemptions_evaluations: evaluate_polynomials_on_coset(
+                    &transition_exemptions,
+                    trace_length,
+                    offset,
+                ),
+                periodic_values_evaluations: evaluate_polynomials_on_coset(
+                    &periodic_column_polynomials,
+                    trace_length,
+                    offset,
+                ),
+                first_index: coset,
+                index_step: blowup_factor,
+            };
+
+            let coset_evaluations = self.evaluate_segment(
+                &segment,
+                domain,
+                transition_coefficients,
+                boundary_coefficients,
+                rap_challenges,
+            );
+            for (i, evaluation) in coset_evaluations.into_iter().enumerate() {
+                evaluations[coset + i * blowup_factor] = evaluation;
+            }
+        }
+        evaluations
+    }
+
+    /// Evaluates the composition polynomial on the points of the LDE domain held by
+    /// `segment`.
+    fn evaluate_segment(
+        &self,
+        segment: &LdeSegment<'_, A::FieldExtension>,
+        domain: &Domain<A::Field>,
+        transition_coefficients: &[FieldElement<A::FieldExtension>],
+        boundary_coefficients: &[FieldElement<A::FieldExtension>],
         rap_challenges: &A::RAPChallenges,
-    ) -> Vec<FieldElement<F>>
+    ) -> Vec<FieldElement<A::FieldExtension>>
     where
-        FieldElement<F>: Serializable + Send + Sync,
+        FieldElement<A::Field>: Serializable + Send + Sync,
+        FieldElement<A::FieldExtension>: Serializable + Send + Sync,
         A: Send + Sync,
         A::RAPChallenges: Send + Sync,
     {
+        let lde_trace = segment.trace;
+        let n_elem = lde_trace.n_rows();
+        let points: Vec<_> = (0..n_elem)
+            .map(|i| &domain.lde_roots_of_unity_coset[segment.first_index + i * segment.index_step])
+            .collect();
+
         let boundary_constraints = &self.boundary_constraints;
         let number_of_b_constraints = boundary_constraints.constraints.len();
-        let boundary_zerofiers_inverse_evaluations: Vec<Vec<FieldElement<F>>> =
+        let boundary_zerofiers_inverse_evaluations: Vec<Vec<FieldElement<A::FieldExtension>>> =
             boundary_constraints
                 .constraints
                 .iter()
                 .map(|bc| {
                     let point = &domain.trace_primitive_root.pow(bc.step as u64);
-                    let mut evals = domain
-                        .lde_roots_of_unity_coset
+                    let mut evals = points
                         .iter()
-                        .map(|v| v.clone() - point)
-                        .collect::<Vec<FieldElement<F>>>();
+                        .map(|v| *v - point)
+                        .collect::<Vec<FieldElement<A::Field>>>();
                     FieldElement::inplace_batch_inverse(&mut evals).unwrap();
                     evals
+                        .into_iter()
+                        .map(|v| v.to_extension())
+                        .collect::<Vec<FieldElement<A::FieldExtension>>>()
                 })
-                .collect::<Vec<Vec<FieldElement<F>>>>();
+                .collect::<Vec<Vec<FieldElement<A::FieldExtension>>>>();
 
         let trace_length = self.air.trace_length();
 
         #[cfg(all(debug_assertions, not(feature = "parallel")))]
-        let boundary_polys: Vec<Polynomial<FieldElement<F>>> = Vec::new();
+        let boundary_polys: Vec<Polynomial<FieldElement<A::FieldExtension>>> = Vec::new();
 
         let n_col = lde_trace.n_cols();
-        let n_elem = domain.lde_roots_of_unity_coset.len();
         let boundary_polys_evaluations = boundary_constraints
             .constraints
             .iter()
@@ -84,14 +217,14 @@ impl<F: IsFFTField, A: AIR + AIR<Field = F>> ConstraintEvaluator<F, A> {
                     .step_by(n_col)
                     .take(n_elem)
                     .map(|v| v - &constraint.value)
-                    .collect::<Vec<FieldElement<F>>>()
+                    .collect::<Vec<FieldElement<A::FieldExtension>>>()
             })
-            .collect::<Vec<Vec<FieldElement<F>>>>();
+            .collect::<Vec<Vec<FieldElement<A::FieldExtension>>>>();
 
         #[cfg(feature = "parallel")]
-        let boundary_eval_iter = (0..domain.lde_roots_of_unity_coset.len()).into_par_iter();
+        let boundary_eval_iter = (0..n_elem).into_par_iter();
         #[cfg(not(feature = "parallel"))]
-        let boundary_eval_iter = 0..domain.lde_roots_of_unity_coset.len();
+        let boundary_eval_iter = 0..n_elem;
 
         let boundary_evaluation = boundary_eval_iter
             .map(|i| {
@@ -103,7 +236,7 @@ impl<F: IsFFTField, A: AIR + AIR<Field = F>> ConstraintEvaluator<F, A> {
                             * &boundary_polys_evaluations[index][i]
                     })
             })
-            .collect::<Vec<FieldElement<F>>>();
+            .collect::<Vec<FieldElement<A::FieldExtension>>>();
 
         #[cfg(all(debug_assertions, not(feature = "parallel")))]
         let boundary_zerofiers = Vec::new();
@@ -112,21 +245,21 @@ impl<F: IsFFTField, A: AIR + AIR<Field = F>> ConstraintEvaluator<F, A> {
         check_boundary_polys_divisibility(boundary_polys, boundary_zerofiers);
 
         let blowup_factor = self.air.blowup_factor();
+        // Rows of the frame are `blowup_factor` points apart in the LDE domain.
+        let frame_step = blowup_factor / segment.index_step as u8;
 
         #[cfg(all(debug_assertions, not(feature = "parallel")))]
         let mut transition_evaluations = Vec::new();
 
-        let transition_exemptions = self.air.transition_exemptions();
-
-        let transition_exemptions_evaluations =
-            evaluate_transition_exemptions(transition_exemptions, domain);
+        let transition_exemptions_evaluations = &segment.transition_exemptions_evaluations;
+        let periodic_values_evaluations = &segment.periodic_values_evaluations;
         let num_exemptions = self.air.context().num_transition_exemptions;
 
         let blowup_factor_order = u64::from(blowup_factor.trailing_zeros());
 
-        let offset = FieldElement::<F>::from(self.air.context().proof_options.coset_offset);
+        let offset = FieldElement::<A::Field>::from(self.air.context().proof_options.coset_offset);
         let offset_pow = offset.pow(trace_length);
-        let one = FieldElement::<F>::one();
+        let one = FieldElement::<A::Field>::one();
         let mut zerofier_evaluations = get_powers_of_primitive_root_coset(
             blowup_factor_order,
             blowup_factor as usize,
@@ -138,33 +271,35 @@ impl<F: IsFFTField, A: AIR + AIR<Field = F>> ConstraintEvaluator<F, A> {
         .collect::<Vec<_>>();
 
         FieldElement::inplace_batch_inverse(&mut zerofier_evaluations).unwrap();
+        let zerofier_evaluations: Vec<FieldElement<A::FieldExtension>> = zerofier_evaluations
+            .into_iter()
+            .map(|v| v.to_extension())
+            .collect();
 
         // Iterate over trace and domain and compute transitions
-        let evaluations_t_iter;
-        let zerofier_iter;
         #[cfg(feature = "parallel")]
-        {
-            evaluations_t_iter = (0..domain.lde_roots_of_unity_coset.len()).into_par_iter();
-            zerofier_iter = evaluations_t_iter
-                .clone()
-                .map(|i| zerofier_evaluations[i % zerofier_evaluations.len()].clone());
-        }
+        let evaluations_t_iter = (0..n_elem).into_par_iter();
         #[cfg(not(feature = "parallel"))]
-        {
-            evaluations_t_iter = 0..domain.lde_roots_of_unity_coset.len();
-            zerofier_iter = zerofier_evaluations.iter().cycle();
-        }
+        let evaluations_t_iter = 0..n_elem;
 
         let evaluations_t = evaluations_t_iter
             .zip(&boundary_evaluation)
-            .zip(zerofier_iter)
-            .map(|((i, boundary), zerofier)| {
+            .map(|(i, boundary)| {
+                // The zerofier of the transitions only depends on the LDE index modulo
+                // the blowup factor.
+                let zerofier = &zerofier_evaluations
+                    [(segment.first_index + i * segment.index_step) % zerofier_evaluations.len()];
+                let periodic_values = periodic_values_evaluations
+                    .iter()
+                    .map(|evaluations| evaluations[i].clone())
+                    .collect();
                 let frame = Frame::read_from_trace(
                     lde_trace,
                     i,
-                    blowup_factor,
+                    frame_step,
                     &self.air.context().transition_offsets,
-                );
+                )
+                .with_periodic_values(periodic_values);
 
                 let evaluations_transition = self.air.compute_transition(&frame, rap_challenges);
 
@@ -179,9 +314,6 @@ impl<F: IsFFTField, A: AIR + AIR<Field = F>> ConstraintEvaluator<F, A> {
                     .fold(
                         FieldElement::zero(),
                         |acc, (((eval, exemption), _), beta)| {
-                            #[cfg(feature = "parallel")]
-                            let zerofier = zerofier.clone();
-
                             if *exemption == 0 {
                                 acc + zerofier * beta * eval
                             } else {
@@ -219,7 +351,7 @@ impl<F: IsFFTField, A: AIR + AIR<Field = F>> ConstraintEvaluator<F, A> {
 
                 acc_transition + boundary
             })
-            .collect::<Vec<FieldElement<F>>>();
+            .collect::<Vec<FieldElement<A::FieldExtension>>>();
 
         evaluations_t
     }
@@ -241,46 +373,80 @@ impl<F: IsFFTField, A: AIR + AIR<Field = F>> ConstraintEvaluator<F, A> {
     /// # Returns
     ///
     /// Returns the sum of the evaluations computed.
+    #[allow(clippy::type_complexity)]
     pub fn compute_constraint_composition_poly_evaluations_sum(
-        evaluations: &[FieldElement<F>],
-        inverse_denominators: &[FieldElement<F>],
-        degree_adjustments: &[FieldElement<F>],
-        constraint_coeffs: &[(FieldElement<F>, FieldElement<F>)],
-    ) -> FieldElement<F> {
+        evaluations: &[FieldElement<A::FieldExtension>],
+        inverse_denominators: &[FieldElement<A::FieldExtension>],
+        degree_adjustments: &[FieldElement<A::FieldExtension>],
+        constraint_coeffs: &[(
+            FieldElement<A::FieldExtension>,
+            FieldElement<A::FieldExtension>,
+        )],
+    ) -> FieldElement<A::FieldExtension> {
         evaluations
             .iter()
             .zip(degree_adjustments)
             .zip(inverse_denominators)
             .zip(constraint_coeffs)
-            .fold(
-                FieldElement::<F>::zero(),
-                |acc, (((ev, _), inv), (_, beta))| acc + ev * beta * inv,
-            )
+            .fold(FieldElement::zero(), |acc, (((ev, _), inv), (_, beta))| {
+                acc + ev * beta * inv
+            })
     }
 }
 
-fn evaluate_transition_exemptions<F: IsFFTField>(
-    transition_exemptions: Vec<Polynomial<FieldElement<F>>>,
+/// Evaluates polynomials over `F`, such as the exemption polynomials or the periodic
+/// columns, on the LDE domain and embeds the results into the extension `E`.
+fn evaluate_polynomials_on_lde_domain<F, E>(
+    polynomials: Vec<Polynomial<FieldElement<F>>>,
     domain: &Domain<F>,
-) -> Vec<Vec<FieldElement<F>>>
+) -> Vec<Vec<FieldElement<E>>>
 where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
     FieldElement<F>: Send + Sync + Serializable,
+    FieldElement<E>: Send + Sync,
     Polynomial<FieldElement<F>>: Send + Sync,
 {
     #[cfg(feature = "parallel")]
-    let exemptions_iter = transition_exemptions.par_iter();
+    let polynomials_iter = polynomials.par_iter();
     #[cfg(not(feature = "parallel"))]
-    let exemptions_iter = transition_exemptions.iter();
+    let polynomials_iter = polynomials.iter();
 
-    exemptions_iter
-        .map(|exemption| {
+    polynomials_iter
+        .map(|poly| {
             evaluate_polynomial_on_lde_domain(
-                exemption,
+                poly,
                 domain.blowup_factor,
                 domain.interpolation_domain_size,
                 &domain.coset_offset,
             )
             .unwrap()
+            .into_iter()
+            .map(|v| v.to_extension())
+            .collect()
+        })
+        .collect()
+}
+
+/// Evaluates polynomials over `F` on the coset `offset·H` of the subgroup `H` of size
+/// `domain_size`, and embeds the results into the extension `E`.
+fn evaluate_polynomials_on_coset<F, E>(
+    polynomials: &[Polynomial<FieldElement<F>>],
+    domain_size: usize,
+    offset: &FieldElement<F>,
+) -> Vec<Vec<FieldElement<E>>>
+where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
+{
+    polynomials
+        .iter()
+        .map(|poly| {
+            evaluate_polynomial_on_coset(poly, domain_size, offset)
+                .unwrap()
+                .into_iter()
+                .map(|v| v.to_extension())
+                .collect()
         })
         .collect()
 }
@@ -0,0 +1,476 @@
+use std::{
+    collections::BTreeMap,
+    ops::{Add, Mul, Neg, Sub},
+    sync::Arc,
+};
+
+use lambdaworks_math::field::{
+    element::FieldElement,
+    traits::{IsField, IsSubFieldOf},
+};
+
+/// Segment of the trace a column belongs to. In a frame, the main columns come first,
+/// followed by the auxiliary columns and the preprocessed columns.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ColumnKind {
+    Main,
+    Auxiliary,
+    Preprocessed,
+}
+
+/// Column of the trace declared in a `ConstraintBuilder`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Column {
+    pub(crate) kind: ColumnKind,
+    pub(crate) index: usize,
+}
+
+impl Column {
+    pub fn kind(&self) -> ColumnKind {
+        self.kind
+    }
+
+    /// Position of the column among the columns of its kind, which is its position in the
+    /// main or auxiliary trace, or among the preprocessed columns.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Value of the column at the current row of the frame.
+    pub fn curr<F: IsField>(&self) -> Expr<F> {
+        self.at(0)
+    }
+
+    /// Value of the column at the row following the current one.
+    pub fn next<F: IsField>(&self) -> Expr<F> {
+        self.at(1)
+    }
+
+    /// Value of the column `offset` rows after the current one.
+    pub fn at<F: IsField>(&self, offset: usize) -> Expr<F> {
+        Expr::Variable(Variable::Trace {
+            column: *self,
+            offset,
+        })
+    }
+}
+
+/// Value a constraint depends on, which is only known when the constraint is evaluated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Variable {
+    /// Value of a column `offset` rows after the current one.
+    Trace { column: Column, offset: usize },
+    /// Value of a periodic column at the current row.
+    Periodic(usize),
+    /// Challenge of the RAP, sampled after the main trace is committed.
+    Challenge(usize),
+}
+
+/// Polynomial expression over the values of the trace, of the periodic columns and of the
+/// challenges, with coefficients in `F`. Expressions are built with the arithmetic
+/// operators and share their subexpressions, so they are cheap to clone.
+#[derive(Clone, Debug)]
+pub enum Expr<F: IsField> {
+    Constant(FieldElement<F>),
+    Variable(Variable),
+    Neg(Arc<Expr<F>>),
+    Add(Arc<Expr<F>>, Arc<Expr<F>>),
+    Sub(Arc<Expr<F>>, Arc<Expr<F>>),
+    Mul(Arc<Expr<F>>, Arc<Expr<F>>),
+}
+
+impl<F: IsField> Expr<F> {
+    pub fn constant(value: FieldElement<F>) -> Self {
+        Self::Constant(value)
+    }
+
+    pub fn zero() -> Self {
+        Self::Constant(FieldElement::zero())
+    }
+
+    pub fn one() -> Self {
+        Self::Constant(FieldElement::one())
+    }
+
+    /// Returns the expression raised to `exponent`, computed by repeated squaring.
+    pub fn pow(&self, exponent: usize) -> Self {
+        let mut result: Option<Self> = None;
+        let mut base = self.clone();
+        let mut exponent = exponent;
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = Some(match result {
+                    Some(result) => result * &base,
+                    None => base.clone(),
+                });
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                base = &base * &base;
+            }
+        }
+        result.unwrap_or_else(Self::one)
+    }
+
+    /// Bound on the degree of the expression as a polynomial in the trace columns. Periodic
+    /// columns count as degree one, since their polynomials can be as large as the ones of
+    /// the trace. Challenges and constants have degree zero. See `exact_degree` for the
+    /// degree once the terms that cancel out are removed.
+    pub fn degree(&self) -> usize {
+        match self {
+            Self::Constant(_) | Self::Variable(Variable::Challenge(_)) => 0,
+            Self::Variable(_) => 1,
+            Self::Neg(expr) => expr.degree(),
+            Self::Add(lhs, rhs) | Self::Sub(lhs, rhs) => lhs.degree().max(rhs.degree()),
+            Self::Mul(lhs, rhs) => lhs.degree() + rhs.degree(),
+        }
+    }
+
+    /// Degree of the expression as a polynomial in the trace columns, counting variables as
+    /// `degree` does. It is computed by expanding the expression into a sum of monomials,
+    /// so terms that cancel out do not count. The zero polynomial has degree zero.
+    pub fn exact_degree(&self) -> usize {
+        self.expand()
+            .keys()
+            .map(|monomial| {
+                monomial
+                    .iter()
+                    .filter(|(variable, _)| !matches!(variable, Variable::Challenge(_)))
+                    .map(|(_, exponent)| exponent)
+                    .sum()
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Expands the expression into a sum of monomials with nonzero coefficients.
+    fn expand(&self) -> Monomials<F> {
+        match self {
+            Self::Constant(constant) => Monomials::constant(constant.clone()),
+            Self::Variable(variable) => {
+                Monomials::from([(BTreeMap::from([(*variable, 1)]), FieldElement::one())])
+            }
+            Self::Neg(expr) => expr
+                .expand()
+                .into_iter()
+                .map(|(monomial, coefficient)| (monomial, -coefficient))
+                .collect(),
+            Self::Add(lhs, rhs) => lhs.expand().add(rhs.expand()),
+            Self::Sub(lhs, rhs) => lhs.expand().add(
+                rhs.expand()
+                    .into_iter()
+                    .map(|(monomial, coefficient)| (monomial, -coefficient))
+                    .collect(),
+            ),
+            Self::Mul(lhs, rhs) => {
+                let (lhs, rhs) = (lhs.expand(), rhs.expand());
+                let mut product = Monomials::new();
+                for (lhs_monomial, lhs_coefficient) in &lhs {
+                    for (rhs_monomial, rhs_coefficient) in &rhs {
+                        let mut monomial = lhs_monomial.clone();
+                        for (variable, exponent) in rhs_monomial {
+                            *monomial.entry(*variable).or_insert(0) += exponent;
+                        }
+                        product = product.add(Monomials::from([(
+                            monomial,
+                            lhs_coefficient * rhs_coefficient,
+                        )]));
+                    }
+                }
+                product
+            }
+        }
+    }
+
+    /// Returns an equivalent expression where the operations on constants are computed
+    /// and the operations that leave their operand unchanged, such as adding zero or
+    /// multiplying by one, are removed. Products by zero and differences of equal
+    /// subexpressions are replaced by zero.
+    pub fn simplify(&self) -> Self {
+        let zero = FieldElement::<F>::zero();
+        let one = FieldElement::<F>::one();
+        match self {
+            Self::Constant(_) | Self::Variable(_) => self.clone(),
+            Self::Neg(expr) => match expr.simplify() {
+                Self::Constant(constant) => Self::Constant(-constant),
+                Self::Neg(expr) => expr.as_ref().clone(),
+                expr => -expr,
+            },
+            Self::Add(lhs, rhs) => match (lhs.simplify(), rhs.simplify()) {
+                (Self::Constant(lhs), Self::Constant(rhs)) => Self::Constant(lhs + rhs),
+                (Self::Constant(constant), expr) | (expr, Self::Constant(constant))
+                    if constant == zero =>
+                {
+                    expr
+                }
+                (lhs, rhs) => lhs + rhs,
+            },
+            Self::Sub(lhs, rhs) => match (lhs.simplify(), rhs.simplify()) {
+                (Self::Constant(lhs), Self::Constant(rhs)) => Self::Constant(lhs - rhs),
+                (expr, Self::Constant(constant)) if constant == zero => expr,
+                (Self::Constant(constant), expr) if constant == zero => -expr,
+                (lhs, rhs) if lhs == rhs => Self::zero(),
+                (lhs, rhs) => lhs - rhs,
+            },
+            Self::Mul(lhs, rhs) => match (lhs.simplify(), rhs.simplify()) {
+                (Self::Constant(lhs), Self::Constant(rhs)) => Self::Constant(lhs * rhs),
+                (Self::Constant(constant), _) | (_, Self::Constant(constant))
+                    if constant == zero =>
+                {
+                    Self::zero()
+                }
+                (Self::Constant(constant), expr) | (expr, Self::Constant(constant))
+                    if constant == one =>
+                {
+                    expr
+                }
+                (lhs, rhs) => lhs * rhs,
+            },
+        }
+    }
+
+    /// Calls `visit` on every variable of the expression, once for each time it appears.
+    pub fn for_each_variable(&self, visit: &mut impl FnMut(&Variable)) {
+        match self {
+            Self::Constant(_) => {}
+            Self::Variable(variable) => visit(variable),
+            Self::Neg(expr) => expr.for_each_variable(visit),
+            Self::Add(lhs, rhs) | Self::Sub(lhs, rhs) | Self::Mul(lhs, rhs) => {
+                lhs.for_each_variable(visit);
+                rhs.for_each_variable(visit);
+            }
+        }
+    }
+
+    /// Largest row offset of the trace values the expression depends on.
+    pub fn max_offset(&self) -> usize {
+        let mut max_offset = 0;
+        self.for_each_variable(&mut |variable| {
+            if let Variable::Trace { offset, .. } = variable {
+                max_offset = max_offset.max(*offset);
+            }
+        });
+        max_offset
+    }
+
+    /// Evaluates the expression over `E`, taking the values of the variables from `value`.
+    pub fn evaluate<E>(&self, value: &impl Fn(&Variable) -> FieldElement<E>) -> FieldElement<E>
+    where
+        F: IsSubFieldOf<E>,
+        E: IsField,
+    {
+        match self {
+            Self::Constant(constant) => constant.clone().to_extension(),
+            Self::Variable(variable) => value(variable),
+            Self::Neg(expr) => -expr.evaluate(value),
+            Self::Add(lhs, rhs) => lhs.evaluate(value) + rhs.evaluate(value),
+            Self::Sub(lhs, rhs) => lhs.evaluate(value) - rhs.evaluate(value),
+            Self::Mul(lhs, rhs) => lhs.evaluate(value) * rhs.evaluate(value),
+        }
+    }
+}
+
+/// Structural equality: expressions are equal if they are built with the same operations
+/// on the same operands, even when they are equal as polynomials otherwise.
+impl<F: IsField> PartialEq for Expr<F> {
+    fn eq(&self, other: &Self) -> bool {
+        let same = |lhs: &Arc<Expr<F>>, rhs: &Arc<Expr<F>>| Arc::ptr_eq(lhs, rhs) || lhs == rhs;
+        match (self, other) {
+            (Self::Constant(lhs), Self::Constant(rhs)) => lhs == rhs,
+            (Self::Variable(lhs), Self::Variable(rhs)) => lhs == rhs,
+            (Self::Neg(lhs), Self::Neg(rhs)) => same(lhs, rhs),
+            (Self::Add(lhs_0, lhs_1), Self::Add(rhs_0, rhs_1))
+            | (Self::Sub(lhs_0, lhs_1), Self::Sub(rhs_0, rhs_1))
+            | (Self::Mul(lhs_0, lhs_1), Self::Mul(rhs_0, rhs_1)) => {
+                same(lhs_0, rhs_0) && same(lhs_1, rhs_1)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Sum of monomials, each of them a product of variables raised to their exponent, mapped
+/// to its coefficient.
+type Monomials<F> = BTreeMap<BTreeMap<Variable, usize>, FieldElement<F>>;
+
+trait MonomialsExt<F: IsField>: Sized {
+    fn constant(constant: FieldElement<F>) -> Self;
+    fn add(self, other: Self) -> Self;
+}
+
+impl<F: IsField> MonomialsExt<F> for Monomials<F> {
+    fn constant(constant: FieldElement<F>) -> Self {
+        Self::new().add(Self::from([(BTreeMap::new(), constant)]))
+    }
+
+    /// Adds the coefficients of the same monomials, dropping the ones that cancel out.
+    fn add(mut self, other: Self) -> Self {
+        for (monomial, coefficient) in other {
+            let sum = match self.remove(&monomial) {
+                Some(existing) => existing + coefficient,
+                None => coefficient,
+            };
+            if sum != FieldElement::zero() {
+                self.insert(monomial, sum);
+            }
+        }
+        self
+    }
+}
+
+impl<F: IsField> From<FieldElement<F>> for Expr<F> {
+    fn from(value: FieldElement<F>) -> Self {
+        Self::Constant(value)
+    }
+}
+
+impl<F: IsField> From<Variable> for Expr<F> {
+    fn from(variable: Variable) -> Self {
+        Self::Variable(variable)
+    }
+}
+
+impl<F: IsField> Neg for Expr<F> {
+    type Output = Expr<F>;
+
+    fn neg(self) -> Self::Output {
+        Expr::Neg(Arc::new(self))
+    }
+}
+
+impl<F: IsField> Neg for &Expr<F> {
+    type Output = Expr<F>;
+
+    fn neg(self) -> Self::Output {
+        -self.clone()
+    }
+}
+
+/// Implements a binary operator for every combination of owned and borrowed expressions
+/// and for field elements on the right hand side.
+macro_rules! impl_binary_operator {
+    ($trait:ident, $method:ident, $variant:ident) => {
+        impl<F: IsField> $trait<Expr<F>> for Expr<F> {
+            type Output = Expr<F>;
+
+            fn $method(self, rhs: Expr<F>) -> Self::Output {
+                Expr::$variant(Arc::new(self), Arc::new(rhs))
+            }
+        }
+
+        impl<F: IsField> $trait<&Expr<F>> for Expr<F> {
+            type Output = Expr<F>;
+
+            fn $method(self, rhs: &Expr<F>) -> Self::Output {
+                self.$method(rhs.clone())
+            }
+        }
+
+        impl<F: IsField> $trait<Expr<F>> for &Expr<F> {
+            type Output = Expr<F>;
+
+            fn $method(self, rhs: Expr<F>) -> Self::Output {
+                self.clone().$method(rhs)
+            }
+        }
+
+        impl<F: IsField> $trait<&Expr<F>> for &Expr<F> {
+            type Output = Expr<F>;
+
+            fn $method(self, rhs: &Expr<F>) -> Self::Output {
+                self.clone().$method(rhs.clone())
+            }
+        }
+
+        impl<F: IsField> $trait<FieldElement<F>> for Expr<F> {
+            type Output = Expr<F>;
+
+            fn $method(self, rhs: FieldElement<F>) -> Self::Output {
+                self.$method(Expr::Constant(rhs))
+            }
+        }
+
+        impl<F: IsField> $trait<FieldElement<F>> for &Expr<F> {
+            type Output = Expr<F>;
+
+            fn $method(self, rhs: FieldElement<F>) -> Self::Output {
+                self.clone().$method(Expr::Constant(rhs))
+            }
+        }
+    };
+}
+
+impl_binary_operator!(Add, add, Add);
+impl_binary_operator!(Sub, sub, Sub);
+impl_binary_operator!(Mul, mul, Mul);
+
+#[cfg(test)]
+mod tests {
+    use lambdaworks_math::field::{
+        element::FieldElement, fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
+    };
+
+    use super::{Column, ColumnKind, Expr, Variable};
+
+    type FE = FieldElement<Stark252PrimeField>;
+    type E = Expr<Stark252PrimeField>;
+
+    fn column(index: usize) -> Column {
+        Column {
+            kind: ColumnKind::Main,
+            index,
+        }
+    }
+
+    #[test]
+    fn terms_that_cancel_out_do_not_count_in_the_exact_degree() {
+        let (a, b) = (column(0).curr::<Stark252PrimeField>(), column(1).curr());
+        let expr = (&a + &b) * (&a - &b) - &a * &a;
+        assert_eq!(expr.degree(), 2);
+        assert_eq!(expr.exact_degree(), 2);
+
+        let expr = (&a + &b) * (&a + &b) - &a * &a - &b * &b - &a * &b * FE::from(2) + &a;
+        assert_eq!(expr.degree(), 2);
+        assert_eq!(expr.exact_degree(), 1);
+        assert_eq!((&expr - &expr).exact_degree(), 0);
+    }
+
+    #[test]
+    fn challenges_do_not_count_in_the_degree() {
+        let a = column(0).curr::<Stark252PrimeField>();
+        let gamma = E::Variable(Variable::Challenge(0));
+        let expr = (&a + &gamma) * &gamma;
+        assert_eq!(expr.degree(), 1);
+        assert_eq!(expr.exact_degree(), 1);
+        assert_eq!(a.pow(5).exact_degree(), 5);
+    }
+
+    #[test]
+    fn simplify_folds_constants_and_removes_neutral_operations() {
+        let a = column(0).curr::<Stark252PrimeField>();
+        let two = E::constant(FE::from(2));
+        let expr = (&a * (E::one() + E::zero())) + (E::zero() * &a) - (&two * &two - FE::from(4));
+        assert_eq!(expr.simplify(), a);
+        assert_eq!((-(-a.clone())).simplify(), a);
+        assert_eq!((&a - &a).simplify(), E::zero());
+        assert_eq!(
+            (E::one() - FE::from(3)).simplify(),
+            E::constant(-FE::from(2))
+        );
+    }
+
+    #[test]
+    fn simplified_expressions_evaluate_to_the_same_value() {
+        let (a, b) = (column(0).curr::<Stark252PrimeField>(), column(0).next());
+        let expr = (&a + E::zero()) * (&b - FE::from(3)) * E::one() - (E::zero() - &a).pow(3);
+        let value = |variable: &Variable| match variable {
+            Variable::Trace { offset, .. } => FE::from(*offset as u64 + 5),
+            _ => unreachable!(),
+        };
+        assert_eq!(expr.simplify().evaluate(&value), expr.evaluate(&value));
+        assert_eq!(
+            expr.evaluate(&value),
+            FE::from(5) * FE::from(6 - 3) + FE::from(125)
+        );
+    }
+}
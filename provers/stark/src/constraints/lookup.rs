@@ -0,0 +1,261 @@
+use lambdaworks_math::field::{
+    element::FieldElement,
+    traits::{IsFFTField, IsField, IsSubFieldOf},
+};
+
+use crate::{frame::Frame, trace::TraceTable, transcript::IsStarkTranscript};
+
+use super::boundary::BoundaryConstraint;
+
+/// Challenges of a LogUp argument: `z` is the point the fractions are evaluated at and
+/// `alpha` compresses the tuples of several columns into a single value,
+/// `c₀ + α c₁ + α² c₂ + ...`.
+#[derive(Clone, Debug)]
+pub struct LogUpChallenges<E: IsField> {
+    pub z: FieldElement<E>,
+    pub alpha: FieldElement<E>,
+}
+
+impl<E: IsField> LogUpChallenges<E> {
+    pub fn sample(transcript: &mut impl IsStarkTranscript<E>) -> Self {
+        Self {
+            z: transcript.sample_field_element(),
+            alpha: transcript.sample_field_element(),
+        }
+    }
+
+    fn compress<'a>(
+        &self,
+        values: impl DoubleEndedIterator<Item = &'a FieldElement<E>>,
+    ) -> FieldElement<E>
+    where
+        E: 'a,
+    {
+        values
+            .rev()
+            .fold(FieldElement::zero(), |acc, value| acc * &self.alpha + value)
+    }
+}
+
+/// LogUp lookup argument between columns of the main trace. Every row of each of the
+/// `looking` column sets must be a row of the `looked` column set, which is looked up
+/// as many times as given by the `multiplicities` column:
+///
+/// `∑ᵢ ∑ₖ 1 / (z - lookingₖ[i]) = ∑ᵢ m[i] / (z - looked[i])`
+///
+/// The argument adds the following columns to the auxiliary trace, starting at
+/// `first_aux_column`:
+/// * One column `hₖ[i] = 1 / (z - lookingₖ[i])` for each looking column set.
+/// * A column `h[i] = m[i] / (z - looked[i])` for the looked column set.
+/// * An accumulator `s`, with `s[0] = 0` and `s[i + 1] = s[i] + ∑ₖ hₖ[i] - h[i]`.
+///
+/// The accumulator transition also holds from the last row to the first one, which can
+/// only happen if both sides of the equation above are equal. The constraints are read
+/// from the first two rows of the frame, so the transition offsets of the AIR must start
+/// with `0, 1`.
+#[derive(Clone, Debug)]
+pub struct LogUp {
+    looking: Vec<Vec<usize>>,
+    looked: Vec<usize>,
+    multiplicities: usize,
+    first_aux_column: usize,
+}
+
+impl LogUp {
+    /// Creates the argument. Columns are given by their index in the main trace, and all
+    /// the column sets must have the same number of columns. `first_aux_column` is the
+    /// index, counting the main columns first, of the first auxiliary column of the
+    /// argument.
+    pub fn new(
+        looking: Vec<Vec<usize>>,
+        looked: Vec<usize>,
+        multiplicities: usize,
+        first_aux_column: usize,
+    ) -> Self {
+        assert!(
+            looking.iter().all(|columns| columns.len() == looked.len()),
+            "All the column sets of a lookup must have the same number of columns"
+        );
+        Self {
+            looking,
+            looked,
+            multiplicities,
+            first_aux_column,
+        }
+    }
+
+    /// Number of auxiliary columns added by the argument.
+    pub fn number_auxiliary_columns(&self) -> usize {
+        self.looking.len() + 2
+    }
+
+    /// Degrees of the transition constraints returned by `compute_transition`.
+    pub fn transition_degrees(&self) -> Vec<usize> {
+        let mut degrees = vec![2; self.looking.len() + 1];
+        degrees.push(1);
+        degrees
+    }
+
+    /// Number of transition constraints returned by `compute_transition`.
+    pub fn num_transition_constraints(&self) -> usize {
+        self.looking.len() + 2
+    }
+
+    /// Index of the accumulator column, counting the main columns first.
+    pub fn accumulator_column(&self) -> usize {
+        self.first_aux_column + self.looking.len() + 1
+    }
+
+    /// Returns the auxiliary columns of the argument for the given main trace.
+    pub fn build_auxiliary_trace<F, E>(
+        &self,
+        main_trace: &TraceTable<F>,
+        challenges: &LogUpChallenges<E>,
+    ) -> TraceTable<E>
+    where
+        F: IsFFTField + IsSubFieldOf<E>,
+        E: IsFFTField,
+    {
+        let trace_length = main_trace.n_rows();
+        let row = |i: usize, columns: &[usize]| -> Vec<FieldElement<E>> {
+            columns
+                .iter()
+                .map(|col| main_trace.get(i, *col).to_extension())
+                .collect()
+        };
+
+        let mut columns: Vec<Vec<FieldElement<E>>> = self
+            .looking
+            .iter()
+            .chain(core::iter::once(&self.looked))
+            .map(|columns| {
+                let mut denominators: Vec<_> = (0..trace_length)
+                    .map(|i| &challenges.z - challenges.compress(row(i, columns).iter()))
+                    .collect();
+                FieldElement::inplace_batch_inverse(&mut denominators)
+                    .expect("the lookup challenge is not a value of the trace");
+                denominators
+            })
+            .collect();
+
+        let looked = columns.last_mut().unwrap();
+        for (i, h) in looked.iter_mut().enumerate() {
+            *h = &*h * main_trace.get(i, self.multiplicities).to_extension();
+        }
+
+        let mut accumulator = Vec::with_capacity(trace_length);
+        accumulator.push(FieldElement::zero());
+        for i in 0..trace_length - 1 {
+            let looking_sum = columns[..self.looking.len()]
+                .iter()
+                .fold(FieldElement::zero(), |acc, h| acc + &h[i]);
+            accumulator.push(&accumulator[i] + looking_sum - &columns[self.looking.len()][i]);
+        }
+        columns.push(accumulator);
+
+        TraceTable::from_columns(&columns)
+    }
+
+    /// Returns the evaluations of the transition constraints of the argument over the
+    /// first two rows of the frame.
+    pub fn compute_transition<E: IsField>(
+        &self,
+        frame: &Frame<E>,
+        challenges: &LogUpChallenges<E>,
+    ) -> Vec<FieldElement<E>> {
+        let first_row = frame.get_row(0);
+        let second_row = frame.get_row(1);
+        let h = &first_row[self.first_aux_column..self.accumulator_column()];
+        let accumulator = self.accumulator_column();
+
+        let mut constraints: Vec<_> = self
+            .looking
+            .iter()
+            .zip(h)
+            .map(|(columns, h)| {
+                let value = challenges.compress(columns.iter().map(|col| &first_row[*col]));
+                h * (&challenges.z - value) - FieldElement::one()
+            })
+            .collect();
+
+        let looked_h = &h[self.looking.len()];
+        let value = challenges.compress(self.looked.iter().map(|col| &first_row[*col]));
+        constraints.push(looked_h * (&challenges.z - value) - &first_row[self.multiplicities]);
+
+        let looking_sum = h[..self.looking.len()]
+            .iter()
+            .fold(FieldElement::zero(), |acc, h| acc + h);
+        constraints
+            .push(&second_row[accumulator] - &first_row[accumulator] - looking_sum + looked_h);
+
+        constraints
+    }
+
+    /// Returns the boundary constraints of the argument.
+    pub fn boundary_constraints<E: IsField>(&self) -> Vec<BoundaryConstraint<E>> {
+        vec![BoundaryConstraint::new(
+            self.accumulator_column(),
+            0,
+            FieldElement::zero(),
+        )]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lambdaworks_math::field::fields::u64_prime_field::{F17, FE17};
+
+    use super::*;
+
+    fn challenges() -> LogUpChallenges<F17> {
+        LogUpChallenges {
+            z: FE17::from(5),
+            alpha: FE17::from(3),
+        }
+    }
+
+    #[test]
+    fn accumulator_wraps_around_to_zero_when_lookups_are_valid() {
+        // Looking column: [0, 2, 2, 1], looked column: [0, 1, 2, 3], multiplicities: [1, 1, 2, 0]
+        let main_trace = TraceTable::<F17>::from_columns(&[
+            [0, 2, 2, 1].map(FE17::from).to_vec(),
+            [0, 1, 2, 3].map(FE17::from).to_vec(),
+            [1, 1, 2, 0].map(FE17::from).to_vec(),
+        ]);
+        let lookup = LogUp::new(vec![vec![0]], vec![1], 2, 3);
+
+        let aux_trace = lookup.build_auxiliary_trace(&main_trace, &challenges());
+        assert_eq!(aux_trace.n_cols(), lookup.number_auxiliary_columns());
+
+        let last = main_trace.n_rows() - 1;
+        assert_eq!(
+            aux_trace.get(last, 2) + aux_trace.get(last, 0) - aux_trace.get(last, 1),
+            FE17::zero()
+        );
+    }
+
+    #[test]
+    fn accumulator_does_not_wrap_around_to_zero_when_a_lookup_is_invalid() {
+        let main_trace = TraceTable::<F17>::from_columns(&[
+            [0, 2, 2, 4].map(FE17::from).to_vec(),
+            [0, 1, 2, 3].map(FE17::from).to_vec(),
+            [1, 1, 2, 0].map(FE17::from).to_vec(),
+        ]);
+        let lookup = LogUp::new(vec![vec![0]], vec![1], 2, 3);
+
+        let aux_trace = lookup.build_auxiliary_trace(&main_trace, &challenges());
+
+        let last = main_trace.n_rows() - 1;
+        assert_ne!(
+            aux_trace.get(last, 2) + aux_trace.get(last, 0) - aux_trace.get(last, 1),
+            FE17::zero()
+        );
+    }
+
+    #[test]
+    fn column_sets_are_compressed_with_powers_of_alpha() {
+        let values = [FE17::from(1), FE17::from(2), FE17::from(4)];
+        // 1 + 2·3 + 4·9 = 43 = 9 mod 17
+        assert_eq!(challenges().compress(values.iter()), FE17::from(9));
+    }
+}
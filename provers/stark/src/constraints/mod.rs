@@ -1,2 +1,6 @@
 pub mod boundary;
+pub mod builder;
+pub mod dag;
 pub mod evaluator;
+pub mod expression;
+pub mod lookup;
@@ -1,50 +1,150 @@
 use crate::frame::Frame;
 use crate::trace::TraceTable;
+use crate::transcript::IsStarkTranscript;
 
 use super::domain::Domain;
 use super::traits::AIR;
+use core::fmt;
 use lambdaworks_math::fft::polynomial::FFTPoly;
 use lambdaworks_math::{
-    field::{element::FieldElement, traits::IsFFTField},
+    field::{
+        element::FieldElement,
+        traits::{IsFFTField, IsField},
+    },
     polynomial::Polynomial,
 };
 use log::{error, info};
 
-/// Validates that the trace is valid with respect to the supplied AIR constraints
-pub fn validate_trace<F: IsFFTField, A: AIR<Field = F>>(
+/// A boundary constraint that does not hold on the trace.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoundaryConstraintFailure<F: IsField> {
+    /// Index of the constraint in the AIR boundary constraints.
+    pub constraint_idx: usize,
+    pub step: usize,
+    pub col: usize,
+    pub expected: FieldElement<F>,
+    pub found: FieldElement<F>,
+}
+
+/// A transition constraint that does not evaluate to zero on a step of the trace.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TransitionConstraintFailure<F: IsField> {
+    /// Index of the constraint in the output of `AIR::compute_transition`.
+    pub constraint_idx: usize,
+    /// First row of the frame the constraint was evaluated on.
+    pub step: usize,
+    /// Rows of the frame, one for each of the transition offsets of the AIR.
+    pub frame: Vec<Vec<FieldElement<F>>>,
+    pub evaluation: FieldElement<F>,
+}
+
+/// Constraints of an AIR that a trace fails to satisfy. An empty report means the
+/// trace is valid.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TraceValidationReport<F: IsField> {
+    pub boundary_failures: Vec<BoundaryConstraintFailure<F>>,
+    pub transition_failures: Vec<TransitionConstraintFailure<F>>,
+}
+
+impl<F: IsField> TraceValidationReport<F> {
+    pub fn is_valid(&self) -> bool {
+        self.boundary_failures.is_empty() && self.transition_failures.is_empty()
+    }
+
+    pub fn num_failures(&self) -> usize {
+        self.boundary_failures.len() + self.transition_failures.len()
+    }
+}
+
+impl<F: IsField> fmt::Display for TraceValidationReport<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_valid() {
+            return writeln!(f, "All constraints hold on the trace");
+        }
+        writeln!(f, "{} constraint failure(s):", self.num_failures())?;
+        for failure in &self.boundary_failures {
+            writeln!(
+                f,
+                "- boundary constraint {} at step {}, column {}: expected {:?}, found {:?}",
+                failure.constraint_idx, failure.step, failure.col, failure.expected, failure.found
+            )?;
+        }
+        for failure in &self.transition_failures {
+            writeln!(
+                f,
+                "- transition constraint {} at step {}: evaluates to {:?}",
+                failure.constraint_idx, failure.step, failure.evaluation
+            )?;
+            for (offset, row) in failure.frame.iter().enumerate() {
+                writeln!(f, "    frame row {}: {:?}", offset, row)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Checks the main trace against the constraints of the AIR without proving. The RAP
+/// challenges are sampled from `transcript` and used to build the auxiliary trace, as
+/// the prover does. When `max_failures` is given, the check stops after that many
+/// failures are found.
+pub fn check_trace<A: AIR>(
     air: &A,
-    trace_polys: &[Polynomial<FieldElement<A::Field>>],
-    domain: &Domain<A::Field>,
-    rap_challenges: &A::RAPChallenges,
-) -> bool {
-    info!("Starting constraints validation over trace...");
-    let mut ret = true;
+    main_trace: &TraceTable<A::Field>,
+    transcript: &mut impl IsStarkTranscript<A::FieldExtension>,
+    max_failures: Option<usize>,
+) -> TraceValidationReport<A::FieldExtension> {
+    let rap_challenges = air.build_rap_challenges(transcript);
+    let aux_trace = air.build_auxiliary_trace(main_trace, &rap_challenges);
 
-    let trace_columns: Vec<_> = trace_polys
-        .iter()
-        .map(|poly| {
-            poly.evaluate_fft(1, Some(domain.interpolation_domain_size))
-                .unwrap()
-        })
-        .collect();
+    let mut columns = main_trace.to_extension::<A::FieldExtension>().columns();
+    if !aux_trace.is_empty() {
+        columns.extend(aux_trace.columns());
+    }
+    columns.extend(
+        air.get_preprocessed_columns()
+            .into_iter()
+            .map(|column| column.into_iter().map(|e| e.to_extension()).collect()),
+    );
+    let trace = TraceTable::from_columns(&columns);
 
-    let trace = TraceTable::from_columns(&trace_columns);
+    check_constraints(air, &trace, &rap_challenges, max_failures)
+}
+
+/// Evaluates every boundary and transition constraint of the AIR over `trace`, which
+/// holds the main, auxiliary and preprocessed columns, and collects the ones that do not hold.
+fn check_constraints<A: AIR>(
+    air: &A,
+    trace: &TraceTable<A::FieldExtension>,
+    rap_challenges: &A::RAPChallenges,
+    max_failures: Option<usize>,
+) -> TraceValidationReport<A::FieldExtension> {
+    let max_failures = max_failures.unwrap_or(usize::MAX);
+    let mut report = TraceValidationReport {
+        boundary_failures: Vec::new(),
+        transition_failures: Vec::new(),
+    };
 
     // --------- VALIDATE BOUNDARY CONSTRAINTS ------------
-    air.boundary_constraints(rap_challenges)
+    for (constraint_idx, constraint) in air
+        .boundary_constraints(rap_challenges)
         .constraints
         .iter()
-        .for_each(|constraint| {
-            let col = constraint.col;
-            let step = constraint.step;
-            let boundary_value = constraint.value.clone();
-            let trace_value = trace.get(step, col);
-
-            if boundary_value != trace_value {
-                ret = false;
-                error!("Boundary constraint inconsistency - Expected value {} in step {} and column {}, found: {}", boundary_value.representative(), step, col, trace_value.representative());
-            }
-        });
+        .enumerate()
+    {
+        if report.num_failures() >= max_failures {
+            return report;
+        }
+        let trace_value = trace.get(constraint.step, constraint.col);
+        if constraint.value != trace_value {
+            report.boundary_failures.push(BoundaryConstraintFailure {
+                constraint_idx,
+                step: constraint.step,
+                col: constraint.col,
+                expected: constraint.value.clone(),
+                found: trace_value,
+            });
+        }
+    }
 
     // --------- VALIDATE TRANSITION CONSTRAINTS -----------
     let n_transition_constraints = air.context().num_transition_constraints();
@@ -56,30 +156,137 @@ pub fn validate_trace<F: IsFFTField, A: AIR<Field = F>>(
         .map(|(trace_steps, exemptions)| trace_steps - exemptions)
         .collect();
 
+    let periodic_columns = air.get_periodic_column_values();
+
     // Iterate over trace and compute transitions
     for step in 0..trace.n_rows() {
-        let frame = Frame::read_from_trace(&trace, step, 1, &air.context().transition_offsets);
+        let periodic_values = periodic_columns
+            .iter()
+            .map(|column| column[step % column.len()].clone().to_extension())
+            .collect();
+        let frame = Frame::read_from_trace(trace, step, 1, &air.context().transition_offsets)
+            .with_periodic_values(periodic_values);
 
         let evaluations = air.compute_transition(&frame, rap_challenges);
         // Iterate over each transition evaluation. When the evaluated step is not from
         // the exemption steps corresponding to the transition, it should have zero as a
         // result
-        evaluations.iter().enumerate().for_each(|(i, eval)| {
-            // Check that all the transition constraint evaluations of the trace are zero.
-            // We don't take into account the transition exemptions.
-            if step < exemption_steps[i] && eval != &FieldElement::<F>::zero() {
-                ret = false;
-                error!(
-                    "Inconsistent evaluation of transition {} in step {} - expected 0, got {}",
-                    i,
-                    step,
-                    eval.representative()
-                );
+        for (constraint_idx, eval) in evaluations.iter().enumerate() {
+            if report.num_failures() >= max_failures {
+                return report;
+            }
+            if step < exemption_steps[constraint_idx] && eval != &FieldElement::zero() {
+                report
+                    .transition_failures
+                    .push(TransitionConstraintFailure {
+                        constraint_idx,
+                        step,
+                        frame: (0..frame.n_rows())
+                            .map(|row| frame.get_row(row).to_vec())
+                            .collect(),
+                        evaluation: eval.clone(),
+                    });
             }
+        }
+    }
+
+    report
+}
+
+/// Checks the context of the AIR against its symbolic transitions, if it has them. The
+/// number of constraints, their exemptions and the frame offsets must match, and no
+/// declared degree can be lower than the exact degree of its constraint, since the
+/// composition polynomial would not fit in its degree bound. Higher degrees are sound,
+/// but make the proofs larger, so they are reported too.
+pub fn validate_symbolic_transitions<A: AIR>(air: &A) -> bool {
+    let Some(transitions) = air.symbolic_transitions() else {
+        return true;
+    };
+    let context = air.context();
+
+    if context.num_transition_constraints != transitions.constraints().len()
+        || context.transition_degrees.len() != transitions.constraints().len()
+    {
+        error!(
+            "The context declares {} transition constraints, but there are {}",
+            context.num_transition_constraints,
+            transitions.constraints().len()
+        );
+        return false;
+    }
+    let mut is_valid = true;
+    if context.transition_offsets != transitions.transition_offsets() {
+        error!(
+            "The context declares the frame offsets {:?}, but the constraints read {:?}",
+            context.transition_offsets,
+            transitions.transition_offsets()
+        );
+        is_valid = false;
+    }
+    if context.transition_exemptions != transitions.transition_exemptions() {
+        error!(
+            "The context declares the exemptions {:?}, but the constraints have {:?}",
+            context.transition_exemptions,
+            transitions.transition_exemptions()
+        );
+        is_valid = false;
+    }
+    for (constraint_idx, (declared, exact)) in context
+        .transition_degrees
+        .iter()
+        .zip(transitions.transition_degrees())
+        .enumerate()
+    {
+        if declared < exact {
+            error!(
+                "Transition {constraint_idx} is declared of degree {declared}, but its degree is {exact}"
+            );
+            is_valid = false;
+        } else if declared > exact {
+            info!(
+                "Transition {constraint_idx} is declared of degree {declared}, but its degree is only {exact}"
+            );
+        }
+    }
+    is_valid
+}
+
+/// Validates that the trace is valid with respect to the supplied AIR constraints
+pub fn validate_trace<A: AIR>(
+    air: &A,
+    trace_polys: &[Polynomial<FieldElement<A::FieldExtension>>],
+    domain: &Domain<A::Field>,
+    rap_challenges: &A::RAPChallenges,
+) -> bool {
+    info!("Starting constraints validation over trace...");
+
+    // Blinded polynomials have more coefficients than the trace has rows, so they are
+    // evaluated on a larger domain, which holds the trace domain at regular steps.
+    let trace_columns: Vec<_> = trace_polys
+        .iter()
+        .map(|poly| {
+            let evaluations = poly
+                .evaluate_fft(1, Some(domain.interpolation_domain_size))
+                .unwrap();
+            let step = evaluations.len() / domain.interpolation_domain_size;
+            evaluations.into_iter().step_by(step).collect()
         })
+        .collect();
+
+    let trace = TraceTable::from_columns(&trace_columns);
+
+    let report = check_constraints(air, &trace, rap_challenges, None);
+    for failure in &report.boundary_failures {
+        error!("Boundary constraint inconsistency - Expected value {:?} in step {} and column {}, found: {:?}", failure.expected, failure.step, failure.col, failure.found);
+    }
+    for failure in &report.transition_failures {
+        error!(
+            "Inconsistent evaluation of transition {} in step {} - expected 0, got {:?}",
+            failure.constraint_idx, failure.step, failure.evaluation
+        );
     }
     info!("Constraints validation check ended");
-    ret
+    report.is_valid()
 }
 
 pub fn check_boundary_polys_divisibility<F: IsFFTField>(
@@ -102,7 +309,7 @@ pub fn check_boundary_polys_divisibility<F: IsFFTField>(
 /// array, returning a true when valid and false when not.
 pub fn validate_2d_structure<F>(data: &[FieldElement<F>], width: usize) -> bool
 where
-    F: IsFFTField,
+    F: IsField,
 {
     let rows: Vec<Vec<FieldElement<F>>> = data.chunks(width).map(|c| c.to_vec()).collect();
     rows.iter().all(|r| r.len() == rows[0].len())
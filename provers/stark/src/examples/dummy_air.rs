@@ -21,6 +21,7 @@ pub struct DummyAIR {
 
 impl AIR for DummyAIR {
     type Field = Stark252PrimeField;
+    type FieldExtension = Stark252PrimeField;
     type RAPChallenges = ();
     type PublicInputs = ();
 
@@ -92,10 +93,6 @@ impl AIR for DummyAIR {
         &self.context
     }
 
-    fn composition_poly_degree_bound(&self) -> usize {
-        self.trace_length
-    }
-
     fn trace_length(&self) -> usize {
         self.trace_length
     }
@@ -1,13 +1,19 @@
-use std::ops::Div;
+use std::{marker::PhantomData, ops::Div};
 
 use lambdaworks_math::{
-    field::{element::FieldElement, traits::IsFFTField},
+    field::{
+        element::FieldElement,
+        traits::{IsFFTField, IsSubFieldOf},
+    },
     helpers::resize_to_next_power_of_two,
     traits::ByteConversion,
 };
 
 use crate::{
-    constraints::boundary::{BoundaryConstraint, BoundaryConstraints},
+    constraints::{
+        boundary::{BoundaryConstraint, BoundaryConstraints},
+        builder::{ConstraintBuilder, TransitionConstraints},
+    },
     context::AirContext,
     frame::Frame,
     proof::options::ProofOptions,
@@ -16,14 +22,19 @@ use crate::{
     transcript::IsStarkTranscript,
 };
 
+/// Fibonacci sequence together with a permutation argument over a permuted copy of it.
+/// The permutation challenge and the auxiliary column live in `E`, which can be an
+/// extension of the trace field `F`.
 #[derive(Clone)]
-pub struct FibonacciRAP<F>
+pub struct FibonacciRAP<F, E = F>
 where
     F: IsFFTField,
 {
     context: AirContext,
+    constraints: TransitionConstraints<F>,
     trace_length: usize,
     pub_inputs: FibonacciRAPPublicInputs<F>,
+    phantom: PhantomData<E>,
 }
 
 #[derive(Clone, Debug)]
@@ -36,13 +47,15 @@ where
     pub a1: FieldElement<F>,
 }
 
-impl<F> AIR for FibonacciRAP<F>
+impl<F, E> AIR for FibonacciRAP<F, E>
 where
-    F: IsFFTField,
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsFFTField,
     FieldElement<F>: ByteConversion,
 {
     type Field = F;
-    type RAPChallenges = FieldElement<Self::Field>;
+    type FieldExtension = E;
+    type RAPChallenges = FieldElement<Self::FieldExtension>;
     type PublicInputs = FibonacciRAPPublicInputs<Self::Field>;
 
     fn new(
@@ -50,22 +63,28 @@ where
         pub_inputs: &Self::PublicInputs,
         proof_options: &ProofOptions,
     ) -> Self {
+        let mut builder = ConstraintBuilder::new();
+        let a = builder.main_column("a");
+        let b = builder.main_column("b");
+        let z = builder.auxiliary_column("z");
+        let gamma = builder.challenge("gamma");
+
+        // The Fibonacci sequence only holds up to the last step, the rows after it are
+        // padding.
         let exemptions = 3 + trace_length - pub_inputs.steps - 1;
+        builder.transition_with_exemptions(a.at(2) - a.next() - a.curr(), exemptions);
+        // Permutation argument: z' (b + 𝛾) = z (a + 𝛾)
+        builder.transition(z.next() * (b.curr() + &gamma) - z.curr() * (a.curr() + &gamma));
 
-        let context = AirContext {
-            proof_options: proof_options.clone(),
-            trace_columns: 3,
-            transition_degrees: vec![1, 2],
-            transition_offsets: vec![0, 1, 2],
-            transition_exemptions: vec![exemptions, 1],
-            num_transition_constraints: 2,
-            num_transition_exemptions: 2,
-        };
+        let constraints = builder.build();
+        let context = constraints.air_context(proof_options);
 
         Self {
             context,
+            constraints,
             trace_length,
             pub_inputs: pub_inputs.clone(),
+            phantom: PhantomData,
         }
     }
 
@@ -73,7 +92,7 @@ where
         &self,
         main_trace: &TraceTable<Self::Field>,
         gamma: &Self::RAPChallenges,
-    ) -> TraceTable<Self::Field> {
+    ) -> TraceTable<Self::FieldExtension> {
         let main_segment_cols = main_trace.columns();
         let not_perm = &main_segment_cols[0];
         let perm = &main_segment_cols[1];
@@ -83,11 +102,11 @@ where
         let mut aux_col = Vec::new();
         for i in 0..trace_len {
             if i == 0 {
-                aux_col.push(FieldElement::<Self::Field>::one());
+                aux_col.push(FieldElement::<Self::FieldExtension>::one());
             } else {
                 let z_i = &aux_col[i - 1];
-                let n_p_term = not_perm[i - 1].clone() + gamma;
-                let p_term = &perm[i - 1] + gamma;
+                let n_p_term = not_perm[i - 1].clone().to_extension() + gamma;
+                let p_term = perm[i - 1].clone().to_extension() + gamma;
 
                 aux_col.push(z_i * n_p_term.div(p_term));
             }
@@ -97,51 +116,38 @@ where
 
     fn build_rap_challenges(
         &self,
-        transcript: &mut impl IsStarkTranscript<F>,
+        transcript: &mut impl IsStarkTranscript<E>,
     ) -> Self::RAPChallenges {
         transcript.sample_field_element()
     }
 
     fn number_auxiliary_rap_columns(&self) -> usize {
-        1
+        self.constraints.number_of_auxiliary_columns()
     }
 
     fn compute_transition(
         &self,
-        frame: &Frame<Self::Field>,
+        frame: &Frame<Self::FieldExtension>,
         gamma: &Self::RAPChallenges,
-    ) -> Vec<FieldElement<Self::Field>> {
-        // Main constraints
-        let first_row = frame.get_row(0);
-        let second_row = frame.get_row(1);
-        let third_row = frame.get_row(2);
-
-        let mut constraints =
-            vec![third_row[0].clone() - second_row[0].clone() - first_row[0].clone()];
-
-        // Auxiliary constraints
-        let z_i = &frame.get_row(0)[2];
-        let z_i_plus_one = &frame.get_row(1)[2];
-
-        let a_i = &frame.get_row(0)[0];
-        let b_i = &frame.get_row(0)[1];
-
-        let eval = z_i_plus_one * (b_i + gamma) - z_i * (a_i + gamma);
+    ) -> Vec<FieldElement<Self::FieldExtension>> {
+        self.constraints
+            .evaluate(frame, std::slice::from_ref(gamma))
+    }
 
-        constraints.push(eval);
-        constraints
+    fn symbolic_transitions(&self) -> Option<&TransitionConstraints<Self::Field>> {
+        Some(&self.constraints)
     }
 
     fn boundary_constraints(
         &self,
         _rap_challenges: &Self::RAPChallenges,
-    ) -> BoundaryConstraints<Self::Field> {
+    ) -> BoundaryConstraints<Self::FieldExtension> {
         // Main boundary constraints
-        let a0 = BoundaryConstraint::new_simple(0, FieldElement::<Self::Field>::one());
-        let a1 = BoundaryConstraint::new_simple(1, FieldElement::<Self::Field>::one());
+        let a0 = BoundaryConstraint::new_simple(0, FieldElement::<Self::FieldExtension>::one());
+        let a1 = BoundaryConstraint::new_simple(1, FieldElement::<Self::FieldExtension>::one());
 
         // Auxiliary boundary constraints
-        let a0_aux = BoundaryConstraint::new(2, 0, FieldElement::<Self::Field>::one());
+        let a0_aux = BoundaryConstraint::new(2, 0, FieldElement::<Self::FieldExtension>::one());
 
         BoundaryConstraints::from_constraints(vec![a0, a1, a0_aux])
     }
@@ -150,10 +156,6 @@ where
         &self.context
     }
 
-    fn composition_poly_degree_bound(&self) -> usize {
-        self.trace_length()
-    }
-
     fn trace_length(&self) -> usize {
         self.trace_length
     }
@@ -0,0 +1,142 @@
+use lambdaworks_math::field::{element::FieldElement, traits::IsFFTField};
+
+use crate::{
+    constraints::boundary::{BoundaryConstraint, BoundaryConstraints},
+    context::AirContext,
+    frame::Frame,
+    proof::options::ProofOptions,
+    trace::TraceTable,
+    traits::AIR,
+    transcript::IsStarkTranscript,
+};
+
+/// AIR of a single column accumulator that adds a fixed value in each step:
+/// `a[i + 1] = a[i] + k[i]`, with `k[i] = i² + 1`. The fixed values are a preprocessed
+/// column, so they are committed once in the verification key instead of in each proof.
+#[derive(Clone)]
+pub struct FixedColumnSumAIR<F>
+where
+    F: IsFFTField,
+{
+    context: AirContext,
+    trace_length: usize,
+    pub_inputs: FixedColumnSumPublicInputs<F>,
+}
+
+#[derive(Clone, Debug)]
+pub struct FixedColumnSumPublicInputs<F>
+where
+    F: IsFFTField,
+{
+    pub a0: FieldElement<F>,
+}
+
+/// Value of the preprocessed column at `step`.
+pub fn fixed_value<F: IsFFTField>(step: usize) -> FieldElement<F> {
+    let step = FieldElement::<F>::from(step as u64);
+    &step * &step + FieldElement::one()
+}
+
+impl<F> AIR for FixedColumnSumAIR<F>
+where
+    F: IsFFTField,
+{
+    type Field = F;
+    type FieldExtension = F;
+    type RAPChallenges = ();
+    type PublicInputs = FixedColumnSumPublicInputs<Self::Field>;
+
+    fn new(
+        trace_length: usize,
+        pub_inputs: &Self::PublicInputs,
+        proof_options: &ProofOptions,
+    ) -> Self {
+        let context = AirContext {
+            proof_options: proof_options.clone(),
+            trace_columns: 1,
+            transition_degrees: vec![1],
+            transition_exemptions: vec![1],
+            transition_offsets: vec![0, 1],
+            num_transition_constraints: 1,
+            num_transition_exemptions: 1,
+        };
+
+        Self {
+            pub_inputs: pub_inputs.clone(),
+            context,
+            trace_length,
+        }
+    }
+
+    fn build_auxiliary_trace(
+        &self,
+        _main_trace: &TraceTable<Self::Field>,
+        _rap_challenges: &Self::RAPChallenges,
+    ) -> TraceTable<Self::Field> {
+        TraceTable::empty()
+    }
+
+    fn build_rap_challenges(
+        &self,
+        _transcript: &mut impl IsStarkTranscript<Self::Field>,
+    ) -> Self::RAPChallenges {
+    }
+
+    fn compute_transition(
+        &self,
+        frame: &Frame<Self::Field>,
+        _rap_challenges: &Self::RAPChallenges,
+    ) -> Vec<FieldElement<Self::Field>> {
+        let first_row = frame.get_row(0);
+        let second_row = frame.get_row(1);
+
+        // The preprocessed column follows the main one.
+        vec![&second_row[0] - &first_row[0] - &first_row[1]]
+    }
+
+    fn number_preprocessed_columns(&self) -> usize {
+        1
+    }
+
+    fn get_preprocessed_columns(&self) -> Vec<Vec<FieldElement<Self::Field>>> {
+        vec![(0..self.trace_length).map(fixed_value).collect()]
+    }
+
+    fn boundary_constraints(
+        &self,
+        _rap_challenges: &Self::RAPChallenges,
+    ) -> BoundaryConstraints<Self::Field> {
+        let a0 = BoundaryConstraint::new_simple(0, self.pub_inputs.a0.clone());
+
+        BoundaryConstraints::from_constraints(vec![a0])
+    }
+
+    fn number_auxiliary_rap_columns(&self) -> usize {
+        0
+    }
+
+    fn context(&self) -> &AirContext {
+        &self.context
+    }
+
+    fn trace_length(&self) -> usize {
+        self.trace_length
+    }
+
+    fn pub_inputs(&self) -> &Self::PublicInputs {
+        &self.pub_inputs
+    }
+}
+
+pub fn fixed_column_sum_trace<F: IsFFTField>(
+    initial_value: FieldElement<F>,
+    trace_length: usize,
+) -> TraceTable<F> {
+    let mut ret: Vec<FieldElement<F>> = vec![initial_value];
+
+    for i in 1..trace_length {
+        ret.push(&ret[i - 1] + fixed_value::<F>(i - 1));
+    }
+
+    TraceTable::from_columns(&[ret])
+}
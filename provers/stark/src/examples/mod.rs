@@ -2,5 +2,10 @@ pub mod dummy_air;
 pub mod fibonacci_2_cols_shifted;
 pub mod fibonacci_2_columns;
 pub mod fibonacci_rap;
+pub mod fixed_column_sum;
+pub mod power_air;
 pub mod quadratic_air;
+pub mod range_check_logup;
+pub mod range_check_tables;
 pub mod simple_fibonacci;
+pub mod simple_periodic_cols;
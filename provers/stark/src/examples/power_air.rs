@@ -0,0 +1,131 @@
+use lambdaworks_math::field::{element::FieldElement, traits::IsFFTField};
+
+use crate::{
+    constraints::boundary::{BoundaryConstraint, BoundaryConstraints},
+    context::AirContext,
+    frame::Frame,
+    proof::options::ProofOptions,
+    trace::TraceTable,
+    traits::AIR,
+    transcript::IsStarkTranscript,
+};
+
+/// AIR of the sequence `a[i + 1] = a[i]^exponent`. The degree of its only transition
+/// constraint is the exponent, which sets the number of parts the composition polynomial
+/// is broken into.
+#[derive(Clone)]
+pub struct PowerAIR<F>
+where
+    F: IsFFTField,
+{
+    context: AirContext,
+    trace_length: usize,
+    pub_inputs: PowerPublicInputs<F>,
+}
+
+#[derive(Clone, Debug)]
+pub struct PowerPublicInputs<F>
+where
+    F: IsFFTField,
+{
+    pub a0: FieldElement<F>,
+    pub exponent: usize,
+}
+
+impl<F> AIR for PowerAIR<F>
+where
+    F: IsFFTField,
+{
+    type Field = F;
+    type FieldExtension = F;
+    type RAPChallenges = ();
+    type PublicInputs = PowerPublicInputs<Self::Field>;
+
+    fn new(
+        trace_length: usize,
+        pub_inputs: &Self::PublicInputs,
+        proof_options: &ProofOptions,
+    ) -> Self {
+        let context = AirContext {
+            proof_options: proof_options.clone(),
+            trace_columns: 1,
+            transition_degrees: vec![pub_inputs.exponent],
+            transition_exemptions: vec![1],
+            transition_offsets: vec![0, 1],
+            num_transition_constraints: 1,
+            num_transition_exemptions: 1,
+        };
+
+        Self {
+            trace_length,
+            context,
+            pub_inputs: pub_inputs.clone(),
+        }
+    }
+
+    fn build_auxiliary_trace(
+        &self,
+        _main_trace: &TraceTable<Self::Field>,
+        _rap_challenges: &Self::RAPChallenges,
+    ) -> TraceTable<Self::Field> {
+        TraceTable::empty()
+    }
+
+    fn build_rap_challenges(
+        &self,
+        _transcript: &mut impl IsStarkTranscript<Self::Field>,
+    ) -> Self::RAPChallenges {
+    }
+
+    fn compute_transition(
+        &self,
+        frame: &Frame<Self::Field>,
+        _rap_challenges: &Self::RAPChallenges,
+    ) -> Vec<FieldElement<Self::Field>> {
+        let first_row = frame.get_row(0);
+        let second_row = frame.get_row(1);
+
+        vec![&second_row[0] - first_row[0].pow(self.pub_inputs.exponent)]
+    }
+
+    fn number_auxiliary_rap_columns(&self) -> usize {
+        0
+    }
+
+    fn boundary_constraints(
+        &self,
+        _rap_challenges: &Self::RAPChallenges,
+    ) -> BoundaryConstraints<Self::Field> {
+        let a0 = BoundaryConstraint::new_simple(0, self.pub_inputs.a0.clone());
+
+        BoundaryConstraints::from_constraints(vec![a0])
+    }
+
+    fn context(&self) -> &AirContext {
+        &self.context
+    }
+
+    fn trace_length(&self) -> usize {
+        self.trace_length
+    }
+
+    fn pub_inputs(&self) -> &Self::PublicInputs {
+        &self.pub_inputs
+    }
+}
+
+pub fn power_trace<F: IsFFTField>(
+    initial_value: FieldElement<F>,
+    exponent: usize,
+    trace_length: usize,
+) -> TraceTable<F> {
+    let mut ret: Vec<FieldElement<F>> = vec![];
+
+    ret.push(initial_value);
+
+    for i in 1..(trace_length) {
+        ret.push(ret[i - 1].pow(exponent));
+    }
+
+    TraceTable::from_columns(&[ret])
+}
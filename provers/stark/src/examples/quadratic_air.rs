@@ -33,6 +33,7 @@ where
     F: IsFFTField,
 {
     type Field = F;
+    type FieldExtension = F;
     type RAPChallenges = ();
     type PublicInputs = QuadraticPublicInputs<Self::Field>;
 
@@ -100,10 +101,6 @@ where
         &self.context
     }
 
-    fn composition_poly_degree_bound(&self) -> usize {
-        2 * self.trace_length()
-    }
-
     fn trace_length(&self) -> usize {
         self.trace_length
     }
@@ -0,0 +1,143 @@
+use std::marker::PhantomData;
+
+use lambdaworks_math::field::{
+    element::FieldElement,
+    traits::{IsFFTField, IsSubFieldOf},
+};
+
+use crate::{
+    constraints::{
+        boundary::{BoundaryConstraint, BoundaryConstraints},
+        lookup::{LogUp, LogUpChallenges},
+    },
+    context::AirContext,
+    frame::Frame,
+    proof::options::ProofOptions,
+    trace::TraceTable,
+    traits::AIR,
+    transcript::IsStarkTranscript,
+};
+
+/// Range check of a column of values `v` against the range `0, 1, ..., n - 1` held in
+/// the column `r`, where `n` is the length of the trace. The column `m` holds how many
+/// times each element of the range appears in `v`, and the lookup of `v` into `r` is
+/// proven with the LogUp argument of `constraints::lookup`.
+#[derive(Clone)]
+pub struct RangeCheckLogUpAIR<F, E = F>
+where
+    F: IsFFTField,
+{
+    context: AirContext,
+    trace_length: usize,
+    lookup: LogUp,
+    phantom: PhantomData<(F, E)>,
+}
+
+impl<F, E> AIR for RangeCheckLogUpAIR<F, E>
+where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsFFTField,
+{
+    type Field = F;
+    type FieldExtension = E;
+    type RAPChallenges = LogUpChallenges<E>;
+    type PublicInputs = ();
+
+    fn new(
+        trace_length: usize,
+        _pub_inputs: &Self::PublicInputs,
+        proof_options: &ProofOptions,
+    ) -> Self {
+        let lookup = LogUp::new(vec![vec![0]], vec![1], 2, 3);
+
+        let mut transition_degrees = vec![1];
+        transition_degrees.extend(lookup.transition_degrees());
+        let mut transition_exemptions = vec![1];
+        transition_exemptions.resize(1 + lookup.num_transition_constraints(), 0);
+
+        let context = AirContext {
+            proof_options: proof_options.clone(),
+            trace_columns: 3 + lookup.number_auxiliary_columns(),
+            num_transition_constraints: transition_degrees.len(),
+            transition_degrees,
+            transition_exemptions,
+            transition_offsets: vec![0, 1],
+            num_transition_exemptions: 1,
+        };
+
+        Self {
+            context,
+            trace_length,
+            lookup,
+            phantom: PhantomData,
+        }
+    }
+
+    fn build_auxiliary_trace(
+        &self,
+        main_trace: &TraceTable<Self::Field>,
+        challenges: &Self::RAPChallenges,
+    ) -> TraceTable<Self::FieldExtension> {
+        self.lookup.build_auxiliary_trace(main_trace, challenges)
+    }
+
+    fn build_rap_challenges(
+        &self,
+        transcript: &mut impl IsStarkTranscript<Self::FieldExtension>,
+    ) -> Self::RAPChallenges {
+        LogUpChallenges::sample(transcript)
+    }
+
+    fn number_auxiliary_rap_columns(&self) -> usize {
+        self.lookup.number_auxiliary_columns()
+    }
+
+    fn compute_transition(
+        &self,
+        frame: &Frame<Self::FieldExtension>,
+        challenges: &Self::RAPChallenges,
+    ) -> Vec<FieldElement<Self::FieldExtension>> {
+        let r = &frame.get_row(0)[1];
+        let r_next = &frame.get_row(1)[1];
+
+        let mut constraints = vec![r_next - r - FieldElement::<Self::FieldExtension>::one()];
+        constraints.extend(self.lookup.compute_transition(frame, challenges));
+        constraints
+    }
+
+    fn boundary_constraints(
+        &self,
+        _challenges: &Self::RAPChallenges,
+    ) -> BoundaryConstraints<Self::FieldExtension> {
+        let mut constraints = vec![BoundaryConstraint::new(1, 0, FieldElement::zero())];
+        constraints.extend(self.lookup.boundary_constraints());
+        BoundaryConstraints::from_constraints(constraints)
+    }
+
+    fn context(&self) -> &AirContext {
+        &self.context
+    }
+
+    fn trace_length(&self) -> usize {
+        self.trace_length
+    }
+
+    fn pub_inputs(&self) -> &Self::PublicInputs {
+        &()
+    }
+}
+
+/// Returns the trace of the range check of `values`, whose length must be the length
+/// of the range.
+pub fn range_check_logup_trace<F: IsFFTField>(values: &[u64]) -> TraceTable<F> {
+    let range_length = values.len();
+    let mut multiplicities = vec![0u64; range_length];
+    for value in values.iter().filter(|v| (**v as usize) < range_length) {
+        multiplicities[*value as usize] += 1;
+    }
+
+    let values = values.iter().map(|v| FieldElement::from(*v)).collect();
+    let range = (0..range_length as u64).map(FieldElement::from).collect();
+    let multiplicities = multiplicities.into_iter().map(FieldElement::from).collect();
+    TraceTable::from_columns(&[values, range, multiplicities])
+}
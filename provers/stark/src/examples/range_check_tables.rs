@@ -0,0 +1,207 @@
+use std::marker::PhantomData;
+
+use lambdaworks_math::field::{
+    element::FieldElement,
+    traits::{IsFFTField, IsSubFieldOf},
+};
+
+use crate::{
+    constraints::boundary::{BoundaryConstraint, BoundaryConstraints},
+    context::AirContext,
+    frame::Frame,
+    proof::options::ProofOptions,
+    trace::TraceTable,
+    traits::AIR,
+    transcript::IsStarkTranscript,
+};
+
+/// The tables of a range check proven with a multi-table proof.
+#[derive(Clone, Debug)]
+pub enum RangeCheckTable {
+    /// A single column of values that must lie in the range.
+    Values,
+    /// The range `0, 1, ..., n - 1`, where `n` is the length of the table, together with
+    /// the number of times each element is looked up by the values table.
+    Range,
+}
+
+/// AIR of both tables of a range check. Each table accumulates in its auxiliary column
+/// `s` the terms of a LogUp argument with challenge `α`:
+///
+/// * Values table, with main column `v`: `s[i] = s[i - 1] + 1 / (α - v[i])`.
+/// * Range table, with main columns `r` and `m`: `s[i] = s[i - 1] - m[i] / (α - r[i])`.
+///
+/// The last values of both `s` columns add up to zero if and only if every value is
+/// an element of the range.
+#[derive(Clone)]
+pub struct RangeCheckAIR<F, E = F>
+where
+    F: IsFFTField,
+{
+    context: AirContext,
+    trace_length: usize,
+    pub_inputs: RangeCheckTable,
+    phantom: PhantomData<(F, E)>,
+}
+
+impl<F, E> AIR for RangeCheckAIR<F, E>
+where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsFFTField,
+{
+    type Field = F;
+    type FieldExtension = E;
+    type RAPChallenges = FieldElement<Self::FieldExtension>;
+    type PublicInputs = RangeCheckTable;
+
+    fn new(
+        trace_length: usize,
+        pub_inputs: &Self::PublicInputs,
+        proof_options: &ProofOptions,
+    ) -> Self {
+        // The last constraint only applies to the first row, so it is exempted from all
+        // the other ones.
+        let (trace_columns, transition_degrees, transition_exemptions) = match pub_inputs {
+            RangeCheckTable::Values => (2, vec![2, 2], vec![1, trace_length - 1]),
+            RangeCheckTable::Range => (3, vec![1, 2, 2], vec![1, 1, trace_length - 1]),
+        };
+
+        let context = AirContext {
+            proof_options: proof_options.clone(),
+            trace_columns,
+            num_transition_constraints: transition_degrees.len(),
+            transition_degrees,
+            transition_exemptions,
+            transition_offsets: vec![0, 1],
+            num_transition_exemptions: 2,
+        };
+
+        Self {
+            context,
+            trace_length,
+            pub_inputs: pub_inputs.clone(),
+            phantom: PhantomData,
+        }
+    }
+
+    fn build_auxiliary_trace(
+        &self,
+        main_trace: &TraceTable<Self::Field>,
+        alpha: &Self::RAPChallenges,
+    ) -> TraceTable<Self::FieldExtension> {
+        let mut accumulated = FieldElement::<Self::FieldExtension>::zero();
+        let aux_col = (0..main_trace.n_rows())
+            .map(|i| {
+                let value = main_trace.get(i, 0).to_extension();
+                let term = (alpha - value).inv().unwrap();
+                let term = match self.pub_inputs {
+                    RangeCheckTable::Values => term,
+                    RangeCheckTable::Range => -term * main_trace.get(i, 1).to_extension(),
+                };
+                accumulated = &accumulated + term;
+                accumulated.clone()
+            })
+            .collect();
+
+        TraceTable::from_columns(&[aux_col])
+    }
+
+    fn build_rap_challenges(
+        &self,
+        transcript: &mut impl IsStarkTranscript<Self::FieldExtension>,
+    ) -> Self::RAPChallenges {
+        transcript.sample_field_element()
+    }
+
+    fn number_auxiliary_rap_columns(&self) -> usize {
+        1
+    }
+
+    fn compute_transition(
+        &self,
+        frame: &Frame<Self::FieldExtension>,
+        alpha: &Self::RAPChallenges,
+    ) -> Vec<FieldElement<Self::FieldExtension>> {
+        let first_row = frame.get_row(0);
+        let second_row = frame.get_row(1);
+
+        match self.pub_inputs {
+            RangeCheckTable::Values => {
+                let (v, s) = (&first_row[0], &first_row[1]);
+                let (v_next, s_next) = (&second_row[0], &second_row[1]);
+                let one = FieldElement::<Self::FieldExtension>::one();
+
+                vec![
+                    (s_next - s) * (alpha - v_next) - &one,
+                    s * (alpha - v) - one,
+                ]
+            }
+            RangeCheckTable::Range => {
+                let (r, m, s) = (&first_row[0], &first_row[1], &first_row[2]);
+                let (r_next, m_next, s_next) = (&second_row[0], &second_row[1], &second_row[2]);
+
+                vec![
+                    r_next - r - FieldElement::<Self::FieldExtension>::one(),
+                    (s_next - s) * (alpha - r_next) + m_next,
+                    s * (alpha - r) + m,
+                ]
+            }
+        }
+    }
+
+    fn boundary_constraints(
+        &self,
+        _rap_challenges: &Self::RAPChallenges,
+    ) -> BoundaryConstraints<Self::FieldExtension> {
+        match self.pub_inputs {
+            RangeCheckTable::Values => BoundaryConstraints::from_constraints(vec![]),
+            RangeCheckTable::Range => {
+                BoundaryConstraints::from_constraints(vec![BoundaryConstraint::new_simple(
+                    0,
+                    FieldElement::zero(),
+                )])
+            }
+        }
+    }
+
+    fn cross_table_lookup_column(&self) -> Option<usize> {
+        Some(self.context.trace_columns - 1)
+    }
+
+    fn context(&self) -> &AirContext {
+        &self.context
+    }
+
+    fn trace_length(&self) -> usize {
+        self.trace_length
+    }
+
+    fn pub_inputs(&self) -> &Self::PublicInputs {
+        &self.pub_inputs
+    }
+}
+
+/// Returns the values table, with a single column holding `values`.
+pub fn range_check_values_trace<F: IsFFTField>(values: &[u64]) -> TraceTable<F> {
+    let column = values
+        .iter()
+        .map(|v| FieldElement::from(*v))
+        .collect::<Vec<_>>();
+    TraceTable::from_columns(&[column])
+}
+
+/// Returns the range table of length `range_length` for the given values: the elements
+/// of the range and how many times each of them appears in `values`.
+pub fn range_check_range_trace<F: IsFFTField>(
+    values: &[u64],
+    range_length: usize,
+) -> TraceTable<F> {
+    let mut multiplicities = vec![0u64; range_length];
+    for value in values.iter().filter(|v| (**v as usize) < range_length) {
+        multiplicities[*value as usize] += 1;
+    }
+
+    let range = (0..range_length as u64).map(FieldElement::from).collect();
+    let multiplicities = multiplicities.into_iter().map(FieldElement::from).collect();
+    TraceTable::from_columns(&[range, multiplicities])
+}
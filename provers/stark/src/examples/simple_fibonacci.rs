@@ -34,6 +34,7 @@ where
     F: IsFFTField,
 {
     type Field = F;
+    type FieldExtension = F;
     type RAPChallenges = ();
     type PublicInputs = FibonacciPublicInputs<Self::Field>;
 
@@ -59,10 +60,6 @@ where
         }
     }
 
-    fn composition_poly_degree_bound(&self) -> usize {
-        self.trace_length()
-    }
-
     fn build_auxiliary_trace(
         &self,
         _main_trace: &TraceTable<Self::Field>,
@@ -0,0 +1,139 @@
+use lambdaworks_math::field::{element::FieldElement, traits::IsFFTField};
+
+use crate::{
+    constraints::boundary::{BoundaryConstraint, BoundaryConstraints},
+    context::AirContext,
+    frame::Frame,
+    proof::options::ProofOptions,
+    trace::TraceTable,
+    traits::AIR,
+    transcript::IsStarkTranscript,
+};
+
+/// Round constants added to the accumulator, repeated along the trace.
+pub const ROUND_CONSTANTS: [u64; 4] = [1, 2, 3, 4];
+
+/// AIR of a single column accumulator that adds a round constant in each step:
+/// `a[i + 1] = a[i] + c[i mod 4]`. The round constants are a periodic column, so
+/// they are not committed as part of the trace.
+#[derive(Clone)]
+pub struct SimplePeriodicAIR<F>
+where
+    F: IsFFTField,
+{
+    context: AirContext,
+    trace_length: usize,
+    pub_inputs: SimplePeriodicPublicInputs<F>,
+}
+
+#[derive(Clone, Debug)]
+pub struct SimplePeriodicPublicInputs<F>
+where
+    F: IsFFTField,
+{
+    pub a0: FieldElement<F>,
+}
+
+impl<F> AIR for SimplePeriodicAIR<F>
+where
+    F: IsFFTField,
+{
+    type Field = F;
+    type FieldExtension = F;
+    type RAPChallenges = ();
+    type PublicInputs = SimplePeriodicPublicInputs<Self::Field>;
+
+    fn new(
+        trace_length: usize,
+        pub_inputs: &Self::PublicInputs,
+        proof_options: &ProofOptions,
+    ) -> Self {
+        let context = AirContext {
+            proof_options: proof_options.clone(),
+            trace_columns: 1,
+            transition_degrees: vec![1],
+            transition_exemptions: vec![1],
+            transition_offsets: vec![0, 1],
+            num_transition_constraints: 1,
+            num_transition_exemptions: 1,
+        };
+
+        Self {
+            pub_inputs: pub_inputs.clone(),
+            context,
+            trace_length,
+        }
+    }
+
+    fn build_auxiliary_trace(
+        &self,
+        _main_trace: &TraceTable<Self::Field>,
+        _rap_challenges: &Self::RAPChallenges,
+    ) -> TraceTable<Self::Field> {
+        TraceTable::empty()
+    }
+
+    fn build_rap_challenges(
+        &self,
+        _transcript: &mut impl IsStarkTranscript<Self::Field>,
+    ) -> Self::RAPChallenges {
+    }
+
+    fn compute_transition(
+        &self,
+        frame: &Frame<Self::Field>,
+        _rap_challenges: &Self::RAPChallenges,
+    ) -> Vec<FieldElement<Self::Field>> {
+        let first_row = frame.get_row(0);
+        let second_row = frame.get_row(1);
+        let round_constant = &frame.get_periodic_values()[0];
+
+        vec![&second_row[0] - &first_row[0] - round_constant]
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<FieldElement<Self::Field>>> {
+        vec![ROUND_CONSTANTS
+            .iter()
+            .map(|c| FieldElement::from(*c))
+            .collect()]
+    }
+
+    fn boundary_constraints(
+        &self,
+        _rap_challenges: &Self::RAPChallenges,
+    ) -> BoundaryConstraints<Self::Field> {
+        let a0 = BoundaryConstraint::new_simple(0, self.pub_inputs.a0.clone());
+
+        BoundaryConstraints::from_constraints(vec![a0])
+    }
+
+    fn number_auxiliary_rap_columns(&self) -> usize {
+        0
+    }
+
+    fn context(&self) -> &AirContext {
+        &self.context
+    }
+
+    fn trace_length(&self) -> usize {
+        self.trace_length
+    }
+
+    fn pub_inputs(&self) -> &Self::PublicInputs {
+        &self.pub_inputs
+    }
+}
+
+pub fn simple_periodic_trace<F: IsFFTField>(
+    initial_value: FieldElement<F>,
+    trace_length: usize,
+) -> TraceTable<F> {
+    let mut ret: Vec<FieldElement<F>> = vec![initial_value];
+
+    for i in 1..trace_length {
+        let round_constant = FieldElement::from(ROUND_CONSTANTS[(i - 1) % ROUND_CONSTANTS.len()]);
+        ret.push(&ret[i - 1] + round_constant);
+    }
+
+    TraceTable::from_columns(&[ret])
+}
@@ -1,19 +1,42 @@
 use super::trace::TraceTable;
 use crate::table::Table;
 use lambdaworks_math::{
-    field::{element::FieldElement, traits::IsFFTField},
+    field::{element::FieldElement, traits::IsField},
     polynomial::Polynomial,
 };
 
 #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
-pub struct Frame<F: IsFFTField> {
+#[serde(bound(
+    serialize = "FieldElement<F>: serde::Serialize",
+    deserialize = "FieldElement<F>: serde::Deserialize<'de>"
+))]
+pub struct Frame<F: IsField> {
     table: Table<F>,
+    /// Values of the periodic columns at the first row of the frame. They are not sent
+    /// in proofs, since the verifier computes them from the AIR.
+    #[serde(skip)]
+    periodic_values: Vec<FieldElement<F>>,
 }
 
-impl<F: IsFFTField> Frame<F> {
+impl<F: IsField> Frame<F> {
     pub fn new(data: Vec<FieldElement<F>>, row_width: usize) -> Self {
         let table = Table::new(&data, row_width);
-        Self { table }
+        Self {
+            table,
+            periodic_values: Vec::new(),
+        }
+    }
+
+    /// Returns the frame with the given values of the periodic columns.
+    pub fn with_periodic_values(self, periodic_values: Vec<FieldElement<F>>) -> Self {
+        Self {
+            periodic_values,
+            ..self
+        }
+    }
+
+    pub fn get_periodic_values(&self) -> &[FieldElement<F>] {
+        &self.periodic_values
     }
 
     pub fn n_rows(&self) -> usize {
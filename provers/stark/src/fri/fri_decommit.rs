@@ -1,13 +1,18 @@
 pub use lambdaworks_crypto::fiat_shamir::transcript::Transcript;
-use lambdaworks_crypto::merkle_tree::proof::Proof;
 
 use lambdaworks_math::field::element::FieldElement;
-use lambdaworks_math::field::traits::IsPrimeField;
-
-use crate::config::Commitment;
+use lambdaworks_math::field::traits::IsField;
 
+/// Evaluations of the FRI layers at the coset of a query, except at the point of the query
+/// itself, which the verifier computes. For each layer, these are the evaluations of the
+/// leaf of the query other than its own, in order; for a folding factor of 2, only the
+/// evaluation at the symmetric point. The Merkle proofs of the openings of all the queries
+/// are batched layer by layer.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-pub struct FriDecommitment<F: IsPrimeField> {
-    pub layers_auth_paths_sym: Vec<Proof<Commitment>>,
+#[serde(bound(
+    serialize = "FieldElement<F>: serde::Serialize",
+    deserialize = "FieldElement<F>: serde::Deserialize<'de>"
+))]
+pub struct FriDecommitment<F: IsField> {
     pub layers_evaluations_sym: Vec<FieldElement<F>>,
 }
@@ -1,4 +1,5 @@
 use super::Polynomial;
+use lambdaworks_math::fft::cpu::bit_reversing::reverse_index;
 use lambdaworks_math::field::{element::FieldElement, traits::IsField};
 
 pub fn fold_polynomial<F>(
@@ -26,13 +27,72 @@ where
     even_poly + odd_poly
 }
 
+/// Folds `poly` by `folding_factor`, a power of two, by folding it by 2 with the challenges
+/// 𝜁, 𝜁², 𝜁⁴, ... Each folding by 2 is scaled by 2, so that it matches the one computed by
+/// `fold_coset_evaluations` from the evaluations of `poly`.
+pub fn fold_polynomial_by_factor<F>(
+    poly: &Polynomial<FieldElement<F>>,
+    zeta: &FieldElement<F>,
+    folding_factor: usize,
+) -> Polynomial<FieldElement<F>>
+where
+    F: IsField,
+{
+    let mut folded_poly = poly.clone();
+    let mut zeta = zeta.clone();
+    for _ in 0..folding_factor.trailing_zeros() {
+        folded_poly = fold_polynomial(&folded_poly, &zeta) * FieldElement::from(2);
+        zeta = zeta.square();
+    }
+    folded_poly
+}
+
+/// Computes pₖ₊₁(xⁿ), where pₖ₊₁ is the folding of pₖ by n with challenge 𝜁, from the
+/// evaluations of pₖ at the coset x·⟨ω⟩ of n elements, given in bit-reversed order.
+/// `coset_offset_inv` is x⁻¹ and `omega_inv` is ω⁻¹, where ω is a primitive n-th root
+/// of unity.
+pub fn fold_coset_evaluations<F>(
+    evaluations: &[FieldElement<F>],
+    zeta: &FieldElement<F>,
+    coset_offset_inv: &FieldElement<F>,
+    omega_inv: &FieldElement<F>,
+) -> FieldElement<F>
+where
+    F: IsField,
+{
+    let mut evaluations = evaluations.to_vec();
+    let mut zeta = zeta.clone();
+    let mut coset_offset_inv = coset_offset_inv.clone();
+    let mut omega_inv = omega_inv.clone();
+    while evaluations.len() > 1 {
+        let half = evaluations.len() / 2;
+        evaluations = (0..half)
+            .map(|i| {
+                // The elements 2i and 2i + 1 of the coset are y and -y, with y = x·ω^rev(i)
+                let point_inv =
+                    &coset_offset_inv * omega_inv.pow(reverse_index(i, half as u64) as u64);
+                let evaluation = &evaluations[2 * i];
+                let evaluation_sym = &evaluations[2 * i + 1];
+                (evaluation + evaluation_sym) + &zeta * (evaluation - evaluation_sym) * point_inv
+            })
+            .collect();
+        zeta = zeta.square();
+        coset_offset_inv = coset_offset_inv.square();
+        omega_inv = omega_inv.square();
+    }
+    evaluations[0].clone()
+}
+
 #[cfg(test)]
 mod tests {
-    use super::fold_polynomial;
+    use super::{fold_coset_evaluations, fold_polynomial, fold_polynomial_by_factor};
+    use lambdaworks_math::fft::cpu::bit_reversing::reverse_index;
     use lambdaworks_math::field::element::FieldElement;
-    use lambdaworks_math::field::fields::u64_prime_field::U64PrimeField;
+    use lambdaworks_math::field::fields::u64_prime_field::{U64PrimeField, F17};
+    use lambdaworks_math::field::traits::IsFFTField;
     const MODULUS: u64 = 293;
     type FE = FieldElement<U64PrimeField<MODULUS>>;
+    type FE17 = FieldElement<F17>;
     use lambdaworks_math::polynomial::Polynomial;
 
     #[test]
@@ -61,4 +121,51 @@ mod tests {
         assert_eq!(p3, Polynomial::new(&[FE::new(143)]));
         assert_eq!(p3.degree(), 0);
     }
+
+    #[test]
+    fn folding_by_4_is_folding_twice_by_2() {
+        let p0 = Polynomial::new(&[
+            FE::new(3),
+            FE::new(1),
+            FE::new(2),
+            FE::new(7),
+            FE::new(3),
+            FE::new(5),
+        ]);
+        let beta = FE::new(4);
+        let p1 = fold_polynomial(&p0, &beta) * FE::new(2);
+        let p2 = fold_polynomial(&p1, &beta.square()) * FE::new(2);
+        assert_eq!(fold_polynomial_by_factor(&p0, &beta, 4), p2);
+    }
+
+    #[test]
+    fn folding_the_evaluations_of_a_coset_matches_the_folded_polynomial() {
+        let p0 = Polynomial::new(&[
+            FE17::new(3),
+            FE17::new(1),
+            FE17::new(2),
+            FE17::new(7),
+            FE17::new(3),
+            FE17::new(5),
+            FE17::new(11),
+            FE17::new(4),
+        ]);
+        let zeta = FE17::new(6);
+        let folding_factor = 8;
+        let omega = F17::get_primitive_root_of_unity(3).unwrap();
+        let x = FE17::new(3);
+
+        let evaluations: Vec<_> = (0..folding_factor)
+            .map(|i| p0.evaluate(&(x * omega.pow(reverse_index(i, folding_factor as u64)))))
+            .collect();
+        let folded_evaluation = fold_coset_evaluations(
+            &evaluations,
+            &zeta,
+            &x.inv().unwrap(),
+            &omega.inv().unwrap(),
+        );
+
+        let p1 = fold_polynomial_by_factor(&p0, &zeta, folding_factor);
+        assert_eq!(folded_evaluation, p1.evaluate(&x.pow(folding_factor)));
+    }
 }
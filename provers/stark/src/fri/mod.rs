@@ -5,119 +5,182 @@ mod fri_functions;
 use lambdaworks_math::fft::cpu::bit_reversing::in_place_bit_reverse_permute;
 use lambdaworks_math::fft::polynomial::FFTPoly;
 use lambdaworks_math::field::traits::IsFFTField;
-use lambdaworks_math::traits::Serializable;
 pub use lambdaworks_math::{
     field::{element::FieldElement, fields::u64_prime_field::U64PrimeField},
     polynomial::Polynomial,
 };
 
-use crate::config::{BatchedMerkleTree, BatchedMerkleTreeBackend};
+use lambdaworks_crypto::merkle_tree::{merkle::MerkleTree, proof::BatchProof};
+
+use crate::config::IsStarkMerkleTreeBackend;
 use crate::transcript::IsStarkTranscript;
 
 use self::fri_commitment::FriLayer;
 use self::fri_decommit::FriDecommitment;
-use self::fri_functions::fold_polynomial;
+pub(crate) use self::fri_functions::fold_coset_evaluations;
+use self::fri_functions::fold_polynomial_by_factor;
+
+/// Returns the degree bound of the remainder polynomial sent at the end of the FRI commit
+/// phase when p₀ has degree bound 2^`number_layers`: the largest power of two that only
+/// bounds polynomials of degree at most `max_remainder_degree`, capped to the degree bound
+/// of p₁, since p₀ is always folded at least once.
+pub fn remainder_degree_bound(number_layers: usize, max_remainder_degree: usize) -> usize {
+    let log_degree_bound = (usize::BITS - 1 - (max_remainder_degree + 1).leading_zeros()) as usize;
+    1 << log_degree_bound.min(number_layers.saturating_sub(1))
+}
 
-pub fn commit_phase<F: IsFFTField>(
+/// Returns the folding factor of each committed FRI layer when the FRI layers are folded by
+/// `folding_factor`, p₀ has degree bound 2^`number_layers` and the commit phase ends with a
+/// polynomial of degree bound `remainder_degree_bound`. p₀ is always folded by 2, since its
+/// evaluations are not committed, and each layer is folded by at most what is left to reach
+/// the degree bound of the remainder.
+pub fn layers_folding_factors(
     number_layers: usize,
+    folding_factor: usize,
+    remainder_degree_bound: usize,
+) -> Vec<usize> {
+    let log_folding_factor = folding_factor.trailing_zeros() as usize;
+    let log_remainder_degree_bound = remainder_degree_bound.trailing_zeros() as usize;
+    // Logarithm of the degree bound of the layer
+    let mut log_degree_bound = number_layers.saturating_sub(1);
+    let mut folding_factors = Vec::new();
+    while log_degree_bound > log_remainder_degree_bound {
+        let log_layer_folding_factor =
+            log_folding_factor.min(log_degree_bound - log_remainder_degree_bound);
+        folding_factors.push(1 << log_layer_folding_factor);
+        log_degree_bound -= log_layer_folding_factor;
+    }
+    folding_factors
+}
+
+/// Commits to the FRI layers of `p_0`, folding them until their degree bound is the one of
+/// the remainder, `remainder_degree_bound` (see `remainder_degree_bound`). Returns the
+/// coefficients of the remainder polynomial, padded with zeros to its degree bound, and the
+/// committed layers.
+pub fn commit_phase<F, B>(
+    number_layers: usize,
+    folding_factor: usize,
+    remainder_degree_bound: usize,
     p_0: Polynomial<FieldElement<F>>,
     transcript: &mut impl IsStarkTranscript<F>,
     coset_offset: &FieldElement<F>,
     domain_size: usize,
-) -> (
-    FieldElement<F>,
-    Vec<FriLayer<F, BatchedMerkleTreeBackend<F>>>,
-)
+) -> (Vec<FieldElement<F>>, Vec<FriLayer<F, B>>)
 where
-    FieldElement<F>: Serializable,
+    F: IsFFTField,
+    B: IsStarkMerkleTreeBackend<F>,
+    B::Node: AsRef<[u8]>,
 {
     let mut domain_size = domain_size;
 
-    let mut fri_layer_list = Vec::with_capacity(number_layers);
-    let mut current_layer: FriLayer<F, BatchedMerkleTreeBackend<F>>;
+    let layers_folding_factors =
+        layers_folding_factors(number_layers, folding_factor, remainder_degree_bound);
+    let mut fri_layer_list = Vec::with_capacity(layers_folding_factors.len());
+    let mut current_layer: FriLayer<F, B>;
     let mut current_poly = p_0;
+    let mut current_folding_factor = 2;
 
     let mut coset_offset = coset_offset.clone();
 
-    for _ in 1..number_layers {
+    for layer_folding_factor in layers_folding_factors {
         // <<<< Receive challenge 𝜁ₖ₋₁
         let zeta = transcript.sample_field_element();
-        coset_offset = coset_offset.square();
-        domain_size /= 2;
+        coset_offset = coset_offset.pow(current_folding_factor);
+        domain_size /= current_folding_factor;
 
         // Compute layer polynomial and domain
-        current_poly = fold_polynomial(&current_poly, &zeta) * FieldElement::from(2);
-        current_layer = new_fri_layer(&current_poly, &coset_offset, domain_size);
-        let new_data = &current_layer.merkle_tree.root;
-        fri_layer_list.push(current_layer.clone()); // TODO: remove this clone
+        current_poly = fold_polynomial_by_factor(&current_poly, &zeta, current_folding_factor);
+        current_layer = new_fri_layer(
+            &current_poly,
+            &coset_offset,
+            domain_size,
+            layer_folding_factor,
+        );
+        current_folding_factor = layer_folding_factor;
 
         // >>>> Send commitment: [pₖ]
-        transcript.append_bytes(new_data);
+        transcript.append_bytes(current_layer.merkle_tree.root.as_ref());
+        fri_layer_list.push(current_layer);
     }
 
     // <<<< Receive challenge: 𝜁ₙ₋₁
     let zeta = transcript.sample_field_element();
 
-    let last_poly = fold_polynomial(&current_poly, &zeta) * FieldElement::from(2);
+    let remainder_poly = fold_polynomial_by_factor(&current_poly, &zeta, current_folding_factor);
 
-    let last_value = last_poly
-        .coefficients()
-        .get(0)
-        .unwrap_or(&FieldElement::zero())
-        .clone();
+    let mut remainder_coefficients = remainder_poly.coefficients().to_vec();
+    remainder_coefficients.resize(remainder_degree_bound, FieldElement::zero());
 
-    // >>>> Send value: pₙ
-    transcript.append_field_element(&last_value);
+    // >>>> Send coefficients: pₙ
+    for coefficient in &remainder_coefficients {
+        transcript.append_field_element(coefficient);
+    }
 
-    (last_value, fri_layer_list)
+    (remainder_coefficients, fri_layer_list)
 }
 
-pub fn query_phase<F: IsFFTField>(
-    fri_layers: &Vec<FriLayer<F, BatchedMerkleTreeBackend<F>>>,
+/// Opens every FRI layer at the coset of each query. Returns the evaluations of each query
+/// and, for each layer, a single Merkle proof of all its opened leaves.
+pub fn query_phase<F, B>(
+    fri_layers: &Vec<FriLayer<F, B>>,
     iotas: &[usize],
-) -> Vec<FriDecommitment<F>>
+) -> (Vec<FriDecommitment<F>>, Vec<BatchProof<B::Node>>)
 where
-    FieldElement<F>: Serializable,
+    F: IsFFTField,
+    B: IsStarkMerkleTreeBackend<F>,
 {
     if !fri_layers.is_empty() {
         let query_list = iotas
             .iter()
             .map(|iota_s| {
                 let mut layers_evaluations_sym = Vec::new();
-                let mut layers_auth_paths_sym = Vec::new();
 
                 let mut index = *iota_s;
                 for layer in fri_layers {
-                    // symmetric element
-                    let evaluation_sym = layer.evaluation[index ^ 1].clone();
-                    let auth_path_sym = layer.merkle_tree.get_proof_by_pos(index >> 1).unwrap();
-                    layers_evaluations_sym.push(evaluation_sym);
-                    layers_auth_paths_sym.push(auth_path_sym);
-
-                    index >>= 1;
+                    // other elements of the coset of the query
+                    let leaf_start = index - index % layer.folding_factor;
+                    let coset = leaf_start..leaf_start + layer.folding_factor;
+                    layers_evaluations_sym.extend(
+                        coset
+                            .filter(|position| *position != index)
+                            .map(|position| layer.evaluation[position].clone()),
+                    );
+
+                    index /= layer.folding_factor;
                 }
 
                 FriDecommitment {
-                    layers_auth_paths_sym,
                     layers_evaluations_sym,
                 }
             })
             .collect();
 
-        query_list
+        let mut positions = iotas.to_vec();
+        let layers_merkle_proofs = fri_layers
+            .iter()
+            .map(|layer| {
+                positions
+                    .iter_mut()
+                    .for_each(|position| *position /= layer.folding_factor);
+                layer.merkle_tree.get_batch_proof(&positions).unwrap()
+            })
+            .collect();
+
+        (query_list, layers_merkle_proofs)
     } else {
-        vec![]
+        (vec![], vec![])
     }
 }
 
-pub fn new_fri_layer<F: IsFFTField>(
+pub fn new_fri_layer<F, B>(
     poly: &Polynomial<FieldElement<F>>,
     coset_offset: &FieldElement<F>,
     domain_size: usize,
-) -> crate::fri::fri_commitment::FriLayer<F, BatchedMerkleTreeBackend<F>>
+    folding_factor: usize,
+) -> crate::fri::fri_commitment::FriLayer<F, B>
 where
     F: IsFFTField,
-    FieldElement<F>: Serializable,
+    B: IsStarkMerkleTreeBackend<F>,
 {
     let mut evaluation = poly
         .evaluate_offset_fft(1, Some(domain_size), coset_offset)
@@ -125,12 +188,20 @@ where
 
     in_place_bit_reverse_permute(&mut evaluation);
 
-    let mut to_commit = Vec::new();
-    for chunk in evaluation.chunks(2) {
-        to_commit.push(vec![chunk[0].clone(), chunk[1].clone()]);
-    }
-
-    let merkle_tree = BatchedMerkleTree::build(&to_commit);
-
-    FriLayer::new(&evaluation, merkle_tree, coset_offset.clone(), domain_size)
+    // Each leaf holds a coset of `folding_factor` elements, which are contiguous in
+    // bit-reversed order
+    let to_commit: Vec<_> = evaluation
+        .chunks(folding_factor)
+        .map(|chunk| chunk.to_vec())
+        .collect();
+
+    let merkle_tree = MerkleTree::<B>::build(&to_commit);
+
+    FriLayer::new(
+        &evaluation,
+        merkle_tree,
+        coset_offset.clone(),
+        domain_size,
+        folding_factor,
+    )
 }
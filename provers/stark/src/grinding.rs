@@ -1,7 +1,118 @@
+use blake2::Blake2s256;
+use core::marker::PhantomData;
+use lambdaworks_crypto::hash::poseidon::{instances::IsPoseidonField, Poseidon};
+use lambdaworks_math::field::{
+    element::FieldElement,
+    fields::fft_friendly::{
+        babybear::Babybear31PrimeField, stark_252_prime_field::Stark252PrimeField,
+    },
+};
+#[cfg(feature = "parallel")]
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
 use sha3::{Digest, Keccak256};
 
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::transcript::PoseidonTranscript;
+
 const PREFIX: [u8; 8] = [0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xed];
 
+/// Number of nonces tested in parallel before checking whether one of them is valid.
+#[cfg(feature = "parallel")]
+const PARALLEL_BATCH_SIZE: u64 = 1 << 16;
+
+/// Hash function used by the proof of work. `Keccak256` is the one of the default and
+/// Stone transcripts. The Poseidon variants are the hashes of `PoseidonTranscript` over
+/// each field, so that a proof using that transcript can be verified inside another
+/// proof system without a bit-oriented hash. Their digests are field elements, whose
+/// high bits are biased, so the proof of work looks at low bits of the elements instead.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GrindingHash {
+    #[default]
+    Keccak256,
+    Blake2s256,
+    PoseidonStark252,
+    PoseidonBabybear31,
+}
+
+/// Hashes the inner and outer data of the proof of work into 32 bytes.
+trait GrindingHasher: Sync {
+    fn hash(&self, data: &[u8]) -> [u8; 32];
+
+    /// Returns 64 uniformly distributed bits of the hash of `data`, whose leading zeros
+    /// are the work of the proof of work.
+    fn work_bits(&self, data: &[u8]) -> u64 {
+        u64::from_be_bytes(self.hash(data)[..8].try_into().unwrap())
+    }
+}
+
+struct DigestHasher<D>(PhantomData<fn() -> D>);
+
+impl<D> DigestHasher<D> {
+    fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<D: Digest> GrindingHasher for DigestHasher<D> {
+    fn hash(&self, data: &[u8]) -> [u8; 32] {
+        D::digest(data)[..32].try_into().unwrap()
+    }
+}
+
+/// Fields whose Poseidon digests can be mapped to 64 uniformly distributed bits.
+trait GrindingField: IsPoseidonField {
+    fn work_bits(digest: &[FieldElement<Self>]) -> u64;
+}
+
+/// The low 64 bits of the element. As p is about 2^251, their distribution is
+/// 2^-187 close to uniform.
+impl GrindingField for Stark252PrimeField {
+    fn work_bits(digest: &[FieldElement<Self>]) -> u64 {
+        digest[0].representative().limbs[3]
+    }
+}
+
+/// The low 16 bits of the first 4 elements. As p is about 2^31, each chunk is 2^-31
+/// close to uniform, while the top bit of an element is always zero.
+impl GrindingField for Babybear31PrimeField {
+    fn work_bits(digest: &[FieldElement<Self>]) -> u64 {
+        digest[..4].iter().fold(0, |bits, element| {
+            (bits << 16) | (element.representative().limbs[0] & 0xffff)
+        })
+    }
+}
+
+/// Poseidon sponge of `F`, absorbing the bytes as `PoseidonTranscript` does. The
+/// parameters are built once per grinding because loading them is expensive.
+struct PoseidonHasher<F: IsPoseidonField>(Poseidon<F>);
+
+impl<F: IsPoseidonField> PoseidonHasher<F> {
+    fn new() -> Self {
+        Self(F::poseidon())
+    }
+
+    fn digest(&self, data: &[u8]) -> Vec<FieldElement<F>> {
+        let elements = PoseidonTranscript::<F>::bytes_to_elements(data);
+        self.0.hash_elements(&elements)
+    }
+}
+
+impl<F: GrindingField> GrindingHasher for PoseidonHasher<F>
+where
+    Poseidon<F>: Sync,
+{
+    fn hash(&self, data: &[u8]) -> [u8; 32] {
+        F::digest_to_bytes(&self.digest(data))
+    }
+
+    fn work_bits(&self, data: &[u8]) -> u64 {
+        F::work_bits(&self.digest(data))
+    }
+}
+
 /// Checks if the bit-string `Hash(Hash(prefix || seed || grinding_factor) || nonce)`
 /// has at least `grinding_factor` zeros to the left.
 /// `prefix` is the bit-string `0x123456789abcded`
@@ -10,15 +121,44 @@ const PREFIX: [u8; 8] = [0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xed];
 ///
 /// * `seed`: the input seed,
 /// * `nonce`: the value to be tested,
-/// * `grinding_factor`: the number of leading zeros needed.
+/// * `grinding_factor`: the number of leading zeros needed,
+/// * `hash`: the hash function of the proof of work.
 ///
 /// # Returns
 ///
 /// `true` if the number of leading zeros is at least `grinding_factor`, and `false` otherwise.
-pub fn is_valid_nonce(seed: &[u8; 32], nonce: u64, grinding_factor: u8) -> bool {
-    let inner_hash = get_inner_hash(seed, grinding_factor);
-    let limit = 1 << (64 - grinding_factor);
-    is_valid_nonce_for_inner_hash(&inner_hash, nonce, limit)
+pub fn is_valid_nonce(
+    seed: &[u8; 32],
+    nonce: u64,
+    grinding_factor: u8,
+    hash: GrindingHash,
+) -> bool {
+    match hash {
+        GrindingHash::Keccak256 => is_valid_nonce_with(
+            &DigestHasher::<Keccak256>::new(),
+            seed,
+            nonce,
+            grinding_factor,
+        ),
+        GrindingHash::Blake2s256 => is_valid_nonce_with(
+            &DigestHasher::<Blake2s256>::new(),
+            seed,
+            nonce,
+            grinding_factor,
+        ),
+        GrindingHash::PoseidonStark252 => is_valid_nonce_with(
+            &PoseidonHasher::<Stark252PrimeField>::new(),
+            seed,
+            nonce,
+            grinding_factor,
+        ),
+        GrindingHash::PoseidonBabybear31 => is_valid_nonce_with(
+            &PoseidonHasher::<Babybear31PrimeField>::new(),
+            seed,
+            nonce,
+            grinding_factor,
+        ),
+    }
 }
 
 /// Performs grinding, returning a new nonce for the proof.
@@ -27,51 +167,124 @@ pub fn is_valid_nonce(seed: &[u8; 32], nonce: u64, grinding_factor: u8) -> bool
 /// to the left.
 /// `prefix` is the bit-string `0x123456789abcded`
 ///
+/// With the `parallel` feature the nonces are tested on all threads. The nonce found is
+/// always the smallest valid one, so proofs do not depend on the feature.
+///
 /// # Parameters
 ///
 /// * `seed`: the input seed,
-/// * `grinding_factor`: the number of leading zeros needed.
+/// * `grinding_factor`: the number of leading zeros needed,
+/// * `hash`: the hash function of the proof of work.
 ///
 /// # Returns
 ///
 /// A `nonce` satisfying the required condition.
-pub fn generate_nonce(seed: &[u8; 32], grinding_factor: u8) -> Option<u64> {
-    let inner_hash = get_inner_hash(seed, grinding_factor);
+pub fn generate_nonce(seed: &[u8; 32], grinding_factor: u8, hash: GrindingHash) -> Option<u64> {
+    match hash {
+        GrindingHash::Keccak256 => {
+            generate_nonce_with(&DigestHasher::<Keccak256>::new(), seed, grinding_factor)
+        }
+        GrindingHash::Blake2s256 => {
+            generate_nonce_with(&DigestHasher::<Blake2s256>::new(), seed, grinding_factor)
+        }
+        GrindingHash::PoseidonStark252 => generate_nonce_with(
+            &PoseidonHasher::<Stark252PrimeField>::new(),
+            seed,
+            grinding_factor,
+        ),
+        GrindingHash::PoseidonBabybear31 => generate_nonce_with(
+            &PoseidonHasher::<Babybear31PrimeField>::new(),
+            seed,
+            grinding_factor,
+        ),
+    }
+}
+
+fn is_valid_nonce_with(
+    hasher: &impl GrindingHasher,
+    seed: &[u8; 32],
+    nonce: u64,
+    grinding_factor: u8,
+) -> bool {
+    let inner_hash = get_inner_hash(hasher, seed, grinding_factor);
+    let limit = 1 << (64 - grinding_factor);
+    is_valid_nonce_for_inner_hash(hasher, &inner_hash, nonce, limit)
+}
+
+#[cfg(not(feature = "parallel"))]
+fn generate_nonce_with(
+    hasher: &impl GrindingHasher,
+    seed: &[u8; 32],
+    grinding_factor: u8,
+) -> Option<u64> {
+    let inner_hash = get_inner_hash(hasher, seed, grinding_factor);
+    let limit = 1 << (64 - grinding_factor);
+    (0..u64::MAX).find(|&candidate_nonce| {
+        is_valid_nonce_for_inner_hash(hasher, &inner_hash, candidate_nonce, limit)
+    })
+}
+
+/// Tests the nonces in consecutive batches, each of them in parallel, and returns the
+/// smallest valid nonce of the first batch that has one.
+#[cfg(feature = "parallel")]
+fn generate_nonce_with(
+    hasher: &impl GrindingHasher,
+    seed: &[u8; 32],
+    grinding_factor: u8,
+) -> Option<u64> {
+    let inner_hash = get_inner_hash(hasher, seed, grinding_factor);
     let limit = 1 << (64 - grinding_factor);
     (0..u64::MAX)
-        .find(|&candidate_nonce| is_valid_nonce_for_inner_hash(&inner_hash, candidate_nonce, limit))
+        .step_by(PARALLEL_BATCH_SIZE as usize)
+        .find_map(|batch_start| {
+            let batch_end = batch_start.saturating_add(PARALLEL_BATCH_SIZE);
+            (batch_start..batch_end)
+                .into_par_iter()
+                .find_first(|&candidate_nonce| {
+                    is_valid_nonce_for_inner_hash(hasher, &inner_hash, candidate_nonce, limit)
+                })
+        })
 }
 
-/// Checks if the leftmost 8 bytes of `Hash(inner_hash || candidate_nonce)` are less than `limit`
-/// when interpreted as `u64`.
+/// Checks if the work bits of `Hash(inner_hash || candidate_nonce)`, the leftmost 8 bytes
+/// interpreted as `u64` for the bit-oriented hashes, are less than `limit`.
 #[inline(always)]
-fn is_valid_nonce_for_inner_hash(inner_hash: &[u8; 32], candidate_nonce: u64, limit: u64) -> bool {
+fn is_valid_nonce_for_inner_hash(
+    hasher: &impl GrindingHasher,
+    inner_hash: &[u8; 32],
+    candidate_nonce: u64,
+    limit: u64,
+) -> bool {
     let mut data = [0; 40];
     data[..32].copy_from_slice(inner_hash);
     data[32..].copy_from_slice(&candidate_nonce.to_be_bytes());
 
-    let digest = Keccak256::digest(data);
-
-    let seed_head = u64::from_be_bytes(digest[..8].try_into().unwrap());
-    seed_head < limit
+    hasher.work_bits(&data) < limit
 }
 
 /// Returns the bit-string constructed as
 /// Hash(prefix || seed || grinding_factor)
 /// `prefix` is the bit-string `0x123456789abcded`
-fn get_inner_hash(seed: &[u8; 32], grinding_factor: u8) -> [u8; 32] {
+fn get_inner_hash(hasher: &impl GrindingHasher, seed: &[u8; 32], grinding_factor: u8) -> [u8; 32] {
     let mut inner_data = [0u8; 41];
     inner_data[0..8].copy_from_slice(&PREFIX);
     inner_data[8..40].copy_from_slice(seed);
     inner_data[40] = grinding_factor;
 
-    let digest = Keccak256::digest(inner_data);
-    digest[..32].try_into().unwrap()
+    hasher.hash(&inner_data)
 }
 
 #[cfg(test)]
 mod test {
-    use crate::grinding::is_valid_nonce;
+    use crate::grinding::{
+        generate_nonce, get_inner_hash, is_valid_nonce, is_valid_nonce_for_inner_hash,
+        DigestHasher, GrindingHash, GrindingHasher, PoseidonHasher,
+    };
+    use blake2::Blake2s256;
+    use lambdaworks_math::field::fields::fft_friendly::{
+        babybear::Babybear31PrimeField, stark_252_prime_field::Stark252PrimeField,
+    };
+    use sha3::Keccak256;
 
     #[test]
     fn test_invalid_nonce_grinding_factor_6() {
@@ -83,7 +296,12 @@ mod test {
         ];
         let nonce = 4;
         let grinding_factor = 6;
-        assert!(!is_valid_nonce(&seed, nonce, grinding_factor));
+        assert!(!is_valid_nonce(
+            &seed,
+            nonce,
+            grinding_factor,
+            GrindingHash::Keccak256
+        ));
     }
 
     #[test]
@@ -96,7 +314,12 @@ mod test {
         ];
         let nonce = 287;
         let grinding_factor = 9;
-        assert!(!is_valid_nonce(&seed, nonce, grinding_factor));
+        assert!(!is_valid_nonce(
+            &seed,
+            nonce,
+            grinding_factor,
+            GrindingHash::Keccak256
+        ));
     }
 
     #[test]
@@ -107,7 +330,12 @@ mod test {
         ];
         let nonce = 0x5ba;
         let grinding_factor = 10;
-        assert!(is_valid_nonce(&seed, nonce, grinding_factor));
+        assert!(is_valid_nonce(
+            &seed,
+            nonce,
+            grinding_factor,
+            GrindingHash::Keccak256
+        ));
     }
 
     #[test]
@@ -118,7 +346,12 @@ mod test {
         ];
         let nonce = 0x2c5db8;
         let grinding_factor = 20;
-        assert!(is_valid_nonce(&seed, nonce, grinding_factor));
+        assert!(is_valid_nonce(
+            &seed,
+            nonce,
+            grinding_factor,
+            GrindingHash::Keccak256
+        ));
     }
 
     #[test]
@@ -132,7 +365,12 @@ mod test {
         ];
         let nonce = 0x2c5db8;
         let grinding_factor = 19;
-        assert!(!is_valid_nonce(&seed, nonce, grinding_factor));
+        assert!(!is_valid_nonce(
+            &seed,
+            nonce,
+            grinding_factor,
+            GrindingHash::Keccak256
+        ));
     }
 
     #[test]
@@ -143,7 +381,12 @@ mod test {
         ];
         let nonce = 0x1ae839e1;
         let grinding_factor = 30;
-        assert!(is_valid_nonce(&seed, nonce, grinding_factor));
+        assert!(is_valid_nonce(
+            &seed,
+            nonce,
+            grinding_factor,
+            GrindingHash::Keccak256
+        ));
     }
 
     #[test]
@@ -154,6 +397,86 @@ mod test {
         ];
         let nonce = 0x4cc3123f;
         let grinding_factor = 33;
-        assert!(is_valid_nonce(&seed, nonce, grinding_factor));
+        assert!(is_valid_nonce(
+            &seed,
+            nonce,
+            grinding_factor,
+            GrindingHash::Keccak256
+        ));
+    }
+
+    #[test]
+    fn test_generate_nonce_finds_the_smallest_valid_nonce() {
+        let seed = [
+            37, 68, 26, 150, 139, 142, 66, 175, 33, 47, 199, 160, 9, 109, 79, 234, 135, 254, 39,
+            11, 225, 219, 206, 108, 224, 165, 25, 72, 189, 96, 218, 95,
+        ];
+        let grinding_factor = 10;
+        let nonce = generate_nonce(&seed, grinding_factor, GrindingHash::Keccak256).unwrap();
+        assert_eq!(nonce, 0x5ba);
+        assert!((0..nonce).all(|nonce| !is_valid_nonce(
+            &seed,
+            nonce,
+            grinding_factor,
+            GrindingHash::Keccak256
+        )));
+    }
+
+    #[test]
+    fn test_generate_nonce_with_blake2s() {
+        let seed = [
+            37, 68, 26, 150, 139, 142, 66, 175, 33, 47, 199, 160, 9, 109, 79, 234, 135, 254, 39,
+            11, 225, 219, 206, 108, 224, 165, 25, 72, 189, 96, 218, 95,
+        ];
+        let grinding_factor = 12;
+        let nonce = generate_nonce(&seed, grinding_factor, GrindingHash::Blake2s256).unwrap();
+        assert!(is_valid_nonce(
+            &seed,
+            nonce,
+            grinding_factor,
+            GrindingHash::Blake2s256
+        ));
+    }
+
+    #[test]
+    fn test_generate_nonce_with_poseidon() {
+        let seed = [
+            37, 68, 26, 150, 139, 142, 66, 175, 33, 47, 199, 160, 9, 109, 79, 234, 135, 254, 39,
+            11, 225, 219, 206, 108, 224, 165, 25, 72, 189, 96, 218, 95,
+        ];
+        let grinding_factor = 4;
+        for hash in [
+            GrindingHash::PoseidonStark252,
+            GrindingHash::PoseidonBabybear31,
+        ] {
+            let nonce = generate_nonce(&seed, grinding_factor, hash).unwrap();
+            assert!(is_valid_nonce(&seed, nonce, grinding_factor, hash));
+            assert!((0..nonce).all(|nonce| !is_valid_nonce(&seed, nonce, grinding_factor, hash)));
+        }
+    }
+
+    /// Counts the valid nonces among the first 4096 for grinding factor 4, which should
+    /// be 256 on average with a standard deviation of about 15.5.
+    fn count_valid_nonces(hasher: &impl GrindingHasher) -> usize {
+        let seed = [7; 32];
+        let grinding_factor = 4;
+        let limit = 1 << (64 - grinding_factor);
+        let inner_hash = get_inner_hash(hasher, &seed, grinding_factor);
+        (0..4096)
+            .filter(|&nonce| is_valid_nonce_for_inner_hash(hasher, &inner_hash, nonce, limit))
+            .count()
+    }
+
+    #[test]
+    fn test_valid_nonces_have_probability_one_over_two_to_the_grinding_factor() {
+        let counts = [
+            count_valid_nonces(&DigestHasher::<Keccak256>::new()),
+            count_valid_nonces(&DigestHasher::<Blake2s256>::new()),
+            count_valid_nonces(&PoseidonHasher::<Stark252PrimeField>::new()),
+            count_valid_nonces(&PoseidonHasher::<Babybear31PrimeField>::new()),
+        ];
+        for count in counts {
+            assert!((180..=332).contains(&count), "{count} valid nonces");
+        }
     }
 }
@@ -10,13 +10,16 @@ pub mod examples;
 pub mod frame;
 pub mod fri;
 pub mod grinding;
+pub mod multi_table;
 pub mod proof;
 pub mod prover;
+pub mod solidity;
 pub mod table;
 pub mod trace;
 pub mod traits;
 pub mod transcript;
 pub mod utils;
+pub mod verification_key;
 pub mod verifier;
 
 #[cfg(test)]
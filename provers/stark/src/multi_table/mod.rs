@@ -0,0 +1,204 @@
+//! Proofs of several traces, or tables, in a single STARK proof.
+//!
+//! Every table is described by the same `AIR` type, which can tell the tables apart by
+//! their public inputs, and may have its own length. The tables are committed in the
+//! same transcript and share the challenges of `AIR::build_rap_challenges`, so their
+//! auxiliary traces can link them through a LogUp cross-table lookup: each table
+//! accumulates its terms in the column given by `AIR::cross_table_lookup_column`, the
+//! proof carries the values of these columns at their last rows and the verifier checks
+//! that they add up to zero.
+//!
+//! The low degree extensions of all the tables are evaluated over the domain of the
+//! longest one, whose length is `N`. If the DEEP composition polynomial of a table of
+//! length `n` is `Dₜ`, a single FRI proof is run over `∑ₜ X^(N - n) Dₜ`.
+
+pub mod prover;
+pub mod verifier;
+
+use lambdaworks_math::{
+    field::{element::FieldElement, traits::IsField},
+    polynomial::Polynomial,
+};
+
+use crate::{
+    constraints::boundary::{BoundaryConstraint, BoundaryConstraints},
+    context::AirContext,
+    frame::Frame,
+    proof::options::ProofOptions,
+    trace::TraceTable,
+    traits::AIR,
+    transcript::IsStarkTranscript,
+};
+
+/// Returns the options of a table of length `trace_length` in a multi-table proof whose
+/// longest table has length `max_trace_length`: the blowup factor is raised so that the
+/// LDE domain of the table is the one of the longest table.
+pub(crate) fn table_options(
+    proof_options: &ProofOptions,
+    trace_length: usize,
+    max_trace_length: usize,
+) -> Option<ProofOptions> {
+    let blowup_factor = proof_options.blowup_factor as usize * (max_trace_length / trace_length);
+    Some(ProofOptions {
+        blowup_factor: u8::try_from(blowup_factor).ok()?,
+        ..proof_options.clone()
+    })
+}
+
+/// The AIR of a table of a multi-table proof, with a boundary constraint that fixes the
+/// last value of its cross-table lookup column to the one sent in the proof.
+#[derive(Clone)]
+pub(crate) struct TableAIR<A: AIR> {
+    air: A,
+    cross_table_sum: Option<FieldElement<A::FieldExtension>>,
+}
+
+impl<A: AIR> TableAIR<A> {
+    pub(crate) fn set_cross_table_sum(&mut self, sum: FieldElement<A::FieldExtension>) {
+        self.cross_table_sum = Some(sum);
+    }
+}
+
+impl<A> AIR for TableAIR<A>
+where
+    A: AIR,
+{
+    type Field = A::Field;
+    type FieldExtension = A::FieldExtension;
+    type RAPChallenges = A::RAPChallenges;
+    type PublicInputs = A::PublicInputs;
+
+    fn new(
+        trace_length: usize,
+        pub_inputs: &Self::PublicInputs,
+        proof_options: &ProofOptions,
+    ) -> Self {
+        Self {
+            air: A::new(trace_length, pub_inputs, proof_options),
+            cross_table_sum: None,
+        }
+    }
+
+    fn build_auxiliary_trace(
+        &self,
+        main_trace: &TraceTable<Self::Field>,
+        rap_challenges: &Self::RAPChallenges,
+    ) -> TraceTable<Self::FieldExtension> {
+        self.air.build_auxiliary_trace(main_trace, rap_challenges)
+    }
+
+    fn build_rap_challenges(
+        &self,
+        transcript: &mut impl IsStarkTranscript<Self::FieldExtension>,
+    ) -> Self::RAPChallenges {
+        self.air.build_rap_challenges(transcript)
+    }
+
+    fn number_auxiliary_rap_columns(&self) -> usize {
+        self.air.number_auxiliary_rap_columns()
+    }
+
+    fn composition_poly_degree_bound(&self) -> usize {
+        self.air.composition_poly_degree_bound()
+    }
+
+    fn number_of_composition_poly_parts(&self) -> usize {
+        self.air.number_of_composition_poly_parts()
+    }
+
+    fn compute_transition(
+        &self,
+        frame: &Frame<Self::FieldExtension>,
+        rap_challenges: &Self::RAPChallenges,
+    ) -> Vec<FieldElement<Self::FieldExtension>> {
+        self.air.compute_transition(frame, rap_challenges)
+    }
+
+    fn boundary_constraints(
+        &self,
+        rap_challenges: &Self::RAPChallenges,
+    ) -> BoundaryConstraints<Self::FieldExtension> {
+        let mut boundary_constraints = self.air.boundary_constraints(rap_challenges);
+        if let (Some(col), Some(sum)) =
+            (self.air.cross_table_lookup_column(), &self.cross_table_sum)
+        {
+            boundary_constraints
+                .constraints
+                .push(BoundaryConstraint::new(
+                    col,
+                    self.trace_length() - 1,
+                    sum.clone(),
+                ));
+        }
+        boundary_constraints
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<FieldElement<Self::Field>>> {
+        self.air.get_periodic_column_values()
+    }
+
+    fn get_periodic_column_polynomials(&self) -> Vec<Polynomial<FieldElement<Self::Field>>> {
+        self.air.get_periodic_column_polynomials()
+    }
+
+    fn number_preprocessed_columns(&self) -> usize {
+        self.air.number_preprocessed_columns()
+    }
+
+    fn get_preprocessed_columns(&self) -> Vec<Vec<FieldElement<Self::Field>>> {
+        self.air.get_preprocessed_columns()
+    }
+
+    fn cross_table_lookup_column(&self) -> Option<usize> {
+        self.air.cross_table_lookup_column()
+    }
+
+    fn transition_exemptions(&self) -> Vec<Polynomial<FieldElement<Self::Field>>> {
+        self.air.transition_exemptions()
+    }
+
+    fn context(&self) -> &AirContext {
+        self.air.context()
+    }
+
+    fn trace_length(&self) -> usize {
+        self.air.trace_length()
+    }
+
+    fn deep_composition_poly_degree_bound(&self) -> usize {
+        self.air.deep_composition_poly_degree_bound()
+    }
+
+    fn pub_inputs(&self) -> &Self::PublicInputs {
+        self.air.pub_inputs()
+    }
+
+    fn transition_exemptions_verifier(
+        &self,
+        root: &FieldElement<Self::Field>,
+    ) -> Vec<Polynomial<FieldElement<Self::Field>>> {
+        self.air.transition_exemptions_verifier(root)
+    }
+}
+
+/// Returns `X^shift p(X)`.
+pub(crate) fn shift_polynomial<F: IsField>(
+    poly: &Polynomial<FieldElement<F>>,
+    shift: usize,
+) -> Polynomial<FieldElement<F>> {
+    let mut coefficients = vec![FieldElement::zero(); shift];
+    coefficients.extend_from_slice(poly.coefficients());
+    Polynomial::new(&coefficients)
+}
+
+/// Returns `𝛾, 𝛾², ..., 𝛾ⁿ`, the coefficients of the terms of the DEEP composition
+/// polynomial of a table. Unlike in single-table proofs the first power is not one, so
+/// no term of a table can cancel a term of another table with a fixed coefficient.
+pub(crate) fn deep_composition_coefficients<F: IsField>(
+    gamma: &FieldElement<F>,
+    number_of_terms: usize,
+) -> Vec<FieldElement<F>> {
+    core::iter::successors(Some(gamma.clone()), |x| Some(x * gamma))
+        .take(number_of_terms)
+        .collect()
+}
@@ -0,0 +1,426 @@
+use lambdaworks_math::{
+    field::element::FieldElement, polynomial::Polynomial, traits::Serializable,
+};
+
+use crate::{
+    domain::Domain,
+    frame::Frame,
+    fri, grinding,
+    proof::{multi_table::MultiTableStarkProof, options::ProofOptions, stark::StarkProof},
+    prover::{IsStarkProver, ProverOptions, ProvingError, Round1},
+    trace::TraceTable,
+    traits::AIR,
+    transcript::IsStarkTranscript,
+};
+
+use super::{deep_composition_coefficients, shift_polynomial, table_options, TableAIR};
+
+/// Prover of multi-table proofs. It is implemented by every `IsStarkProver`, so the
+/// tables are committed with the same backends as single-table proofs.
+pub trait IsMultiTableStarkProver: IsStarkProver {
+    /// Proves that each trace of `traces` satisfies the AIR built from the public inputs
+    /// at the same position of `pub_inputs`, and that the cross-table lookups between
+    /// them hold. The transcript type is a parameter of its own, as it is also used to
+    /// tell `round_2_compute_composition_polynomial` that no zero-knowledge randomness
+    /// is needed.
+    #[allow(clippy::type_complexity)]
+    fn prove_multi_table<A, T>(
+        traces: &[TraceTable<Self::Field>],
+        pub_inputs: &[A::PublicInputs],
+        proof_options: &ProofOptions,
+        transcript: T,
+    ) -> Result<
+        MultiTableStarkProof<Self::Field, Self::FieldExtension, Self::Commitment>,
+        ProvingError,
+    >
+    where
+        A: AIR<Field = Self::Field, FieldExtension = Self::FieldExtension> + Send + Sync,
+        A::RAPChallenges: Clone + Send + Sync,
+        T: IsStarkTranscript<Self::FieldExtension>,
+        FieldElement<Self::Field>: Serializable + Send + Sync,
+        FieldElement<Self::FieldExtension>: Serializable + Send + Sync,
+    {
+        Self::prove_multi_table_with_prover_options::<A, T>(
+            traces,
+            pub_inputs,
+            proof_options,
+            &ProverOptions::default(),
+            transcript,
+        )
+    }
+
+    /// Same as `prove_multi_table`, with settings of the prover that do not change the
+    /// proof.
+    #[allow(clippy::type_complexity)]
+    fn prove_multi_table_with_prover_options<A, T>(
+        traces: &[TraceTable<Self::Field>],
+        pub_inputs: &[A::PublicInputs],
+        proof_options: &ProofOptions,
+        prover_options: &ProverOptions,
+        mut transcript: T,
+    ) -> Result<
+        MultiTableStarkProof<Self::Field, Self::FieldExtension, Self::Commitment>,
+        ProvingError,
+    >
+    where
+        A: AIR<Field = Self::Field, FieldExtension = Self::FieldExtension> + Send + Sync,
+        A::RAPChallenges: Clone + Send + Sync,
+        T: IsStarkTranscript<Self::FieldExtension>,
+        FieldElement<Self::Field>: Serializable + Send + Sync,
+        FieldElement<Self::FieldExtension>: Serializable + Send + Sync,
+    {
+        if traces.is_empty() || traces.len() != pub_inputs.len() {
+            return Err(ProvingError::WrongParameter(
+                "Expected at least one table and one set of public inputs for each table"
+                    .to_string(),
+            ));
+        }
+        if proof_options.zero_knowledge {
+            return Err(ProvingError::WrongParameter(
+                "Multi-table proofs do not support the zero-knowledge mode".to_string(),
+            ));
+        }
+        Self::check_fri_folding_factor(proof_options)?;
+        if let Some(trace) = traces.iter().find(|t| !t.n_rows().is_power_of_two()) {
+            return Err(ProvingError::WrongParameter(format!(
+                "Table length {} is not a power of two",
+                trace.n_rows()
+            )));
+        }
+
+        let max_trace_length = traces.iter().map(|trace| trace.n_rows()).max().unwrap();
+        let longest = traces
+            .iter()
+            .position(|trace| trace.n_rows() == max_trace_length)
+            .unwrap();
+
+        let mut airs = Vec::with_capacity(traces.len());
+        let mut domains = Vec::with_capacity(traces.len());
+        for (trace, pub_input) in traces.iter().zip(pub_inputs) {
+            let options = table_options(proof_options, trace.n_rows(), max_trace_length)
+                .ok_or_else(|| {
+                    ProvingError::WrongParameter(format!(
+                        "Table of length {} is too short compared to the longest one, of \
+                         length {max_trace_length}",
+                        trace.n_rows()
+                    ))
+                })?;
+            let air = TableAIR::<A>::new(trace.n_rows(), pub_input, &options);
+            Self::check_blowup_factor(&air)?;
+            Self::check_periodic_columns(&air)?;
+            if air.number_preprocessed_columns() > 0 {
+                return Err(ProvingError::WrongParameter(
+                    "Multi-table proofs do not support preprocessed columns".to_string(),
+                ));
+            }
+            domains.push(Domain::new(&air));
+            airs.push(air);
+        }
+
+        // ===================================
+        // ==========|   Round 1   |==========
+        // ===================================
+
+        // >>>> Send commitments: [tⱼ] of the main trace of every table
+        let main_commitments: Vec<_> = traces
+            .iter()
+            .zip(&domains)
+            .map(|(trace, domain)| {
+                Self::interpolate_and_commit::<Self::Field, Self::MerkleTreeBackend>(
+                    trace,
+                    domain,
+                    &mut transcript,
+                    None,
+                    prover_options.stream_lde,
+                )
+            })
+            .collect();
+
+        // <<<< Receive challenges shared by all the tables
+        let rap_challenges = airs[0].build_rap_challenges(&mut transcript);
+
+        // >>>> Send commitments: [tⱼ] of the auxiliary trace of every table
+        let mut round_1_results = Vec::with_capacity(traces.len());
+        let mut cross_table_sums = Vec::new();
+        for ((air, domain), (trace, (main_trace_polys, main))) in airs
+            .iter_mut()
+            .zip(&domains)
+            .zip(traces.iter().zip(main_commitments))
+        {
+            let aux_trace = air.build_auxiliary_trace(trace, &rap_challenges);
+            if let Some(col) = air.cross_table_lookup_column() {
+                let sum = aux_trace.get(trace.n_rows() - 1, col - trace.n_cols());
+                air.set_cross_table_sum(sum.clone());
+                cross_table_sums.push(sum);
+            }
+
+            let mut trace_polys: Vec<_> = main_trace_polys
+                .into_iter()
+                .map(|poly| poly.to_extension())
+                .collect();
+            let aux = if !aux_trace.is_empty() {
+                let (aux_trace_polys, aux) = Self::interpolate_and_commit::<
+                    Self::FieldExtension,
+                    Self::MerkleTreeBackendExtension,
+                >(
+                    &aux_trace,
+                    domain,
+                    &mut transcript,
+                    None,
+                    prover_options.stream_lde,
+                );
+                trace_polys.extend_from_slice(&aux_trace_polys);
+                Some(aux)
+            } else {
+                None
+            };
+
+            round_1_results.push(Round1 {
+                trace_polys,
+                main,
+                aux,
+                preprocessed: None,
+                rap_challenges: rap_challenges.clone(),
+            });
+        }
+
+        let total_sum = cross_table_sums
+            .iter()
+            .fold(FieldElement::zero(), |acc, sum| acc + sum);
+        if total_sum != FieldElement::zero() {
+            return Err(ProvingError::WrongParameter(
+                "The cross-table lookup sums do not add up to zero".to_string(),
+            ));
+        }
+
+        // >>>> Send values: last values of the cross-table lookup columns
+        for sum in cross_table_sums.iter() {
+            transcript.append_field_element(sum);
+        }
+
+        // ===================================
+        // ==========|   Round 2   |==========
+        // ===================================
+
+        let mut round_2_results = Vec::with_capacity(traces.len());
+        for ((air, domain), round_1_result) in airs.iter().zip(&domains).zip(&round_1_results) {
+            // <<<< Receive challenge: 𝛽
+            let beta = transcript.sample_field_element();
+            let num_boundary_constraints =
+                air.boundary_constraints(&rap_challenges).constraints.len();
+            let num_transition_constraints = air.context().num_transition_constraints;
+
+            let mut coefficients: Vec<_> =
+                core::iter::successors(Some(FieldElement::one()), |x| Some(x * &beta))
+                    .take(num_boundary_constraints + num_transition_constraints)
+                    .collect();
+            let transition_coefficients: Vec<_> =
+                coefficients.drain(..num_transition_constraints).collect();
+            let boundary_coefficients = coefficients;
+
+            let round_2_result = Self::round_2_compute_composition_polynomial(
+                air,
+                domain,
+                round_1_result,
+                &transition_coefficients,
+                &boundary_coefficients,
+                None::<&mut T>,
+                prover_options.stream_lde,
+            );
+
+            // >>>> Send commitments: [H₁], [H₂]
+            transcript.append_bytes(round_2_result.composition_poly_root.as_ref());
+            round_2_results.push(round_2_result);
+        }
+
+        // ===================================
+        // ==========|   Round 3   |==========
+        // ===================================
+
+        // <<<< Receive challenge: z
+        let z = transcript.sample_z_ood(
+            &domains[longest].lde_roots_of_unity_coset,
+            &domains[longest].trace_roots_of_unity,
+        );
+
+        let mut round_3_results = Vec::with_capacity(traces.len());
+        for (((air, domain), round_1_result), round_2_result) in airs
+            .iter()
+            .zip(&domains)
+            .zip(&round_1_results)
+            .zip(&round_2_results)
+        {
+            let round_3_result = Self::round_3_evaluate_polynomials_in_out_of_domain_element(
+                air,
+                domain,
+                round_1_result,
+                round_2_result,
+                &z,
+            );
+
+            // >>>> Send values: tⱼ(zgᵏ)
+            for i in 0..round_3_result.trace_ood_evaluations[0].len() {
+                for j in 0..round_3_result.trace_ood_evaluations.len() {
+                    transcript.append_field_element(&round_3_result.trace_ood_evaluations[j][i]);
+                }
+            }
+
+            // >>>> Send values: Hᵢ(z^N)
+            for element in round_3_result.composition_poly_parts_ood_evaluation.iter() {
+                transcript.append_field_element(element);
+            }
+            round_3_results.push(round_3_result);
+        }
+
+        // ===================================
+        // ==========|   Round 4   |==========
+        // ===================================
+
+        let mut deep_composition_poly = Polynomial::zero();
+        for (((air, domain), round_1_result), (round_2_result, round_3_result)) in airs
+            .iter()
+            .zip(&domains)
+            .zip(&round_1_results)
+            .zip(round_2_results.iter().zip(&round_3_results))
+        {
+            // <<<< Receive challenges: 𝛾, 𝛾'
+            let gamma = transcript.sample_field_element();
+            let n_terms_composition_poly = round_2_result.composition_poly_parts.len();
+            let n_terms_trace =
+                air.context().transition_offsets.len() * air.context().trace_columns;
+            let mut deep_composition_coefficients =
+                deep_composition_coefficients(&gamma, n_terms_composition_poly + n_terms_trace);
+            let trace_poly_coefficients: Vec<_> = deep_composition_coefficients
+                .drain(..n_terms_trace)
+                .collect();
+            let gammas = deep_composition_coefficients;
+
+            let table_deep_composition_poly = Self::compute_deep_composition_poly(
+                air,
+                &round_1_result.trace_polys,
+                round_2_result,
+                round_3_result,
+                &z,
+                &domain.trace_primitive_root.clone().to_extension(),
+                &gammas,
+                &trace_poly_coefficients,
+            );
+
+            // Xᴺ⁻ⁿ Dₜ
+            deep_composition_poly = deep_composition_poly
+                + shift_polynomial(
+                    &table_deep_composition_poly,
+                    max_trace_length - air.trace_length(),
+                );
+        }
+
+        let domain = &domains[longest];
+        let coset_offset =
+            FieldElement::<Self::Field>::from(proof_options.coset_offset).to_extension();
+
+        // FRI commit and query phases
+        let number_of_fri_layers = max_trace_length.trailing_zeros() as usize;
+        let (fri_remainder_coefficients, fri_layers) =
+            fri::commit_phase::<Self::FieldExtension, Self::MerkleTreeBackendExtension>(
+                number_of_fri_layers,
+                proof_options.fri_folding_factor as usize,
+                fri::remainder_degree_bound(
+                    number_of_fri_layers,
+                    proof_options.fri_max_remainder_degree,
+                ),
+                deep_composition_poly,
+                &mut transcript,
+                &coset_offset,
+                domain.lde_roots_of_unity_coset.len(),
+            );
+
+        // grinding: generate nonce and append it to the transcript
+        let security_bits = proof_options.grinding_factor;
+        let mut nonce = 0;
+        if security_bits > 0 {
+            nonce = grinding::generate_nonce(
+                &transcript.state(),
+                security_bits,
+                proof_options.grinding_hash,
+            )
+            .expect("nonce not found");
+            transcript.append_bytes(&nonce.to_be_bytes());
+        }
+
+        let iotas = Self::sample_query_indexes(
+            proof_options.fri_number_of_queries,
+            domain,
+            &mut transcript,
+        );
+        let (query_list, fri_layers_merkle_proofs) = fri::query_phase(&fri_layers, &iotas);
+
+        let fri_layers_merkle_roots: Vec<_> = fri_layers
+            .iter()
+            .map(|layer| layer.merkle_tree.root.clone())
+            .collect();
+
+        let tables = airs
+            .iter()
+            .zip(&domains)
+            .zip(round_1_results.iter().zip(round_2_results))
+            .zip(round_3_results)
+            .map(
+                |(((air, domain), (round_1_result, round_2_result)), round_3_result)| {
+                    let (deep_poly_openings, deep_poly_openings_sym) =
+                        Self::open_deep_composition_poly(
+                            domain,
+                            round_1_result,
+                            &round_2_result,
+                            &iotas,
+                        );
+                    let (lde_trace_merkle_proofs, _, lde_composition_poly_proof) =
+                        Self::batch_open_trace_and_composition_polys(
+                            round_1_result,
+                            &round_2_result,
+                            &iotas,
+                        );
+
+                    let trace_ood_frame_evaluations = Frame::new(
+                        round_3_result
+                            .trace_ood_evaluations
+                            .into_iter()
+                            .flatten()
+                            .collect(),
+                        round_1_result.trace_polys.len(),
+                    );
+
+                    StarkProof {
+                        lde_trace_merkle_roots: round_1_result.lde_trace_merkle_roots(),
+                        trace_ood_frame_evaluations,
+                        composition_poly_root: round_2_result.composition_poly_root,
+                        composition_poly_parts_ood_evaluation: round_3_result
+                            .composition_poly_parts_ood_evaluation,
+                        fri_layers_merkle_roots: Vec::new(),
+                        fri_remainder_coefficients: Vec::new(),
+                        query_list: Vec::new(),
+                        fri_layers_merkle_proofs: Vec::new(),
+                        deep_poly_openings,
+                        deep_poly_openings_sym,
+                        lde_trace_merkle_proofs,
+                        lde_preprocessed_merkle_proof: None,
+                        lde_composition_poly_proof,
+                        nonce: 0,
+                        trace_length: air.trace_length(),
+                    }
+                },
+            )
+            .collect();
+
+        Ok(MultiTableStarkProof {
+            tables,
+            cross_table_sums,
+            fri_layers_merkle_roots,
+            fri_remainder_coefficients,
+            query_list,
+            fri_layers_merkle_proofs,
+            nonce,
+        })
+    }
+}
+
+impl<P: IsStarkProver> IsMultiTableStarkProver for P {}
@@ -0,0 +1,357 @@
+use log::error;
+
+use lambdaworks_math::{field::element::FieldElement, traits::Serializable};
+
+use crate::{
+    domain::Domain,
+    fri, grinding,
+    proof::{multi_table::MultiTableStarkProof, options::ProofOptions},
+    traits::{has_valid_periodic_columns, AIR},
+    transcript::IsStarkTranscript,
+    verifier::{Challenges, IsStarkVerifier},
+};
+
+use super::{deep_composition_coefficients, table_options, TableAIR};
+
+/// Verifier of multi-table proofs. It is implemented by every `IsStarkVerifier`.
+pub trait IsMultiTableStarkVerifier: IsStarkVerifier {
+    /// Verifies a proof generated by `IsMultiTableStarkProver::prove_multi_table`. The
+    /// public inputs must be given in the same order as the tables were proven.
+    fn verify_multi_table<A>(
+        proof: &MultiTableStarkProof<Self::Field, Self::FieldExtension, Self::Commitment>,
+        pub_inputs: &[A::PublicInputs],
+        proof_options: &ProofOptions,
+        mut transcript: impl IsStarkTranscript<Self::FieldExtension>,
+    ) -> bool
+    where
+        A: AIR<Field = Self::Field, FieldExtension = Self::FieldExtension>,
+        A::RAPChallenges: Clone,
+        FieldElement<Self::Field>: Serializable,
+        FieldElement<Self::FieldExtension>: Serializable,
+    {
+        if proof.tables.is_empty() || proof.tables.len() != pub_inputs.len() {
+            error!("Wrong number of tables");
+            return false;
+        }
+        if proof_options.zero_knowledge {
+            error!("Multi-table proofs do not support the zero-knowledge mode");
+            return false;
+        }
+        // Verify there are enough queries
+        if proof.query_list.len() < proof_options.fri_number_of_queries {
+            return false;
+        }
+        if proof.tables.iter().any(|table| {
+            !table.trace_length.is_power_of_two() || table.lde_trace_merkle_roots.is_empty()
+        }) {
+            error!("Malformed table proof");
+            return false;
+        }
+
+        let max_trace_length = proof
+            .tables
+            .iter()
+            .map(|table| table.trace_length)
+            .max()
+            .unwrap();
+        let longest = proof
+            .tables
+            .iter()
+            .position(|table| table.trace_length == max_trace_length)
+            .unwrap();
+
+        if !ProofOptions::SUPPORTED_FRI_FOLDING_FACTORS.contains(&proof_options.fri_folding_factor)
+        {
+            error!("Unsupported FRI folding factor");
+            return false;
+        }
+        let number_of_fri_layers = max_trace_length.trailing_zeros() as usize;
+        let remainder_degree_bound = fri::remainder_degree_bound(
+            number_of_fri_layers,
+            proof_options.fri_max_remainder_degree,
+        );
+        let layers_folding_factors = fri::layers_folding_factors(
+            number_of_fri_layers,
+            proof_options.fri_folding_factor as usize,
+            remainder_degree_bound,
+        );
+        if proof.fri_layers_merkle_roots.len() != layers_folding_factors.len() {
+            error!("Wrong number of FRI layers");
+            return false;
+        }
+        if proof.fri_remainder_coefficients.len() != remainder_degree_bound {
+            error!("Wrong number of coefficients of the FRI remainder");
+            return false;
+        }
+
+        let mut airs = Vec::with_capacity(proof.tables.len());
+        let mut domains = Vec::with_capacity(proof.tables.len());
+        for (table, pub_input) in proof.tables.iter().zip(pub_inputs) {
+            let Some(options) = table_options(proof_options, table.trace_length, max_trace_length)
+            else {
+                error!("Table too short compared to the longest one");
+                return false;
+            };
+            let air = TableAIR::<A>::new(table.trace_length, pub_input, &options);
+            if !has_valid_periodic_columns(&air) {
+                error!("Invalid periods of the periodic columns");
+                return false;
+            }
+            if air.number_preprocessed_columns() > 0 {
+                error!("Multi-table proofs do not support preprocessed columns");
+                return false;
+            }
+            if table.composition_poly_parts_ood_evaluation.len()
+                != air.number_of_composition_poly_parts()
+            {
+                error!("Wrong number of composition polynomial parts");
+                return false;
+            }
+            domains.push(Domain::new(&air));
+            airs.push(air);
+        }
+
+        // ===================================
+        // ==========|   Round 1   |==========
+        // ===================================
+
+        // <<<< Receive commitments: [tⱼ] of the main trace of every table
+        for table in proof.tables.iter() {
+            transcript.append_bytes(table.lde_trace_merkle_roots[0].as_ref());
+        }
+
+        let rap_challenges = airs[0].build_rap_challenges(&mut transcript);
+
+        // <<<< Receive commitments: [tⱼ] of the auxiliary trace of every table
+        for table in proof.tables.iter() {
+            if let Some(root) = table.lde_trace_merkle_roots.get(1) {
+                transcript.append_bytes(root.as_ref());
+            }
+        }
+
+        // <<<< Receive values: last values of the cross-table lookup columns
+        let mut cross_table_sums = proof.cross_table_sums.iter();
+        for air in airs.iter_mut() {
+            if air.cross_table_lookup_column().is_some() {
+                let Some(sum) = cross_table_sums.next() else {
+                    error!("Missing cross-table lookup sums");
+                    return false;
+                };
+                air.set_cross_table_sum(sum.clone());
+            }
+        }
+        if cross_table_sums.next().is_some() {
+            error!("Too many cross-table lookup sums");
+            return false;
+        }
+        let total_sum = proof
+            .cross_table_sums
+            .iter()
+            .fold(FieldElement::zero(), |acc, sum| acc + sum);
+        if total_sum != FieldElement::zero() {
+            error!("Cross-table lookup sums do not add up to zero");
+            return false;
+        }
+        for sum in proof.cross_table_sums.iter() {
+            transcript.append_field_element(sum);
+        }
+
+        // ===================================
+        // ==========|   Round 2   |==========
+        // ===================================
+
+        let mut constraint_coefficients = Vec::with_capacity(proof.tables.len());
+        for (air, table) in airs.iter().zip(&proof.tables) {
+            // <<<< Receive challenge: 𝛽
+            let beta = transcript.sample_field_element();
+            let num_boundary_constraints =
+                air.boundary_constraints(&rap_challenges).constraints.len();
+            let num_transition_constraints = air.context().num_transition_constraints;
+
+            let mut coefficients: Vec<_> =
+                core::iter::successors(Some(FieldElement::one()), |x| Some(x * &beta))
+                    .take(num_boundary_constraints + num_transition_constraints)
+                    .collect();
+            let transition_coeffs: Vec<_> =
+                coefficients.drain(..num_transition_constraints).collect();
+            constraint_coefficients.push((transition_coeffs, coefficients));
+
+            // <<<< Receive commitments: [H₁], [H₂]
+            transcript.append_bytes(table.composition_poly_root.as_ref());
+        }
+
+        // ===================================
+        // ==========|   Round 3   |==========
+        // ===================================
+
+        // >>>> Send challenge: z
+        let z = transcript.sample_z_ood(
+            &domains[longest].lde_roots_of_unity_coset,
+            &domains[longest].trace_roots_of_unity,
+        );
+
+        for table in proof.tables.iter() {
+            // <<<< Receive values: tⱼ(zgᵏ)
+            let frame = &table.trace_ood_frame_evaluations;
+            for i in 0..frame.n_cols() {
+                for j in 0..frame.n_rows() {
+                    transcript.append_field_element(&frame.get_row(j)[i]);
+                }
+            }
+            // <<<< Receive value: Hᵢ(z^N)
+            for element in table.composition_poly_parts_ood_evaluation.iter() {
+                transcript.append_field_element(element);
+            }
+        }
+
+        // ===================================
+        // ==========|   Round 4   |==========
+        // ===================================
+
+        let mut deep_composition_coeffs = Vec::with_capacity(proof.tables.len());
+        for (air, table) in airs.iter().zip(&proof.tables) {
+            // <<<< Receive challenges: 𝛾, 𝛾'
+            let gamma = transcript.sample_field_element();
+            let n_terms_composition_poly = table.composition_poly_parts_ood_evaluation.len();
+            let n_terms_trace =
+                air.context().transition_offsets.len() * air.context().trace_columns;
+            let mut coefficients =
+                deep_composition_coefficients(&gamma, n_terms_composition_poly + n_terms_trace);
+            let trace_term_coeffs: Vec<_> = coefficients
+                .drain(..n_terms_trace)
+                .collect::<Vec<_>>()
+                .chunks(air.context().transition_offsets.len())
+                .map(|chunk| chunk.to_vec())
+                .collect();
+            deep_composition_coeffs.push((trace_term_coeffs, coefficients));
+        }
+
+        // FRI commit phase
+        let mut zetas = proof
+            .fri_layers_merkle_roots
+            .iter()
+            .map(|root| {
+                // >>>> Send challenge 𝜁ₖ
+                let element = transcript.sample_field_element();
+                // <<<< Receive commitment: [pₖ] (the first one is [p₀])
+                transcript.append_bytes(root.as_ref());
+                element
+            })
+            .collect::<Vec<FieldElement<Self::FieldExtension>>>();
+
+        // >>>> Send challenge 𝜁ₙ₋₁
+        zetas.push(transcript.sample_field_element());
+
+        // <<<< Receive coefficients: pₙ
+        for coefficient in &proof.fri_remainder_coefficients {
+            transcript.append_field_element(coefficient);
+        }
+
+        // verify grinding
+        let security_bits = proof_options.grinding_factor;
+        let mut grinding_seed = [0u8; 32];
+        if security_bits > 0 {
+            grinding_seed = transcript.state();
+            transcript.append_bytes(&proof.nonce.to_be_bytes());
+            if !grinding::is_valid_nonce(
+                &grinding_seed,
+                proof.nonce,
+                security_bits,
+                proof_options.grinding_hash,
+            ) {
+                error!("Grinding factor not satisfied");
+                return false;
+            }
+        }
+
+        // FRI query phase
+        // <<<< Send challenges 𝜄ₛ (iota_s)
+        let iotas = Self::sample_query_indexes(
+            proof_options.fri_number_of_queries,
+            &domains[longest],
+            &mut transcript,
+        );
+
+        let mut deep_poly_evaluations = vec![FieldElement::zero(); iotas.len()];
+        let mut deep_poly_evaluations_sym = vec![FieldElement::zero(); iotas.len()];
+        for ((((air, domain), table), (transition_coeffs, boundary_coeffs)), coeffs) in airs
+            .iter()
+            .zip(&domains)
+            .zip(&proof.tables)
+            .zip(constraint_coefficients)
+            .zip(deep_composition_coeffs)
+        {
+            if table.deep_poly_openings.len() != iotas.len()
+                || table.deep_poly_openings_sym.len() != iotas.len()
+            {
+                error!("Wrong number of DEEP composition polynomial openings");
+                return false;
+            }
+
+            let (trace_term_coeffs, gammas) = coeffs;
+            let challenges = Challenges {
+                z: z.clone(),
+                boundary_coeffs,
+                transition_coeffs,
+                trace_term_coeffs,
+                gammas,
+                zetas: zetas.clone(),
+                iotas: iotas.clone(),
+                rap_challenges: rap_challenges.clone(),
+                grinding_seed,
+            };
+
+            if !Self::step_2_verify_claimed_composition_polynomial(air, table, domain, &challenges)
+            {
+                error!("Composition Polynomial verification failed");
+                return false;
+            }
+
+            if !Self::step_4_verify_trace_and_composition_openings(table, domain, &challenges, None)
+            {
+                error!("DEEP Composition Polynomial verification failed");
+                return false;
+            }
+
+            // Xᴺ⁻ⁿ Dₜ(X)
+            let shift = max_trace_length - air.trace_length();
+            let (evaluations, evaluations_sym) =
+                Self::reconstruct_deep_composition_poly_evaluations_for_all_queries(
+                    air,
+                    &challenges,
+                    domain,
+                    table,
+                );
+            for (i, iota) in iotas.iter().enumerate() {
+                let point = Self::query_challenge_to_evaluation_point(*iota, domain)
+                    .pow(shift)
+                    .to_extension();
+                let point_sym = Self::query_challenge_to_evaluation_point_sym(*iota, domain)
+                    .pow(shift)
+                    .to_extension();
+                deep_poly_evaluations[i] = &deep_poly_evaluations[i] + point * &evaluations[i];
+                deep_poly_evaluations_sym[i] =
+                    &deep_poly_evaluations_sym[i] + point_sym * &evaluations_sym[i];
+            }
+        }
+
+        let fri_ok = Self::verify_fri_queries(
+            &proof.fri_layers_merkle_roots,
+            &proof.fri_layers_merkle_proofs,
+            &proof.fri_remainder_coefficients,
+            &proof.query_list,
+            &zetas,
+            &iotas,
+            &layers_folding_factors,
+            &domains[longest],
+            &deep_poly_evaluations,
+            &deep_poly_evaluations_sym,
+        );
+        if !fri_ok {
+            error!("FRI verification failed");
+        }
+        fri_ok
+    }
+}
+
+impl<V: IsStarkVerifier> IsMultiTableStarkVerifier for V {}
@@ -0,0 +1,829 @@
+//! Canonical binary encoding of STARK proofs.
+//!
+//! A proof is encoded as a header followed by the body of the proof. All integers are
+//! big-endian and every length is a `u32` written before the items it counts.
+//!
+//! | Item            | Encoding                                                        |
+//! |-----------------|-----------------------------------------------------------------|
+//! | Magic bytes     | `MAGIC`                                                         |
+//! | Format version  | `u16`                                                           |
+//! | Field           | `u16` identifier of the base field, `u8` extension degree       |
+//! | Hash            | `u8` identifier of the hash of the Merkle trees                 |
+//! | `ProofOptions`  | Each option in order of declaration, booleans as one byte       |
+//! | `StarkProof`    | Each field in order of declaration                              |
+//!
+//! Elements of the base field are encoded as their canonical representative in a fixed
+//! number of bytes, and elements of the extension as their coordinates over the base
+//! field. Any other encoding of the same proof is rejected, so that encoded proofs can be
+//! compared byte by byte.
+//!
+//! The identifiers of fields and hashes and the layout of each version are never reused:
+//! new versions of the library keep decoding the versions of the format they know about.
+//!
+//! | Version | Changes                                                                 |
+//! |---------|-------------------------------------------------------------------------|
+//! | 1       | Initial layout                                                          |
+//! | 2       | Openings of the preprocessed columns after the auxiliary trace in each  |
+//! |         | `DeepPolynomialOpening`, and `lde_preprocessed_merkle_proof` as a `u8`  |
+//! |         | presence flag followed by its nodes after `lde_trace_merkle_proofs`     |
+//! | 3       | No byte after `fri_max_remainder_degree` in `ProofOptions`. Versions 1  |
+//! |         | and 2 hold there a prover setting that does not change the proof, which |
+//! |         | is written as 0 and ignored when decoding                               |
+
+use std::marker::PhantomData;
+
+use lambdaworks_crypto::merkle_tree::{
+    backends::{field_element_vector::FieldElementVectorBackend, poseidon::BatchPoseidonBackend},
+    proof::BatchProof,
+    traits::IsMerkleTreeBackend,
+};
+use lambdaworks_math::{
+    errors::DeserializationError,
+    field::{
+        element::FieldElement,
+        fields::fft_friendly::{
+            babybear::Babybear31PrimeField, goldilocks::Goldilocks64Field,
+            stark_252_prime_field::Stark252PrimeField,
+        },
+        traits::{IsField, IsPrimeField, IsSubFieldOf},
+    },
+    traits::{ByteConversion, Deserializable, Serializable},
+};
+use sha2::{Sha256, Sha512};
+use sha3::{Keccak256, Keccak512, Sha3_256, Sha3_512};
+
+use super::{
+    options::ProofOptions,
+    stark::{DeepPolynomialOpening, StarkProof},
+};
+use crate::{
+    config::BatchedMerkleTreeBackend, frame::Frame, fri::fri_decommit::FriDecommitment,
+    grinding::GrindingHash,
+};
+
+/// Bytes every encoded proof starts with.
+pub const MAGIC: [u8; 4] = *b"LWSP";
+
+/// Version of the format written by `Serializable`.
+pub const FORMAT_VERSION: u16 = 3;
+
+/// Prime fields whose proofs can be encoded, with the identifier written in the header.
+pub trait HasFieldId: IsPrimeField {
+    const FIELD_ID: u16;
+}
+
+impl HasFieldId for Stark252PrimeField {
+    const FIELD_ID: u16 = 1;
+}
+
+impl HasFieldId for Babybear31PrimeField {
+    const FIELD_ID: u16 = 2;
+}
+
+impl HasFieldId for Goldilocks64Field {
+    const FIELD_ID: u16 = 3;
+}
+
+/// Hash functions of the Merkle trees of a proof, with the identifier written in the header.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HashId {
+    Keccak256 = 1,
+    Sha3_256 = 2,
+    Sha2_256 = 3,
+    Keccak512 = 4,
+    Sha3_512 = 5,
+    Sha2_512 = 6,
+    PoseidonStark252 = 7,
+    PoseidonBabybear = 8,
+}
+
+impl HashId {
+    /// Number of bytes of a node of the Merkle trees.
+    pub fn node_size(&self) -> usize {
+        match self {
+            HashId::Keccak512 | HashId::Sha3_512 | HashId::Sha2_512 => 64,
+            _ => 32,
+        }
+    }
+}
+
+/// Merkle tree backends whose proofs can be encoded.
+pub trait HasHashId {
+    const HASH_ID: HashId;
+}
+
+impl<F> HasHashId for FieldElementVectorBackend<F, Keccak256, 32> {
+    const HASH_ID: HashId = HashId::Keccak256;
+}
+
+impl<F> HasHashId for FieldElementVectorBackend<F, Sha3_256, 32> {
+    const HASH_ID: HashId = HashId::Sha3_256;
+}
+
+impl<F> HasHashId for FieldElementVectorBackend<F, Sha256, 32> {
+    const HASH_ID: HashId = HashId::Sha2_256;
+}
+
+impl<F> HasHashId for FieldElementVectorBackend<F, Keccak512, 64> {
+    const HASH_ID: HashId = HashId::Keccak512;
+}
+
+impl<F> HasHashId for FieldElementVectorBackend<F, Sha3_512, 64> {
+    const HASH_ID: HashId = HashId::Sha3_512;
+}
+
+impl<F> HasHashId for FieldElementVectorBackend<F, Sha512, 64> {
+    const HASH_ID: HashId = HashId::Sha2_512;
+}
+
+impl<T> HasHashId for BatchPoseidonBackend<Stark252PrimeField, T> {
+    const HASH_ID: HashId = HashId::PoseidonStark252;
+}
+
+impl<T> HasHashId for BatchPoseidonBackend<Babybear31PrimeField, T> {
+    const HASH_ID: HashId = HashId::PoseidonBabybear;
+}
+
+/// A `StarkProof` committed with the backend `B`, together with the options it was
+/// generated with. It is the value encoded in the binary format of proofs.
+pub struct VersionedStarkProof<F, E = F, B = BatchedMerkleTreeBackend<F>>
+where
+    F: IsField,
+    E: IsField,
+    B: IsMerkleTreeBackend,
+{
+    /// Options the prover claims to have used. They are read from the encoded proof, so
+    /// they are chosen by the prover and not trusted: a verifier checks them against its
+    /// own with `check_options` and verifies the proof with its own.
+    pub options: ProofOptions,
+    pub proof: StarkProof<F, E, B::Node>,
+    phantom: PhantomData<B>,
+}
+
+impl<F, E, B> VersionedStarkProof<F, E, B>
+where
+    F: IsField,
+    E: IsField,
+    B: IsMerkleTreeBackend,
+{
+    pub fn new(proof: StarkProof<F, E, B::Node>, options: ProofOptions) -> Self {
+        Self {
+            options,
+            proof,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Checks that the options of the encoded proof are `expected`, the options the
+    /// verifier requires, so that a prover cannot lower the security of the proof by
+    /// choosing its own options.
+    pub fn check_options(&self, expected: &ProofOptions) -> Result<(), DeserializationError> {
+        if &self.options != expected {
+            return Err(DeserializationError::OptionsMismatch);
+        }
+        Ok(())
+    }
+}
+
+/// Errors of the encoding of a proof.
+#[derive(Debug, PartialEq, Eq)]
+pub enum SerializationError {
+    /// A length or an option does not fit in the `u32` the format writes it as.
+    ValueTooLarge,
+}
+
+impl<F, E, B> Serializable for VersionedStarkProof<F, E, B>
+where
+    F: HasFieldId + IsSubFieldOf<E>,
+    E: IsField,
+    B: IsMerkleTreeBackend + HasHashId,
+    B::Node: AsRef<[u8]>,
+    FieldElement<F>: ByteConversion,
+{
+    /// Panics if a length or an option does not fit in a `u32`; see `try_serialize`.
+    fn serialize(&self) -> Vec<u8> {
+        self.try_serialize()
+            .expect("lengths and options of a proof fit in a u32")
+    }
+}
+
+impl<F, E, B> VersionedStarkProof<F, E, B>
+where
+    F: HasFieldId + IsSubFieldOf<E>,
+    E: IsField,
+    B: IsMerkleTreeBackend + HasHashId,
+    B::Node: AsRef<[u8]>,
+    FieldElement<F>: ByteConversion,
+{
+    /// Encodes the proof in the current version of the format. Fails if a length or an
+    /// option does not fit in the `u32` the format writes it as.
+    pub fn try_serialize(&self) -> Result<Vec<u8>, SerializationError> {
+        self.serialize_with_version(FORMAT_VERSION)
+    }
+
+    /// Encodes the proof in the given version of the format. Versions before 2 cannot
+    /// hold the openings of preprocessed columns, which are left out.
+    fn serialize_with_version(&self, version: u16) -> Result<Vec<u8>, SerializationError> {
+        let mut writer = Writer::<F, E>::new(version);
+        writer.bytes(&MAGIC);
+        writer.u16(version);
+        writer.u16(F::FIELD_ID);
+        writer.u8(<F as IsSubFieldOf<E>>::EXTENSION_DEGREE as u8);
+        writer.u8(B::HASH_ID as u8);
+        writer.options(&self.options)?;
+        writer.proof(&self.proof)?;
+        Ok(writer.bytes)
+    }
+}
+
+impl<F, E, B> Deserializable for VersionedStarkProof<F, E, B>
+where
+    F: HasFieldId + IsSubFieldOf<E>,
+    E: IsField,
+    B: IsMerkleTreeBackend + HasHashId,
+    B::Node: for<'a> TryFrom<&'a [u8]>,
+    FieldElement<F>: ByteConversion,
+{
+    fn deserialize(bytes: &[u8]) -> Result<Self, DeserializationError>
+    where
+        Self: Sized,
+    {
+        let mut reader = Reader::<F, E>::new(bytes);
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(DeserializationError::InvalidMagicBytes);
+        }
+        reader.version = match reader.u16()? {
+            version @ 1..=FORMAT_VERSION => version,
+            _ => return Err(DeserializationError::UnsupportedVersion),
+        };
+        let field_id = reader.u16()?;
+        let extension_degree = reader.u8()?;
+        if field_id != F::FIELD_ID
+            || extension_degree as usize != <F as IsSubFieldOf<E>>::EXTENSION_DEGREE
+        {
+            return Err(DeserializationError::FieldMismatch);
+        }
+        if reader.u8()? != B::HASH_ID as u8 {
+            return Err(DeserializationError::HashMismatch);
+        }
+        let options = reader.options()?;
+        let proof = reader.proof(B::HASH_ID.node_size())?;
+        if !reader.is_empty() {
+            return Err(DeserializationError::TrailingBytes);
+        }
+        Ok(Self::new(proof, options))
+    }
+}
+
+fn grinding_hash_id(hash: GrindingHash) -> u8 {
+    match hash {
+        GrindingHash::Keccak256 => 0,
+        GrindingHash::Blake2s256 => 1,
+        GrindingHash::PoseidonStark252 => 2,
+        GrindingHash::PoseidonBabybear31 => 3,
+    }
+}
+
+fn grinding_hash_from_id(id: u8) -> Result<GrindingHash, DeserializationError> {
+    match id {
+        0 => Ok(GrindingHash::Keccak256),
+        1 => Ok(GrindingHash::Blake2s256),
+        2 => Ok(GrindingHash::PoseidonStark252),
+        3 => Ok(GrindingHash::PoseidonBabybear31),
+        _ => Err(DeserializationError::InvalidValue),
+    }
+}
+
+struct Writer<F, E> {
+    bytes: Vec<u8>,
+    version: u16,
+    phantom: PhantomData<(F, E)>,
+}
+
+impl<F, E> Writer<F, E>
+where
+    F: IsSubFieldOf<E>,
+    E: IsField,
+    FieldElement<F>: ByteConversion,
+{
+    fn new(version: u16) -> Self {
+        Self {
+            bytes: Vec::new(),
+            version,
+            phantom: PhantomData,
+        }
+    }
+
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    fn u8(&mut self, value: u8) {
+        self.bytes.push(value);
+    }
+
+    fn u16(&mut self, value: u16) {
+        self.bytes(&value.to_be_bytes());
+    }
+
+    fn u32(&mut self, value: u32) {
+        self.bytes(&value.to_be_bytes());
+    }
+
+    fn u64(&mut self, value: u64) {
+        self.bytes(&value.to_be_bytes());
+    }
+
+    /// Writes `value` as a `u32`, failing instead of truncating it.
+    fn usize_as_u32(&mut self, value: usize) -> Result<(), SerializationError> {
+        let value = u32::try_from(value).map_err(|_| SerializationError::ValueTooLarge)?;
+        self.u32(value);
+        Ok(())
+    }
+
+    fn length(&mut self, length: usize) -> Result<(), SerializationError> {
+        self.usize_as_u32(length)
+    }
+
+    fn options(&mut self, options: &ProofOptions) -> Result<(), SerializationError> {
+        self.u8(options.blowup_factor);
+        self.usize_as_u32(options.fri_number_of_queries)?;
+        self.u64(options.coset_offset);
+        self.u8(options.grinding_factor);
+        self.u8(options.zero_knowledge as u8);
+        self.u8(options.fri_folding_factor);
+        self.usize_as_u32(options.fri_max_remainder_degree)?;
+        if self.version < 3 {
+            self.u8(0);
+        }
+        self.u8(grinding_hash_id(options.grinding_hash));
+        Ok(())
+    }
+
+    fn base_element(&mut self, element: &FieldElement<F>) {
+        self.bytes(&element.to_bytes_be());
+    }
+
+    fn element(&mut self, element: &FieldElement<E>) {
+        for coordinate in F::to_coordinates(element.value()) {
+            self.base_element(&FieldElement::from_raw(&coordinate));
+        }
+    }
+
+    fn base_elements(&mut self, elements: &[FieldElement<F>]) -> Result<(), SerializationError> {
+        self.length(elements.len())?;
+        elements
+            .iter()
+            .for_each(|element| self.base_element(element));
+        Ok(())
+    }
+
+    fn elements(&mut self, elements: &[FieldElement<E>]) -> Result<(), SerializationError> {
+        self.length(elements.len())?;
+        elements.iter().for_each(|element| self.element(element));
+        Ok(())
+    }
+
+    fn nodes<C: AsRef<[u8]>>(&mut self, nodes: &[C]) -> Result<(), SerializationError> {
+        self.length(nodes.len())?;
+        nodes.iter().for_each(|node| self.bytes(node.as_ref()));
+        Ok(())
+    }
+
+    fn batch_proofs<C: AsRef<[u8]> + Eq>(
+        &mut self,
+        proofs: &[BatchProof<C>],
+    ) -> Result<(), SerializationError> {
+        self.length(proofs.len())?;
+        proofs
+            .iter()
+            .try_for_each(|proof| self.nodes(&proof.auth_nodes))
+    }
+
+    fn openings(
+        &mut self,
+        openings: &[DeepPolynomialOpening<F, E>],
+    ) -> Result<(), SerializationError> {
+        self.length(openings.len())?;
+        for opening in openings {
+            self.elements(&opening.lde_composition_poly_parts_evaluation)?;
+            self.base_elements(&opening.lde_trace_evaluations)?;
+            self.elements(&opening.lde_aux_trace_evaluations)?;
+            if self.version >= 2 {
+                self.base_elements(&opening.lde_preprocessed_evaluations)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn proof<C: AsRef<[u8]> + Eq>(
+        &mut self,
+        proof: &StarkProof<F, E, C>,
+    ) -> Result<(), SerializationError> {
+        self.u64(proof.trace_length as u64);
+        self.nodes(&proof.lde_trace_merkle_roots)?;
+
+        let frame = &proof.trace_ood_frame_evaluations;
+        self.length(frame.n_rows())?;
+        self.length(frame.n_cols())?;
+        (0..frame.n_rows()).for_each(|row| frame.get_row(row).iter().for_each(|e| self.element(e)));
+
+        self.bytes(proof.composition_poly_root.as_ref());
+        self.elements(&proof.composition_poly_parts_ood_evaluation)?;
+        self.nodes(&proof.fri_layers_merkle_roots)?;
+        self.elements(&proof.fri_remainder_coefficients)?;
+        self.length(proof.query_list.len())?;
+        proof
+            .query_list
+            .iter()
+            .try_for_each(|decommitment| self.elements(&decommitment.layers_evaluations_sym))?;
+        self.batch_proofs(&proof.fri_layers_merkle_proofs)?;
+        self.openings(&proof.deep_poly_openings)?;
+        self.openings(&proof.deep_poly_openings_sym)?;
+        self.batch_proofs(&proof.lde_trace_merkle_proofs)?;
+        if self.version >= 2 {
+            match &proof.lde_preprocessed_merkle_proof {
+                Some(merkle_proof) => {
+                    self.u8(1);
+                    self.nodes(&merkle_proof.auth_nodes)?;
+                }
+                None => self.u8(0),
+            }
+        }
+        self.nodes(&proof.lde_composition_poly_proof.auth_nodes)?;
+        self.u64(proof.nonce);
+        Ok(())
+    }
+}
+
+struct Reader<'a, F, E> {
+    bytes: &'a [u8],
+    offset: usize,
+    /// Version of the format being read, set once the header is read.
+    version: u16,
+    base_element_size: usize,
+    phantom: PhantomData<(F, E)>,
+}
+
+impl<'a, F, E> Reader<'a, F, E>
+where
+    F: IsSubFieldOf<E>,
+    E: IsField,
+    FieldElement<F>: ByteConversion,
+{
+    fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            offset: 0,
+            version: FORMAT_VERSION,
+            base_element_size: FieldElement::<F>::zero().to_bytes_be().len(),
+            phantom: PhantomData,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.offset == self.bytes.len()
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8], DeserializationError> {
+        let end = self
+            .offset
+            .checked_add(n)
+            .ok_or(DeserializationError::InvalidAmountOfBytes)?;
+        let bytes = self
+            .bytes
+            .get(self.offset..end)
+            .ok_or(DeserializationError::InvalidAmountOfBytes)?;
+        self.offset = end;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], DeserializationError> {
+        self.take(N)?
+            .try_into()
+            .map_err(|_| DeserializationError::InvalidAmountOfBytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, DeserializationError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, DeserializationError> {
+        Ok(u16::from_be_bytes(self.array()?))
+    }
+
+    fn u32(&mut self) -> Result<u32, DeserializationError> {
+        Ok(u32::from_be_bytes(self.array()?))
+    }
+
+    fn u64(&mut self) -> Result<u64, DeserializationError> {
+        Ok(u64::from_be_bytes(self.array()?))
+    }
+
+    fn usize(&mut self) -> Result<usize, DeserializationError> {
+        usize::try_from(self.u64()?).map_err(|_| DeserializationError::PointerSizeError)
+    }
+
+    fn length(&mut self) -> Result<usize, DeserializationError> {
+        usize::try_from(self.u32()?).map_err(|_| DeserializationError::PointerSizeError)
+    }
+
+    fn bool(&mut self) -> Result<bool, DeserializationError> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(DeserializationError::InvalidValue),
+        }
+    }
+
+    /// Reads `length` items with `read_item`. The capacity of the vector is not taken from
+    /// the input, so that a malformed length cannot trigger a large allocation.
+    fn vec<T>(
+        &mut self,
+        length: usize,
+        mut read_item: impl FnMut(&mut Self) -> Result<T, DeserializationError>,
+    ) -> Result<Vec<T>, DeserializationError> {
+        let mut items = Vec::new();
+        for _ in 0..length {
+            items.push(read_item(self)?);
+        }
+        Ok(items)
+    }
+
+    fn options(&mut self) -> Result<ProofOptions, DeserializationError> {
+        let blowup_factor = self.u8()?;
+        let fri_number_of_queries = self.length()?;
+        let coset_offset = self.u64()?;
+        let grinding_factor = self.u8()?;
+        let zero_knowledge = self.bool()?;
+        let fri_folding_factor = self.u8()?;
+        if !ProofOptions::SUPPORTED_FRI_FOLDING_FACTORS.contains(&fri_folding_factor) {
+            return Err(DeserializationError::InvalidValue);
+        }
+        let fri_max_remainder_degree = self.length()?;
+        if self.version < 3 {
+            self.bool()?;
+        }
+        let grinding_hash = grinding_hash_from_id(self.u8()?)?;
+        Ok(ProofOptions {
+            blowup_factor,
+            fri_number_of_queries,
+            coset_offset,
+            grinding_factor,
+            zero_knowledge,
+            fri_folding_factor,
+            fri_max_remainder_degree,
+            grinding_hash,
+        })
+    }
+
+    /// Reads an element of `F`, rejecting representatives that are not reduced.
+    fn base_element(&mut self) -> Result<FieldElement<F>, DeserializationError> {
+        let bytes = self.take(self.base_element_size)?;
+        let element = FieldElement::<F>::from_bytes_be(bytes)?;
+        if element.to_bytes_be() != bytes {
+            return Err(DeserializationError::FieldFromBytesError);
+        }
+        Ok(element)
+    }
+
+    fn element(&mut self) -> Result<FieldElement<E>, DeserializationError> {
+        let coordinates = self.vec(<F as IsSubFieldOf<E>>::EXTENSION_DEGREE, |reader| {
+            Ok(reader.base_element()?.value().clone())
+        })?;
+        Ok(FieldElement::from_raw(&F::from_coordinates(&coordinates)))
+    }
+
+    fn base_elements(&mut self) -> Result<Vec<FieldElement<F>>, DeserializationError> {
+        let length = self.length()?;
+        self.vec(length, Self::base_element)
+    }
+
+    fn elements(&mut self) -> Result<Vec<FieldElement<E>>, DeserializationError> {
+        let length = self.length()?;
+        self.vec(length, Self::element)
+    }
+
+    fn node<C>(&mut self, node_size: usize) -> Result<C, DeserializationError>
+    where
+        C: for<'b> TryFrom<&'b [u8]>,
+    {
+        C::try_from(self.take(node_size)?).map_err(|_| DeserializationError::InvalidAmountOfBytes)
+    }
+
+    fn nodes<C>(&mut self, node_size: usize) -> Result<Vec<C>, DeserializationError>
+    where
+        C: for<'b> TryFrom<&'b [u8]>,
+    {
+        let length = self.length()?;
+        self.vec(length, |reader| reader.node(node_size))
+    }
+
+    fn batch_proofs<C>(
+        &mut self,
+        node_size: usize,
+    ) -> Result<Vec<BatchProof<C>>, DeserializationError>
+    where
+        C: for<'b> TryFrom<&'b [u8]> + Eq,
+    {
+        let length = self.length()?;
+        self.vec(length, |reader| {
+            Ok(BatchProof {
+                auth_nodes: reader.nodes(node_size)?,
+            })
+        })
+    }
+
+    fn openings(&mut self) -> Result<Vec<DeepPolynomialOpening<F, E>>, DeserializationError> {
+        let length = self.length()?;
+        self.vec(length, |reader| {
+            Ok(DeepPolynomialOpening {
+                lde_composition_poly_parts_evaluation: reader.elements()?,
+                lde_trace_evaluations: reader.base_elements()?,
+                lde_aux_trace_evaluations: reader.elements()?,
+                lde_preprocessed_evaluations: if reader.version >= 2 {
+                    reader.base_elements()?
+                } else {
+                    Vec::new()
+                },
+            })
+        })
+    }
+
+    fn frame(&mut self) -> Result<Frame<E>, DeserializationError> {
+        let n_rows = self.length()?;
+        let n_cols = self.length()?;
+        // An empty frame has no rows, since tables of width zero have height zero.
+        if n_cols == 0 && n_rows != 0 {
+            return Err(DeserializationError::InvalidValue);
+        }
+        let n_elements = n_rows
+            .checked_mul(n_cols)
+            .ok_or(DeserializationError::InvalidValue)?;
+        let data = self.vec(n_elements, Self::element)?;
+        Ok(Frame::new(data, n_cols))
+    }
+
+    fn proof<C>(&mut self, node_size: usize) -> Result<StarkProof<F, E, C>, DeserializationError>
+    where
+        C: for<'b> TryFrom<&'b [u8]> + Eq,
+    {
+        let trace_length = self.usize()?;
+        let lde_trace_merkle_roots = self.nodes(node_size)?;
+        let trace_ood_frame_evaluations = self.frame()?;
+        let composition_poly_root = self.node(node_size)?;
+        let composition_poly_parts_ood_evaluation = self.elements()?;
+        let fri_layers_merkle_roots = self.nodes(node_size)?;
+        let fri_remainder_coefficients = self.elements()?;
+        let query_list_length = self.length()?;
+        let query_list = self.vec(query_list_length, |reader| {
+            Ok(FriDecommitment {
+                layers_evaluations_sym: reader.elements()?,
+            })
+        })?;
+        let fri_layers_merkle_proofs = self.batch_proofs(node_size)?;
+        let deep_poly_openings = self.openings()?;
+        let deep_poly_openings_sym = self.openings()?;
+        let lde_trace_merkle_proofs = self.batch_proofs(node_size)?;
+        let lde_preprocessed_merkle_proof = if self.version >= 2 && self.bool()? {
+            Some(BatchProof {
+                auth_nodes: self.nodes(node_size)?,
+            })
+        } else {
+            None
+        };
+        let lde_composition_poly_proof = BatchProof {
+            auth_nodes: self.nodes(node_size)?,
+        };
+        let nonce = self.u64()?;
+        Ok(StarkProof {
+            trace_length,
+            lde_trace_merkle_roots,
+            trace_ood_frame_evaluations,
+            composition_poly_root,
+            composition_poly_parts_ood_evaluation,
+            fri_layers_merkle_roots,
+            fri_remainder_coefficients,
+            query_list,
+            fri_layers_merkle_proofs,
+            deep_poly_openings,
+            deep_poly_openings_sym,
+            lde_trace_merkle_proofs,
+            lde_preprocessed_merkle_proof,
+            lde_composition_poly_proof,
+            nonce,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lambdaworks_math::{
+        errors::DeserializationError,
+        field::{
+            element::FieldElement,
+            fields::fft_friendly::{
+                babybear::{Babybear31PrimeField, Degree4Babybear31ExtensionField},
+                stark_252_prime_field::Stark252PrimeField,
+            },
+            traits::IsSubFieldOf,
+        },
+        traits::{Deserializable, Serializable},
+    };
+
+    use super::{Reader, SerializationError, VersionedStarkProof, FORMAT_VERSION};
+    use crate::{
+        examples::simple_fibonacci::{self, FibonacciAIR, FibonacciPublicInputs},
+        proof::options::ProofOptions,
+        prover::{IsStarkProver, Prover},
+        transcript::StoneProverTranscript,
+    };
+
+    #[test]
+    fn non_reduced_elements_are_rejected() {
+        let bytes = [0xff; 32];
+        let mut reader = Reader::<Stark252PrimeField, Stark252PrimeField>::new(&bytes);
+        assert_eq!(
+            reader.base_element(),
+            Err(DeserializationError::FieldFromBytesError)
+        );
+    }
+
+    #[test]
+    fn extension_elements_are_read_as_coordinates_over_the_base_field() {
+        let bytes: Vec<u8> = (1..=4u64).flat_map(|x| x.to_be_bytes()).collect();
+        let mut reader =
+            Reader::<Babybear31PrimeField, Degree4Babybear31ExtensionField>::new(&bytes);
+        let element = reader.element().unwrap();
+        assert!(reader.is_empty());
+        assert_eq!(
+            <Babybear31PrimeField as IsSubFieldOf<Degree4Babybear31ExtensionField>>::to_coordinates(
+                element.value()
+            ),
+            (1..=4u64)
+                .map(|x| *FieldElement::<Babybear31PrimeField>::from(x).value())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn proofs_in_older_versions_are_still_decoded() {
+        let trace = simple_fibonacci::fibonacci_trace(
+            [
+                FieldElement::<Stark252PrimeField>::one(),
+                FieldElement::one(),
+            ],
+            8,
+        );
+        let options = ProofOptions::default_test_options();
+        let pub_inputs = FibonacciPublicInputs {
+            a0: FieldElement::one(),
+            a1: FieldElement::one(),
+        };
+        let proof = Prover::prove::<FibonacciAIR<Stark252PrimeField>>(
+            &trace,
+            &pub_inputs,
+            &options,
+            StoneProverTranscript::new(&[]),
+        )
+        .unwrap();
+        let proof = VersionedStarkProof::<Stark252PrimeField>::new(proof, options);
+
+        for version in 1..FORMAT_VERSION {
+            let bytes = proof.serialize_with_version(version).unwrap();
+            let decoded = VersionedStarkProof::<Stark252PrimeField>::deserialize(&bytes).unwrap();
+            assert_eq!(decoded.serialize_with_version(version).unwrap(), bytes);
+            assert_eq!(decoded.serialize(), proof.serialize());
+        }
+    }
+
+    #[test]
+    fn options_that_do_not_fit_in_a_u32_are_not_truncated() {
+        let trace = simple_fibonacci::fibonacci_trace(
+            [
+                FieldElement::<Stark252PrimeField>::one(),
+                FieldElement::one(),
+            ],
+            8,
+        );
+        let options = ProofOptions::default_test_options();
+        let pub_inputs = FibonacciPublicInputs {
+            a0: FieldElement::one(),
+            a1: FieldElement::one(),
+        };
+        let proof = Prover::prove::<FibonacciAIR<Stark252PrimeField>>(
+            &trace,
+            &pub_inputs,
+            &options,
+            StoneProverTranscript::new(&[]),
+        )
+        .unwrap();
+        let mut proof = VersionedStarkProof::<Stark252PrimeField>::new(proof, options);
+        proof.options.fri_max_remainder_degree = u32::MAX as usize + 1;
+
+        assert_eq!(
+            proof.try_serialize(),
+            Err(SerializationError::ValueTooLarge)
+        );
+    }
+}
@@ -1,3 +1,5 @@
 pub mod errors;
+pub mod format;
+pub mod multi_table;
 pub mod options;
 pub mod stark;
@@ -0,0 +1,30 @@
+use lambdaworks_crypto::merkle_tree::proof::BatchProof;
+use lambdaworks_math::field::{element::FieldElement, traits::IsField};
+
+use crate::{config::Commitment, fri::fri_decommit::FriDecommitment};
+
+use super::stark::StarkProof;
+
+/// Proof of several tables. See `crate::multi_table`.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(bound(
+    serialize = "FieldElement<F>: serde::Serialize, FieldElement<E>: serde::Serialize, C: serde::Serialize",
+    deserialize = "FieldElement<F>: serde::Deserialize<'de>, FieldElement<E>: serde::Deserialize<'de>, C: serde::Deserialize<'de>"
+))]
+pub struct MultiTableStarkProof<F: IsField, E: IsField = F, C: PartialEq + Eq = Commitment> {
+    // Commitments, out of domain evaluations and openings of each table. Their FRI
+    // fields are empty, since the FRI proof below covers all of the tables.
+    pub tables: Vec<StarkProof<F, E, C>>,
+    // Last values of the cross-table lookup columns, in the order of the tables
+    pub cross_table_sums: Vec<FieldElement<E>>,
+    // [pₖ]
+    pub fri_layers_merkle_roots: Vec<C>,
+    // Coefficients of the remainder pₙ
+    pub fri_remainder_coefficients: Vec<FieldElement<E>>,
+    // Open(pₖ(Dₖ), −𝜐ₛ^(2ᵏ))
+    pub query_list: Vec<FriDecommitment<E>>,
+    // Merkle proofs of the openings of each pₖ, batched over all the queries
+    pub fri_layers_merkle_proofs: Vec<BatchProof<C>>,
+    // nonce obtained from grinding
+    pub nonce: u64,
+}
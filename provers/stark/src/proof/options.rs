@@ -1,5 +1,6 @@
 use super::errors::InsecureOptionError;
-use lambdaworks_math::field::traits::IsPrimeField;
+use crate::grinding::GrindingHash;
+use lambdaworks_math::field::traits::{IsField, IsPrimeField, IsSubFieldOf};
 
 #[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::wasm_bindgen;
@@ -19,21 +20,34 @@ pub enum SecurityLevel {
 /// - `fri_number_of_queries`: the number of queries for the FRI layer
 /// - `coset_offset`: the offset for the coset
 /// - `grinding_factor`: the number of leading zeros that we want for the Hash(hash || nonce)
+/// - `zero_knowledge`: whether the trace and composition polynomials are blinded with random
+///   terms so that the proof does not leak information about the trace
+/// - `fri_folding_factor`: the number of elements each FRI layer is folded by, one of
+///   `SUPPORTED_FRI_FOLDING_FACTORS`
+/// - `fri_max_remainder_degree`: the maximum degree of the polynomial sent at the end of
+///   the FRI commit phase. FRI stops folding once the layers have a degree bound that fits
+///   it, so a larger value means fewer FRI layers
+/// - `grinding_hash`: the hash function of the proof of work
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ProofOptions {
     pub blowup_factor: u8,
     pub fri_number_of_queries: usize,
     pub coset_offset: u64,
     pub grinding_factor: u8,
+    pub zero_knowledge: bool,
+    pub fri_folding_factor: u8,
+    pub fri_max_remainder_degree: usize,
+    pub grinding_hash: GrindingHash,
 }
 
 impl ProofOptions {
-    // TODO: Make it work for extended fields
-    const EXTENSION_DEGREE: usize = 1;
     // Estimated maximum domain size. 2^40 = 1 TB
     const NUM_BITS_MAX_DOMAIN_SIZE: usize = 40;
 
+    /// Folding factors of the FRI layers supported by the prover and the verifier.
+    pub const SUPPORTED_FRI_FOLDING_FACTORS: [u8; 4] = [2, 4, 8, 16];
+
     /// See section 5.10.1 of https://eprint.iacr.org/2021/582.pdf
     pub fn new_secure(security_level: SecurityLevel, coset_offset: u64) -> Self {
         match security_level {
@@ -42,49 +56,77 @@ impl ProofOptions {
                 fri_number_of_queries: 31,
                 coset_offset,
                 grinding_factor: 20,
+                zero_knowledge: false,
+                fri_folding_factor: 2,
+                fri_max_remainder_degree: 0,
+                grinding_hash: GrindingHash::Keccak256,
             },
             SecurityLevel::Conjecturable100Bits => ProofOptions {
                 blowup_factor: 4,
                 fri_number_of_queries: 41,
                 coset_offset,
                 grinding_factor: 20,
+                zero_knowledge: false,
+                fri_folding_factor: 2,
+                fri_max_remainder_degree: 0,
+                grinding_hash: GrindingHash::Keccak256,
             },
             SecurityLevel::Conjecturable128Bits => ProofOptions {
                 blowup_factor: 4,
                 fri_number_of_queries: 55,
                 coset_offset,
                 grinding_factor: 20,
+                zero_knowledge: false,
+                fri_folding_factor: 2,
+                fri_max_remainder_degree: 0,
+                grinding_hash: GrindingHash::Keccak256,
             },
             SecurityLevel::Provable80Bits => ProofOptions {
                 blowup_factor: 4,
                 fri_number_of_queries: 80,
                 coset_offset,
                 grinding_factor: 20,
+                zero_knowledge: false,
+                fri_folding_factor: 2,
+                fri_max_remainder_degree: 0,
+                grinding_hash: GrindingHash::Keccak256,
             },
             SecurityLevel::Provable100Bits => ProofOptions {
                 blowup_factor: 4,
                 fri_number_of_queries: 104,
                 coset_offset,
                 grinding_factor: 20,
+                zero_knowledge: false,
+                fri_folding_factor: 2,
+                fri_max_remainder_degree: 0,
+                grinding_hash: GrindingHash::Keccak256,
             },
             SecurityLevel::Provable128Bits => ProofOptions {
                 blowup_factor: 4,
                 fri_number_of_queries: 140,
                 coset_offset,
                 grinding_factor: 20,
+                zero_knowledge: false,
+                fri_folding_factor: 2,
+                fri_max_remainder_degree: 0,
+                grinding_hash: GrindingHash::Keccak256,
             },
         }
     }
 
     /// Checks security of proof options given 128 bits of security
-    pub fn new_with_checked_security<F: IsPrimeField>(
+    pub fn new_with_checked_security<F, E>(
         blowup_factor: u8,
         fri_number_of_queries: usize,
         coset_offset: u64,
         grinding_factor: u8,
         security_target: u8,
-    ) -> Result<Self, InsecureOptionError> {
-        Self::check_field_security::<F>(security_target)?;
+    ) -> Result<Self, InsecureOptionError>
+    where
+        F: IsPrimeField + IsSubFieldOf<E>,
+        E: IsField,
+    {
+        Self::check_field_security::<F, E>(security_target)?;
 
         let num_bits_blowup_factor = blowup_factor.trailing_zeros() as usize;
 
@@ -99,20 +141,28 @@ impl ProofOptions {
             fri_number_of_queries,
             coset_offset,
             grinding_factor,
+            zero_knowledge: false,
+            fri_folding_factor: 2,
+            fri_max_remainder_degree: 0,
+            grinding_hash: GrindingHash::Keccak256,
         })
     }
 
     /// Checks provable security of proof options given 128 bits of security
     /// This is an approximation. It's stricter than the formula in the paper.
     /// See https://eprint.iacr.org/2021/582.pdf
-    pub fn new_with_checked_provable_security<F: IsPrimeField>(
+    pub fn new_with_checked_provable_security<F, E>(
         blowup_factor: u8,
         fri_number_of_queries: usize,
         coset_offset: u64,
         grinding_factor: u8,
         security_target: u8,
-    ) -> Result<Self, InsecureOptionError> {
-        Self::check_field_security::<F>(security_target)?;
+    ) -> Result<Self, InsecureOptionError>
+    where
+        F: IsPrimeField + IsSubFieldOf<E>,
+        E: IsField,
+    {
+        Self::check_field_security::<F, E>(security_target)?;
 
         let num_bits_blowup_factor = blowup_factor.leading_zeros() as usize;
 
@@ -127,13 +177,21 @@ impl ProofOptions {
             fri_number_of_queries,
             coset_offset,
             grinding_factor,
+            zero_knowledge: false,
+            fri_folding_factor: 2,
+            fri_max_remainder_degree: 0,
+            grinding_hash: GrindingHash::Keccak256,
         })
     }
 
-    fn check_field_security<F: IsPrimeField>(
-        security_target: u8,
-    ) -> Result<(), InsecureOptionError> {
-        if F::field_bit_size() * Self::EXTENSION_DEGREE
+    /// Checks that the field where the challenges are sampled, `E`, is large enough.
+    /// Its size is the one of the trace field `F` times the degree of the extension.
+    fn check_field_security<F, E>(security_target: u8) -> Result<(), InsecureOptionError>
+    where
+        F: IsPrimeField + IsSubFieldOf<E>,
+        E: IsField,
+    {
+        if F::field_bit_size() * <F as IsSubFieldOf<E>>::EXTENSION_DEGREE
             <= security_target as usize + Self::NUM_BITS_MAX_DOMAIN_SIZE
         {
             return Err(InsecureOptionError::FieldSize);
@@ -150,6 +208,44 @@ impl ProofOptions {
             fri_number_of_queries: 3,
             coset_offset: 3,
             grinding_factor: 1,
+            zero_knowledge: false,
+            fri_folding_factor: 2,
+            fri_max_remainder_degree: 0,
+            grinding_hash: GrindingHash::Keccak256,
+        }
+    }
+
+    /// Returns the same options with the zero-knowledge mode enabled.
+    pub fn with_zero_knowledge(self) -> Self {
+        Self {
+            zero_knowledge: true,
+            ..self
+        }
+    }
+
+    /// Returns the same options with the FRI layers folded by `fri_folding_factor`, which
+    /// should be one of `SUPPORTED_FRI_FOLDING_FACTORS`.
+    pub fn with_fri_folding_factor(self, fri_folding_factor: u8) -> Self {
+        Self {
+            fri_folding_factor,
+            ..self
+        }
+    }
+
+    /// Returns the same options with the FRI commit phase stopped at a polynomial of degree
+    /// at most `fri_max_remainder_degree` instead of a constant.
+    pub fn with_fri_max_remainder_degree(self, fri_max_remainder_degree: usize) -> Self {
+        Self {
+            fri_max_remainder_degree,
+            ..self
+        }
+    }
+
+    /// Returns the same options with the proof of work done with `grinding_hash`.
+    pub fn with_grinding_hash(self, grinding_hash: GrindingHash) -> Self {
+        Self {
+            grinding_hash,
+            ..self
         }
     }
 }
@@ -157,7 +253,11 @@ impl ProofOptions {
 #[cfg(test)]
 mod tests {
     use lambdaworks_math::field::fields::{
-        fft_friendly::stark_252_prime_field::Stark252PrimeField, u64_prime_field::F17,
+        fft_friendly::{
+            babybear::{Babybear31PrimeField, Degree4Babybear31ExtensionField},
+            stark_252_prime_field::Stark252PrimeField,
+        },
+        u64_prime_field::F17,
     };
 
     use crate::proof::{errors::InsecureOptionError, options::SecurityLevel};
@@ -171,9 +271,10 @@ mod tests {
             fri_number_of_queries,
             coset_offset,
             grinding_factor,
+            ..
         } = ProofOptions::new_secure(SecurityLevel::Conjecturable128Bits, 1);
 
-        let u64_options = ProofOptions::new_with_checked_security::<F17>(
+        let u64_options = ProofOptions::new_with_checked_security::<F17, F17>(
             blowup_factor,
             fri_number_of_queries,
             coset_offset,
@@ -191,15 +292,17 @@ mod tests {
             fri_number_of_queries,
             coset_offset,
             grinding_factor,
+            ..
         } = ProofOptions::new_secure(SecurityLevel::Conjecturable128Bits, 1);
 
-        let secure_options = ProofOptions::new_with_checked_security::<Stark252PrimeField>(
-            blowup_factor,
-            fri_number_of_queries,
-            coset_offset,
-            grinding_factor,
-            128,
-        );
+        let secure_options =
+            ProofOptions::new_with_checked_security::<Stark252PrimeField, Stark252PrimeField>(
+                blowup_factor,
+                fri_number_of_queries,
+                coset_offset,
+                grinding_factor,
+                128,
+            );
 
         assert!(secure_options.is_ok());
     }
@@ -211,15 +314,17 @@ mod tests {
             fri_number_of_queries,
             coset_offset,
             grinding_factor,
+            ..
         } = ProofOptions::new_secure(SecurityLevel::Conjecturable128Bits, 1);
 
-        let insecure_options = ProofOptions::new_with_checked_security::<Stark252PrimeField>(
-            blowup_factor,
-            fri_number_of_queries - 1,
-            coset_offset,
-            grinding_factor,
-            128,
-        );
+        let insecure_options =
+            ProofOptions::new_with_checked_security::<Stark252PrimeField, Stark252PrimeField>(
+                blowup_factor,
+                fri_number_of_queries - 1,
+                coset_offset,
+                grinding_factor,
+                128,
+            );
 
         assert!(matches!(
             insecure_options,
@@ -234,36 +339,76 @@ mod tests {
             fri_number_of_queries,
             coset_offset,
             grinding_factor,
+            ..
         } = ProofOptions::new_secure(SecurityLevel::Conjecturable100Bits, 1);
 
-        let secure_options = ProofOptions::new_with_checked_security::<Stark252PrimeField>(
+        let secure_options =
+            ProofOptions::new_with_checked_security::<Stark252PrimeField, Stark252PrimeField>(
+                blowup_factor,
+                fri_number_of_queries,
+                coset_offset,
+                grinding_factor,
+                100,
+            );
+
+        assert!(secure_options.is_ok());
+    }
+
+    #[test]
+    fn generated_stark_proof_options_for_80_bits_are_secure_for_80_target_bits() {
+        let ProofOptions {
             blowup_factor,
             fri_number_of_queries,
             coset_offset,
             grinding_factor,
-            100,
-        );
+            ..
+        } = ProofOptions::new_secure(SecurityLevel::Conjecturable80Bits, 1);
+
+        let secure_options =
+            ProofOptions::new_with_checked_security::<Stark252PrimeField, Stark252PrimeField>(
+                blowup_factor,
+                fri_number_of_queries,
+                coset_offset,
+                grinding_factor,
+                80,
+            );
 
         assert!(secure_options.is_ok());
     }
 
     #[test]
-    fn generated_stark_proof_options_for_80_bits_are_secure_for_80_target_bits() {
+    fn babybear_is_only_secure_for_80_target_bits_when_challenges_are_in_its_degree_4_extension() {
         let ProofOptions {
             blowup_factor,
             fri_number_of_queries,
             coset_offset,
             grinding_factor,
+            ..
         } = ProofOptions::new_secure(SecurityLevel::Conjecturable80Bits, 1);
 
-        let secure_options = ProofOptions::new_with_checked_security::<Stark252PrimeField>(
+        let base_field_options =
+            ProofOptions::new_with_checked_security::<Babybear31PrimeField, Babybear31PrimeField>(
+                blowup_factor,
+                fri_number_of_queries,
+                coset_offset,
+                grinding_factor,
+                80,
+            );
+        assert!(matches!(
+            base_field_options,
+            Err(InsecureOptionError::FieldSize)
+        ));
+
+        let extension_options = ProofOptions::new_with_checked_security::<
+            Babybear31PrimeField,
+            Degree4Babybear31ExtensionField,
+        >(
             blowup_factor,
             fri_number_of_queries,
             coset_offset,
             grinding_factor,
             80,
         );
-
-        assert!(secure_options.is_ok());
+        assert!(extension_options.is_ok());
     }
 }
@@ -1,41 +1,65 @@
-use lambdaworks_crypto::merkle_tree::proof::Proof;
-use lambdaworks_math::field::{element::FieldElement, traits::IsFFTField};
+use lambdaworks_crypto::merkle_tree::proof::BatchProof;
+use lambdaworks_math::field::{element::FieldElement, traits::IsField};
 
 use crate::{config::Commitment, frame::Frame, fri::fri_decommit::FriDecommitment};
 
+/// Openings of the trace and composition polynomials at one point of the LDE domain.
+/// The main trace and the preprocessed columns live in the field `F`, while the auxiliary
+/// trace and the parts of the composition polynomial live in the extension `E`. The Merkle
+/// proofs of the openings of all the queries are batched in the `StarkProof`.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-pub struct DeepPolynomialOpening<F: IsFFTField> {
-    pub lde_composition_poly_proof: Proof<Commitment>,
-    pub lde_composition_poly_parts_evaluation: Vec<FieldElement<F>>,
-    pub lde_trace_merkle_proofs: Vec<Proof<Commitment>>,
+#[serde(bound(
+    serialize = "FieldElement<F>: serde::Serialize, FieldElement<E>: serde::Serialize",
+    deserialize = "FieldElement<F>: serde::Deserialize<'de>, FieldElement<E>: serde::Deserialize<'de>"
+))]
+pub struct DeepPolynomialOpening<F: IsField, E: IsField = F> {
+    pub lde_composition_poly_parts_evaluation: Vec<FieldElement<E>>,
     pub lde_trace_evaluations: Vec<FieldElement<F>>,
+    pub lde_aux_trace_evaluations: Vec<FieldElement<E>>,
+    pub lde_preprocessed_evaluations: Vec<FieldElement<F>>,
 }
 
-pub type DeepPolynomialOpenings<F> = Vec<DeepPolynomialOpening<F>>;
+pub type DeepPolynomialOpenings<F, E = F> = Vec<DeepPolynomialOpening<F, E>>;
 
+/// STARK proof whose commitments are Merkle tree nodes of type `C`.
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
-pub struct StarkProof<F: IsFFTField> {
+#[serde(bound(
+    serialize = "FieldElement<F>: serde::Serialize, FieldElement<E>: serde::Serialize, C: serde::Serialize",
+    deserialize = "FieldElement<F>: serde::Deserialize<'de>, FieldElement<E>: serde::Deserialize<'de>, C: serde::Deserialize<'de>"
+))]
+pub struct StarkProof<F: IsField, E: IsField = F, C: PartialEq + Eq = Commitment> {
     // Length of the execution trace
     pub trace_length: usize,
     // Commitments of the trace columns
     // [tⱼ]
-    pub lde_trace_merkle_roots: Vec<Commitment>,
+    pub lde_trace_merkle_roots: Vec<C>,
     // tⱼ(zgᵏ)
-    pub trace_ood_frame_evaluations: Frame<F>,
+    pub trace_ood_frame_evaluations: Frame<E>,
     // Commitments to Hᵢ
-    pub composition_poly_root: Commitment,
+    pub composition_poly_root: C,
     // Hᵢ(z^N)
-    pub composition_poly_parts_ood_evaluation: Vec<FieldElement<F>>,
+    pub composition_poly_parts_ood_evaluation: Vec<FieldElement<E>>,
     // [pₖ]
-    pub fri_layers_merkle_roots: Vec<Commitment>,
-    // pₙ
-    pub fri_last_value: FieldElement<F>,
+    pub fri_layers_merkle_roots: Vec<C>,
+    // Coefficients of the remainder pₙ
+    pub fri_remainder_coefficients: Vec<FieldElement<E>>,
     // Open(pₖ(Dₖ), −𝜐ₛ^(2ᵏ))
-    pub query_list: Vec<FriDecommitment<F>>,
+    pub query_list: Vec<FriDecommitment<E>>,
+    // Merkle proofs of the openings of each pₖ, batched over all the queries
+    pub fri_layers_merkle_proofs: Vec<BatchProof<C>>,
     // Open(H₁(D_LDE, 𝜐ᵢ), Open(H₂(D_LDE, 𝜐ᵢ), Open(tⱼ(D_LDE), 𝜐ᵢ)
-    pub deep_poly_openings: DeepPolynomialOpenings<F>,
+    pub deep_poly_openings: DeepPolynomialOpenings<F, E>,
     // Open(H₁(D_LDE, -𝜐ᵢ), Open(H₂(D_LDE, -𝜐ᵢ), Open(tⱼ(D_LDE), -𝜐ᵢ)
-    pub deep_poly_openings_sym: DeepPolynomialOpenings<F>,
+    pub deep_poly_openings_sym: DeepPolynomialOpenings<F, E>,
+    // Merkle proofs of the openings of tⱼ at 𝜐ᵢ and -𝜐ᵢ, batched over all the queries,
+    // one for the main trace and one for the auxiliary trace
+    pub lde_trace_merkle_proofs: Vec<BatchProof<C>>,
+    // Merkle proof of the openings of the preprocessed columns at 𝜐ᵢ and -𝜐ᵢ, batched over
+    // all the queries, against the root of the verification key. Only present if the AIR
+    // has preprocessed columns
+    pub lde_preprocessed_merkle_proof: Option<BatchProof<C>>,
+    // Merkle proof of the openings of Hᵢ at 𝜐ᵢ and -𝜐ᵢ, batched over all the queries
+    pub lde_composition_poly_proof: BatchProof<C>,
     // nonce obtained from grinding
     pub nonce: u64,
 }
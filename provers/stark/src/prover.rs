@@ -1,10 +1,13 @@
 #[cfg(feature = "instruments")]
 use std::time::Instant;
 
-use lambdaworks_crypto::merkle_tree::proof::Proof;
+use std::marker::PhantomData;
+
+use lambdaworks_crypto::merkle_tree::{merkle::MerkleTree, proof::BatchProof};
 use lambdaworks_math::fft::cpu::bit_reversing::{in_place_bit_reverse_permute, reverse_index};
 use lambdaworks_math::fft::{errors::FFTError, polynomial::FFTPoly};
 use lambdaworks_math::field::fields::fft_friendly::stark_252_prime_field::Stark252PrimeField;
+use lambdaworks_math::field::traits::{IsField, IsSubFieldOf};
 use lambdaworks_math::traits::Serializable;
 use lambdaworks_math::{
     field::{element::FieldElement, traits::IsFFTField},
@@ -16,12 +19,12 @@ use log::info;
 use rayon::prelude::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
 
 #[cfg(debug_assertions)]
-use crate::debug::validate_trace;
+use crate::debug::{validate_symbolic_transitions, validate_trace};
 use crate::fri;
 use crate::proof::stark::DeepPolynomialOpenings;
 use crate::transcript::IsStarkTranscript;
 
-use super::config::{BatchedMerkleTree, Commitment};
+use super::config::{BatchedMerkleTreeBackend, IsStarkMerkleTreeBackend};
 use super::constraints::evaluator::ConstraintEvaluator;
 use super::domain::Domain;
 use super::frame::Frame;
@@ -30,12 +33,33 @@ use super::grinding;
 use super::proof::options::ProofOptions;
 use super::proof::stark::{DeepPolynomialOpening, StarkProof};
 use super::trace::TraceTable;
-use super::traits::AIR;
+use super::traits::{has_valid_periodic_columns, number_of_blinding_coefficients, AIR};
+
+/// Prover for AIRs whose main trace lives in `F` and whose challenges, auxiliary trace
+/// and FRI run over the extension `E`. The main trace is committed with the Merkle tree
+/// backend `B` and every other polynomial with `BE`. Both backends must have the same
+/// type of nodes, which is the type of the commitments of the proof.
+pub struct ProverWithBackends<F, E, B, BE> {
+    phantom: PhantomData<(F, E, B, BE)>,
+}
 
-pub struct Prover;
+/// Prover committing with the default Keccak256 backend.
+pub type Prover<F = Stark252PrimeField, E = F> =
+    ProverWithBackends<F, E, BatchedMerkleTreeBackend<F>, BatchedMerkleTreeBackend<E>>;
 
-impl IsStarkProver for Prover {
-    type Field = Stark252PrimeField;
+impl<F, E, B, BE> IsStarkProver for ProverWithBackends<F, E, B, BE>
+where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsFFTField,
+    B: IsStarkMerkleTreeBackend<F>,
+    B::Node: AsRef<[u8]>,
+    BE: IsStarkMerkleTreeBackend<E, Node = B::Node>,
+{
+    type Field = F;
+    type FieldExtension = E;
+    type Commitment = B::Node;
+    type MerkleTreeBackend = B;
+    type MerkleTreeBackendExtension = BE;
 }
 
 #[derive(Debug)]
@@ -43,43 +67,184 @@ pub enum ProvingError {
     WrongParameter(String),
 }
 
-pub struct Round1<F, A>
+/// Settings of the prover that do not change the proof, so they are neither part of the
+/// `ProofOptions` nor of the encoded proof.
+///
+/// - `stream_lde`: whether the prover computes the low degree extensions of the trace and
+///   of the composition polynomial one coset of the trace domain at a time instead of
+///   keeping them in memory, recomputing the openings of the queries from the polynomials.
+///   The prover then holds no evaluations of the trace on the whole LDE domain, so its
+///   memory no longer grows with `blowup_factor` times the number of columns. It still
+///   holds vectors as long as the LDE domain, whatever the number of columns: the domain
+///   itself, the Merkle trees of the commitments, with up to `2 · blowup_factor ·
+///   trace_length` nodes each, and the evaluations of the composition and DEEP composition
+///   polynomials on it. Overall it holds about `8 · (blowup_factor + columns) ·
+///   trace_length` field elements and nodes at most, where `columns` counts the main,
+///   auxiliary and preprocessed columns. Streaming costs proving time and does not change
+///   the proof
+#[derive(Clone, Debug, Default)]
+pub struct ProverOptions {
+    pub stream_lde: bool,
+}
+
+impl ProverOptions {
+    /// Returns the same options with the low degree extensions streamed by the prover.
+    pub fn with_stream_lde(mut self) -> Self {
+        self.stream_lde = true;
+        self
+    }
+}
+
+/// Low degree extension of a committed trace table over the field `F`.
+pub(crate) enum LdeTrace<F: IsField> {
+    /// Evaluations of the trace polynomials on the LDE domain, in natural order.
+    Evaluations(TraceTable<F>),
+    /// The trace polynomials themselves, when the LDE is streamed. They are only evaluated
+    /// at the points of the LDE domain that get opened.
+    Polynomials(Vec<Polynomial<FieldElement<F>>>),
+}
+
+/// Commitment to the low degree extension of a trace table over the field `F`.
+pub struct Round1CommitmentData<F, B>
 where
-    F: IsFFTField,
-    A: AIR<Field = F>,
-    FieldElement<F>: Serializable,
+    F: IsField,
+    B: IsStarkMerkleTreeBackend<F>,
+{
+    pub(crate) lde_trace: LdeTrace<F>,
+    pub(crate) lde_trace_merkle_tree: MerkleTree<B>,
+    pub(crate) lde_trace_merkle_root: B::Node,
+}
+
+pub struct Round1<A, B, BE>
+where
+    A: AIR,
+    B: IsStarkMerkleTreeBackend<A::Field>,
+    BE: IsStarkMerkleTreeBackend<A::FieldExtension>,
 {
-    pub(crate) trace_polys: Vec<Polynomial<FieldElement<F>>>,
-    pub(crate) lde_trace: TraceTable<F>,
-    pub(crate) lde_trace_merkle_trees: Vec<BatchedMerkleTree<F>>,
-    pub(crate) lde_trace_merkle_roots: Vec<Commitment>,
+    /// Main trace polynomials embedded into the extension followed by the auxiliary ones
+    /// and the preprocessed ones.
+    pub(crate) trace_polys: Vec<Polynomial<FieldElement<A::FieldExtension>>>,
+    pub(crate) main: Round1CommitmentData<A::Field, B>,
+    pub(crate) aux: Option<Round1CommitmentData<A::FieldExtension, BE>>,
+    /// Commitment to the preprocessed columns, whose root is the one of the verification key.
+    pub(crate) preprocessed: Option<Round1CommitmentData<A::Field, B>>,
     pub(crate) rap_challenges: A::RAPChallenges,
 }
 
-pub struct Round2<F>
+impl<A, B, BE> Round1<A, B, BE>
 where
-    F: IsFFTField,
-    FieldElement<F>: Serializable,
+    A: AIR,
+    B: IsStarkMerkleTreeBackend<A::Field>,
+    BE: IsStarkMerkleTreeBackend<A::FieldExtension, Node = B::Node>,
+{
+    pub(crate) fn lde_trace_merkle_roots(&self) -> Vec<B::Node> {
+        let mut roots = vec![self.main.lde_trace_merkle_root.clone()];
+        if let Some(aux) = &self.aux {
+            roots.push(aux.lde_trace_merkle_root.clone());
+        }
+        roots
+    }
+
+    /// Returns the LDE of the main, auxiliary and preprocessed traces as a single table over
+    /// the extension, or `None` if the LDE is streamed.
+    fn lde_trace(&self) -> Option<TraceTable<A::FieldExtension>> {
+        let LdeTrace::Evaluations(main_lde_trace) = &self.main.lde_trace else {
+            return None;
+        };
+        let mut lde_trace = main_lde_trace.to_extension();
+        match &self.aux {
+            Some(Round1CommitmentData {
+                lde_trace: LdeTrace::Evaluations(aux_lde_trace),
+                ..
+            }) => {
+                lde_trace =
+                    lde_trace.concatenate(aux_lde_trace.table.data.clone(), aux_lde_trace.n_cols())
+            }
+            Some(_) => return None,
+            None => {}
+        }
+        match &self.preprocessed {
+            Some(Round1CommitmentData {
+                lde_trace: LdeTrace::Evaluations(preprocessed_lde_trace),
+                ..
+            }) => {
+                let preprocessed_lde_trace = preprocessed_lde_trace.to_extension();
+                lde_trace = lde_trace.concatenate(
+                    preprocessed_lde_trace.table.data,
+                    preprocessed_lde_trace.table.width,
+                )
+            }
+            Some(_) => return None,
+            None => {}
+        }
+        Some(lde_trace)
+    }
+}
+
+pub struct Round2<F, B>
+where
+    F: IsField,
+    B: IsStarkMerkleTreeBackend<F>,
 {
+    /// Parts of the composition polynomial followed, in zero-knowledge mode, by a random
+    /// polynomial that masks them.
     pub(crate) composition_poly_parts: Vec<Polynomial<FieldElement<F>>>,
-    pub(crate) lde_composition_poly_evaluations: Vec<Vec<FieldElement<F>>>,
-    pub(crate) composition_poly_merkle_tree: BatchedMerkleTree<F>,
-    pub(crate) composition_poly_root: Commitment,
+    /// Number of parts the composition polynomial was broken into, not counting the mask.
+    pub(crate) number_of_parts: usize,
+    /// Evaluations of the parts on the LDE domain, or `None` if the LDE is streamed, in
+    /// which case the openings are computed from `composition_poly_parts`.
+    pub(crate) lde_composition_poly_evaluations: Option<Vec<Vec<FieldElement<F>>>>,
+    pub(crate) composition_poly_merkle_tree: MerkleTree<B>,
+    pub(crate) composition_poly_root: B::Node,
 }
 
-pub struct Round3<F: IsFFTField> {
-    trace_ood_evaluations: Vec<Vec<FieldElement<F>>>,
-    composition_poly_parts_ood_evaluation: Vec<FieldElement<F>>,
+pub struct Round3<F: IsField> {
+    pub(crate) trace_ood_evaluations: Vec<Vec<FieldElement<F>>>,
+    pub(crate) composition_poly_parts_ood_evaluation: Vec<FieldElement<F>>,
 }
 
-pub struct Round4<F: IsFFTField> {
-    fri_last_value: FieldElement<F>,
-    fri_layers_merkle_roots: Vec<Commitment>,
-    deep_poly_openings: DeepPolynomialOpenings<F>,
-    deep_poly_openings_sym: DeepPolynomialOpenings<F>,
-    query_list: Vec<FriDecommitment<F>>,
+pub struct Round4<F: IsField, E: IsField, C: PartialEq + Eq> {
+    fri_remainder_coefficients: Vec<FieldElement<E>>,
+    fri_layers_merkle_roots: Vec<C>,
+    deep_poly_openings: DeepPolynomialOpenings<F, E>,
+    deep_poly_openings_sym: DeepPolynomialOpenings<F, E>,
+    lde_trace_merkle_proofs: Vec<BatchProof<C>>,
+    lde_preprocessed_merkle_proof: Option<BatchProof<C>>,
+    lde_composition_poly_proof: BatchProof<C>,
+    query_list: Vec<FriDecommitment<E>>,
+    fri_layers_merkle_proofs: Vec<BatchProof<C>>,
     nonce: u64,
 }
+
+/// Returns a uniformly distributed element of the prime field `F`, obtained by reducing
+/// 512 random bits. Used to blind polynomials in zero-knowledge mode.
+fn sample_random_field_element<F: IsField>() -> FieldElement<F> {
+    let two_to_the_64 = FieldElement::<F>::from(1 << 32).square();
+    (0..8).fold(FieldElement::zero(), |acc, _| {
+        acc * &two_to_the_64 + FieldElement::from(rand::random::<u64>())
+    })
+}
+
+/// Returns `t(X) + Z(X) r(X)` for each trace polynomial `t`, where `Z` is the vanishing
+/// polynomial of the trace domain and `r` a random polynomial whose coefficients are
+/// produced by `sample`. The result still interpolates the trace.
+fn blind_trace_polys<F: IsField>(
+    trace_polys: Vec<Polynomial<FieldElement<F>>>,
+    trace_length: usize,
+    number_of_coefficients: usize,
+    mut sample: impl FnMut() -> FieldElement<F>,
+) -> Vec<Polynomial<FieldElement<F>>> {
+    let vanishing_poly =
+        Polynomial::new_monomial(FieldElement::one(), trace_length) - FieldElement::one();
+    trace_polys
+        .into_iter()
+        .map(|poly| {
+            let coefficients: Vec<_> = (0..number_of_coefficients).map(|_| sample()).collect();
+            poly + &vanishing_poly * Polynomial::new(&coefficients)
+        })
+        .collect()
+}
+
 pub fn evaluate_polynomial_on_lde_domain<F>(
     p: &Polynomial<FieldElement<F>>,
     blowup_factor: usize,
@@ -98,67 +263,232 @@ where
     }
 }
 
-pub trait IsStarkProver {
-    type Field: IsFFTField;
+/// Evaluates `p` on the coset `offset·H` in natural order, where `H` is the subgroup of
+/// size `domain_size`. Coefficients of degree `domain_size` or more are folded onto the lower
+/// ones, so `p` may have any degree.
+pub fn evaluate_polynomial_on_coset<F>(
+    p: &Polynomial<FieldElement<F>>,
+    domain_size: usize,
+    offset: &FieldElement<F>,
+) -> Result<Vec<FieldElement<F>>, FFTError>
+where
+    F: IsFFTField,
+    Polynomial<FieldElement<F>>: FFTPoly<F>,
+{
+    let mut coefficients = vec![FieldElement::zero(); domain_size];
+    for (i, coefficient) in p.scale(offset).coefficients().iter().enumerate() {
+        coefficients[i % domain_size] = &coefficients[i % domain_size] + coefficient;
+    }
+    Polynomial::new(&coefficients).evaluate_fft(1, Some(domain_size))
+}
 
-    fn batch_commit(
-        vectors: &[Vec<FieldElement<Self::Field>>],
-    ) -> (BatchedMerkleTree<Self::Field>, Commitment)
+pub trait IsStarkProver {
+    type Field: IsFFTField + IsSubFieldOf<Self::FieldExtension>;
+    type FieldExtension: IsFFTField;
+    /// Nodes of the Merkle trees, which are the commitments sent to the verifier.
+    type Commitment: PartialEq + Eq + Clone + AsRef<[u8]>;
+    /// Merkle tree backend that commits to the main trace.
+    type MerkleTreeBackend: IsStarkMerkleTreeBackend<Self::Field, Node = Self::Commitment>;
+    /// Merkle tree backend that commits to the auxiliary trace, the composition
+    /// polynomial and the FRI layers.
+    type MerkleTreeBackendExtension: IsStarkMerkleTreeBackend<
+        Self::FieldExtension,
+        Node = Self::Commitment,
+    >;
+
+    fn batch_commit<T, B>(vectors: &[Vec<FieldElement<T>>]) -> (MerkleTree<B>, B::Node)
     where
-        FieldElement<Self::Field>: Serializable,
+        T: IsField,
+        B: IsStarkMerkleTreeBackend<T>,
     {
-        let tree = BatchedMerkleTree::<Self::Field>::build(vectors);
-        let commitment = tree.root;
+        let tree = MerkleTree::<B>::build(vectors);
+        let commitment = tree.root.clone();
         (tree, commitment)
     }
 
+    /// Interpolates the columns of `trace`, which may be the main trace over `Self::Field`
+    /// or the auxiliary trace over `Self::FieldExtension`, and commits to their LDE.
+    /// If `blinding_sampler` is given, the trace polynomials are blinded with the random
+    /// coefficients it produces before being committed. If `stream_lde` is set, the LDE is
+    /// committed one coset at a time and not kept.
     #[allow(clippy::type_complexity)]
-    fn interpolate_and_commit(
-        trace: &TraceTable<Self::Field>,
+    fn interpolate_and_commit<T, B>(
+        trace: &TraceTable<T>,
         domain: &Domain<Self::Field>,
-        transcript: &mut impl IsStarkTranscript<Self::Field>,
-    ) -> (
-        Vec<Polynomial<FieldElement<Self::Field>>>,
-        Vec<Vec<FieldElement<Self::Field>>>,
-        BatchedMerkleTree<Self::Field>,
-        Commitment,
-    )
+        transcript: &mut impl IsStarkTranscript<Self::FieldExtension>,
+        blinding_sampler: Option<(usize, &mut dyn FnMut() -> FieldElement<T>)>,
+        stream_lde: bool,
+    ) -> (Vec<Polynomial<FieldElement<T>>>, Round1CommitmentData<T, B>)
     where
-        FieldElement<Self::Field>: Serializable + Send + Sync,
+        T: IsFFTField,
+        Self::Field: IsSubFieldOf<T>,
+        FieldElement<T>: Serializable + Send + Sync,
+        B: IsStarkMerkleTreeBackend<T, Node = Self::Commitment>,
     {
-        let trace_polys = trace.compute_trace_polys();
+        let mut trace_polys = trace.compute_trace_polys();
+
+        if let Some((number_of_coefficients, sample)) = blinding_sampler {
+            trace_polys = blind_trace_polys(
+                trace_polys,
+                domain.interpolation_domain_size,
+                number_of_coefficients,
+                sample,
+            );
+        }
 
-        // Evaluate those polynomials t_j on the large domain D_LDE.
-        let lde_trace_evaluations = Self::compute_lde_trace_evaluations(&trace_polys, domain);
+        let commitment_data = Self::commit_trace_polys::<T, B>(&trace_polys, domain, stream_lde);
 
-        let mut lde_trace_permuted = lde_trace_evaluations.clone();
+        // >>>> Send commitments: [tⱼ]
+        transcript.append_bytes(commitment_data.lde_trace_merkle_root.as_ref());
 
-        for col in lde_trace_permuted.iter_mut() {
-            in_place_bit_reverse_permute(col);
-        }
+        (trace_polys, commitment_data)
+    }
+
+    /// Commits to the LDE of the polynomials of a trace table. If `stream_lde` is set, the
+    /// LDE is committed one coset at a time and not kept.
+    fn commit_trace_polys<T, B>(
+        trace_polys: &[Polynomial<FieldElement<T>>],
+        domain: &Domain<Self::Field>,
+        stream_lde: bool,
+    ) -> Round1CommitmentData<T, B>
+    where
+        T: IsFFTField,
+        Self::Field: IsSubFieldOf<T>,
+        FieldElement<T>: Serializable + Send + Sync,
+        B: IsStarkMerkleTreeBackend<T, Node = Self::Commitment>,
+    {
+        let (lde_trace, lde_trace_merkle_tree, lde_trace_merkle_root) = if stream_lde {
+            let lde_trace_merkle_tree =
+                Self::commit_lde_by_cosets::<T, B>(trace_polys, domain, |rows| rows);
+            let lde_trace_merkle_root = lde_trace_merkle_tree.root.clone();
+            (
+                LdeTrace::Polynomials(trace_polys.to_vec()),
+                lde_trace_merkle_tree,
+                lde_trace_merkle_root,
+            )
+        } else {
+            // Evaluate those polynomials t_j on the large domain D_LDE.
+            let lde_trace_evaluations =
+                Self::compute_lde_trace_evaluations::<T>(trace_polys, domain);
 
-        // Compute commitments [t_j].
-        let lde_trace = TraceTable::from_columns(&lde_trace_permuted);
-        let (lde_trace_merkle_tree, lde_trace_merkle_root) = Self::batch_commit(&lde_trace.rows());
+            let mut lde_trace_permuted = lde_trace_evaluations.clone();
 
-        // >>>> Send commitments: [tⱼ]
-        transcript.append_bytes(&lde_trace_merkle_root);
+            for col in lde_trace_permuted.iter_mut() {
+                in_place_bit_reverse_permute(col);
+            }
 
-        (
-            trace_polys,
-            lde_trace_evaluations,
+            // Compute commitments [t_j].
+            let lde_trace = TraceTable::from_columns(&lde_trace_permuted);
+            let (lde_trace_merkle_tree, lde_trace_merkle_root) =
+                Self::batch_commit::<T, B>(&lde_trace.rows());
+            (
+                LdeTrace::Evaluations(TraceTable::from_columns(&lde_trace_evaluations)),
+                lde_trace_merkle_tree,
+                lde_trace_merkle_root,
+            )
+        };
+
+        Round1CommitmentData {
+            lde_trace,
             lde_trace_merkle_tree,
             lde_trace_merkle_root,
-        )
+        }
+    }
+
+    /// Interpolates the preprocessed columns of the AIR and commits to their LDE as to the
+    /// main trace, so that the root is the one of `VerificationKey::new`. Returns `None` if
+    /// the AIR has no preprocessed columns.
+    #[allow(clippy::type_complexity)]
+    fn commit_preprocessed_columns<A>(
+        air: &A,
+        domain: &Domain<Self::Field>,
+        stream_lde: bool,
+    ) -> Result<
+        Option<(
+            Vec<Polynomial<FieldElement<Self::Field>>>,
+            Round1CommitmentData<Self::Field, Self::MerkleTreeBackend>,
+        )>,
+        ProvingError,
+    >
+    where
+        A: AIR<Field = Self::Field, FieldExtension = Self::FieldExtension>,
+        FieldElement<Self::Field>: Serializable + Send + Sync,
+    {
+        let columns = air.get_preprocessed_columns();
+        if columns.len() != air.number_preprocessed_columns()
+            || columns
+                .iter()
+                .any(|column| column.len() != air.trace_length())
+        {
+            return Err(ProvingError::WrongParameter(format!(
+                "Expected {} preprocessed columns of length {}",
+                air.number_preprocessed_columns(),
+                air.trace_length()
+            )));
+        }
+        if columns.is_empty() {
+            return Ok(None);
+        }
+
+        let polys = TraceTable::from_columns(&columns).compute_trace_polys();
+        let commitment_data = Self::commit_trace_polys::<Self::Field, Self::MerkleTreeBackend>(
+            &polys, domain, stream_lde,
+        );
+        Ok(Some((polys, commitment_data)))
     }
 
-    fn compute_lde_trace_evaluations(
-        trace_polys: &[Polynomial<FieldElement<Self::Field>>],
+    /// Commits to the LDE of `polys` without holding it in memory. In bit-reversed order,
+    /// the LDE domain is made of `blowup_factor` contiguous blocks, each of them a coset of
+    /// the trace domain, so the leaves are hashed one block at a time. `leaves` turns the
+    /// rows of a block, in bit-reversed order, into leaves of the tree.
+    fn commit_lde_by_cosets<T, B>(
+        polys: &[Polynomial<FieldElement<T>>],
         domain: &Domain<Self::Field>,
-    ) -> Vec<Vec<FieldElement<Self::Field>>>
+        leaves: impl Fn(Vec<Vec<FieldElement<T>>>) -> Vec<Vec<FieldElement<T>>>,
+    ) -> MerkleTree<B>
     where
-        FieldElement<Self::Field>: Send + Sync,
+        T: IsFFTField,
+        Self::Field: IsSubFieldOf<T>,
+        B: IsStarkMerkleTreeBackend<T>,
     {
+        let blowup_factor = domain.blowup_factor;
+        let trace_length = domain.interpolation_domain_size;
+        let hasher = B::default();
+
+        let mut hashed_leaves = Vec::with_capacity(blowup_factor * trace_length);
+        for block in 0..blowup_factor {
+            // The block holds the points whose index in the LDE domain is congruent to
+            // `coset` modulo the blowup factor.
+            let coset = reverse_index(block, blowup_factor as u64);
+            let offset = domain.lde_roots_of_unity_coset[coset]
+                .clone()
+                .to_extension::<T>();
+            let columns: Vec<_> = polys
+                .iter()
+                .map(|poly| {
+                    let mut evaluations =
+                        evaluate_polynomial_on_coset(poly, trace_length, &offset).unwrap();
+                    in_place_bit_reverse_permute(&mut evaluations);
+                    evaluations
+                })
+                .collect();
+            let rows = TraceTable::from_columns(&columns).rows();
+            hashed_leaves.extend(hasher.hash_leaves(&leaves(rows)));
+        }
+        MerkleTree::build_from_hashed_leaves(hashed_leaves)
+    }
+
+    fn compute_lde_trace_evaluations<T>(
+        trace_polys: &[Polynomial<FieldElement<T>>],
+        domain: &Domain<Self::Field>,
+    ) -> Vec<Vec<FieldElement<T>>>
+    where
+        T: IsFFTField,
+        Self::Field: IsSubFieldOf<T>,
+        FieldElement<T>: Send + Sync,
+    {
+        let coset_offset = domain.coset_offset.clone().to_extension::<T>();
+
         #[cfg(not(feature = "parallel"))]
         let trace_polys_iter = trace_polys.iter();
         #[cfg(feature = "parallel")]
@@ -170,57 +500,195 @@ pub trait IsStarkProver {
                     poly,
                     domain.blowup_factor,
                     domain.interpolation_domain_size,
-                    &domain.coset_offset,
+                    &coset_offset,
                 )
             })
-            .collect::<Result<Vec<Vec<FieldElement<Self::Field>>>, FFTError>>()
+            .collect::<Result<Vec<Vec<FieldElement<T>>>, FFTError>>()
             .unwrap()
     }
 
-    fn round_1_randomized_air_with_preprocessing<A: AIR<Field = Self::Field>>(
+    /// Checks that the composition polynomial is determined by its evaluations on the LDE
+    /// domain, that is, that the blowup factor is large enough for the degrees of the
+    /// transition constraints.
+    fn check_blowup_factor<A: AIR>(air: &A) -> Result<(), ProvingError> {
+        let degree_bound = air.composition_poly_degree_bound();
+        let lde_domain_size = air.blowup_factor() as usize * air.trace_length();
+        if degree_bound > lde_domain_size {
+            let max_transition_degree = air
+                .context()
+                .transition_degrees
+                .iter()
+                .max()
+                .copied()
+                .unwrap_or(1);
+            return Err(ProvingError::WrongParameter(format!(
+                "Blowup factor {} is too small for constraints of degree {max_transition_degree}: \
+                 the composition polynomial has degree bound {degree_bound}, which exceeds the \
+                 size {lde_domain_size} of the LDE domain",
+                air.blowup_factor()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Checks that the periods of the periodic columns are powers of two dividing the trace
+    /// length.
+    fn check_periodic_columns<A: AIR>(air: &A) -> Result<(), ProvingError> {
+        if !has_valid_periodic_columns(air) {
+            return Err(ProvingError::WrongParameter(format!(
+                "The periods of the periodic columns must be powers of two dividing the trace \
+                 length {}",
+                air.trace_length()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Checks that the FRI layers can be folded by the folding factor of the options.
+    fn check_fri_folding_factor(proof_options: &ProofOptions) -> Result<(), ProvingError> {
+        let folding_factor = proof_options.fri_folding_factor;
+        if !ProofOptions::SUPPORTED_FRI_FOLDING_FACTORS.contains(&folding_factor) {
+            return Err(ProvingError::WrongParameter(format!(
+                "FRI folding factor {folding_factor} is not one of {:?}",
+                ProofOptions::SUPPORTED_FRI_FOLDING_FACTORS
+            )));
+        }
+        Ok(())
+    }
+
+    /// Checks that the blinded polynomials of the zero-knowledge mode fit in the degree
+    /// bounds of the proof: the blinding terms must not exceed the trace length and the
+    /// composition polynomial must still be determined by its evaluations on the LDE domain.
+    fn check_zero_knowledge_parameters<A: AIR>(air: &A) -> Result<(), ProvingError> {
+        let trace_length = air.trace_length();
+        let number_of_coefficients = number_of_blinding_coefficients(air);
+        if number_of_coefficients > trace_length {
+            return Err(ProvingError::WrongParameter(format!(
+                "Zero-knowledge mode needs {number_of_coefficients} blinding coefficients, \
+                 which exceeds the trace length {trace_length}"
+            )));
+        }
+
+        let max_transition_degree = air
+            .context()
+            .transition_degrees
+            .iter()
+            .max()
+            .copied()
+            .unwrap_or(1);
+        let blowup_factor = air.blowup_factor() as usize;
+        if max_transition_degree * (trace_length + number_of_coefficients - 1)
+            >= blowup_factor * trace_length
+        {
+            return Err(ProvingError::WrongParameter(format!(
+                "Blowup factor {blowup_factor} is too small for zero-knowledge proofs \
+                 of constraints of degree {max_transition_degree}"
+            )));
+        }
+        Ok(())
+    }
+
+    fn round_1_randomized_air_with_preprocessing<A>(
         air: &A,
         main_trace: &TraceTable<Self::Field>,
         domain: &Domain<Self::Field>,
-        transcript: &mut impl IsStarkTranscript<Self::Field>,
-    ) -> Result<Round1<Self::Field, A>, ProvingError>
+        transcript: &mut impl IsStarkTranscript<Self::FieldExtension>,
+        mut zk_randomness: Option<&mut impl IsStarkTranscript<Self::FieldExtension>>,
+        stream_lde: bool,
+    ) -> Result<Round1<A, Self::MerkleTreeBackend, Self::MerkleTreeBackendExtension>, ProvingError>
     where
+        A: AIR<Field = Self::Field, FieldExtension = Self::FieldExtension>,
         FieldElement<Self::Field>: Serializable + Send + Sync,
+        FieldElement<Self::FieldExtension>: Serializable + Send + Sync,
     {
-        let (mut trace_polys, mut evaluations, main_merkle_tree, main_merkle_root) =
-            Self::interpolate_and_commit(main_trace, domain, transcript);
+        let zero_knowledge = zk_randomness.is_some();
+        let number_of_coefficients = number_of_blinding_coefficients(air);
+
+        // >>>> Send commitment: root of the verification key
+        let preprocessed = Self::commit_preprocessed_columns(air, domain, stream_lde)?;
+        if let Some((_, preprocessed)) = &preprocessed {
+            transcript.append_bytes(preprocessed.lde_trace_merkle_root.as_ref());
+        }
+
+        let mut sample_main = sample_random_field_element::<Self::Field>;
+        let main_blinding_sampler = zero_knowledge.then_some((
+            number_of_coefficients,
+            &mut sample_main as &mut dyn FnMut() -> FieldElement<Self::Field>,
+        ));
+        let (main_trace_polys, main) =
+            Self::interpolate_and_commit::<Self::Field, Self::MerkleTreeBackend>(
+                main_trace,
+                domain,
+                transcript,
+                main_blinding_sampler,
+                stream_lde,
+            );
 
         let rap_challenges = air.build_rap_challenges(transcript);
 
         let aux_trace = air.build_auxiliary_trace(main_trace, &rap_challenges);
 
-        let mut lde_trace_merkle_trees = vec![main_merkle_tree];
-        let mut lde_trace_merkle_roots = vec![main_merkle_root];
-        if !aux_trace.is_empty() {
+        let mut trace_polys: Vec<_> = main_trace_polys
+            .into_iter()
+            .map(|poly| poly.to_extension())
+            .collect();
+
+        let aux = if !aux_trace.is_empty() {
+            // The auxiliary trace lives in the extension, so its blinding coefficients are
+            // sampled from the private copy of the transcript.
+            let mut sample_aux = || {
+                zk_randomness
+                    .as_mut()
+                    .map(|randomness| randomness.sample_field_element())
+                    .unwrap_or_else(FieldElement::zero)
+            };
+            let aux_blinding_sampler = zero_knowledge.then_some((
+                number_of_coefficients,
+                &mut sample_aux as &mut dyn FnMut() -> FieldElement<Self::FieldExtension>,
+            ));
             // Check that this is valid for interpolation
-            let (aux_trace_polys, aux_trace_polys_evaluations, aux_merkle_tree, aux_merkle_root) =
-                Self::interpolate_and_commit(&aux_trace, domain, transcript);
+            let (aux_trace_polys, aux) = Self::interpolate_and_commit::<
+                Self::FieldExtension,
+                Self::MerkleTreeBackendExtension,
+            >(
+                &aux_trace,
+                domain,
+                transcript,
+                aux_blinding_sampler,
+                stream_lde,
+            );
             trace_polys.extend_from_slice(&aux_trace_polys);
-            evaluations.extend_from_slice(&aux_trace_polys_evaluations);
-            lde_trace_merkle_trees.push(aux_merkle_tree);
-            lde_trace_merkle_roots.push(aux_merkle_root);
-        }
+            Some(aux)
+        } else {
+            None
+        };
 
-        let lde_trace = TraceTable::from_columns(&evaluations);
+        let preprocessed = preprocessed.map(|(preprocessed_polys, preprocessed)| {
+            trace_polys.extend(
+                preprocessed_polys
+                    .into_iter()
+                    .map(|poly| poly.to_extension()),
+            );
+            preprocessed
+        });
 
         Ok(Round1 {
             trace_polys,
-            lde_trace,
-            lde_trace_merkle_roots,
-            lde_trace_merkle_trees,
+            main,
+            aux,
+            preprocessed,
             rap_challenges,
         })
     }
 
     fn commit_composition_polynomial(
-        lde_composition_poly_parts_evaluations: &[Vec<FieldElement<Self::Field>>],
-    ) -> (BatchedMerkleTree<Self::Field>, Commitment)
+        lde_composition_poly_parts_evaluations: &[Vec<FieldElement<Self::FieldExtension>>],
+    ) -> (
+        MerkleTree<Self::MerkleTreeBackendExtension>,
+        Self::Commitment,
+    )
     where
-        FieldElement<Self::Field>: Serializable,
+        FieldElement<Self::FieldExtension>: Serializable,
     {
         // TODO: Remove clones
         let mut lde_composition_poly_evaluations = Vec::new();
@@ -241,75 +709,114 @@ pub trait IsStarkProver {
             lde_composition_poly_evaluations_merged.push(chunk0);
         }
 
-        Self::batch_commit(&lde_composition_poly_evaluations_merged)
+        Self::batch_commit::<Self::FieldExtension, Self::MerkleTreeBackendExtension>(
+            &lde_composition_poly_evaluations_merged,
+        )
     }
 
     fn round_2_compute_composition_polynomial<A>(
         air: &A,
         domain: &Domain<Self::Field>,
-        round_1_result: &Round1<Self::Field, A>,
-        transition_coefficients: &[FieldElement<Self::Field>],
-        boundary_coefficients: &[FieldElement<Self::Field>],
-    ) -> Round2<Self::Field>
+        round_1_result: &Round1<A, Self::MerkleTreeBackend, Self::MerkleTreeBackendExtension>,
+        transition_coefficients: &[FieldElement<Self::FieldExtension>],
+        boundary_coefficients: &[FieldElement<Self::FieldExtension>],
+        zk_randomness: Option<&mut impl IsStarkTranscript<Self::FieldExtension>>,
+        stream_lde: bool,
+    ) -> Round2<Self::FieldExtension, Self::MerkleTreeBackendExtension>
     where
-        A: AIR<Field = Self::Field> + Send + Sync,
+        A: AIR<Field = Self::Field, FieldExtension = Self::FieldExtension> + Send + Sync,
         A::RAPChallenges: Send + Sync,
         FieldElement<Self::Field>: Serializable + Send + Sync,
+        FieldElement<Self::FieldExtension>: Serializable + Send + Sync,
     {
         // Create evaluation table
         let evaluator = ConstraintEvaluator::new(air, &round_1_result.rap_challenges);
 
-        let constraint_evaluations = evaluator.evaluate(
-            &round_1_result.lde_trace,
-            domain,
-            transition_coefficients,
-            boundary_coefficients,
-            &round_1_result.rap_challenges,
-        );
+        let constraint_evaluations = match round_1_result.lde_trace() {
+            Some(lde_trace) => evaluator.evaluate(
+                &lde_trace,
+                domain,
+                transition_coefficients,
+                boundary_coefficients,
+                &round_1_result.rap_challenges,
+            ),
+            None => evaluator.evaluate_by_cosets(
+                &round_1_result.trace_polys,
+                domain,
+                transition_coefficients,
+                boundary_coefficients,
+                &round_1_result.rap_challenges,
+            ),
+        };
+
+        let coset_offset = domain.coset_offset.clone().to_extension();
 
         // Get the composition poly H
         let composition_poly =
-            Polynomial::interpolate_offset_fft(&constraint_evaluations, &domain.coset_offset)
-                .unwrap();
+            Polynomial::interpolate_offset_fft(&constraint_evaluations, &coset_offset).unwrap();
 
-        let number_of_parts = air.composition_poly_degree_bound() / air.trace_length();
-        let composition_poly_parts = composition_poly.break_in_parts(number_of_parts);
+        let number_of_parts = air.number_of_composition_poly_parts();
+        let mut composition_poly_parts = composition_poly.break_in_parts(number_of_parts);
 
-        let lde_composition_poly_parts_evaluations: Vec<_> = composition_poly_parts
-            .iter()
-            .map(|part| {
-                evaluate_polynomial_on_lde_domain(
-                    part,
-                    domain.blowup_factor,
-                    domain.interpolation_domain_size,
-                    &domain.coset_offset,
-                )
-                .unwrap()
-            })
-            .collect();
+        if let Some(randomness) = zk_randomness {
+            let mask_coefficients: Vec<_> = (0..air.deep_composition_poly_degree_bound())
+                .map(|_| randomness.sample_field_element())
+                .collect();
+            composition_poly_parts.push(Polynomial::new(&mask_coefficients));
+        }
+
+        // In streaming mode, each leaf holds the evaluations at two consecutive points in
+        // bit-reversed order, which belong to the same block.
+        let (lde_composition_poly_evaluations, composition_poly_merkle_tree) = if stream_lde {
+            let merkle_tree = Self::commit_lde_by_cosets::<
+                Self::FieldExtension,
+                Self::MerkleTreeBackendExtension,
+            >(&composition_poly_parts, domain, |rows| {
+                rows.chunks(2).map(|pair| pair.concat()).collect()
+            });
+            (None, merkle_tree)
+        } else {
+            let lde_composition_poly_parts_evaluations: Vec<_> = composition_poly_parts
+                .iter()
+                .map(|part| {
+                    evaluate_polynomial_on_lde_domain(
+                        part,
+                        domain.blowup_factor,
+                        domain.interpolation_domain_size,
+                        &coset_offset,
+                    )
+                    .unwrap()
+                })
+                .collect();
 
-        let (composition_poly_merkle_tree, composition_poly_root) =
-            Self::commit_composition_polynomial(&lde_composition_poly_parts_evaluations);
+            let (merkle_tree, _) =
+                Self::commit_composition_polynomial(&lde_composition_poly_parts_evaluations);
+            (Some(lde_composition_poly_parts_evaluations), merkle_tree)
+        };
+        let composition_poly_root = composition_poly_merkle_tree.root.clone();
 
         Round2 {
-            lde_composition_poly_evaluations: lde_composition_poly_parts_evaluations,
+            lde_composition_poly_evaluations,
             composition_poly_parts,
+            number_of_parts,
             composition_poly_merkle_tree,
             composition_poly_root,
         }
     }
 
-    fn round_3_evaluate_polynomials_in_out_of_domain_element<A: AIR<Field = Self::Field>>(
+    fn round_3_evaluate_polynomials_in_out_of_domain_element<A>(
         air: &A,
         domain: &Domain<Self::Field>,
-        round_1_result: &Round1<Self::Field, A>,
-        round_2_result: &Round2<Self::Field>,
-        z: &FieldElement<Self::Field>,
-    ) -> Round3<Self::Field>
+        round_1_result: &Round1<A, Self::MerkleTreeBackend, Self::MerkleTreeBackendExtension>,
+        round_2_result: &Round2<Self::FieldExtension, Self::MerkleTreeBackendExtension>,
+        z: &FieldElement<Self::FieldExtension>,
+    ) -> Round3<Self::FieldExtension>
     where
+        A: AIR<Field = Self::Field, FieldExtension = Self::FieldExtension>,
         FieldElement<Self::Field>: Serializable,
+        FieldElement<Self::FieldExtension>: Serializable,
     {
-        let z_power = z.pow(round_2_result.composition_poly_parts.len());
+        let z_power = z.pow(round_2_result.number_of_parts);
 
         // Evaluate H_i in z^N for all i, where N is the number of parts the composition poly was
         // broken into.
@@ -330,7 +837,7 @@ pub trait IsStarkProver {
             &round_1_result.trace_polys,
             z,
             &air.context().transition_offsets,
-            &domain.trace_primitive_root,
+            &domain.trace_primitive_root.clone().to_extension(),
         );
 
         Round3 {
@@ -339,24 +846,27 @@ pub trait IsStarkProver {
         }
     }
 
-    fn round_4_compute_and_run_fri_on_the_deep_composition_polynomial<A: AIR<Field = Self::Field>>(
+    fn round_4_compute_and_run_fri_on_the_deep_composition_polynomial<A>(
         air: &A,
         domain: &Domain<Self::Field>,
-        round_1_result: &Round1<Self::Field, A>,
-        round_2_result: &Round2<Self::Field>,
-        round_3_result: &Round3<Self::Field>,
-        z: &FieldElement<Self::Field>,
-        transcript: &mut impl IsStarkTranscript<Self::Field>,
-    ) -> Round4<Self::Field>
+        round_1_result: &Round1<A, Self::MerkleTreeBackend, Self::MerkleTreeBackendExtension>,
+        round_2_result: &Round2<Self::FieldExtension, Self::MerkleTreeBackendExtension>,
+        round_3_result: &Round3<Self::FieldExtension>,
+        z: &FieldElement<Self::FieldExtension>,
+        transcript: &mut impl IsStarkTranscript<Self::FieldExtension>,
+    ) -> Round4<Self::Field, Self::FieldExtension, Self::Commitment>
     where
+        A: AIR<Field = Self::Field, FieldExtension = Self::FieldExtension>,
         FieldElement<Self::Field>: Serializable + Send + Sync,
+        FieldElement<Self::FieldExtension>: Serializable + Send + Sync,
     {
         let coset_offset_u64 = air.context().proof_options.coset_offset;
-        let coset_offset = FieldElement::<Self::Field>::from(coset_offset_u64);
+        let coset_offset = FieldElement::<Self::Field>::from(coset_offset_u64).to_extension();
 
         let gamma = transcript.sample_field_element();
-        let n_terms_composition_poly = round_2_result.lde_composition_poly_evaluations.len();
-        let n_terms_trace = air.context().transition_offsets.len() * air.context().trace_columns;
+        let n_terms_composition_poly = round_2_result.composition_poly_parts.len();
+        let n_terms_trace = air.context().transition_offsets.len()
+            * (air.context().trace_columns + air.number_preprocessed_columns());
 
         // <<<< Receive challenges: 𝛾, 𝛾'
         let mut deep_composition_coefficients: Vec<_> =
@@ -378,7 +888,7 @@ pub trait IsStarkProver {
             round_2_result,
             round_3_result,
             z,
-            &domain.trace_primitive_root,
+            &domain.trace_primitive_root.clone().to_extension(),
             &gammas,
             &trace_poly_coeffients,
         );
@@ -386,41 +896,59 @@ pub trait IsStarkProver {
         let domain_size = domain.lde_roots_of_unity_coset.len();
 
         // FRI commit and query phases
-        let (fri_last_value, fri_layers) = fri::commit_phase(
-            domain.root_order as usize,
-            deep_composition_poly,
-            transcript,
-            &coset_offset,
-            domain_size,
-        );
+        let number_of_fri_layers =
+            air.deep_composition_poly_degree_bound().trailing_zeros() as usize;
+        let (fri_remainder_coefficients, fri_layers) =
+            fri::commit_phase::<Self::FieldExtension, Self::MerkleTreeBackendExtension>(
+                number_of_fri_layers,
+                air.options().fri_folding_factor as usize,
+                fri::remainder_degree_bound(
+                    number_of_fri_layers,
+                    air.options().fri_max_remainder_degree,
+                ),
+                deep_composition_poly,
+                transcript,
+                &coset_offset,
+                domain_size,
+            );
 
         // grinding: generate nonce and append it to the transcript
         let security_bits = air.context().proof_options.grinding_factor;
         let mut nonce = 0;
         if security_bits > 0 {
-            nonce = grinding::generate_nonce(&transcript.state(), security_bits)
-                .expect("nonce not found");
+            nonce = grinding::generate_nonce(
+                &transcript.state(),
+                security_bits,
+                air.options().grinding_hash,
+            )
+            .expect("nonce not found");
             transcript.append_bytes(&nonce.to_be_bytes());
         }
 
         let number_of_queries = air.options().fri_number_of_queries;
         let iotas = Self::sample_query_indexes(number_of_queries, domain, transcript);
-        let query_list = fri::query_phase(&fri_layers, &iotas);
+        let (query_list, fri_layers_merkle_proofs) = fri::query_phase(&fri_layers, &iotas);
 
         let fri_layers_merkle_roots: Vec<_> = fri_layers
             .iter()
-            .map(|layer| layer.merkle_tree.root)
+            .map(|layer| layer.merkle_tree.root.clone())
             .collect();
 
         let (deep_poly_openings, deep_poly_openings_sym) =
             Self::open_deep_composition_poly(domain, round_1_result, round_2_result, &iotas);
+        let (lde_trace_merkle_proofs, lde_preprocessed_merkle_proof, lde_composition_poly_proof) =
+            Self::batch_open_trace_and_composition_polys(round_1_result, round_2_result, &iotas);
 
         Round4 {
-            fri_last_value,
+            fri_remainder_coefficients,
             fri_layers_merkle_roots,
             deep_poly_openings,
             deep_poly_openings_sym,
+            lde_trace_merkle_proofs,
+            lde_preprocessed_merkle_proof,
+            lde_composition_poly_proof,
             query_list,
+            fri_layers_merkle_proofs,
             nonce,
         }
     }
@@ -428,7 +956,7 @@ pub trait IsStarkProver {
     fn sample_query_indexes(
         number_of_queries: usize,
         domain: &Domain<Self::Field>,
-        transcript: &mut impl IsStarkTranscript<Self::Field>,
+        transcript: &mut impl IsStarkTranscript<Self::FieldExtension>,
     ) -> Vec<usize> {
         let domain_size = domain.lde_roots_of_unity_coset.len() as u64;
         (0..number_of_queries)
@@ -442,19 +970,19 @@ pub trait IsStarkProver {
     #[allow(clippy::too_many_arguments)]
     fn compute_deep_composition_poly<A>(
         air: &A,
-        trace_polys: &[Polynomial<FieldElement<Self::Field>>],
-        round_2_result: &Round2<Self::Field>,
-        round_3_result: &Round3<Self::Field>,
-        z: &FieldElement<Self::Field>,
-        primitive_root: &FieldElement<Self::Field>,
-        composition_poly_gammas: &[FieldElement<Self::Field>],
-        trace_terms_gammas: &[FieldElement<Self::Field>],
-    ) -> Polynomial<FieldElement<Self::Field>>
+        trace_polys: &[Polynomial<FieldElement<Self::FieldExtension>>],
+        round_2_result: &Round2<Self::FieldExtension, Self::MerkleTreeBackendExtension>,
+        round_3_result: &Round3<Self::FieldExtension>,
+        z: &FieldElement<Self::FieldExtension>,
+        primitive_root: &FieldElement<Self::FieldExtension>,
+        composition_poly_gammas: &[FieldElement<Self::FieldExtension>],
+        trace_terms_gammas: &[FieldElement<Self::FieldExtension>],
+    ) -> Polynomial<FieldElement<Self::FieldExtension>>
     where
-        A: AIR<Field = Self::Field>,
-        FieldElement<Self::Field>: Serializable + Send + Sync,
+        A: AIR<Field = Self::Field, FieldExtension = Self::FieldExtension>,
+        FieldElement<Self::FieldExtension>: Serializable + Send + Sync,
     {
-        let z_power = z.pow(round_2_result.composition_poly_parts.len());
+        let z_power = z.pow(round_2_result.number_of_parts);
 
         // ∑ᵢ 𝛾ᵢ ( Hᵢ − Hᵢ(z^N) ) / ( X − z^N )
         let mut h_terms = Polynomial::zero();
@@ -520,16 +1048,19 @@ pub trait IsStarkProver {
     }
 
     fn compute_trace_term(
-        trace_terms: &Polynomial<FieldElement<Self::Field>>,
-        (i, t_j): (usize, &Polynomial<FieldElement<Self::Field>>),
+        trace_terms: &Polynomial<FieldElement<Self::FieldExtension>>,
+        (i, t_j): (usize, &Polynomial<FieldElement<Self::FieldExtension>>),
         trace_frame_length: usize,
-        trace_terms_gammas: &[FieldElement<Self::Field>],
-        trace_frame_evaluations: &[Vec<FieldElement<Self::Field>>],
+        trace_terms_gammas: &[FieldElement<Self::FieldExtension>],
+        trace_frame_evaluations: &[Vec<FieldElement<Self::FieldExtension>>],
         transition_offsets: &[usize],
-        (z, primitive_root): (&FieldElement<Self::Field>, &FieldElement<Self::Field>),
-    ) -> Polynomial<FieldElement<Self::Field>>
+        (z, primitive_root): (
+            &FieldElement<Self::FieldExtension>,
+            &FieldElement<Self::FieldExtension>,
+        ),
+    ) -> Polynomial<FieldElement<Self::FieldExtension>>
     where
-        FieldElement<Self::Field>: Serializable + Send + Sync,
+        FieldElement<Self::FieldExtension>: Serializable + Send + Sync,
     {
         let i_times_trace_frame_evaluation = i * trace_frame_length;
         let iter_trace_gammas = trace_terms_gammas
@@ -556,133 +1087,254 @@ pub trait IsStarkProver {
     }
 
     fn open_composition_poly(
-        composition_poly_merkle_tree: &BatchedMerkleTree<Self::Field>,
-        lde_composition_poly_evaluations: &[Vec<FieldElement<Self::Field>>],
+        domain: &Domain<Self::Field>,
+        round_2_result: &Round2<Self::FieldExtension, Self::MerkleTreeBackendExtension>,
         index: usize,
-    ) -> (Proof<Commitment>, Vec<FieldElement<Self::Field>>)
-    where
-        FieldElement<Self::Field>: Serializable,
-    {
-        let proof = composition_poly_merkle_tree
-            .get_proof_by_pos(index)
-            .unwrap();
-
-        let lde_composition_poly_parts_evaluation: Vec<_> = lde_composition_poly_evaluations
-            .iter()
-            .flat_map(|part| {
-                vec![
-                    part[reverse_index(index * 2, part.len() as u64)].clone(),
-                    part[reverse_index(index * 2 + 1, part.len() as u64)].clone(),
-                ]
-            })
-            .collect();
-
-        (proof, lde_composition_poly_parts_evaluation)
+    ) -> Vec<FieldElement<Self::FieldExtension>> {
+        let domain_size = domain.lde_roots_of_unity_coset.len() as u64;
+        let positions = [
+            reverse_index(index * 2, domain_size),
+            reverse_index(index * 2 + 1, domain_size),
+        ];
+        match &round_2_result.lde_composition_poly_evaluations {
+            Some(lde_composition_poly_evaluations) => lde_composition_poly_evaluations
+                .iter()
+                .flat_map(|part| positions.map(|position| part[position].clone()))
+                .collect(),
+            None => round_2_result
+                .composition_poly_parts
+                .iter()
+                .flat_map(|part| {
+                    positions.map(|position| {
+                        part.evaluate(
+                            &domain.lde_roots_of_unity_coset[position]
+                                .clone()
+                                .to_extension(),
+                        )
+                    })
+                })
+                .collect(),
+        }
     }
 
-    fn open_trace_polys(
+    /// Opens the committed LDE of a trace table, over either the field or its extension,
+    /// at the given position.
+    fn open_trace_polys<T, B>(
         domain: &Domain<Self::Field>,
-        lde_trace_merkle_trees: &[BatchedMerkleTree<Self::Field>],
-        lde_trace: &TraceTable<Self::Field>,
+        commitment_data: &Round1CommitmentData<T, B>,
         index: usize,
-    ) -> (Vec<Proof<Commitment>>, Vec<FieldElement<Self::Field>>)
+    ) -> Vec<FieldElement<T>>
     where
-        FieldElement<Self::Field>: Serializable,
+        T: IsField,
+        Self::Field: IsSubFieldOf<T>,
+        B: IsStarkMerkleTreeBackend<T>,
     {
         let domain_size = domain.lde_roots_of_unity_coset.len();
-        let lde_trace_evaluations = lde_trace
-            .get_row(reverse_index(index, domain_size as u64))
-            .to_vec();
-
-        // Trace polynomials openings
-        #[cfg(feature = "parallel")]
-        let merkle_trees_iter = lde_trace_merkle_trees.par_iter();
-        #[cfg(not(feature = "parallel"))]
-        let merkle_trees_iter = lde_trace_merkle_trees.iter();
-
-        let lde_trace_merkle_proofs: Vec<Proof<[u8; 32]>> = merkle_trees_iter
-            .map(|tree| tree.get_proof_by_pos(index).unwrap())
-            .collect();
+        let position = reverse_index(index, domain_size as u64);
+        match &commitment_data.lde_trace {
+            LdeTrace::Evaluations(lde_trace) => lde_trace.get_row(position).to_vec(),
+            LdeTrace::Polynomials(trace_polys) => {
+                let point = domain.lde_roots_of_unity_coset[position]
+                    .clone()
+                    .to_extension::<T>();
+                trace_polys
+                    .iter()
+                    .map(|poly| poly.evaluate(&point))
+                    .collect()
+            }
+        }
+    }
 
-        (lde_trace_merkle_proofs, lde_trace_evaluations)
+    /// Opens the main, auxiliary and preprocessed traces at the given position.
+    #[allow(clippy::type_complexity)]
+    fn open_committed_traces<A>(
+        domain: &Domain<Self::Field>,
+        round_1_result: &Round1<A, Self::MerkleTreeBackend, Self::MerkleTreeBackendExtension>,
+        index: usize,
+    ) -> (
+        Vec<FieldElement<Self::Field>>,
+        Vec<FieldElement<Self::FieldExtension>>,
+        Vec<FieldElement<Self::Field>>,
+    )
+    where
+        A: AIR<Field = Self::Field, FieldExtension = Self::FieldExtension>,
+    {
+        let main_evaluations = Self::open_trace_polys::<Self::Field, Self::MerkleTreeBackend>(
+            domain,
+            &round_1_result.main,
+            index,
+        );
+        let aux_evaluations = round_1_result
+            .aux
+            .as_ref()
+            .map(|aux| {
+                Self::open_trace_polys::<Self::FieldExtension, Self::MerkleTreeBackendExtension>(
+                    domain, aux, index,
+                )
+            })
+            .unwrap_or_default();
+        let preprocessed_evaluations = round_1_result
+            .preprocessed
+            .as_ref()
+            .map(|preprocessed| {
+                Self::open_trace_polys::<Self::Field, Self::MerkleTreeBackend>(
+                    domain,
+                    preprocessed,
+                    index,
+                )
+            })
+            .unwrap_or_default();
+        (main_evaluations, aux_evaluations, preprocessed_evaluations)
     }
 
     /// Open the deep composition polynomial on a list of indexes
     /// and their symmetric elements.
-    fn open_deep_composition_poly<A: AIR<Field = Self::Field>>(
+    #[allow(clippy::type_complexity)]
+    fn open_deep_composition_poly<A>(
         domain: &Domain<Self::Field>,
-        round_1_result: &Round1<Self::Field, A>,
-        round_2_result: &Round2<Self::Field>,
+        round_1_result: &Round1<A, Self::MerkleTreeBackend, Self::MerkleTreeBackendExtension>,
+        round_2_result: &Round2<Self::FieldExtension, Self::MerkleTreeBackendExtension>,
         indexes_to_open: &[usize],
     ) -> (
-        DeepPolynomialOpenings<Self::Field>,
-        DeepPolynomialOpenings<Self::Field>,
+        DeepPolynomialOpenings<Self::Field, Self::FieldExtension>,
+        DeepPolynomialOpenings<Self::Field, Self::FieldExtension>,
     )
     where
-        FieldElement<Self::Field>: Serializable,
+        A: AIR<Field = Self::Field, FieldExtension = Self::FieldExtension>,
     {
         let mut openings = Vec::new();
         let mut openings_symmetric = Vec::new();
 
         for index in indexes_to_open.iter() {
-            let (lde_trace_merkle_proofs, lde_trace_evaluations) = Self::open_trace_polys(
-                domain,
-                &round_1_result.lde_trace_merkle_trees,
-                &round_1_result.lde_trace,
-                index * 2,
-            );
+            let (lde_trace_evaluations, lde_aux_trace_evaluations, lde_preprocessed_evaluations) =
+                Self::open_committed_traces(domain, round_1_result, index * 2);
 
-            let (lde_trace_sym_merkle_proofs, lde_trace_sym_evaluations) = Self::open_trace_polys(
-                domain,
-                &round_1_result.lde_trace_merkle_trees,
-                &round_1_result.lde_trace,
-                index * 2 + 1,
-            );
+            let (
+                lde_trace_sym_evaluations,
+                lde_aux_trace_sym_evaluations,
+                lde_preprocessed_sym_evaluations,
+            ) = Self::open_committed_traces(domain, round_1_result, index * 2 + 1);
 
-            let (lde_composition_poly_proof, lde_composition_poly_parts_evaluation) =
-                Self::open_composition_poly(
-                    &round_2_result.composition_poly_merkle_tree,
-                    &round_2_result.lde_composition_poly_evaluations,
-                    *index,
-                );
+            let lde_composition_poly_parts_evaluation =
+                Self::open_composition_poly(domain, round_2_result, *index);
 
             openings.push(DeepPolynomialOpening {
-                lde_composition_poly_proof: lde_composition_poly_proof.clone(),
                 lde_composition_poly_parts_evaluation: lde_composition_poly_parts_evaluation
                     .clone()
                     .into_iter()
                     .step_by(2)
                     .collect(),
-                lde_trace_merkle_proofs,
                 lde_trace_evaluations,
+                lde_aux_trace_evaluations,
+                lde_preprocessed_evaluations,
             });
 
             openings_symmetric.push(DeepPolynomialOpening {
-                lde_composition_poly_proof,
                 lde_composition_poly_parts_evaluation: lde_composition_poly_parts_evaluation
                     .into_iter()
                     .skip(1)
                     .step_by(2)
                     .collect(),
-                lde_trace_merkle_proofs: lde_trace_sym_merkle_proofs,
                 lde_trace_evaluations: lde_trace_sym_evaluations,
+                lde_aux_trace_evaluations: lde_aux_trace_sym_evaluations,
+                lde_preprocessed_evaluations: lde_preprocessed_sym_evaluations,
             });
         }
 
         (openings, openings_symmetric)
     }
 
-    // FIXME remove unwrap() calls and return errors
+    /// Returns the Merkle proofs of the openings of `open_deep_composition_poly`, batched
+    /// over all the indexes: one for the main and the auxiliary trace tables, one for the
+    /// preprocessed columns if there are any, which are opened at the leaves `2𝜄` and
+    /// `2𝜄 + 1`, and one for the composition polynomial, whose leaf `𝜄` holds both
+    /// symmetric evaluations.
+    #[allow(clippy::type_complexity)]
+    fn batch_open_trace_and_composition_polys<A>(
+        round_1_result: &Round1<A, Self::MerkleTreeBackend, Self::MerkleTreeBackendExtension>,
+        round_2_result: &Round2<Self::FieldExtension, Self::MerkleTreeBackendExtension>,
+        indexes_to_open: &[usize],
+    ) -> (
+        Vec<BatchProof<Self::Commitment>>,
+        Option<BatchProof<Self::Commitment>>,
+        BatchProof<Self::Commitment>,
+    )
+    where
+        A: AIR<Field = Self::Field, FieldExtension = Self::FieldExtension>,
+    {
+        let trace_positions: Vec<_> = indexes_to_open
+            .iter()
+            .flat_map(|index| [index * 2, index * 2 + 1])
+            .collect();
+
+        let mut lde_trace_merkle_proofs = vec![round_1_result
+            .main
+            .lde_trace_merkle_tree
+            .get_batch_proof(&trace_positions)
+            .unwrap()];
+        if let Some(aux) = &round_1_result.aux {
+            lde_trace_merkle_proofs.push(
+                aux.lde_trace_merkle_tree
+                    .get_batch_proof(&trace_positions)
+                    .unwrap(),
+            );
+        }
+
+        let lde_preprocessed_merkle_proof = round_1_result.preprocessed.as_ref().map(|data| {
+            data.lde_trace_merkle_tree
+                .get_batch_proof(&trace_positions)
+                .unwrap()
+        });
+
+        let lde_composition_poly_proof = round_2_result
+            .composition_poly_merkle_tree
+            .get_batch_proof(indexes_to_open)
+            .unwrap();
+
+        (
+            lde_trace_merkle_proofs,
+            lde_preprocessed_merkle_proof,
+            lde_composition_poly_proof,
+        )
+    }
+
+    #[allow(clippy::type_complexity)]
     fn prove<A>(
         main_trace: &TraceTable<Self::Field>,
         pub_inputs: &A::PublicInputs,
         proof_options: &ProofOptions,
-        mut transcript: impl IsStarkTranscript<Self::Field>,
-    ) -> Result<StarkProof<Self::Field>, ProvingError>
+        transcript: impl IsStarkTranscript<Self::FieldExtension> + Clone,
+    ) -> Result<StarkProof<Self::Field, Self::FieldExtension, Self::Commitment>, ProvingError>
     where
-        A: AIR<Field = Self::Field> + Send + Sync,
+        A: AIR<Field = Self::Field, FieldExtension = Self::FieldExtension> + Send + Sync,
         A::RAPChallenges: Send + Sync,
         FieldElement<Self::Field>: Serializable + Send + Sync,
+        FieldElement<Self::FieldExtension>: Serializable + Send + Sync,
+    {
+        Self::prove_with_prover_options::<A>(
+            main_trace,
+            pub_inputs,
+            proof_options,
+            &ProverOptions::default(),
+            transcript,
+        )
+    }
+
+    /// Same as `prove`, with settings of the prover that do not change the proof.
+    // FIXME remove unwrap() calls and return errors
+    #[allow(clippy::type_complexity)]
+    fn prove_with_prover_options<A>(
+        main_trace: &TraceTable<Self::Field>,
+        pub_inputs: &A::PublicInputs,
+        proof_options: &ProofOptions,
+        prover_options: &ProverOptions,
+        mut transcript: impl IsStarkTranscript<Self::FieldExtension> + Clone,
+    ) -> Result<StarkProof<Self::Field, Self::FieldExtension, Self::Commitment>, ProvingError>
+    where
+        A: AIR<Field = Self::Field, FieldExtension = Self::FieldExtension> + Send + Sync,
+        A::RAPChallenges: Send + Sync,
+        FieldElement<Self::Field>: Serializable + Send + Sync,
+        FieldElement<Self::FieldExtension>: Serializable + Send + Sync,
     {
         info!("Started proof generation...");
         #[cfg(feature = "instruments")]
@@ -691,8 +1343,25 @@ pub trait IsStarkProver {
         let timer0 = Instant::now();
 
         let air = A::new(main_trace.n_rows(), pub_inputs, proof_options);
+        Self::check_blowup_factor(&air)?;
+        Self::check_periodic_columns(&air)?;
+        Self::check_fri_folding_factor(proof_options)?;
+        #[cfg(debug_assertions)]
+        validate_symbolic_transitions(&air);
         let domain = Domain::new(&air);
 
+        // In zero-knowledge mode the random values that blind the polynomials over the
+        // extension are sampled from a copy of the transcript seeded with secret randomness,
+        // which the verifier cannot reproduce.
+        let mut zk_randomness = if proof_options.zero_knowledge {
+            Self::check_zero_knowledge_parameters(&air)?;
+            let mut randomness = transcript.clone();
+            randomness.append_bytes(&rand::random::<[u8; 32]>());
+            Some(randomness)
+        } else {
+            None
+        };
+
         #[cfg(feature = "instruments")]
         let elapsed0 = timer0.elapsed();
         #[cfg(feature = "instruments")]
@@ -712,6 +1381,8 @@ pub trait IsStarkProver {
             main_trace,
             &domain,
             &mut transcript,
+            zk_randomness.as_mut(),
+            prover_options.stream_lde,
         )?;
 
         #[cfg(debug_assertions)]
@@ -760,10 +1431,12 @@ pub trait IsStarkProver {
             &round_1_result,
             &transition_coefficients,
             &boundary_coefficients,
+            zk_randomness.as_mut(),
+            prover_options.stream_lde,
         );
 
         // >>>> Send commitments: [H₁], [H₂]
-        transcript.append_bytes(&round_2_result.composition_poly_root);
+        transcript.append_bytes(round_2_result.composition_poly_root.as_ref());
 
         #[cfg(feature = "instruments")]
         let elapsed2 = timer2.elapsed();
@@ -863,7 +1536,7 @@ pub trait IsStarkProver {
 
         Ok(StarkProof {
             // [tⱼ]
-            lde_trace_merkle_roots: round_1_result.lde_trace_merkle_roots,
+            lde_trace_merkle_roots: round_1_result.lde_trace_merkle_roots(),
             // tⱼ(zgᵏ)
             trace_ood_frame_evaluations,
             // [H₁] and [H₂]
@@ -874,13 +1547,19 @@ pub trait IsStarkProver {
             // [pₖ]
             fri_layers_merkle_roots: round_4_result.fri_layers_merkle_roots,
             // pₙ
-            fri_last_value: round_4_result.fri_last_value,
+            fri_remainder_coefficients: round_4_result.fri_remainder_coefficients,
             // Open(p₀(D₀), 𝜐ₛ), Open(pₖ(Dₖ), −𝜐ₛ^(2ᵏ))
             query_list: round_4_result.query_list,
+            // Batched Merkle proofs of the FRI layer openings
+            fri_layers_merkle_proofs: round_4_result.fri_layers_merkle_proofs,
             // Open(H₁(D_LDE, 𝜐₀), Open(H₂(D_LDE, 𝜐₀), Open(tⱼ(D_LDE), 𝜐₀)
             deep_poly_openings: round_4_result.deep_poly_openings,
             // Open(H₁(D_LDE, 𝜐₀), Open(H₂(D_LDE, 𝜐₀), Open(tⱼ(D_LDE), 𝜐₀)
             deep_poly_openings_sym: round_4_result.deep_poly_openings_sym,
+            // Batched Merkle proofs of the trace and composition polynomial openings
+            lde_trace_merkle_proofs: round_4_result.lde_trace_merkle_proofs,
+            lde_preprocessed_merkle_proof: round_4_result.lde_preprocessed_merkle_proof,
+            lde_composition_poly_proof: round_4_result.lde_composition_poly_proof,
             // nonce obtained from grinding
             nonce: round_4_result.nonce,
 
@@ -904,6 +1583,7 @@ mod tests {
             fibonacci_2_cols_shifted::{self, Fibonacci2ColsShifted},
             simple_fibonacci::{self, FibonacciPublicInputs},
         },
+        grinding::GrindingHash,
         proof::options::ProofOptions,
         transcript::StoneProverTranscript,
         verifier::{Challenges, IsStarkVerifier, Verifier},
@@ -936,6 +1616,10 @@ mod tests {
             fri_number_of_queries: 1,
             coset_offset,
             grinding_factor,
+            zero_knowledge: false,
+            fri_folding_factor: 2,
+            fri_max_remainder_degree: 0,
+            grinding_hash: GrindingHash::Keccak256,
         };
 
         let domain = Domain::new(&simple_fibonacci::FibonacciAIR::new(
@@ -1051,7 +1735,7 @@ mod tests {
     }
 
     fn stone_compatibility_case_1_challenges(
-    ) -> Challenges<Stark252PrimeField, Fibonacci2ColsShifted<Stark252PrimeField>> {
+    ) -> Challenges<Fibonacci2ColsShifted<Stark252PrimeField>> {
         let (proof, public_inputs, options, seed) = proof_parts_stone_compatibility_case_1();
 
         let air = Fibonacci2ColsShifted::new(proof.trace_length, &public_inputs, &options);
@@ -1060,6 +1744,7 @@ mod tests {
             &air,
             &proof,
             &domain,
+            None,
             &mut StoneProverTranscript::new(&seed),
         )
     }
@@ -1225,7 +1910,7 @@ mod tests {
         let proof = stone_compatibility_case_1_proof();
 
         assert_eq!(
-            proof.fri_last_value,
+            proof.fri_remainder_coefficients[0],
             FieldElement::from_hex_unchecked(
                 "43fedf9f9e3d1469309862065c7d7ca0e7e9ce451906e9c01553056f695aec9"
             )
@@ -1281,19 +1966,19 @@ mod tests {
 
         // Trace poly auth path level 1
         assert_eq!(
-            proof.deep_poly_openings[0].lde_trace_merkle_proofs[0].merkle_path[1].to_vec(),
+            proof.lde_trace_merkle_proofs[0].auth_nodes[0].to_vec(),
             decode_hex("91b0c0b24b9d00067b0efab50832b76cf97192091624d42b86740666c5d369e6").unwrap()
         );
 
         // Trace poly auth path level 2
         assert_eq!(
-            proof.deep_poly_openings[0].lde_trace_merkle_proofs[0].merkle_path[2].to_vec(),
+            proof.lde_trace_merkle_proofs[0].auth_nodes[1].to_vec(),
             decode_hex("993b044db22444c0c0ebf1095b9a51faeb001c9b4dea36abe905f7162620dbbd").unwrap()
         );
 
         // Trace poly auth path level 3
         assert_eq!(
-            proof.deep_poly_openings[0].lde_trace_merkle_proofs[0].merkle_path[3].to_vec(),
+            proof.lde_trace_merkle_proofs[0].auth_nodes[2].to_vec(),
             decode_hex("5017abeca33fa82576b5c5c2c61792693b48c9d4414a407eef66b6029dae07ea").unwrap()
         );
     }
@@ -1324,28 +2009,19 @@ mod tests {
 
         // Composition poly auth path level 0
         assert_eq!(
-            proof.deep_poly_openings[0]
-                .lde_composition_poly_proof
-                .merkle_path[0]
-                .to_vec(),
+            proof.lde_composition_poly_proof.auth_nodes[0].to_vec(),
             decode_hex("403b75a122eaf90a298e5d3db2cc7ca096db478078122379a6e3616e72da7546").unwrap()
         );
 
         // Composition poly auth path level 1
         assert_eq!(
-            proof.deep_poly_openings[0]
-                .lde_composition_poly_proof
-                .merkle_path[1]
-                .to_vec(),
+            proof.lde_composition_poly_proof.auth_nodes[1].to_vec(),
             decode_hex("07950888c0355c204a1e83ecbee77a0a6a89f93d41cc2be6b39ddd1e727cc965").unwrap()
         );
 
         // Composition poly auth path level 2
         assert_eq!(
-            proof.deep_poly_openings[0]
-                .lde_composition_poly_proof
-                .merkle_path[2]
-                .to_vec(),
+            proof.lde_composition_poly_proof.auth_nodes[2].to_vec(),
             decode_hex("58befe2c5de74cc5a002aa82ea219c5b242e761b45fd266eb95521e9f53f44eb").unwrap()
         );
     }
@@ -1358,12 +2034,7 @@ mod tests {
 
         assert_eq!(proof.query_list[0].layers_evaluations_sym.len(), 1);
 
-        assert_eq!(
-            proof.query_list[0].layers_auth_paths_sym[0]
-                .merkle_path
-                .len(),
-            2
-        );
+        assert_eq!(proof.fri_layers_merkle_proofs[0].auth_nodes.len(), 2);
     }
 
     #[test]
@@ -1384,13 +2055,13 @@ mod tests {
 
         // FRI layer 1 auth path level 0
         assert_eq!(
-            proof.query_list[0].layers_auth_paths_sym[0].merkle_path[0].to_vec(),
+            proof.fri_layers_merkle_proofs[0].auth_nodes[0].to_vec(),
             decode_hex("0683622478e9e93cc2d18754872f043619f030b494d7ec8e003b1cbafe83b67b").unwrap()
         );
 
         // FRI layer 1 auth path level 1
         assert_eq!(
-            proof.query_list[0].layers_auth_paths_sym[0].merkle_path[1].to_vec(),
+            proof.fri_layers_merkle_proofs[0].auth_nodes[1].to_vec(),
             decode_hex("7985d945abe659a7502698051ec739508ed6bab594984c7f25e095a0a57a2e55").unwrap()
         );
     }
@@ -1434,7 +2105,7 @@ mod tests {
     }
 
     fn stone_compatibility_case_2_challenges(
-    ) -> Challenges<Stark252PrimeField, Fibonacci2ColsShifted<Stark252PrimeField>> {
+    ) -> Challenges<Fibonacci2ColsShifted<Stark252PrimeField>> {
         let (proof, public_inputs, options, seed) = proof_parts_stone_compatibility_case_2();
 
         let air = Fibonacci2ColsShifted::new(proof.trace_length, &public_inputs, &options);
@@ -1443,6 +2114,7 @@ mod tests {
             &air,
             &proof,
             &domain,
+            None,
             &mut StoneProverTranscript::new(&seed),
         )
     }
@@ -1481,7 +2153,7 @@ mod tests {
 
         // FRI layer 7 auth path level 5
         assert_eq!(
-            proof.query_list[0].layers_auth_paths_sym[7].merkle_path[5].to_vec(),
+            proof.fri_layers_merkle_proofs[7].auth_nodes[5].to_vec(),
             decode_hex("f12f159b548ca2c571a270870d43e7ec2ead78b3e93b635738c31eb9bcda3dda").unwrap()
         );
     }
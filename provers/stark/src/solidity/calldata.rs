@@ -0,0 +1,169 @@
+use lambdaworks_math::{
+    field::fields::fft_friendly::stark_252_prime_field::Stark252PrimeField, traits::ByteConversion,
+};
+use sha3::{Digest, Keccak256};
+
+use crate::{
+    config::Commitment,
+    proof::{
+        options::ProofOptions,
+        stark::{DeepPolynomialOpening, StarkProof},
+    },
+    traits::AIR,
+};
+
+use super::{ProofShape, SolidityVerifierError, FE};
+
+const VERIFY_SIGNATURE: &str = "verify(uint256[],uint256[],bytes)";
+
+type Word = [u8; 32];
+
+fn element_word(element: &FE) -> Word {
+    let mut word = [0; 32];
+    word.copy_from_slice(&element.representative().to_bytes_be());
+    word
+}
+
+fn integer_word(value: u64) -> Word {
+    let mut word = [0; 32];
+    word[24..].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
+/// Encodes a call to `verify` of the contract generated by `generate_verifier` for the AIR
+/// `A`, the public inputs `pub_inputs` and the options `proof_options`, with the proof
+/// `proof` and the bytes `transcript_seed` the transcript of the proof was started with.
+///
+/// Field elements are encoded by their canonical representatives and commitments as they
+/// are. The proof is encoded in the order the contract reads it: the trace length, the
+/// roots of the trace, the out of domain frame by rows, the root and the out of domain
+/// values of the composition polynomial, the FRI roots, the remainder and the nonce. Then
+/// the openings of the queries: the symmetric values of the FRI layers and the trace and
+/// composition values at each query point and at its symmetric. Last, the batched Merkle
+/// proofs, each as its number of nodes followed by them, in the order of the main trace,
+/// the auxiliary trace, the composition polynomial and the FRI layers.
+pub fn encode_calldata<A>(
+    proof: &StarkProof<Stark252PrimeField, Stark252PrimeField, Commitment>,
+    pub_inputs: &A::PublicInputs,
+    proof_options: &ProofOptions,
+    transcript_seed: &[u8],
+) -> Result<Vec<u8>, SolidityVerifierError>
+where
+    A: AIR<Field = Stark252PrimeField, FieldExtension = Stark252PrimeField>,
+{
+    let air = A::new(proof.trace_length, pub_inputs, proof_options);
+    let shape = ProofShape::new(&air)?;
+    let proof_words = encode_proof(proof, &shape)?;
+    let boundary_values: Vec<_> = shape
+        .boundary_constraints
+        .constraints
+        .iter()
+        .map(|constraint| element_word(&constraint.value))
+        .collect();
+
+    // Head of the three dynamic arguments, followed by their contents
+    let mut calldata = Keccak256::digest(VERIFY_SIGNATURE.as_bytes())[..4].to_vec();
+    let seed_offset = 32 * (5 + proof_words.len() + boundary_values.len());
+    for offset in [0x60, 32 * (4 + proof_words.len()), seed_offset] {
+        calldata.extend_from_slice(&integer_word(offset as u64));
+    }
+    for words in [&proof_words, &boundary_values] {
+        calldata.extend_from_slice(&integer_word(words.len() as u64));
+        for word in words {
+            calldata.extend_from_slice(word);
+        }
+    }
+    calldata.extend_from_slice(&integer_word(transcript_seed.len() as u64));
+    calldata.extend_from_slice(transcript_seed);
+    calldata.resize(calldata.len() + (32 - transcript_seed.len() % 32) % 32, 0);
+    Ok(calldata)
+}
+
+fn encode_proof(
+    proof: &StarkProof<Stark252PrimeField, Stark252PrimeField, Commitment>,
+    shape: &ProofShape,
+) -> Result<Vec<Word>, SolidityVerifierError> {
+    let malformed = |part: &str| {
+        Err(SolidityVerifierError::MalformedProof(format!(
+            "{part} do not have the shape of the AIR"
+        )))
+    };
+    let number_of_trace_roots = 1 + usize::from(shape.number_of_auxiliary_columns > 0);
+    let frame = &proof.trace_ood_frame_evaluations;
+    let has_shape = |openings: &[DeepPolynomialOpening<Stark252PrimeField>]| {
+        openings.len() == shape.number_of_queries
+            && openings.iter().all(|opening| {
+                opening.lde_trace_evaluations.len() == shape.number_of_main_columns
+                    && opening.lde_aux_trace_evaluations.len() == shape.number_of_auxiliary_columns
+                    && opening.lde_composition_poly_parts_evaluation.len() == shape.number_of_parts
+                    && opening.lde_preprocessed_evaluations.is_empty()
+            })
+    };
+    if proof.lde_trace_merkle_roots.len() != number_of_trace_roots
+        || proof.lde_trace_merkle_proofs.len() != number_of_trace_roots
+        || proof.lde_preprocessed_merkle_proof.is_some()
+    {
+        return malformed("the trace commitments");
+    }
+    if frame.n_rows() != shape.transition_offsets.len()
+        || frame.n_cols() != shape.number_of_columns()
+        || proof.composition_poly_parts_ood_evaluation.len() != shape.number_of_parts
+    {
+        return malformed("the out of domain values");
+    }
+    if proof.fri_layers_merkle_roots.len() != shape.number_of_fri_layers
+        || proof.fri_layers_merkle_proofs.len() != shape.number_of_fri_layers
+        || proof.fri_remainder_coefficients.len() != shape.remainder_length
+        || proof.query_list.len() != shape.number_of_queries
+        || proof
+            .query_list
+            .iter()
+            .any(|query| query.layers_evaluations_sym.len() != shape.number_of_fri_layers)
+    {
+        return malformed("the FRI layers");
+    }
+    if !has_shape(&proof.deep_poly_openings) || !has_shape(&proof.deep_poly_openings_sym) {
+        return malformed("the openings");
+    }
+
+    let mut words = vec![integer_word(proof.trace_length as u64)];
+    words.extend(proof.lde_trace_merkle_roots.iter().copied());
+    for row in 0..frame.n_rows() {
+        words.extend(frame.get_row(row).iter().map(element_word));
+    }
+    words.push(proof.composition_poly_root);
+    words.extend(
+        proof
+            .composition_poly_parts_ood_evaluation
+            .iter()
+            .map(element_word),
+    );
+    words.extend(proof.fri_layers_merkle_roots.iter().copied());
+    words.extend(proof.fri_remainder_coefficients.iter().map(element_word));
+    words.push(integer_word(proof.nonce));
+    for query in &proof.query_list {
+        words.extend(query.layers_evaluations_sym.iter().map(element_word));
+    }
+    for openings in [&proof.deep_poly_openings, &proof.deep_poly_openings_sym] {
+        for opening in openings {
+            words.extend(
+                opening
+                    .lde_trace_evaluations
+                    .iter()
+                    .chain(&opening.lde_aux_trace_evaluations)
+                    .chain(&opening.lde_composition_poly_parts_evaluation)
+                    .map(element_word),
+            );
+        }
+    }
+    for merkle_proof in proof
+        .lde_trace_merkle_proofs
+        .iter()
+        .chain([&proof.lde_composition_poly_proof])
+        .chain(&proof.fri_layers_merkle_proofs)
+    {
+        words.push(integer_word(merkle_proof.auth_nodes.len() as u64));
+        words.extend(merkle_proof.auth_nodes.iter().copied());
+    }
+    Ok(words)
+}
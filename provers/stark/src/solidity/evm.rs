@@ -0,0 +1,73 @@
+//! Compilation of the generated verifiers with `solc` and an EVM to run them on.
+//!
+//! The tests that use them need `solc` in the `PATH`, so they are ignored by default and
+//! run with `make test-solidity`.
+
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use revm::{
+    db::{CacheDB, EmptyDB},
+    primitives::{AccountInfo, Address, Bytecode, ExecutionResult, Output, TxKind},
+    Evm,
+};
+
+/// Compiles the Solidity contract `contract`, named `name`, with `solc` and returns its
+/// runtime bytecode.
+pub(crate) fn compile(contract: &str, name: &str) -> Vec<u8> {
+    let mut solc = Command::new("solc")
+        .args(["--optimize", "--combined-json", "bin-runtime", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("solc should be installed to run the tests of the Solidity verifiers");
+    solc.stdin
+        .take()
+        .unwrap()
+        .write_all(contract.as_bytes())
+        .unwrap();
+    let output = solc.wait_with_output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let bytecode = json["contracts"][format!("<stdin>:{name}")]["bin-runtime"]
+        .as_str()
+        .unwrap();
+    hex::decode(bytecode).unwrap()
+}
+
+/// Calls a contract with the code `code` with `calldata`, and returns its output, or the
+/// reason it did not return.
+pub(crate) fn call(code: Vec<u8>, calldata: Vec<u8>) -> Result<Vec<u8>, String> {
+    let address = Address::repeat_byte(0x11);
+    let mut db = CacheDB::new(EmptyDB::default());
+    db.insert_account_info(
+        address,
+        AccountInfo {
+            code: Some(Bytecode::new_raw(code.into())),
+            ..Default::default()
+        },
+    );
+    let mut evm = Evm::builder()
+        .with_db(db)
+        .modify_tx_env(|tx| {
+            tx.caller = Address::repeat_byte(0x22);
+            tx.transact_to = TxKind::Call(address);
+            tx.data = calldata.into();
+        })
+        .build();
+    match evm.transact().unwrap().result {
+        ExecutionResult::Success {
+            output: Output::Call(output),
+            ..
+        } => Ok(output.to_vec()),
+        result => Err(format!("{result:?}")),
+    }
+}
@@ -0,0 +1,439 @@
+use std::fmt::Write;
+
+use itertools::Itertools;
+use lambdaworks_math::{
+    field::{
+        fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
+        traits::{IsFFTField, IsPrimeField},
+    },
+    unsigned_integer::element::U256,
+};
+
+use crate::{
+    constraints::{
+        dag::Node,
+        expression::{ColumnKind, Variable},
+    },
+    proof::options::ProofOptions,
+    traits::AIR,
+    transcript::StoneProverTranscript,
+};
+
+use super::{ProofShape, SolidityVerifierError, FE};
+
+const TEMPLATE: &str = include_str!("template.sol");
+
+/// Prefix of the hash of the proof of work. See `grinding::is_valid_nonce`.
+const GRINDING_PREFIX: &str = "0x0123456789abcded";
+
+/// Addresses of the memory regions of the generated verifier. Regions are allocated one
+/// after the other, from the first free address of the Solidity memory layout.
+struct MemoryLayout {
+    end: usize,
+    regions: Vec<(&'static str, usize)>,
+}
+
+impl MemoryLayout {
+    fn new() -> Self {
+        Self {
+            end: 0x80,
+            regions: Vec::new(),
+        }
+    }
+
+    fn allocate(&mut self, name: &'static str, words: usize) {
+        self.regions.push((name, self.end));
+        self.end += 32 * words;
+    }
+
+    fn address(&self, name: &str) -> usize {
+        self.regions
+            .iter()
+            .find(|(region, _)| *region == name)
+            .map(|(_, address)| *address)
+            .unwrap()
+    }
+}
+
+/// Generates a Solidity contract called `contract_name` that verifies the proofs of the
+/// AIR `A` for traces of `trace_length` rows, the public inputs `pub_inputs` and the
+/// options `proof_options`. See the module documentation for the supported AIRs.
+///
+/// The contract has a single function, `verify(uint256[], uint256[], bytes)`, which takes
+/// the proof and the values of the boundary constraints, both encoded by
+/// `encode_calldata`, and the bytes the transcript was started with.
+pub fn generate_verifier<A>(
+    contract_name: &str,
+    trace_length: usize,
+    pub_inputs: &A::PublicInputs,
+    proof_options: &ProofOptions,
+) -> Result<String, SolidityVerifierError>
+where
+    A: AIR<Field = Stark252PrimeField, FieldExtension = Stark252PrimeField>,
+{
+    let is_identifier = contract_name
+        .chars()
+        .next()
+        .map_or(false, |first| first.is_ascii_alphabetic() || first == '_')
+        && contract_name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !is_identifier {
+        return Err(SolidityVerifierError::InvalidContractName(
+            contract_name.to_string(),
+        ));
+    }
+
+    let air = A::new(trace_length, pub_inputs, proof_options);
+    let shape = ProofShape::new(&air)?;
+    let constraints = air.symbolic_transitions().unwrap();
+    let dag = constraints.dag();
+    let exemptions: Vec<usize> = constraints
+        .transition_exemptions()
+        .iter()
+        .copied()
+        .filter(|exemptions| *exemptions > 0)
+        .unique()
+        .collect();
+
+    let number_of_columns = shape.number_of_columns();
+    let number_of_frame_rows = shape.transition_offsets.len();
+    let number_of_transitions = constraints.constraints().len();
+    let number_of_boundary_constraints = shape.boundary_constraints.constraints.len();
+    let number_of_queries = shape.number_of_queries;
+    let number_of_fri_layers = shape.number_of_fri_layers;
+    let opening_size = shape.opening_size();
+
+    let mut memory = MemoryLayout::new();
+    // Transcript and parsing
+    memory.allocate("T_STATE", 1);
+    memory.allocate("T_COUNTER", 1);
+    memory.allocate("T_SPARE", 1);
+    memory.allocate("T_SPARE_LENGTH", 1);
+    memory.allocate("T_BUFFER", 3);
+    memory.allocate("CURSOR", 1);
+    memory.allocate("PROOF_END", 1);
+    memory.allocate("AUTH_REMAINING", 1);
+    memory.allocate("MODEXP", 6);
+    memory.allocate("Z", 1);
+    memory.allocate("Z_POWER", 1);
+    memory.allocate("NONCE", 1);
+    memory.allocate("GRINDING_SEED", 1);
+    // Proof
+    memory.allocate("MAIN_ROOT", 1);
+    memory.allocate("AUXILIARY_ROOT", 1);
+    memory.allocate("COMPOSITION_ROOT", 1);
+    memory.allocate("FRI_ROOTS", number_of_fri_layers);
+    memory.allocate("OOD_FRAME", number_of_frame_rows * number_of_columns);
+    memory.allocate("PARTS_OOD", shape.number_of_parts);
+    memory.allocate("REMAINDER", shape.remainder_length);
+    memory.allocate("FRI_SYMMETRIC", number_of_queries * number_of_fri_layers);
+    memory.allocate("OPENINGS", number_of_queries * opening_size);
+    memory.allocate("OPENINGS_SYMMETRIC", number_of_queries * opening_size);
+    memory.allocate("BOUNDARY_VALUES", number_of_boundary_constraints);
+    // Challenges
+    memory.allocate("CHALLENGES", shape.number_of_challenges);
+    memory.allocate(
+        "CONSTRAINT_COEFFICIENTS",
+        number_of_transitions + number_of_boundary_constraints,
+    );
+    memory.allocate(
+        "DEEP_COEFFICIENTS",
+        number_of_columns * number_of_frame_rows + shape.number_of_parts,
+    );
+    memory.allocate("ZETAS", number_of_fri_layers + 1);
+    memory.allocate("IOTAS", number_of_queries);
+    // Evaluation of the AIR
+    memory.allocate("PERIODIC", constraints.periodic_column_names().len());
+    memory.allocate("NODES", dag.nodes().len());
+    memory.allocate("EXEMPTIONS", exemptions.len());
+    memory.allocate("TRANSITIONS", number_of_transitions);
+    memory.allocate("SHIFTED_Z", number_of_frame_rows);
+    memory.allocate("DENOMINATORS", number_of_frame_rows);
+    // Merkle leaves, as pairs of position and hash
+    memory.allocate("MAIN_LEAVES", 4 * number_of_queries);
+    memory.allocate("AUXILIARY_LEAVES", 4 * number_of_queries);
+    memory.allocate("COMPOSITION_LEAVES", 2 * number_of_queries);
+    memory.allocate("FRI_LEAVES", 2 * number_of_queries * number_of_fri_layers);
+    memory.allocate(
+        "LEAF_BUFFER",
+        shape
+            .number_of_main_columns
+            .max(shape.number_of_auxiliary_columns)
+            .max(2 * shape.number_of_parts)
+            .max(2),
+    );
+    memory.allocate("MEMORY_END", 0);
+
+    let lde_size = shape.lde_size;
+    let coset_offset = FE::from(proof_options.coset_offset);
+    let lde_root: FE =
+        Stark252PrimeField::get_primitive_root_of_unity(lde_size.trailing_zeros() as u64).unwrap();
+    let trace_root: FE =
+        Stark252PrimeField::get_primitive_root_of_unity(trace_length.trailing_zeros() as u64)
+            .unwrap();
+    let modulus = Stark252PrimeField::modulus_minus_one() + U256::from_u64(1);
+    let air_name = std::any::type_name::<A>()
+        .split('<')
+        .next()
+        .and_then(|path| path.rsplit("::").next())
+        .unwrap_or_default();
+
+    let mut substitutions: Vec<(&str, String)> = memory
+        .regions
+        .iter()
+        .map(|(name, address)| (*name, format!("{address:#x}")))
+        .collect();
+    substitutions.extend([
+        ("CONTRACT_NAME", contract_name.to_string()),
+        ("AIR", air_name.to_string()),
+        ("MODULUS", modulus.to_string()),
+        (
+            "MONTGOMERY_R",
+            FE::from(2).pow(256_u64).representative().to_string(),
+        ),
+        ("TRANSCRIPT_R_INV", StoneProverTranscript::R_INV.to_string()),
+        (
+            "MODULUS_MAX_MULTIPLE",
+            StoneProverTranscript::MODULUS_MAX_MULTIPLE.to_string(),
+        ),
+        ("TRACE_LENGTH", trace_length.to_string()),
+        ("LDE_SIZE", lde_size.to_string()),
+        ("HALF_LDE_SIZE", (lde_size / 2).to_string()),
+        ("LOG_LDE_SIZE", lde_size.trailing_zeros().to_string()),
+        ("COSET_OFFSET", coset_offset.representative().to_string()),
+        ("LDE_ROOT", lde_root.representative().to_string()),
+        (
+            "TRACE_ROOT_INV",
+            trace_root.inv().unwrap().representative().to_string(),
+        ),
+        (
+            "COSET_OFFSET_INV_POW_LDE_SIZE",
+            coset_offset
+                .pow(lde_size)
+                .inv()
+                .unwrap()
+                .representative()
+                .to_string(),
+        ),
+        (
+            "TOTAL_FOLDING_FACTOR",
+            (2_usize << number_of_fri_layers).to_string(),
+        ),
+        (
+            "NUMBER_OF_MAIN_COLUMNS",
+            shape.number_of_main_columns.to_string(),
+        ),
+        (
+            "NUMBER_OF_AUXILIARY_COLUMNS",
+            shape.number_of_auxiliary_columns.to_string(),
+        ),
+        (
+            "NUMBER_OF_AUXILIARY_ROOTS",
+            usize::from(shape.number_of_auxiliary_columns > 0).to_string(),
+        ),
+        ("NUMBER_OF_COLUMNS", number_of_columns.to_string()),
+        ("NUMBER_OF_FRAME_ROWS", number_of_frame_rows.to_string()),
+        ("NUMBER_OF_PARTS", shape.number_of_parts.to_string()),
+        ("NUMBER_OF_FRI_LAYERS", number_of_fri_layers.to_string()),
+        ("REMAINDER_LENGTH", shape.remainder_length.to_string()),
+        ("NUMBER_OF_QUERIES", number_of_queries.to_string()),
+        ("OPENING_SIZE", opening_size.to_string()),
+        (
+            "NUMBER_OF_CHALLENGES",
+            shape.number_of_challenges.to_string(),
+        ),
+        ("NUMBER_OF_TRANSITIONS", number_of_transitions.to_string()),
+        (
+            "NUMBER_OF_BOUNDARY_CONSTRAINTS",
+            number_of_boundary_constraints.to_string(),
+        ),
+        ("GRINDING_FACTOR", proof_options.grinding_factor.to_string()),
+        ("GRINDING_PREFIX", GRINDING_PREFIX.to_string()),
+    ]);
+
+    // The functions of the AIR use the placeholders of the template too.
+    let mut contract = TEMPLATE.replace(
+        "{{AIR_FUNCTIONS}}",
+        &air_functions(&air, &shape, &memory, &exemptions, &trace_root),
+    );
+    for (name, value) in substitutions {
+        contract = contract.replace(&format!("{{{{{name}}}}}"), &value);
+    }
+    debug_assert!(!contract.contains("{{"));
+    Ok(contract)
+}
+
+/// Functions of the generated verifier that are specific to the AIR, with its constants
+/// and constraints unrolled: the evaluation of the periodic columns, the transitions and
+/// the boundary constraints at z, and the shifts of z by the frame offsets.
+fn air_functions<A>(
+    air: &A,
+    shape: &ProofShape,
+    memory: &MemoryLayout,
+    exemptions: &[usize],
+    trace_root: &FE,
+) -> String
+where
+    A: AIR<Field = Stark252PrimeField, FieldExtension = Stark252PrimeField>,
+{
+    let constraints = air.symbolic_transitions().unwrap();
+    let dag = constraints.dag();
+    let number_of_columns = shape.number_of_columns();
+    let hex = |element: &FE| element.representative().to_string();
+    let address =
+        |region: &str, index: usize| format!("{:#x}", memory.address(region) + 32 * index);
+    let ood_frame_address =
+        |row: usize, column: usize| address("OOD_FRAME", row * number_of_columns + column);
+    let mut code = String::new();
+
+    // Each periodic column of period p is q(z^(n/p)), where q interpolates its values over
+    // the roots of unity of order p.
+    writeln!(code, "            function evaluate_periodic_columns(z) {{").unwrap();
+    let periodic_columns = air.get_periodic_column_values();
+    if !periodic_columns.is_empty() {
+        writeln!(code, "                let power := 0").unwrap();
+        writeln!(code, "                let value := 0").unwrap();
+    }
+    for (index, (values, poly)) in periodic_columns
+        .iter()
+        .zip(air.get_periodic_column_polynomials())
+        .enumerate()
+    {
+        let stride = shape.trace_length / values.len();
+        writeln!(code, "                power := fpow(z, {stride})").unwrap();
+        writeln!(code, "                value := 0").unwrap();
+        for i in (0..values.len()).rev() {
+            let coefficient = poly
+                .coefficients()
+                .get(i * stride)
+                .cloned()
+                .unwrap_or_else(FE::zero);
+            writeln!(
+                code,
+                "                value := addmod(mulmod(value, power, {{{{MODULUS}}}}), {}, {{{{MODULUS}}}})",
+                hex(&coefficient)
+            )
+            .unwrap();
+        }
+        writeln!(
+            code,
+            "                mstore({}, value)",
+            address("PERIODIC", index)
+        )
+        .unwrap();
+    }
+    writeln!(code, "            }}\n").unwrap();
+
+    // The nodes of the graph are evaluated in order. Constants and variables are read
+    // where they are used.
+    let operand = |node: usize| match dag.nodes()[node] {
+        Node::Constant(index) => hex(&dag.constants()[index]),
+        Node::Variable(Variable::Trace { column, offset }) => {
+            let row = shape.transition_offsets.binary_search(&offset).unwrap();
+            let frame_column = match column.kind {
+                ColumnKind::Main => column.index,
+                _ => shape.number_of_main_columns + column.index,
+            };
+            format!("mload({})", ood_frame_address(row, frame_column))
+        }
+        Node::Variable(Variable::Periodic(index)) => {
+            format!("mload({})", address("PERIODIC", index))
+        }
+        Node::Variable(Variable::Challenge(index)) => {
+            format!("mload({})", address("CHALLENGES", index))
+        }
+        _ => format!("mload({})", address("NODES", node)),
+    };
+    writeln!(code, "            function evaluate_transitions(z) {{").unwrap();
+    for (index, exemptions) in exemptions.iter().enumerate() {
+        writeln!(
+            code,
+            "                mstore({}, exemptions_evaluation(z, {exemptions}))",
+            address("EXEMPTIONS", index)
+        )
+        .unwrap();
+    }
+    for (index, node) in dag.nodes().iter().enumerate() {
+        let value = match *node {
+            Node::Constant(_) | Node::Variable(_) => continue,
+            Node::Neg(operand_node) => format!("fneg({})", operand(operand_node)),
+            Node::Add(lhs, rhs) => format!(
+                "addmod({}, {}, {{{{MODULUS}}}})",
+                operand(lhs),
+                operand(rhs)
+            ),
+            Node::Sub(lhs, rhs) => format!("fsub({}, {})", operand(lhs), operand(rhs)),
+            Node::Mul(lhs, rhs) => format!(
+                "mulmod({}, {}, {{{{MODULUS}}}})",
+                operand(lhs),
+                operand(rhs)
+            ),
+        };
+        writeln!(
+            code,
+            "                mstore({}, {value})",
+            address("NODES", index)
+        )
+        .unwrap();
+    }
+    for (index, (output, exemption)) in dag
+        .outputs()
+        .iter()
+        .zip(constraints.transition_exemptions())
+        .enumerate()
+    {
+        let value = match exemptions.iter().position(|e| e == exemption) {
+            Some(position) => format!(
+                "mulmod({}, mload({}), {{{{MODULUS}}}})",
+                operand(*output),
+                address("EXEMPTIONS", position)
+            ),
+            None => operand(*output),
+        };
+        writeln!(
+            code,
+            "                mstore({}, {value})",
+            address("TRANSITIONS", index)
+        )
+        .unwrap();
+    }
+    writeln!(code, "            }}\n").unwrap();
+
+    // Sum of (t(z) - v) / (z - gˢ) for each boundary constraint t(gˢ) = v, weighted by its
+    // coefficient.
+    writeln!(code, "            function boundary_sum(z) -> sum {{").unwrap();
+    let number_of_transitions = constraints.constraints().len();
+    for (index, constraint) in shape.boundary_constraints.constraints.iter().enumerate() {
+        writeln!(
+            code,
+            "                // Column {} at row {}",
+            constraint.col, constraint.step
+        )
+        .unwrap();
+        writeln!(
+            code,
+            "                sum := addmod(sum, mulmod(mulmod(fsub(mload({}), mload({})), finv(fsub(z, {})), {{{{MODULUS}}}}), mload({}), {{{{MODULUS}}}}), {{{{MODULUS}}}})",
+            ood_frame_address(0, constraint.col),
+            address("BOUNDARY_VALUES", index),
+            hex(&trace_root.pow(constraint.step)),
+            address("CONSTRAINT_COEFFICIENTS", number_of_transitions + index),
+        )
+        .unwrap();
+    }
+    writeln!(code, "            }}\n").unwrap();
+
+    // z gᵏ for each offset k of the frame.
+    writeln!(code, "            function shift_z(z) {{").unwrap();
+    for (row, offset) in shape.transition_offsets.iter().enumerate() {
+        writeln!(
+            code,
+            "                mstore({}, mulmod(z, {}, {{{{MODULUS}}}}))",
+            address("SHIFTED_Z", row),
+            hex(&trace_root.pow(*offset))
+        )
+        .unwrap();
+    }
+    write!(code, "            }}").unwrap();
+    code
+}
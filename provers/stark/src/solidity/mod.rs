@@ -0,0 +1,623 @@
+//! Solidity verifiers of STARK proofs.
+//!
+//! `generate_verifier` emits a contract that verifies the proofs of one AIR instance: the
+//! trace length, the constraints and the proof options are fixed when the contract is
+//! generated, and only the proof, the values of the boundary constraints and the seed of
+//! the transcript are given to it. `encode_calldata` encodes a call to the contract.
+//!
+//! The verifiers follow `IsStarkVerifier::verify` with a `StoneProverTranscript`, for
+//! proofs over `Stark252PrimeField` committed with the default `BatchedMerkleTreeBackend`.
+//! They support the AIRs built with a `ConstraintBuilder`, whose transitions are
+//! translated from their `ExpressionDag`, as long as their RAP challenges are field
+//! elements sampled from the transcript and their boundary constraints do not depend on
+//! them. FRI must fold by 2, and zero-knowledge proofs and preprocessed columns are not
+//! supported.
+
+mod calldata;
+#[cfg(test)]
+mod evm;
+mod generator;
+
+use lambdaworks_math::field::{
+    element::FieldElement, fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
+};
+
+use crate::{
+    constraints::boundary::BoundaryConstraints,
+    debug::validate_symbolic_transitions,
+    frame::Frame,
+    fri,
+    grinding::GrindingHash,
+    traits::AIR,
+    transcript::{IsStarkTranscript, StoneProverTranscript},
+};
+
+pub use calldata::encode_calldata;
+pub use generator::generate_verifier;
+
+type FE = FieldElement<Stark252PrimeField>;
+
+#[derive(Debug)]
+pub enum SolidityVerifierError {
+    /// The AIR or the proof options use a feature the generated verifiers do not support.
+    Unsupported(String),
+    /// The name given to the contract is not a Solidity identifier.
+    InvalidContractName(String),
+    /// The proof does not have the shape of the proofs of the AIR, so it cannot be encoded.
+    MalformedProof(String),
+}
+
+/// Shape of the proofs of an AIR instance, which the generated verifiers are specialised
+/// to, together with its boundary constraints.
+pub(crate) struct ProofShape {
+    pub(crate) trace_length: usize,
+    pub(crate) lde_size: usize,
+    pub(crate) number_of_main_columns: usize,
+    pub(crate) number_of_auxiliary_columns: usize,
+    pub(crate) transition_offsets: Vec<usize>,
+    pub(crate) number_of_parts: usize,
+    pub(crate) number_of_fri_layers: usize,
+    pub(crate) remainder_length: usize,
+    pub(crate) number_of_queries: usize,
+    pub(crate) number_of_challenges: usize,
+    pub(crate) boundary_constraints: BoundaryConstraints<Stark252PrimeField>,
+}
+
+impl ProofShape {
+    /// Checks that the generated verifiers support `air` and returns the shape of its
+    /// proofs.
+    pub(crate) fn new<A>(air: &A) -> Result<Self, SolidityVerifierError>
+    where
+        A: AIR<Field = Stark252PrimeField, FieldExtension = Stark252PrimeField>,
+    {
+        let unsupported = |reason: &str| Err(SolidityVerifierError::Unsupported(reason.into()));
+        let options = air.options();
+        if options.zero_knowledge {
+            return unsupported("zero-knowledge proofs are not supported");
+        }
+        if options.fri_folding_factor != 2 {
+            return unsupported("FRI must fold by 2");
+        }
+        if options.grinding_factor > 0 && options.grinding_hash != GrindingHash::Keccak256 {
+            return unsupported("the grinding hash must be Keccak256");
+        }
+        if options.grinding_factor >= 64 {
+            return unsupported("the grinding factor must be less than 64");
+        }
+        let Some(constraints) = air.symbolic_transitions() else {
+            return unsupported("the AIR has no symbolic transitions");
+        };
+        if air.number_preprocessed_columns() > 0 || constraints.number_of_preprocessed_columns() > 0
+        {
+            return unsupported("preprocessed columns are not supported");
+        }
+        let number_of_main_columns = constraints.number_of_main_columns();
+        let number_of_auxiliary_columns = constraints.number_of_auxiliary_columns();
+        if !validate_symbolic_transitions(air)
+            || air.number_auxiliary_rap_columns() != number_of_auxiliary_columns
+            || air.context().trace_columns != number_of_main_columns + number_of_auxiliary_columns
+            || air.get_periodic_column_values().len() != constraints.periodic_column_names().len()
+        {
+            return unsupported("the AIR does not match its symbolic transitions");
+        }
+
+        // The verifiers sample the challenges on their own, so the AIR must sample a field
+        // element for each challenge of the constraints and nothing else.
+        let sample_challenges = |seed: &[u8]| {
+            let mut recorder = ChallengeRecorder::new(seed);
+            let challenges = air.build_rap_challenges(&mut recorder);
+            (challenges, recorder)
+        };
+        let (challenges, recorder) = sample_challenges(b"challenges");
+        let (other_challenges, other_recorder) = sample_challenges(b"other challenges");
+        if !recorder.only_samples_elements
+            || !other_recorder.only_samples_elements
+            || recorder.samples.len() != constraints.number_of_challenges()
+        {
+            return unsupported("the RAP challenges must be field elements sampled in order");
+        }
+
+        let boundary_constraints = air.boundary_constraints(&challenges);
+        let other_boundary_constraints = air.boundary_constraints(&other_challenges);
+        let constraint_tuples = |constraints: &BoundaryConstraints<Stark252PrimeField>| {
+            constraints
+                .constraints
+                .iter()
+                .map(|constraint| (constraint.col, constraint.step, constraint.value))
+                .collect::<Vec<_>>()
+        };
+        if constraint_tuples(&boundary_constraints)
+            != constraint_tuples(&other_boundary_constraints)
+        {
+            return unsupported("the boundary constraints depend on the RAP challenges");
+        }
+        let number_of_columns = number_of_main_columns + number_of_auxiliary_columns;
+        if boundary_constraints.constraints.iter().any(|constraint| {
+            constraint.col >= number_of_columns || constraint.step >= air.trace_length()
+        }) {
+            return unsupported("a boundary constraint is out of the trace");
+        }
+
+        // The verifiers evaluate the symbolic transitions, which must be the ones the
+        // Rust verifier evaluates with `compute_transition`.
+        let transition_offsets = constraints.transition_offsets().to_vec();
+        let mut values = StoneProverTranscript::new(b"frame");
+        let frame = Frame::new(
+            (0..transition_offsets.len() * number_of_columns)
+                .map(|_| values.sample_field_element())
+                .collect(),
+            number_of_columns,
+        )
+        .with_periodic_values(
+            (0..constraints.periodic_column_names().len())
+                .map(|_| values.sample_field_element())
+                .collect(),
+        );
+        if air.compute_transition(&frame, &challenges)
+            != constraints.evaluate(&frame, &recorder.samples)
+        {
+            return unsupported("compute_transition does not evaluate the symbolic transitions");
+        }
+
+        let number_of_layers = air.deep_composition_poly_degree_bound().trailing_zeros() as usize;
+        let remainder_length =
+            fri::remainder_degree_bound(number_of_layers, options.fri_max_remainder_degree);
+        let number_of_fri_layers =
+            fri::layers_folding_factors(number_of_layers, 2, remainder_length).len();
+
+        Ok(Self {
+            trace_length: air.trace_length(),
+            lde_size: air.trace_length() * options.blowup_factor as usize,
+            number_of_main_columns,
+            number_of_auxiliary_columns,
+            transition_offsets,
+            number_of_parts: air.number_of_composition_poly_parts(),
+            number_of_fri_layers,
+            remainder_length,
+            number_of_queries: options.fri_number_of_queries,
+            number_of_challenges: constraints.number_of_challenges(),
+            boundary_constraints,
+        })
+    }
+
+    pub(crate) fn number_of_columns(&self) -> usize {
+        self.number_of_main_columns + self.number_of_auxiliary_columns
+    }
+
+    /// Number of values opened by a query at a point: the trace columns and the parts of
+    /// the composition polynomial.
+    pub(crate) fn opening_size(&self) -> usize {
+        self.number_of_columns() + self.number_of_parts
+    }
+}
+
+/// Transcript that records the elements sampled from it by `AIR::build_rap_challenges`,
+/// and whether anything else was done with it.
+struct ChallengeRecorder {
+    transcript: StoneProverTranscript,
+    samples: Vec<FE>,
+    only_samples_elements: bool,
+}
+
+impl ChallengeRecorder {
+    fn new(seed: &[u8]) -> Self {
+        Self {
+            transcript: StoneProverTranscript::new(seed),
+            samples: Vec::new(),
+            only_samples_elements: true,
+        }
+    }
+}
+
+impl IsStarkTranscript<Stark252PrimeField> for ChallengeRecorder {
+    fn append_field_element(&mut self, element: &FE) {
+        self.only_samples_elements = false;
+        self.transcript.append_field_element(element);
+    }
+
+    fn append_bytes(&mut self, new_bytes: &[u8]) {
+        self.only_samples_elements = false;
+        self.transcript.append_bytes(new_bytes);
+    }
+
+    fn state(&self) -> [u8; 32] {
+        self.transcript.state()
+    }
+
+    fn sample_field_element(&mut self) -> FE {
+        let sample = self.transcript.sample_field_element();
+        self.samples.push(sample);
+        sample
+    }
+
+    fn sample_u64(&mut self, upper_bound: u64) -> u64 {
+        self.only_samples_elements = false;
+        self.transcript.sample_u64(upper_bound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lambdaworks_math::field::fields::fft_friendly::stark_252_prime_field::Stark252PrimeField;
+
+    use super::{encode_calldata, evm, generate_verifier, SolidityVerifierError};
+    use crate::{
+        constraints::{
+            boundary::{BoundaryConstraint, BoundaryConstraints},
+            builder::{ConstraintBuilder, TransitionConstraints},
+        },
+        context::AirContext,
+        examples::{
+            fibonacci_rap::{fibonacci_rap_trace, FibonacciRAP, FibonacciRAPPublicInputs},
+            simple_periodic_cols::{
+                simple_periodic_trace, SimplePeriodicAIR, SimplePeriodicPublicInputs,
+                ROUND_CONSTANTS,
+            },
+        },
+        frame::Frame,
+        proof::{options::ProofOptions, stark::StarkProof},
+        prover::{IsStarkProver, Prover},
+        trace::TraceTable,
+        traits::AIR,
+        transcript::{IsStarkTranscript, StoneProverTranscript},
+        verifier::{IsStarkVerifier, Verifier},
+        Felt252,
+    };
+
+    type Proof = StarkProof<Stark252PrimeField>;
+
+    /// Accumulator of the round constants of `SimplePeriodicAIR` together with its square,
+    /// so that the constraints have a periodic column, a constraint without exemptions and
+    /// two boundary constraints given by the public input.
+    #[derive(Clone)]
+    struct SquaredAccumulatorAIR {
+        context: AirContext,
+        constraints: TransitionConstraints<Stark252PrimeField>,
+        trace_length: usize,
+        pub_inputs: SimplePeriodicPublicInputs<Stark252PrimeField>,
+    }
+
+    impl AIR for SquaredAccumulatorAIR {
+        type Field = Stark252PrimeField;
+        type FieldExtension = Stark252PrimeField;
+        type RAPChallenges = ();
+        type PublicInputs = SimplePeriodicPublicInputs<Stark252PrimeField>;
+
+        fn new(
+            trace_length: usize,
+            pub_inputs: &Self::PublicInputs,
+            proof_options: &ProofOptions,
+        ) -> Self {
+            let mut builder = ConstraintBuilder::new();
+            let a = builder.main_column("a");
+            let b = builder.main_column("b");
+            let round_constants = ROUND_CONSTANTS.iter().map(|c| Felt252::from(*c));
+            let k = builder.periodic_column("k", round_constants.collect());
+            builder.transition(a.next() - a.curr() - k);
+            builder.transition(b.curr() - a.curr() * a.curr());
+            let constraints = builder.build();
+
+            Self {
+                context: constraints.air_context(proof_options),
+                constraints,
+                trace_length,
+                pub_inputs: pub_inputs.clone(),
+            }
+        }
+
+        fn build_auxiliary_trace(
+            &self,
+            _main_trace: &TraceTable<Self::Field>,
+            _rap_challenges: &Self::RAPChallenges,
+        ) -> TraceTable<Self::Field> {
+            TraceTable::empty()
+        }
+
+        fn build_rap_challenges(
+            &self,
+            _transcript: &mut impl IsStarkTranscript<Self::Field>,
+        ) -> Self::RAPChallenges {
+        }
+
+        fn number_auxiliary_rap_columns(&self) -> usize {
+            0
+        }
+
+        fn compute_transition(
+            &self,
+            frame: &Frame<Self::Field>,
+            _rap_challenges: &Self::RAPChallenges,
+        ) -> Vec<Felt252> {
+            self.constraints.evaluate(frame, &[])
+        }
+
+        fn symbolic_transitions(&self) -> Option<&TransitionConstraints<Self::Field>> {
+            Some(&self.constraints)
+        }
+
+        fn get_periodic_column_values(&self) -> Vec<Vec<Felt252>> {
+            self.constraints.periodic_column_values().to_vec()
+        }
+
+        fn boundary_constraints(
+            &self,
+            _rap_challenges: &Self::RAPChallenges,
+        ) -> BoundaryConstraints<Self::Field> {
+            let a0 = &self.pub_inputs.a0;
+            BoundaryConstraints::from_constraints(vec![
+                BoundaryConstraint::new(0, 0, *a0),
+                BoundaryConstraint::new(1, 0, a0 * a0),
+            ])
+        }
+
+        fn context(&self) -> &AirContext {
+            &self.context
+        }
+
+        fn trace_length(&self) -> usize {
+            self.trace_length
+        }
+
+        fn pub_inputs(&self) -> &Self::PublicInputs {
+            &self.pub_inputs
+        }
+    }
+
+    fn squared_accumulator_trace(
+        a0: Felt252,
+        trace_length: usize,
+    ) -> TraceTable<Stark252PrimeField> {
+        let accumulator = simple_periodic_trace(a0, trace_length).columns().remove(0);
+        let squares = accumulator.iter().map(|a| a * a).collect();
+        TraceTable::from_columns(&[accumulator, squares])
+    }
+
+    fn fibonacci_rap_proof(
+        proof_options: &ProofOptions,
+        seed: &[u8],
+    ) -> (Proof, FibonacciRAPPublicInputs<Stark252PrimeField>) {
+        let steps = 16;
+        let trace = fibonacci_rap_trace([Felt252::one(), Felt252::one()], steps);
+        let pub_inputs = FibonacciRAPPublicInputs {
+            steps,
+            a0: Felt252::one(),
+            a1: Felt252::one(),
+        };
+        let proof = Prover::prove::<FibonacciRAP<Stark252PrimeField>>(
+            &trace,
+            &pub_inputs,
+            proof_options,
+            StoneProverTranscript::new(seed),
+        )
+        .unwrap();
+        (proof, pub_inputs)
+    }
+
+    /// Returns whether the generated verifier of `A` accepts `proof`, after checking that
+    /// the Rust verifier agrees with it.
+    fn verify_on_evm<A>(
+        proof: &Proof,
+        pub_inputs: &A::PublicInputs,
+        proof_options: &ProofOptions,
+        seed: &[u8],
+    ) -> bool
+    where
+        A: AIR<Field = Stark252PrimeField, FieldExtension = Stark252PrimeField>,
+    {
+        let contract = generate_verifier::<A>(
+            "StarkVerifier",
+            proof.trace_length,
+            pub_inputs,
+            proof_options,
+        )
+        .unwrap();
+        let calldata = encode_calldata::<A>(proof, pub_inputs, proof_options, seed).unwrap();
+        let output = evm::call(evm::compile(&contract, "StarkVerifier"), calldata).unwrap();
+        assert_eq!(output.len(), 32);
+        assert!(output[..31].iter().all(|byte| *byte == 0) && output[31] <= 1);
+
+        let accepted = output[31] == 1;
+        let transcript = StoneProverTranscript::new(seed);
+        assert_eq!(
+            accepted,
+            Verifier::verify::<A>(proof, pub_inputs, proof_options, transcript)
+        );
+        accepted
+    }
+
+    fn tampered(proof: &Proof, tamper: impl FnOnce(&mut Proof)) -> Proof {
+        let mut copy: Proof = serde_json::from_str(&serde_json::to_string(proof).unwrap()).unwrap();
+        tamper(&mut copy);
+        copy
+    }
+
+    fn increment(element: &mut Felt252) {
+        *element += Felt252::one();
+    }
+
+    #[test]
+    #[ignore = "needs solc"]
+    fn the_generated_verifier_accepts_valid_proofs() {
+        // With many queries some of them open the same leaves.
+        let many_queries = ProofOptions {
+            fri_number_of_queries: 40,
+            ..ProofOptions::default_test_options()
+        };
+        for proof_options in [
+            ProofOptions::default_test_options(),
+            ProofOptions::default_test_options().with_fri_max_remainder_degree(3),
+            many_queries,
+        ] {
+            let (proof, pub_inputs) = fibonacci_rap_proof(&proof_options, b"seed");
+
+            assert!(verify_on_evm::<FibonacciRAP<Stark252PrimeField>>(
+                &proof,
+                &pub_inputs,
+                &proof_options,
+                b"seed"
+            ));
+        }
+    }
+
+    #[test]
+    #[ignore = "needs solc"]
+    fn the_generated_verifier_rejects_the_proofs_the_rust_verifier_rejects() {
+        let proof_options = ProofOptions {
+            grinding_factor: 8,
+            ..ProofOptions::default_test_options()
+        };
+        let (proof, pub_inputs) = fibonacci_rap_proof(&proof_options, b"seed");
+        let tampered_proofs = [
+            tampered(&proof, |p| {
+                increment(&mut p.trace_ood_frame_evaluations.get_row_mut(1)[2])
+            }),
+            tampered(&proof, |p| {
+                increment(&mut p.composition_poly_parts_ood_evaluation[0])
+            }),
+            tampered(&proof, |p| {
+                increment(&mut p.deep_poly_openings[1].lde_trace_evaluations[0])
+            }),
+            tampered(&proof, |p| {
+                increment(&mut p.deep_poly_openings_sym[0].lde_aux_trace_evaluations[0])
+            }),
+            tampered(&proof, |p| {
+                increment(&mut p.deep_poly_openings[2].lde_composition_poly_parts_evaluation[0])
+            }),
+            tampered(&proof, |p| {
+                increment(&mut p.query_list[0].layers_evaluations_sym[1])
+            }),
+            tampered(&proof, |p| increment(&mut p.fri_remainder_coefficients[0])),
+            tampered(&proof, |p| {
+                p.lde_trace_merkle_proofs[0].auth_nodes[0][0] ^= 1
+            }),
+            tampered(&proof, |p| {
+                p.fri_layers_merkle_proofs[1].auth_nodes[2][31] ^= 1
+            }),
+            tampered(&proof, |p| {
+                p.lde_composition_poly_proof.auth_nodes.pop();
+            }),
+            tampered(&proof, |p| p.composition_poly_root[5] ^= 1),
+        ];
+        for tampered_proof in &tampered_proofs {
+            assert!(!verify_on_evm::<FibonacciRAP<Stark252PrimeField>>(
+                tampered_proof,
+                &pub_inputs,
+                &proof_options,
+                b"seed"
+            ));
+        }
+        assert!(!verify_on_evm::<FibonacciRAP<Stark252PrimeField>>(
+            &proof,
+            &pub_inputs,
+            &proof_options,
+            b"other seed"
+        ));
+        // Another nonce may be valid too, but both verifiers must agree on it.
+        verify_on_evm::<FibonacciRAP<Stark252PrimeField>>(
+            &tampered(&proof, |p| p.nonce += 1),
+            &pub_inputs,
+            &proof_options,
+            b"seed",
+        );
+    }
+
+    #[test]
+    #[ignore = "needs solc"]
+    fn the_generated_verifier_evaluates_periodic_columns_and_public_boundary_values() {
+        let proof_options = ProofOptions::default_test_options();
+        let pub_inputs = SimplePeriodicPublicInputs {
+            a0: Felt252::from(3),
+        };
+        let trace = squared_accumulator_trace(pub_inputs.a0, 32);
+        let proof = Prover::prove::<SquaredAccumulatorAIR>(
+            &trace,
+            &pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        )
+        .unwrap();
+
+        assert!(verify_on_evm::<SquaredAccumulatorAIR>(
+            &proof,
+            &pub_inputs,
+            &proof_options,
+            &[]
+        ));
+        let other_pub_inputs = SimplePeriodicPublicInputs {
+            a0: Felt252::from(4),
+        };
+        assert!(!verify_on_evm::<SquaredAccumulatorAIR>(
+            &proof,
+            &other_pub_inputs,
+            &proof_options,
+            &[]
+        ));
+    }
+
+    #[test]
+    fn unsupported_airs_and_options_are_reported() {
+        let pub_inputs = FibonacciRAPPublicInputs {
+            steps: 16,
+            a0: Felt252::one(),
+            a1: Felt252::one(),
+        };
+        let generate = |proof_options: &ProofOptions| {
+            generate_verifier::<FibonacciRAP<Stark252PrimeField>>(
+                "StarkVerifier",
+                32,
+                &pub_inputs,
+                proof_options,
+            )
+        };
+        let options = ProofOptions::default_test_options();
+        assert!(generate(&options).is_ok());
+        assert!(matches!(
+            generate(&options.clone().with_zero_knowledge()),
+            Err(SolidityVerifierError::Unsupported(_))
+        ));
+        assert!(matches!(
+            generate(&options.clone().with_fri_folding_factor(4)),
+            Err(SolidityVerifierError::Unsupported(_))
+        ));
+        assert!(matches!(
+            generate_verifier::<FibonacciRAP<Stark252PrimeField>>(
+                "Stark Verifier",
+                32,
+                &pub_inputs,
+                &options
+            ),
+            Err(SolidityVerifierError::InvalidContractName(_))
+        ));
+
+        // Its transitions are only given by `compute_transition`.
+        let pub_inputs = SimplePeriodicPublicInputs { a0: Felt252::one() };
+        assert!(matches!(
+            generate_verifier::<SimplePeriodicAIR<Stark252PrimeField>>(
+                "StarkVerifier",
+                32,
+                &pub_inputs,
+                &options
+            ),
+            Err(SolidityVerifierError::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn proofs_of_another_shape_are_not_encoded() {
+        let proof_options = ProofOptions::default_test_options();
+        let (proof, pub_inputs) = fibonacci_rap_proof(&proof_options, &[]);
+        let proof = tampered(&proof, |p| {
+            p.query_list.pop();
+        });
+
+        assert!(matches!(
+            encode_calldata::<FibonacciRAP<Stark252PrimeField>>(
+                &proof,
+                &pub_inputs,
+                &proof_options,
+                &[]
+            ),
+            Err(SolidityVerifierError::MalformedProof(_))
+        ));
+    }
+}
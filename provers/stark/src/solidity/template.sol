@@ -0,0 +1,623 @@
+// SPDX-License-Identifier: Apache-2.0
+pragma solidity ^0.8.19;
+
+/// @notice Verifier of STARK proofs of `{{AIR}}` generated by stark-platinum-prover, for
+/// traces of {{TRACE_LENGTH}} rows. Proofs are over the Stark252 prime field, with a Stone
+/// prover transcript and Keccak256 Merkle trees.
+/// @dev Generated by `stark_platinum_prover::solidity::generate_verifier`. Do not edit.
+contract {{CONTRACT_NAME}} {
+    /// @notice Returns whether a proof is valid. The arguments are, in order: the proof as
+    /// encoded by `stark_platinum_prover::solidity::encode_calldata`, the values of the
+    /// boundary constraints of the AIR, which are its public inputs, and the bytes the
+    /// transcript of the proof was started with.
+    function verify(uint256[] calldata, uint256[] calldata, bytes calldata)
+        external
+        view
+        returns (bool)
+    {
+        assembly {
+            let proofStart, proofLength := calldata_array(0, 32)
+            mstore({{CURSOR}}, proofStart)
+            mstore({{PROOF_END}}, add(proofStart, mul(32, proofLength)))
+
+            let valuesStart, valuesLength := calldata_array(1, 32)
+            if iszero(eq(valuesLength, {{NUMBER_OF_BOUNDARY_CONSTRAINTS}})) { fail() }
+            read_boundary_values(valuesStart)
+
+            let seedStart, seedLength := calldata_array(2, 1)
+            calldatacopy({{MEMORY_END}}, seedStart, seedLength)
+            mstore({{T_STATE}}, keccak256({{MEMORY_END}}, seedLength))
+
+            read_proof()
+            replay_transcript()
+            if {{GRINDING_FACTOR}} { check_grinding() }
+            if iszero(eq(composition_claimed_evaluation(), composition_evaluation())) { fail() }
+            verify_fri_queries()
+            verify_openings()
+            if iszero(eq(mload({{CURSOR}}), mload({{PROOF_END}}))) { fail() }
+
+            mstore(0, 1)
+            return(0, 32)
+
+            // ===================================
+            // ==========|   Calldata  |==========
+            // ===================================
+
+            function fail() {
+                mstore(0, 0)
+                return(0, 32)
+            }
+
+            // Start and length of the dynamic argument `argument` of the call, whose items
+            // have `itemSize` bytes.
+            function calldata_array(argument, itemSize) -> start, length {
+                let offset := calldataload(add(4, mul(32, argument)))
+                if gt(offset, 0xffffffff) { fail() }
+                start := add(add(4, offset), 32)
+                length := calldataload(sub(start, 32))
+                if gt(length, 0xffffffff) { fail() }
+                if gt(add(start, mul(itemSize, length)), calldatasize()) { fail() }
+            }
+
+            function read_word() -> word {
+                let cursor := mload({{CURSOR}})
+                if iszero(lt(cursor, mload({{PROOF_END}}))) { fail() }
+                word := calldataload(cursor)
+                mstore({{CURSOR}}, add(cursor, 32))
+            }
+
+            function read_element() -> element {
+                element := read_word()
+                if iszero(lt(element, {{MODULUS}})) { fail() }
+            }
+
+            function read_words(target, count) {
+                for { let i := 0 } lt(i, count) { i := add(i, 1) } {
+                    mstore(add(target, mul(32, i)), read_word())
+                }
+            }
+
+            function read_elements(target, count) {
+                for { let i := 0 } lt(i, count) { i := add(i, 1) } {
+                    mstore(add(target, mul(32, i)), read_element())
+                }
+            }
+
+            function read_boundary_values(start) {
+                for { let i := 0 } lt(i, {{NUMBER_OF_BOUNDARY_CONSTRAINTS}}) { i := add(i, 1) } {
+                    let value := calldataload(add(start, mul(32, i)))
+                    if iszero(lt(value, {{MODULUS}})) { fail() }
+                    mstore(add({{BOUNDARY_VALUES}}, mul(32, i)), value)
+                }
+            }
+
+            // Reads every part of the proof but the Merkle proofs, which are read as they
+            // are checked.
+            function read_proof() {
+                if iszero(eq(read_word(), {{TRACE_LENGTH}})) { fail() }
+                mstore({{MAIN_ROOT}}, read_word())
+                read_words({{AUXILIARY_ROOT}}, {{NUMBER_OF_AUXILIARY_ROOTS}})
+                read_elements({{OOD_FRAME}}, mul({{NUMBER_OF_FRAME_ROWS}}, {{NUMBER_OF_COLUMNS}}))
+                mstore({{COMPOSITION_ROOT}}, read_word())
+                read_elements({{PARTS_OOD}}, {{NUMBER_OF_PARTS}})
+                read_words({{FRI_ROOTS}}, {{NUMBER_OF_FRI_LAYERS}})
+                read_elements({{REMAINDER}}, {{REMAINDER_LENGTH}})
+                let nonce := read_word()
+                if gt(nonce, 0xffffffffffffffff) { fail() }
+                mstore({{NONCE}}, nonce)
+                read_elements({{FRI_SYMMETRIC}}, mul({{NUMBER_OF_QUERIES}}, {{NUMBER_OF_FRI_LAYERS}}))
+                read_elements({{OPENINGS}}, mul({{NUMBER_OF_QUERIES}}, {{OPENING_SIZE}}))
+                read_elements({{OPENINGS_SYMMETRIC}}, mul({{NUMBER_OF_QUERIES}}, {{OPENING_SIZE}}))
+            }
+
+            // ===================================
+            // ==========|    Field    |==========
+            // ===================================
+
+            function fsub(a, b) -> c {
+                c := addmod(a, sub({{MODULUS}}, b), {{MODULUS}})
+            }
+
+            function fneg(a) -> c {
+                c := mod(sub({{MODULUS}}, a), {{MODULUS}})
+            }
+
+            function fpow(base, exponent) -> power {
+                power := 1
+                for {} gt(exponent, 0) { exponent := shr(1, exponent) } {
+                    if and(exponent, 1) { power := mulmod(power, base, {{MODULUS}}) }
+                    base := mulmod(base, base, {{MODULUS}})
+                }
+            }
+
+            // Inverse of a nonzero element, computed with the modular exponentiation
+            // precompile.
+            function finv(a) -> inverse {
+                mstore({{MODEXP}}, 32)
+                mstore(add({{MODEXP}}, 32), 32)
+                mstore(add({{MODEXP}}, 64), 32)
+                mstore(add({{MODEXP}}, 96), a)
+                mstore(add({{MODEXP}}, 128), sub({{MODULUS}}, 2))
+                mstore(add({{MODEXP}}, 160), {{MODULUS}})
+                if iszero(staticcall(gas(), 0x05, {{MODEXP}}, 192, {{MODEXP}}, 32)) { fail() }
+                inverse := mload({{MODEXP}})
+            }
+
+            // Stores `1, base, base², ...` at `target`.
+            function store_powers(base, target, count) {
+                let power := 1
+                for { let i := 0 } lt(i, count) { i := add(i, 1) } {
+                    mstore(add(target, mul(32, i)), power)
+                    power := mulmod(power, base, {{MODULUS}})
+                }
+            }
+
+            // Evaluates the polynomial with the `count` coefficients at `coefficients` at
+            // `point`.
+            function horner(coefficients, count, point) -> value {
+                for { let i := count } gt(i, 0) { i := sub(i, 1) } {
+                    value := addmod(
+                        mulmod(value, point, {{MODULUS}}),
+                        mload(add(coefficients, mul(32, sub(i, 1)))),
+                        {{MODULUS}}
+                    )
+                }
+            }
+
+            function reverse_bits(value, bits) -> reversed {
+                for { let i := 0 } lt(i, bits) { i := add(i, 1) } {
+                    reversed := or(shl(1, reversed), and(value, 1))
+                    value := shr(1, value)
+                }
+            }
+
+            // ===================================
+            // ==========| Transcript  |==========
+            // ===================================
+
+            function transcript_append_word(word) {
+                mstore({{T_BUFFER}}, add(mload({{T_STATE}}), 1))
+                mstore(add({{T_BUFFER}}, 32), word)
+                mstore({{T_STATE}}, keccak256({{T_BUFFER}}, 64))
+                mstore({{T_COUNTER}}, 0)
+                mstore({{T_SPARE_LENGTH}}, 0)
+            }
+
+            // Elements are appended in Montgomery form, as the Rust transcript does.
+            function transcript_append_element(element) {
+                transcript_append_word(mulmod(element, {{MONTGOMERY_R}}, {{MODULUS}}))
+            }
+
+            function transcript_append_nonce(nonce) {
+                mstore({{T_BUFFER}}, add(mload({{T_STATE}}), 1))
+                mstore(add({{T_BUFFER}}, 32), shl(192, nonce))
+                mstore({{T_STATE}}, keccak256({{T_BUFFER}}, 40))
+                mstore({{T_COUNTER}}, 0)
+                mstore({{T_SPARE_LENGTH}}, 0)
+            }
+
+            function transcript_block() -> block {
+                mstore({{T_BUFFER}}, mload({{T_STATE}}))
+                mstore(add({{T_BUFFER}}, 32), mload({{T_COUNTER}}))
+                block := keccak256({{T_BUFFER}}, 64)
+                mstore({{T_COUNTER}}, add(mload({{T_COUNTER}}), 1))
+            }
+
+            function transcript_sample_element() -> element {
+                let block := transcript_block()
+                for {} iszero(lt(block, {{MODULUS_MAX_MULTIPLE}})) {} { block := transcript_block() }
+                element := mulmod(block, {{TRANSCRIPT_R_INV}}, {{MODULUS}})
+            }
+
+            // Samples 8 bytes. The rest of a block is kept for the next samples; it is
+            // always a multiple of 8 bytes long, since only 8 byte samples leave bytes.
+            function transcript_sample_u64() -> value {
+                let spareLength := mload({{T_SPARE_LENGTH}})
+                if lt(spareLength, 8) {
+                    let block := transcript_block()
+                    value := shr(192, block)
+                    mstore({{T_SPARE}}, shl(64, block))
+                    mstore({{T_SPARE_LENGTH}}, 24)
+                    leave
+                }
+                let spare := mload({{T_SPARE}})
+                value := shr(192, spare)
+                mstore({{T_SPARE}}, shl(64, spare))
+                mstore({{T_SPARE_LENGTH}}, sub(spareLength, 8))
+            }
+
+            function is_out_of_domain(z) -> isOut {
+                let inTrace := eq(fpow(z, {{TRACE_LENGTH}}), 1)
+                let inLde := eq(
+                    mulmod(fpow(z, {{LDE_SIZE}}), {{COSET_OFFSET_INV_POW_LDE_SIZE}}, {{MODULUS}}),
+                    1
+                )
+                isOut := iszero(or(inTrace, inLde))
+            }
+
+            function replay_transcript() {
+                transcript_append_word(mload({{MAIN_ROOT}}))
+                for { let i := 0 } lt(i, {{NUMBER_OF_CHALLENGES}}) { i := add(i, 1) } {
+                    mstore(add({{CHALLENGES}}, mul(32, i)), transcript_sample_element())
+                }
+                if {{NUMBER_OF_AUXILIARY_ROOTS}} { transcript_append_word(mload({{AUXILIARY_ROOT}})) }
+
+                let beta := transcript_sample_element()
+                store_powers(
+                    beta,
+                    {{CONSTRAINT_COEFFICIENTS}},
+                    add({{NUMBER_OF_TRANSITIONS}}, {{NUMBER_OF_BOUNDARY_CONSTRAINTS}})
+                )
+                transcript_append_word(mload({{COMPOSITION_ROOT}}))
+
+                let z := transcript_sample_element()
+                for {} iszero(is_out_of_domain(z)) {} { z := transcript_sample_element() }
+                mstore({{Z}}, z)
+                for { let column := 0 } lt(column, {{NUMBER_OF_COLUMNS}}) { column := add(column, 1) } {
+                    for { let row := 0 } lt(row, {{NUMBER_OF_FRAME_ROWS}}) { row := add(row, 1) } {
+                        transcript_append_element(mload(ood_frame_element(row, column)))
+                    }
+                }
+                for { let i := 0 } lt(i, {{NUMBER_OF_PARTS}}) { i := add(i, 1) } {
+                    transcript_append_element(mload(add({{PARTS_OOD}}, mul(32, i))))
+                }
+
+                let gamma := transcript_sample_element()
+                store_powers(
+                    gamma,
+                    {{DEEP_COEFFICIENTS}},
+                    add(mul({{NUMBER_OF_COLUMNS}}, {{NUMBER_OF_FRAME_ROWS}}), {{NUMBER_OF_PARTS}})
+                )
+
+                for { let i := 0 } lt(i, {{NUMBER_OF_FRI_LAYERS}}) { i := add(i, 1) } {
+                    mstore(add({{ZETAS}}, mul(32, i)), transcript_sample_element())
+                    transcript_append_word(mload(add({{FRI_ROOTS}}, mul(32, i))))
+                }
+                mstore(add({{ZETAS}}, mul(32, {{NUMBER_OF_FRI_LAYERS}})), transcript_sample_element())
+                for { let i := 0 } lt(i, {{REMAINDER_LENGTH}}) { i := add(i, 1) } {
+                    transcript_append_element(mload(add({{REMAINDER}}, mul(32, i))))
+                }
+
+                if {{GRINDING_FACTOR}} {
+                    mstore({{GRINDING_SEED}}, mload({{T_STATE}}))
+                    transcript_append_nonce(mload({{NONCE}}))
+                }
+                for { let i := 0 } lt(i, {{NUMBER_OF_QUERIES}}) { i := add(i, 1) } {
+                    mstore(add({{IOTAS}}, mul(32, i)), mod(transcript_sample_u64(), {{HALF_LDE_SIZE}}))
+                }
+            }
+
+            // Checks that `Keccak(Keccak(prefix || seed || grinding factor) || nonce)` has
+            // as many leading zeros as the grinding factor.
+            function check_grinding() {
+                mstore({{T_BUFFER}}, shl(192, {{GRINDING_PREFIX}}))
+                mstore(add({{T_BUFFER}}, 8), mload({{GRINDING_SEED}}))
+                mstore8(add({{T_BUFFER}}, 40), {{GRINDING_FACTOR}})
+                let innerHash := keccak256({{T_BUFFER}}, 41)
+                mstore({{T_BUFFER}}, innerHash)
+                mstore(add({{T_BUFFER}}, 32), shl(192, mload({{NONCE}})))
+                let head := shr(192, keccak256({{T_BUFFER}}, 40))
+                if iszero(lt(head, shl(sub(64, {{GRINDING_FACTOR}}), 1))) { fail() }
+            }
+
+            // ===================================
+            // ==========| Composition |==========
+            // ===================================
+
+            function ood_frame_element(row, column) -> pointer {
+                pointer := add({{OOD_FRAME}}, mul(32, add(mul(row, {{NUMBER_OF_COLUMNS}}), column)))
+            }
+
+            // Value at z of the composition polynomial given by its parts.
+            function composition_claimed_evaluation() -> value {
+                value := horner({{PARTS_OOD}}, {{NUMBER_OF_PARTS}}, mload({{Z}}))
+            }
+
+            // Value at z of the composition polynomial computed from the out of domain
+            // frame.
+            function composition_evaluation() -> value {
+                let z := mload({{Z}})
+                evaluate_periodic_columns(z)
+                evaluate_transitions(z)
+                let transitions := 0
+                for { let i := 0 } lt(i, {{NUMBER_OF_TRANSITIONS}}) { i := add(i, 1) } {
+                    transitions := addmod(
+                        transitions,
+                        mulmod(
+                            mload(add({{TRANSITIONS}}, mul(32, i))),
+                            mload(add({{CONSTRAINT_COEFFICIENTS}}, mul(32, i))),
+                            {{MODULUS}}
+                        ),
+                        {{MODULUS}}
+                    )
+                }
+                let zerofierInverse := finv(fsub(fpow(z, {{TRACE_LENGTH}}), 1))
+                value := addmod(mulmod(transitions, zerofierInverse, {{MODULUS}}), boundary_sum(z), {{MODULUS}})
+            }
+
+            // Product of `z - g⁻ᵏ` for `k` from 1 to `exemptions`, which vanishes on the
+            // last `exemptions` rows of the trace.
+            function exemptions_evaluation(z, exemptions) -> value {
+                value := 1
+                let root := {{TRACE_ROOT_INV}}
+                for { let k := 0 } lt(k, exemptions) { k := add(k, 1) } {
+                    value := mulmod(value, fsub(z, root), {{MODULUS}})
+                    root := mulmod(root, {{TRACE_ROOT_INV}}, {{MODULUS}})
+                }
+            }
+
+            // ===================================
+            // ==========|     FRI     |==========
+            // ===================================
+
+            function fold(evaluation, evaluationSymmetric, zeta, pointInverse) -> folded {
+                folded := addmod(
+                    addmod(evaluation, evaluationSymmetric, {{MODULUS}}),
+                    mulmod(mulmod(zeta, fsub(evaluation, evaluationSymmetric), {{MODULUS}}), pointInverse, {{MODULUS}}),
+                    {{MODULUS}}
+                )
+            }
+
+            // Value of the DEEP composition polynomial at `point`, given the opening of the
+            // trace and the parts of the composition polynomial at it.
+            function deep_evaluation(point, opening) -> value {
+                for { let row := 0 } lt(row, {{NUMBER_OF_FRAME_ROWS}}) { row := add(row, 1) } {
+                    let shiftedZ := mload(add({{SHIFTED_Z}}, mul(32, row)))
+                    mstore(add({{DENOMINATORS}}, mul(32, row)), finv(fsub(point, shiftedZ)))
+                }
+                for { let column := 0 } lt(column, {{NUMBER_OF_COLUMNS}}) { column := add(column, 1) } {
+                    value := addmod(value, trace_term(column, mload(add(opening, mul(32, column)))), {{MODULUS}})
+                }
+                let partsDenominator := finv(fsub(point, mload({{Z_POWER}})))
+                value := addmod(value, mulmod(parts_term(opening), partsDenominator, {{MODULUS}}), {{MODULUS}})
+            }
+
+            function parts_term(opening) -> term {
+                for { let i := 0 } lt(i, {{NUMBER_OF_PARTS}}) { i := add(i, 1) } {
+                    let evaluation := mload(add(opening, mul(32, add({{NUMBER_OF_COLUMNS}}, i))))
+                    let coefficient := mload(
+                        add({{DEEP_COEFFICIENTS}}, mul(32, add(mul({{NUMBER_OF_COLUMNS}}, {{NUMBER_OF_FRAME_ROWS}}), i)))
+                    )
+                    term := addmod(
+                        term,
+                        mulmod(fsub(evaluation, mload(add({{PARTS_OOD}}, mul(32, i)))), coefficient, {{MODULUS}}),
+                        {{MODULUS}}
+                    )
+                }
+            }
+
+            function trace_term(column, evaluation) -> term {
+                for { let row := 0 } lt(row, {{NUMBER_OF_FRAME_ROWS}}) { row := add(row, 1) } {
+                    let quotient := mulmod(
+                        fsub(evaluation, mload(ood_frame_element(row, column))),
+                        mload(add({{DENOMINATORS}}, mul(32, row))),
+                        {{MODULUS}}
+                    )
+                    let coefficient := mload(
+                        add({{DEEP_COEFFICIENTS}}, mul(32, add(mul(column, {{NUMBER_OF_FRAME_ROWS}}), row)))
+                    )
+                    term := addmod(term, mulmod(quotient, coefficient, {{MODULUS}}), {{MODULUS}})
+                }
+            }
+
+            // Folds the query `query` through all the FRI layers, starting from p₁ at the
+            // point of inverse `pointInverse`, and stores the leaf it opens in each layer.
+            function fold_layers(query, index, value, pointInverse) -> folded {
+                folded := value
+                for { let k := 0 } lt(k, {{NUMBER_OF_FRI_LAYERS}}) { k := add(k, 1) } {
+                    let evaluationSymmetric := mload(
+                        add({{FRI_SYMMETRIC}}, mul(32, add(mul(query, {{NUMBER_OF_FRI_LAYERS}}), k)))
+                    )
+                    let evaluation := folded
+                    let offsetInverse := pointInverse
+                    if and(index, 1) {
+                        evaluation := evaluationSymmetric
+                        evaluationSymmetric := folded
+                        offsetInverse := fneg(pointInverse)
+                    }
+                    store_leaf(
+                        add({{FRI_LEAVES}}, mul(k, mul(64, {{NUMBER_OF_QUERIES}}))),
+                        query,
+                        shr(1, index),
+                        hash_pair(evaluation, evaluationSymmetric)
+                    )
+                    folded := fold(evaluation, evaluationSymmetric, mload(add({{ZETAS}}, mul(32, add(k, 1)))), offsetInverse)
+                    index := shr(1, index)
+                    pointInverse := mulmod(pointInverse, pointInverse, {{MODULUS}})
+                }
+            }
+
+            function verify_fri_queries() {
+                mstore({{Z_POWER}}, fpow(mload({{Z}}), {{NUMBER_OF_PARTS}}))
+                shift_z(mload({{Z}}))
+                for { let query := 0 } lt(query, {{NUMBER_OF_QUERIES}}) { query := add(query, 1) } {
+                    verify_fri_query(query)
+                }
+            }
+
+            function verify_fri_query(query) {
+                let iota := mload(add({{IOTAS}}, mul(32, query)))
+                let point := mulmod(
+                    {{COSET_OFFSET}},
+                    fpow({{LDE_ROOT}}, reverse_bits(mul(2, iota), {{LOG_LDE_SIZE}})),
+                    {{MODULUS}}
+                )
+                let opening := add({{OPENINGS}}, mul(32, mul(query, {{OPENING_SIZE}})))
+                let openingSymmetric := add({{OPENINGS_SYMMETRIC}}, mul(32, mul(query, {{OPENING_SIZE}})))
+                store_trace_leaves(query, iota, opening, openingSymmetric)
+
+                let pointInverse := finv(point)
+                let value := fold(
+                    deep_evaluation(point, opening),
+                    deep_evaluation(fneg(point), openingSymmetric),
+                    mload({{ZETAS}}),
+                    pointInverse
+                )
+                value := fold_layers(query, iota, value, mulmod(pointInverse, pointInverse, {{MODULUS}}))
+                let remainderPoint := fpow(point, {{TOTAL_FOLDING_FACTOR}})
+                if iszero(eq(value, horner({{REMAINDER}}, {{REMAINDER_LENGTH}}, remainderPoint))) { fail() }
+            }
+
+            // ===================================
+            // ==========|   Merkle    |==========
+            // ===================================
+
+            // Keccak256 of the elements at `source`, in Montgomery form, followed by the
+            // ones at `sourceNext`.
+            function hash_elements(source, count, sourceNext, countNext) -> hash {
+                for { let i := 0 } lt(i, count) { i := add(i, 1) } {
+                    let element := mload(add(source, mul(32, i)))
+                    mstore(add({{LEAF_BUFFER}}, mul(32, i)), mulmod(element, {{MONTGOMERY_R}}, {{MODULUS}}))
+                }
+                for { let i := 0 } lt(i, countNext) { i := add(i, 1) } {
+                    let element := mload(add(sourceNext, mul(32, i)))
+                    mstore(add({{LEAF_BUFFER}}, mul(32, add(count, i))), mulmod(element, {{MONTGOMERY_R}}, {{MODULUS}}))
+                }
+                hash := keccak256({{LEAF_BUFFER}}, mul(32, add(count, countNext)))
+            }
+
+            function hash_pair(evaluation, evaluationSymmetric) -> hash {
+                mstore({{LEAF_BUFFER}}, evaluation)
+                mstore(add({{LEAF_BUFFER}}, 32), evaluationSymmetric)
+                hash := hash_elements({{LEAF_BUFFER}}, 2, 0, 0)
+            }
+
+            function store_leaf(leaves, i, position, hash) {
+                let entry := add(leaves, mul(64, i))
+                mstore(entry, position)
+                mstore(add(entry, 32), hash)
+            }
+
+            // Stores the leaves opened by the query `query` in the trace trees, at the
+            // positions 2𝜄 and 2𝜄 + 1, and in the tree of the composition polynomial, at 𝜄.
+            function store_trace_leaves(query, iota, opening, openingSymmetric) {
+                store_row_leaves({{MAIN_LEAVES}}, query, iota, opening, openingSymmetric, {{NUMBER_OF_MAIN_COLUMNS}})
+                let auxiliaryOffset := mul(32, {{NUMBER_OF_MAIN_COLUMNS}})
+                store_row_leaves(
+                    {{AUXILIARY_LEAVES}},
+                    query,
+                    iota,
+                    add(opening, auxiliaryOffset),
+                    add(openingSymmetric, auxiliaryOffset),
+                    {{NUMBER_OF_AUXILIARY_COLUMNS}}
+                )
+                let partsOffset := mul(32, {{NUMBER_OF_COLUMNS}})
+                let parts := hash_elements(
+                    add(opening, partsOffset),
+                    {{NUMBER_OF_PARTS}},
+                    add(openingSymmetric, partsOffset),
+                    {{NUMBER_OF_PARTS}}
+                )
+                store_leaf({{COMPOSITION_LEAVES}}, query, iota, parts)
+            }
+
+            function store_row_leaves(leaves, query, iota, row, rowSymmetric, width) {
+                store_leaf(leaves, mul(2, query), mul(2, iota), hash_elements(row, width, 0, 0))
+                store_leaf(leaves, add(mul(2, query), 1), add(mul(2, iota), 1), hash_elements(rowSymmetric, width, 0, 0))
+            }
+
+            function hash_node(node, sibling, isRight) -> hash {
+                mstore(0, node)
+                mstore(32, sibling)
+                if isRight {
+                    mstore(0, sibling)
+                    mstore(32, node)
+                }
+                hash := keccak256(0, 64)
+            }
+
+            // Sorts the `count` leaves at `leaves` by position.
+            function sort_leaves(leaves, count) {
+                for { let i := 1 } lt(i, count) { i := add(i, 1) } {
+                    let position := mload(add(leaves, mul(64, i)))
+                    let hash := mload(add(leaves, add(mul(64, i), 32)))
+                    let j := i
+                    for {} gt(j, 0) { j := sub(j, 1) } {
+                        let previous := add(leaves, mul(64, sub(j, 1)))
+                        if iszero(gt(mload(previous), position)) { break }
+                        mstore(add(previous, 64), mload(previous))
+                        mstore(add(previous, 96), mload(add(previous, 32)))
+                    }
+                    store_leaf(leaves, j, position, hash)
+                }
+            }
+
+            // Removes the repeated positions of the sorted leaves at `leaves`, which must
+            // have the same hash.
+            function deduplicate_leaves(leaves, count) -> unique {
+                unique := 1
+                for { let i := 1 } lt(i, count) { i := add(i, 1) } {
+                    let entry := add(leaves, mul(64, i))
+                    let last := add(leaves, mul(64, sub(unique, 1)))
+                    if eq(mload(entry), mload(last)) {
+                        if iszero(eq(mload(add(entry, 32)), mload(add(last, 32)))) { fail() }
+                        continue
+                    }
+                    store_leaf(leaves, unique, mload(entry), mload(add(entry, 32)))
+                    unique := add(unique, 1)
+                }
+            }
+
+            // Replaces the sorted leaves at `leaves` by their parents, taking the siblings
+            // that are not leaves from the proof.
+            function hash_level(leaves, count) -> parents {
+                for { let i := 0 } lt(i, count) { i := add(i, 1) } {
+                    let entry := add(leaves, mul(64, i))
+                    let position := mload(entry)
+                    let paired := and(
+                        iszero(and(position, 1)),
+                        and(lt(add(i, 1), count), eq(mload(add(entry, 64)), add(position, 1)))
+                    )
+                    let sibling := 0
+                    if paired {
+                        sibling := mload(add(entry, 96))
+                        i := add(i, 1)
+                    }
+                    if iszero(paired) {
+                        let remaining := mload({{AUTH_REMAINING}})
+                        if iszero(remaining) { fail() }
+                        mstore({{AUTH_REMAINING}}, sub(remaining, 1))
+                        sibling := read_word()
+                    }
+                    let parent := hash_node(mload(add(entry, 32)), sibling, and(position, 1))
+                    store_leaf(leaves, parents, shr(1, position), parent)
+                    parents := add(parents, 1)
+                }
+            }
+
+            // Checks the batched Merkle proof read from the proof of the `count` leaves at
+            // `leaves` against `root`. The proof is its number of nodes followed by them.
+            function verify_batch(root, numberOfLeaves, leaves, count) {
+                sort_leaves(leaves, count)
+                count := deduplicate_leaves(leaves, count)
+                mstore({{AUTH_REMAINING}}, read_word())
+                for {} gt(numberOfLeaves, 1) { numberOfLeaves := shr(1, numberOfLeaves) } {
+                    count := hash_level(leaves, count)
+                }
+                if mload({{AUTH_REMAINING}}) { fail() }
+                if iszero(eq(mload(add(leaves, 32)), root)) { fail() }
+            }
+
+            function verify_openings() {
+                verify_batch(mload({{MAIN_ROOT}}), {{LDE_SIZE}}, {{MAIN_LEAVES}}, mul(2, {{NUMBER_OF_QUERIES}}))
+                if {{NUMBER_OF_AUXILIARY_ROOTS}} {
+                    verify_batch(mload({{AUXILIARY_ROOT}}), {{LDE_SIZE}}, {{AUXILIARY_LEAVES}}, mul(2, {{NUMBER_OF_QUERIES}}))
+                }
+                verify_batch(mload({{COMPOSITION_ROOT}}), {{HALF_LDE_SIZE}}, {{COMPOSITION_LEAVES}}, {{NUMBER_OF_QUERIES}})
+                for { let k := 0 } lt(k, {{NUMBER_OF_FRI_LAYERS}}) { k := add(k, 1) } {
+                    verify_batch(
+                        mload(add({{FRI_ROOTS}}, mul(32, k))),
+                        shr(add(k, 2), {{LDE_SIZE}}),
+                        add({{FRI_LEAVES}}, mul(k, mul(64, {{NUMBER_OF_QUERIES}}))),
+                        {{NUMBER_OF_QUERIES}}
+                    )
+                }
+            }
+
+            // ===================================
+            // ==========|     AIR     |==========
+            // ===================================
+{{AIR_FUNCTIONS}}
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use lambdaworks_math::field::{element::FieldElement, traits::IsFFTField};
+use lambdaworks_math::field::{element::FieldElement, traits::IsField};
 
 /// A two-dimensional Table holding field elements, arranged in a row-major order.
 /// This is the basic underlying data structure used for any two-dimensional component in the
@@ -6,13 +6,17 @@ use lambdaworks_math::field::{element::FieldElement, traits::IsFFTField};
 /// Since this struct is a representation of a two-dimensional table, all rows should have the same
 /// length.
 #[derive(Clone, Default, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
-pub struct Table<F: IsFFTField> {
+#[serde(bound(
+    serialize = "FieldElement<F>: serde::Serialize",
+    deserialize = "FieldElement<F>: serde::Deserialize<'de>"
+))]
+pub struct Table<F: IsField> {
     pub data: Vec<FieldElement<F>>,
     pub width: usize,
     pub height: usize,
 }
 
-impl<F: IsFFTField> Table<F> {
+impl<F: IsField> Table<F> {
     /// Crates a new Table instance from a one-dimensional array in row major order
     /// and the intended width of the table.
     pub fn new(data: &[FieldElement<F>], width: usize) -> Self {
@@ -1,20 +1,56 @@
-use lambdaworks_math::field::{
-    element::FieldElement, fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
+use lambdaworks_crypto::merkle_tree::backends::types::{
+    BatchPoseidonBabybearBackend, BatchPoseidonStark252Backend, BatchSha2_256Backend,
+    BatchSha3_512Backend,
+};
+use lambdaworks_math::{
+    errors::DeserializationError,
+    field::{
+        element::FieldElement,
+        fields::fft_friendly::{
+            babybear::{Babybear31PrimeField, Degree4Babybear31ExtensionField},
+            goldilocks::{
+                Degree2Goldilocks64ExtensionField, Degree3Goldilocks64ExtensionField,
+                Goldilocks64Field,
+            },
+            stark_252_prime_field::Stark252PrimeField,
+        },
+    },
+    traits::{Deserializable, Serializable},
 };
 
 use crate::{
+    config::{BatchedMerkleTreeBackend, Commitment},
+    debug::{check_trace, validate_symbolic_transitions},
     examples::{
         dummy_air::{self, DummyAIR},
         fibonacci_2_cols_shifted::{self, Fibonacci2ColsShifted},
         fibonacci_2_columns::{self, Fibonacci2ColsAIR},
         fibonacci_rap::{fibonacci_rap_trace, FibonacciRAP, FibonacciRAPPublicInputs},
+        fixed_column_sum::{fixed_column_sum_trace, FixedColumnSumAIR, FixedColumnSumPublicInputs},
+        power_air::{self, PowerAIR, PowerPublicInputs},
         quadratic_air::{self, QuadraticAIR, QuadraticPublicInputs},
+        range_check_logup::{range_check_logup_trace, RangeCheckLogUpAIR},
+        range_check_tables::{
+            range_check_range_trace, range_check_values_trace, RangeCheckAIR, RangeCheckTable,
+        },
         simple_fibonacci::{self, FibonacciAIR, FibonacciPublicInputs},
+        simple_periodic_cols::{
+            simple_periodic_trace, SimplePeriodicAIR, SimplePeriodicPublicInputs,
+        },
     },
-    proof::options::ProofOptions,
-    prover::{IsStarkProver, Prover},
-    transcript::StoneProverTranscript,
-    verifier::{IsStarkVerifier, Verifier},
+    grinding::GrindingHash,
+    multi_table::{prover::IsMultiTableStarkProver, verifier::IsMultiTableStarkVerifier},
+    proof::{
+        format::{VersionedStarkProof, FORMAT_VERSION, MAGIC},
+        options::ProofOptions,
+        stark::StarkProof,
+    },
+    prover::{IsStarkProver, Prover, ProverOptions, ProverWithBackends, ProvingError},
+    trace::TraceTable,
+    traits::AIR,
+    transcript::{DefaultTranscript, PoseidonTranscript, StoneProverTranscript},
+    verification_key::VerificationKey,
+    verifier::{IsStarkVerifier, Verifier, VerifierWithBackends},
     Felt252,
 };
 
@@ -54,6 +90,10 @@ fn test_prove_fib17() {
         fri_number_of_queries: 7,
         coset_offset: 3,
         grinding_factor: 1,
+        zero_knowledge: false,
+        fri_folding_factor: 2,
+        fri_max_remainder_degree: 0,
+        grinding_hash: GrindingHash::Keccak256,
     };
 
     let pub_inputs = FibonacciPublicInputs {
@@ -155,6 +195,95 @@ fn test_prove_quadratic() {
     ));
 }
 
+fn prove_and_verify_power(exponent: usize) -> bool {
+    let trace = power_air::power_trace(Felt252::from(3), exponent, 16);
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let pub_inputs = PowerPublicInputs {
+        a0: Felt252::from(3),
+        exponent,
+    };
+
+    let proof = Prover::prove::<PowerAIR<Stark252PrimeField>>(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    Verifier::verify::<PowerAIR<Stark252PrimeField>>(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+}
+
+#[test_log::test]
+fn test_prove_power_with_two_composition_parts() {
+    assert!(prove_and_verify_power(3));
+}
+
+#[test_log::test]
+fn test_prove_power_with_three_composition_parts() {
+    assert!(prove_and_verify_power(4));
+}
+
+#[test_log::test]
+fn test_prove_power_with_four_composition_parts() {
+    assert!(prove_and_verify_power(5));
+}
+
+#[test_log::test]
+fn test_prove_power_with_blowup_factor_too_small_fails() {
+    let trace = power_air::power_trace(Felt252::from(3), 6, 16);
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let pub_inputs = PowerPublicInputs {
+        a0: Felt252::from(3),
+        exponent: 6,
+    };
+
+    let result = Prover::prove::<PowerAIR<Stark252PrimeField>>(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    );
+    assert!(matches!(result, Err(ProvingError::WrongParameter(_))));
+}
+
+#[test_log::test]
+fn test_verify_power_with_wrong_number_of_composition_parts_fails() {
+    let trace = power_air::power_trace(Felt252::from(3), 3, 16);
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let pub_inputs = PowerPublicInputs {
+        a0: Felt252::from(3),
+        exponent: 3,
+    };
+
+    let mut proof = Prover::prove::<PowerAIR<Stark252PrimeField>>(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    proof
+        .composition_poly_parts_ood_evaluation
+        .push(Felt252::zero());
+    assert!(!Verifier::verify::<PowerAIR<Stark252PrimeField>>(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[])
+    ));
+}
+
 #[test_log::test]
 fn test_prove_rap_fib() {
     let steps = 16;
@@ -184,19 +313,1829 @@ fn test_prove_rap_fib() {
 }
 
 #[test_log::test]
-fn test_prove_dummy() {
-    let trace_length = 16;
-    let trace = dummy_air::dummy_trace(trace_length);
+fn test_prove_rap_fib_babybear_with_challenges_in_degree_4_extension() {
+    type FE = FieldElement<Babybear31PrimeField>;
+    type BabybearFibonacciRAP = FibonacciRAP<Babybear31PrimeField, Degree4Babybear31ExtensionField>;
+
+    let steps = 16;
+    let trace = fibonacci_rap_trace([FE::from(1), FE::from(1)], steps);
 
     let proof_options = ProofOptions::default_test_options();
 
-    let proof =
-        Prover::prove::<DummyAIR>(&trace, &(), &proof_options, StoneProverTranscript::new(&[]))
-            .unwrap();
-    assert!(Verifier::verify::<DummyAIR>(
+    let pub_inputs = FibonacciRAPPublicInputs {
+        steps,
+        a0: FE::one(),
+        a1: FE::one(),
+    };
+
+    let proof = Prover::prove::<BabybearFibonacciRAP>(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        DefaultTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(Verifier::verify::<BabybearFibonacciRAP>(
         &proof,
-        &(),
+        &pub_inputs,
+        &proof_options,
+        DefaultTranscript::new(&[])
+    ));
+}
+
+#[test_log::test]
+fn test_prove_rap_fib_goldilocks_with_challenges_in_degree_2_extension() {
+    type FE = FieldElement<Goldilocks64Field>;
+    type GoldilocksFibonacciRAP =
+        FibonacciRAP<Goldilocks64Field, Degree2Goldilocks64ExtensionField>;
+
+    let steps = 16;
+    let trace = fibonacci_rap_trace([FE::from(1), FE::from(1)], steps);
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let pub_inputs = FibonacciRAPPublicInputs {
+        steps,
+        a0: FE::one(),
+        a1: FE::one(),
+    };
+
+    let proof = Prover::prove::<GoldilocksFibonacciRAP>(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        DefaultTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(Verifier::verify::<GoldilocksFibonacciRAP>(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        DefaultTranscript::new(&[])
+    ));
+}
+
+#[test_log::test]
+fn test_binary_proof_of_rap_fib_goldilocks_in_degree_3_extension_round_trips() {
+    type FE = FieldElement<Goldilocks64Field>;
+    type GoldilocksFibonacciRAP =
+        FibonacciRAP<Goldilocks64Field, Degree3Goldilocks64ExtensionField>;
+    type BinaryProof = VersionedStarkProof<Goldilocks64Field, Degree3Goldilocks64ExtensionField>;
+
+    let steps = 16;
+    let trace = fibonacci_rap_trace([FE::from(1), FE::from(1)], steps);
+    let proof_options = ProofOptions::default_test_options().with_zero_knowledge();
+    let pub_inputs = FibonacciRAPPublicInputs {
+        steps,
+        a0: FE::one(),
+        a1: FE::one(),
+    };
+
+    let proof = Prover::prove::<GoldilocksFibonacciRAP>(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        DefaultTranscript::new(&[]),
+    )
+    .unwrap();
+    let bytes = BinaryProof::new(proof, proof_options.clone()).serialize();
+
+    let decoded = BinaryProof::deserialize(&bytes).unwrap();
+    assert_eq!(decoded.serialize(), bytes);
+    decoded.check_options(&proof_options).unwrap();
+    assert!(Verifier::verify::<GoldilocksFibonacciRAP>(
+        &decoded.proof,
+        &pub_inputs,
+        &proof_options,
+        DefaultTranscript::new(&[])
+    ));
+}
+
+#[test_log::test]
+fn test_prove_fib_with_poseidon_commitments_and_transcript() {
+    type PoseidonProver = ProverWithBackends<
+        Stark252PrimeField,
+        Stark252PrimeField,
+        BatchPoseidonStark252Backend,
+        BatchPoseidonStark252Backend,
+    >;
+    type PoseidonVerifier = VerifierWithBackends<
+        Stark252PrimeField,
+        Stark252PrimeField,
+        BatchPoseidonStark252Backend,
+        BatchPoseidonStark252Backend,
+    >;
+
+    let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 8);
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let proof = PoseidonProver::prove::<FibonacciAIR<Stark252PrimeField>>(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        PoseidonTranscript::<Stark252PrimeField>::new(&[]),
+    )
+    .unwrap();
+    assert!(
+        PoseidonVerifier::verify::<FibonacciAIR<Stark252PrimeField>>(
+            &proof,
+            &pub_inputs,
+            &proof_options,
+            PoseidonTranscript::<Stark252PrimeField>::new(&[]),
+        )
+    );
+
+    // A proof is bound to the hash it was built with.
+    assert!(!Verifier::verify::<FibonacciAIR<Stark252PrimeField>>(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    ));
+}
+
+#[test_log::test]
+fn test_prove_rap_fib_babybear_with_poseidon_commitments_and_transcript() {
+    type FE = FieldElement<Babybear31PrimeField>;
+    type BabybearFibonacciRAP = FibonacciRAP<Babybear31PrimeField, Degree4Babybear31ExtensionField>;
+    type PoseidonProver = ProverWithBackends<
+        Babybear31PrimeField,
+        Degree4Babybear31ExtensionField,
+        BatchPoseidonBabybearBackend,
+        BatchPoseidonBabybearBackend<Degree4Babybear31ExtensionField>,
+    >;
+    type PoseidonVerifier = VerifierWithBackends<
+        Babybear31PrimeField,
+        Degree4Babybear31ExtensionField,
+        BatchPoseidonBabybearBackend,
+        BatchPoseidonBabybearBackend<Degree4Babybear31ExtensionField>,
+    >;
+
+    let steps = 16;
+    let trace = fibonacci_rap_trace([FE::from(1), FE::from(1)], steps);
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let pub_inputs = FibonacciRAPPublicInputs {
+        steps,
+        a0: FE::one(),
+        a1: FE::one(),
+    };
+
+    let proof = PoseidonProver::prove::<BabybearFibonacciRAP>(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        PoseidonTranscript::<Babybear31PrimeField>::new(&[]),
+    )
+    .unwrap();
+    assert!(PoseidonVerifier::verify::<BabybearFibonacciRAP>(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        PoseidonTranscript::<Babybear31PrimeField>::new(&[])
+    ));
+}
+
+#[test_log::test]
+fn test_prove_fib_with_64_byte_commitments() {
+    type Sha3_512Prover = ProverWithBackends<
+        Stark252PrimeField,
+        Stark252PrimeField,
+        BatchSha3_512Backend<Stark252PrimeField>,
+        BatchSha3_512Backend<Stark252PrimeField>,
+    >;
+    type Sha3_512Verifier = VerifierWithBackends<
+        Stark252PrimeField,
+        Stark252PrimeField,
+        BatchSha3_512Backend<Stark252PrimeField>,
+        BatchSha3_512Backend<Stark252PrimeField>,
+    >;
+
+    let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 8);
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let proof = Sha3_512Prover::prove::<FibonacciAIR<Stark252PrimeField>>(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert_eq!(proof.composition_poly_root.len(), 64);
+    assert!(
+        Sha3_512Verifier::verify::<FibonacciAIR<Stark252PrimeField>>(
+            &proof,
+            &pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        )
+    );
+}
+
+#[test_log::test]
+fn test_prove_rap_fib_with_sha2_256_commitments() {
+    type Sha2_256Prover = ProverWithBackends<
+        Stark252PrimeField,
+        Stark252PrimeField,
+        BatchSha2_256Backend<Stark252PrimeField>,
+        BatchSha2_256Backend<Stark252PrimeField>,
+    >;
+    type Sha2_256Verifier = VerifierWithBackends<
+        Stark252PrimeField,
+        Stark252PrimeField,
+        BatchSha2_256Backend<Stark252PrimeField>,
+        BatchSha2_256Backend<Stark252PrimeField>,
+    >;
+
+    let steps = 16;
+    let trace = fibonacci_rap_trace([Felt252::from(1), Felt252::from(1)], steps);
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let pub_inputs = FibonacciRAPPublicInputs {
+        steps,
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let proof = Sha2_256Prover::prove::<FibonacciRAP<Stark252PrimeField>>(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(
+        Sha2_256Verifier::verify::<FibonacciRAP<Stark252PrimeField>>(
+            &proof,
+            &pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&[])
+        )
+    );
+
+    // Both backends have 32-byte nodes, but a proof is bound to the hash it was built with.
+    assert!(!Verifier::verify::<FibonacciRAP<Stark252PrimeField>>(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[])
+    ));
+}
+
+#[test_log::test]
+fn test_prove_simple_periodic() {
+    let trace = simple_periodic_trace(Felt252::from(3), 32);
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let pub_inputs = SimplePeriodicPublicInputs {
+        a0: Felt252::from(3),
+    };
+
+    let proof = Prover::prove::<SimplePeriodicAIR<Stark252PrimeField>>(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(Verifier::verify::<SimplePeriodicAIR<Stark252PrimeField>>(
+        &proof,
+        &pub_inputs,
         &proof_options,
         StoneProverTranscript::new(&[])
     ));
 }
+
+#[test_log::test]
+fn test_prove_simple_periodic_with_wrong_round_constants_fails() {
+    // Adds 1 in every step instead of cycling through the round constants.
+    let column: Vec<_> = (0..32u64).map(|i| Felt252::from(3 + i)).collect();
+    let trace = TraceTable::from_columns(&[column]);
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let pub_inputs = SimplePeriodicPublicInputs {
+        a0: Felt252::from(3),
+    };
+
+    let proof = Prover::prove::<SimplePeriodicAIR<Stark252PrimeField>>(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(!Verifier::verify::<SimplePeriodicAIR<Stark252PrimeField>>(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[])
+    ));
+}
+
+#[test_log::test]
+fn test_prove_simple_periodic_with_a_trace_shorter_than_the_period_fails() {
+    // The round constants have period 4.
+    let trace = simple_periodic_trace(Felt252::from(3), 2);
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let pub_inputs = SimplePeriodicPublicInputs {
+        a0: Felt252::from(3),
+    };
+
+    let result = Prover::prove::<SimplePeriodicAIR<Stark252PrimeField>>(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    );
+    assert!(matches!(result, Err(ProvingError::WrongParameter(_))));
+}
+
+#[test_log::test]
+fn test_prove_range_check_logup() {
+    let trace = range_check_logup_trace::<Stark252PrimeField>(&[3, 1, 4, 1, 5, 1, 2, 6]);
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let proof = Prover::prove::<RangeCheckLogUpAIR<Stark252PrimeField>>(
+        &trace,
+        &(),
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(Verifier::verify::<RangeCheckLogUpAIR<Stark252PrimeField>>(
+        &proof,
+        &(),
+        &proof_options,
+        StoneProverTranscript::new(&[])
+    ));
+}
+
+#[test_log::test]
+fn test_prove_range_check_logup_with_value_out_of_range_fails() {
+    let trace = range_check_logup_trace::<Stark252PrimeField>(&[3, 1, 4, 1, 9, 1, 2, 6]);
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let proof = Prover::prove::<RangeCheckLogUpAIR<Stark252PrimeField>>(
+        &trace,
+        &(),
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(!Verifier::verify::<RangeCheckLogUpAIR<Stark252PrimeField>>(
+        &proof,
+        &(),
+        &proof_options,
+        StoneProverTranscript::new(&[])
+    ));
+}
+
+const RANGE_CHECKED_VALUES: [u64; 16] = [3, 1, 4, 1, 5, 1, 2, 6, 5, 3, 5, 7, 0, 7, 2, 3];
+
+#[test_log::test]
+fn test_prove_range_check_tables() {
+    let traces = [
+        range_check_values_trace::<Stark252PrimeField>(&RANGE_CHECKED_VALUES),
+        range_check_range_trace(&RANGE_CHECKED_VALUES, 8),
+    ];
+    let pub_inputs = [RangeCheckTable::Values, RangeCheckTable::Range];
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let proof = Prover::prove_multi_table::<RangeCheckAIR<Stark252PrimeField>, _>(
+        &traces,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(Verifier::verify_multi_table::<
+        RangeCheckAIR<Stark252PrimeField>,
+    >(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[])
+    ));
+}
+
+#[test_log::test]
+fn test_prove_range_check_tables_with_value_out_of_range_fails() {
+    let mut values = RANGE_CHECKED_VALUES;
+    values[5] = 8;
+    let traces = [
+        range_check_values_trace::<Stark252PrimeField>(&values),
+        range_check_range_trace(&values, 8),
+    ];
+    let pub_inputs = [RangeCheckTable::Values, RangeCheckTable::Range];
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let result = Prover::prove_multi_table::<RangeCheckAIR<Stark252PrimeField>, _>(
+        &traces,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    );
+    assert!(matches!(result, Err(ProvingError::WrongParameter(_))));
+}
+
+#[test_log::test]
+fn test_verify_range_check_tables_with_tampered_sums_fails() {
+    let traces = [
+        range_check_values_trace::<Stark252PrimeField>(&RANGE_CHECKED_VALUES),
+        range_check_range_trace(&RANGE_CHECKED_VALUES, 8),
+    ];
+    let pub_inputs = [RangeCheckTable::Values, RangeCheckTable::Range];
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let mut proof = Prover::prove_multi_table::<RangeCheckAIR<Stark252PrimeField>, _>(
+        &traces,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    // The sums still add up to zero, but do not match the auxiliary traces.
+    proof.cross_table_sums[0] += Felt252::one();
+    proof.cross_table_sums[1] = proof.cross_table_sums[1] - Felt252::one();
+    assert!(!Verifier::verify_multi_table::<
+        RangeCheckAIR<Stark252PrimeField>,
+    >(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[])
+    ));
+}
+
+#[test_log::test]
+fn test_prove_dummy() {
+    let trace_length = 16;
+    let trace = dummy_air::dummy_trace(trace_length);
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let proof =
+        Prover::prove::<DummyAIR>(&trace, &(), &proof_options, StoneProverTranscript::new(&[]))
+            .unwrap();
+    assert!(Verifier::verify::<DummyAIR>(
+        &proof,
+        &(),
+        &proof_options,
+        StoneProverTranscript::new(&[])
+    ));
+}
+
+#[test_log::test]
+fn test_prove_fib_with_zero_knowledge() {
+    let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 32);
+
+    let proof_options = ProofOptions::default_test_options().with_zero_knowledge();
+
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let proof = Prover::prove::<FibonacciAIR<Stark252PrimeField>>(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(Verifier::verify::<FibonacciAIR<Stark252PrimeField>>(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    ));
+}
+
+#[test_log::test]
+fn test_zero_knowledge_proofs_of_the_same_trace_differ() {
+    let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 32);
+
+    let proof_options = ProofOptions::default_test_options().with_zero_knowledge();
+
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let prove = || {
+        Prover::prove::<FibonacciAIR<Stark252PrimeField>>(
+            &trace,
+            &pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        )
+        .unwrap()
+    };
+    let first_proof = prove();
+    let second_proof = prove();
+
+    assert_ne!(
+        first_proof.lde_trace_merkle_roots,
+        second_proof.lde_trace_merkle_roots
+    );
+    assert_ne!(
+        first_proof.trace_ood_frame_evaluations.get_row(0),
+        second_proof.trace_ood_frame_evaluations.get_row(0)
+    );
+    for proof in [first_proof, second_proof] {
+        assert!(Verifier::verify::<FibonacciAIR<Stark252PrimeField>>(
+            &proof,
+            &pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        ));
+    }
+}
+
+#[test_log::test]
+fn test_zero_knowledge_proof_does_not_verify_without_zero_knowledge() {
+    let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 32);
+
+    let proof_options = ProofOptions::default_test_options().with_zero_knowledge();
+
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let proof = Prover::prove::<FibonacciAIR<Stark252PrimeField>>(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(!Verifier::verify::<FibonacciAIR<Stark252PrimeField>>(
+        &proof,
+        &pub_inputs,
+        &ProofOptions::default_test_options(),
+        StoneProverTranscript::new(&[]),
+    ));
+}
+
+#[test_log::test]
+fn test_prove_with_zero_knowledge_fails_when_the_trace_is_too_short() {
+    let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 8);
+
+    let proof_options = ProofOptions::default_test_options().with_zero_knowledge();
+
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let result = Prover::prove::<FibonacciAIR<Stark252PrimeField>>(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    );
+    assert!(matches!(result, Err(ProvingError::WrongParameter(_))));
+}
+
+#[test_log::test]
+fn test_prove_quadratic_with_zero_knowledge() {
+    let trace = quadratic_air::quadratic_trace(Felt252::from(3), 32);
+
+    let proof_options = ProofOptions::default_test_options().with_zero_knowledge();
+
+    let pub_inputs = QuadraticPublicInputs {
+        a0: Felt252::from(3),
+    };
+
+    let proof = Prover::prove::<QuadraticAIR<Stark252PrimeField>>(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(Verifier::verify::<QuadraticAIR<Stark252PrimeField>>(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[])
+    ));
+}
+
+#[test_log::test]
+fn test_prove_rap_fib_babybear_with_zero_knowledge() {
+    type FE = FieldElement<Babybear31PrimeField>;
+    type BabybearFibonacciRAP = FibonacciRAP<Babybear31PrimeField, Degree4Babybear31ExtensionField>;
+
+    let steps = 16;
+    let trace = fibonacci_rap_trace([FE::from(1), FE::from(1)], steps);
+
+    let proof_options = ProofOptions::default_test_options().with_zero_knowledge();
+
+    let pub_inputs = FibonacciRAPPublicInputs {
+        steps,
+        a0: FE::one(),
+        a1: FE::one(),
+    };
+
+    let proof = Prover::prove::<BabybearFibonacciRAP>(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        DefaultTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(Verifier::verify::<BabybearFibonacciRAP>(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        DefaultTranscript::new(&[])
+    ));
+}
+
+fn prove_and_verify_fib_with_fri_folding_factor(fri_folding_factor: u8) -> bool {
+    let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 64);
+
+    let proof_options =
+        ProofOptions::default_test_options().with_fri_folding_factor(fri_folding_factor);
+
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let proof = Prover::prove::<FibonacciAIR<Stark252PrimeField>>(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    Verifier::verify::<FibonacciAIR<Stark252PrimeField>>(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+}
+
+#[test_log::test]
+fn test_prove_fib_with_fri_folding_factor_4() {
+    assert!(prove_and_verify_fib_with_fri_folding_factor(4));
+}
+
+#[test_log::test]
+fn test_prove_fib_with_fri_folding_factor_8() {
+    assert!(prove_and_verify_fib_with_fri_folding_factor(8));
+}
+
+#[test_log::test]
+fn test_prove_fib_with_fri_folding_factor_16() {
+    assert!(prove_and_verify_fib_with_fri_folding_factor(16));
+}
+
+#[test_log::test]
+fn test_prove_with_unsupported_fri_folding_factor_fails() {
+    let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 8);
+
+    let proof_options = ProofOptions::default_test_options().with_fri_folding_factor(3);
+
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let result = Prover::prove::<FibonacciAIR<Stark252PrimeField>>(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    );
+    assert!(matches!(result, Err(ProvingError::WrongParameter(_))));
+}
+
+#[test_log::test]
+fn test_proof_with_fri_folding_factor_4_does_not_verify_with_folding_factor_2() {
+    let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 64);
+
+    let proof_options = ProofOptions::default_test_options().with_fri_folding_factor(4);
+
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let proof = Prover::prove::<FibonacciAIR<Stark252PrimeField>>(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(!Verifier::verify::<FibonacciAIR<Stark252PrimeField>>(
+        &proof,
+        &pub_inputs,
+        &ProofOptions::default_test_options(),
+        StoneProverTranscript::new(&[]),
+    ));
+}
+
+#[test_log::test]
+fn test_prove_rap_fib_babybear_with_fri_folding_factor_8() {
+    type FE = FieldElement<Babybear31PrimeField>;
+    type BabybearFibonacciRAP = FibonacciRAP<Babybear31PrimeField, Degree4Babybear31ExtensionField>;
+
+    let steps = 32;
+    let trace = fibonacci_rap_trace([FE::from(1), FE::from(1)], steps);
+
+    let proof_options = ProofOptions::default_test_options().with_fri_folding_factor(8);
+
+    let pub_inputs = FibonacciRAPPublicInputs {
+        steps,
+        a0: FE::one(),
+        a1: FE::one(),
+    };
+
+    let proof = Prover::prove::<BabybearFibonacciRAP>(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        DefaultTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(Verifier::verify::<BabybearFibonacciRAP>(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        DefaultTranscript::new(&[])
+    ));
+}
+
+#[test_log::test]
+fn test_prove_range_check_tables_with_fri_folding_factor_4() {
+    let traces = [
+        range_check_values_trace::<Stark252PrimeField>(&RANGE_CHECKED_VALUES),
+        range_check_range_trace(&RANGE_CHECKED_VALUES, 8),
+    ];
+    let pub_inputs = [RangeCheckTable::Values, RangeCheckTable::Range];
+
+    let proof_options = ProofOptions::default_test_options().with_fri_folding_factor(4);
+
+    let proof = Prover::prove_multi_table::<RangeCheckAIR<Stark252PrimeField>, _>(
+        &traces,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(Verifier::verify_multi_table::<
+        RangeCheckAIR<Stark252PrimeField>,
+    >(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[])
+    ));
+}
+
+#[test_log::test]
+fn test_prove_fib_with_fri_remainder_of_degree_7() {
+    let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 64);
+
+    let proof_options = ProofOptions::default_test_options().with_fri_max_remainder_degree(7);
+
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let proof = Prover::prove::<FibonacciAIR<Stark252PrimeField>>(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    // p₁ has degree bound 32 and is folded down to degree bound 8
+    assert_eq!(proof.fri_layers_merkle_roots.len(), 2);
+    assert_eq!(proof.fri_remainder_coefficients.len(), 8);
+    assert!(Verifier::verify::<FibonacciAIR<Stark252PrimeField>>(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    ));
+}
+
+#[test_log::test]
+fn test_prove_fib_with_fri_remainder_and_fri_folding_factor_4() {
+    let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 128);
+
+    let proof_options = ProofOptions::default_test_options()
+        .with_fri_folding_factor(4)
+        .with_fri_max_remainder_degree(3);
+
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let proof = Prover::prove::<FibonacciAIR<Stark252PrimeField>>(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(Verifier::verify::<FibonacciAIR<Stark252PrimeField>>(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    ));
+}
+
+#[test_log::test]
+fn test_verify_fib_with_tampered_fri_remainder_fails() {
+    let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 64);
+
+    let proof_options = ProofOptions::default_test_options().with_fri_max_remainder_degree(7);
+
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let mut proof = Prover::prove::<FibonacciAIR<Stark252PrimeField>>(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    proof.fri_remainder_coefficients[7] += Felt252::one();
+    assert!(!Verifier::verify::<FibonacciAIR<Stark252PrimeField>>(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    ));
+}
+
+#[test_log::test]
+fn test_proof_with_fri_remainder_does_not_verify_without_it() {
+    let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 64);
+
+    let proof_options = ProofOptions::default_test_options().with_fri_max_remainder_degree(7);
+
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let proof = Prover::prove::<FibonacciAIR<Stark252PrimeField>>(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(!Verifier::verify::<FibonacciAIR<Stark252PrimeField>>(
+        &proof,
+        &pub_inputs,
+        &ProofOptions::default_test_options(),
+        StoneProverTranscript::new(&[]),
+    ));
+}
+
+#[test_log::test]
+fn test_prove_range_check_tables_with_fri_remainder() {
+    let traces = [
+        range_check_values_trace::<Stark252PrimeField>(&RANGE_CHECKED_VALUES),
+        range_check_range_trace(&RANGE_CHECKED_VALUES, 8),
+    ];
+    let pub_inputs = [RangeCheckTable::Values, RangeCheckTable::Range];
+
+    let proof_options = ProofOptions::default_test_options().with_fri_max_remainder_degree(3);
+
+    let proof = Prover::prove_multi_table::<RangeCheckAIR<Stark252PrimeField>, _>(
+        &traces,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(Verifier::verify_multi_table::<
+        RangeCheckAIR<Stark252PrimeField>,
+    >(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[])
+    ));
+}
+
+#[test_log::test]
+fn test_check_trace_reports_no_failures_on_valid_trace() {
+    let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 8);
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+    let air = FibonacciAIR::<Stark252PrimeField>::new(
+        trace.n_rows(),
+        &pub_inputs,
+        &ProofOptions::default_test_options(),
+    );
+
+    let report = check_trace(&air, &trace, &mut StoneProverTranscript::new(&[]), None);
+    assert!(report.is_valid());
+}
+
+#[test_log::test]
+fn test_check_trace_reports_failing_transition_constraints() {
+    let mut trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 8);
+    trace.set_or_extend(4, 0, &Felt252::from(100));
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+    let air = FibonacciAIR::<Stark252PrimeField>::new(
+        trace.n_rows(),
+        &pub_inputs,
+        &ProofOptions::default_test_options(),
+    );
+
+    let report = check_trace(&air, &trace, &mut StoneProverTranscript::new(&[]), None);
+    assert!(report.boundary_failures.is_empty());
+    // Row 4 belongs to the frames starting at steps 2, 3 and 4.
+    let steps: Vec<_> = report
+        .transition_failures
+        .iter()
+        .map(|failure| failure.step)
+        .collect();
+    assert_eq!(steps, vec![2, 3, 4]);
+
+    let first = &report.transition_failures[0];
+    assert_eq!(first.constraint_idx, 0);
+    assert_eq!(
+        first.frame,
+        vec![
+            vec![Felt252::from(2)],
+            vec![Felt252::from(3)],
+            vec![Felt252::from(100)]
+        ]
+    );
+    assert_eq!(first.evaluation, Felt252::from(95));
+}
+
+#[test_log::test]
+fn test_check_trace_stops_after_max_failures() {
+    let mut trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 8);
+    trace.set_or_extend(0, 0, &Felt252::from(7));
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+    let air = FibonacciAIR::<Stark252PrimeField>::new(
+        trace.n_rows(),
+        &pub_inputs,
+        &ProofOptions::default_test_options(),
+    );
+
+    let report = check_trace(&air, &trace, &mut StoneProverTranscript::new(&[]), Some(1));
+    assert_eq!(report.num_failures(), 1);
+    let failure = &report.boundary_failures[0];
+    assert_eq!(
+        (failure.constraint_idx, failure.step, failure.col),
+        (0, 0, 0)
+    );
+    assert_eq!(failure.expected, Felt252::one());
+    assert_eq!(failure.found, Felt252::from(7));
+
+    let report = check_trace(&air, &trace, &mut StoneProverTranscript::new(&[]), None);
+    assert_eq!(report.boundary_failures.len(), 1);
+    assert_eq!(report.transition_failures.len(), 1);
+}
+
+#[test_log::test]
+fn test_check_trace_builds_auxiliary_trace_of_rap() {
+    let steps = 16;
+    let trace = fibonacci_rap_trace([Felt252::from(1), Felt252::from(1)], steps);
+    let pub_inputs = FibonacciRAPPublicInputs {
+        steps,
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+    let air = FibonacciRAP::<Stark252PrimeField>::new(
+        trace.n_rows(),
+        &pub_inputs,
+        &ProofOptions::default_test_options(),
+    );
+
+    let report = check_trace(&air, &trace, &mut StoneProverTranscript::new(&[]), None);
+    assert!(report.is_valid(), "{}", report);
+}
+
+#[test_log::test]
+fn test_prove_fib_with_streamed_lde_gives_the_same_proof() {
+    let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 32);
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let proof_options = ProofOptions::default_test_options();
+    let prove = |prover_options: &ProverOptions| {
+        Prover::prove_with_prover_options::<FibonacciAIR<Stark252PrimeField>>(
+            &trace,
+            &pub_inputs,
+            &proof_options,
+            prover_options,
+            StoneProverTranscript::new(&[]),
+        )
+        .unwrap()
+    };
+    let proof = prove(&ProverOptions::default());
+    let streamed_proof = prove(&ProverOptions::default().with_stream_lde());
+
+    assert_eq!(
+        serde_json::to_string(&proof).unwrap(),
+        serde_json::to_string(&streamed_proof).unwrap()
+    );
+    assert!(Verifier::verify::<FibonacciAIR<Stark252PrimeField>>(
+        &streamed_proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[])
+    ));
+}
+
+#[test_log::test]
+fn test_prove_simple_periodic_with_streamed_lde_gives_the_same_proof() {
+    let trace = simple_periodic_trace(Felt252::from(3), 32);
+    let pub_inputs = SimplePeriodicPublicInputs {
+        a0: Felt252::from(3),
+    };
+
+    let proof_options = ProofOptions::default_test_options();
+    let prove = |prover_options: &ProverOptions| {
+        Prover::prove_with_prover_options::<SimplePeriodicAIR<Stark252PrimeField>>(
+            &trace,
+            &pub_inputs,
+            &proof_options,
+            prover_options,
+            StoneProverTranscript::new(&[]),
+        )
+        .unwrap()
+    };
+    let proof = prove(&ProverOptions::default());
+    let streamed_proof = prove(&ProverOptions::default().with_stream_lde());
+
+    assert_eq!(
+        serde_json::to_string(&proof).unwrap(),
+        serde_json::to_string(&streamed_proof).unwrap()
+    );
+}
+
+#[test_log::test]
+fn test_prove_rap_fib_babybear_with_streamed_lde_gives_the_same_proof() {
+    type FE = FieldElement<Babybear31PrimeField>;
+    type BabybearFibonacciRAP = FibonacciRAP<Babybear31PrimeField, Degree4Babybear31ExtensionField>;
+
+    let steps = 16;
+    let trace = fibonacci_rap_trace([FE::from(1), FE::from(1)], steps);
+    let pub_inputs = FibonacciRAPPublicInputs {
+        steps,
+        a0: FE::one(),
+        a1: FE::one(),
+    };
+
+    let proof_options = ProofOptions::default_test_options();
+    let prove = |prover_options: &ProverOptions| {
+        Prover::prove_with_prover_options::<BabybearFibonacciRAP>(
+            &trace,
+            &pub_inputs,
+            &proof_options,
+            prover_options,
+            DefaultTranscript::new(&[]),
+        )
+        .unwrap()
+    };
+    let proof = prove(&ProverOptions::default());
+    let streamed_proof = prove(&ProverOptions::default().with_stream_lde());
+
+    assert_eq!(format!("{proof:?}"), format!("{streamed_proof:?}"));
+    assert!(Verifier::verify::<BabybearFibonacciRAP>(
+        &streamed_proof,
+        &pub_inputs,
+        &proof_options,
+        DefaultTranscript::new(&[])
+    ));
+}
+
+#[test_log::test]
+fn test_prove_fib_with_zero_knowledge_and_streamed_lde() {
+    let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 32);
+    let proof_options = ProofOptions::default_test_options().with_zero_knowledge();
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let proof = Prover::prove_with_prover_options::<FibonacciAIR<Stark252PrimeField>>(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        &ProverOptions::default().with_stream_lde(),
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(Verifier::verify::<FibonacciAIR<Stark252PrimeField>>(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[])
+    ));
+}
+
+#[test_log::test]
+fn test_prove_range_check_tables_with_streamed_lde_gives_the_same_proof() {
+    let traces = [
+        range_check_values_trace::<Stark252PrimeField>(&RANGE_CHECKED_VALUES),
+        range_check_range_trace(&RANGE_CHECKED_VALUES, 8),
+    ];
+    let pub_inputs = [RangeCheckTable::Values, RangeCheckTable::Range];
+
+    let proof_options = ProofOptions::default_test_options();
+    let prove = |prover_options: &ProverOptions| {
+        Prover::prove_multi_table_with_prover_options::<RangeCheckAIR<Stark252PrimeField>, _>(
+            &traces,
+            &pub_inputs,
+            &proof_options,
+            prover_options,
+            StoneProverTranscript::new(&[]),
+        )
+        .unwrap()
+    };
+    let proof = prove(&ProverOptions::default());
+    let streamed_proof = prove(&ProverOptions::default().with_stream_lde());
+
+    assert_eq!(
+        serde_json::to_string(&proof).unwrap(),
+        serde_json::to_string(&streamed_proof).unwrap()
+    );
+}
+
+#[test_log::test]
+fn test_prove_fib_with_blake2s_grinding() {
+    let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 8);
+    let proof_options = ProofOptions {
+        grinding_factor: 10,
+        ..ProofOptions::default_test_options()
+    }
+    .with_grinding_hash(GrindingHash::Blake2s256);
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let proof = Prover::prove::<FibonacciAIR<Stark252PrimeField>>(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(Verifier::verify::<FibonacciAIR<Stark252PrimeField>>(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[])
+    ));
+}
+
+#[test_log::test]
+fn test_verify_fib_with_a_different_grinding_hash_fails() {
+    let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 8);
+    let proof_options = ProofOptions {
+        grinding_factor: 10,
+        ..ProofOptions::default_test_options()
+    }
+    .with_grinding_hash(GrindingHash::Blake2s256);
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let proof = Prover::prove::<FibonacciAIR<Stark252PrimeField>>(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(!Verifier::verify::<FibonacciAIR<Stark252PrimeField>>(
+        &proof,
+        &pub_inputs,
+        &proof_options.with_grinding_hash(GrindingHash::Keccak256),
+        StoneProverTranscript::new(&[])
+    ));
+}
+
+#[test_log::test]
+fn test_prove_range_check_tables_with_blake2s_grinding() {
+    let traces = [
+        range_check_values_trace::<Stark252PrimeField>(&RANGE_CHECKED_VALUES),
+        range_check_range_trace(&RANGE_CHECKED_VALUES, 8),
+    ];
+    let pub_inputs = [RangeCheckTable::Values, RangeCheckTable::Range];
+    let proof_options = ProofOptions {
+        grinding_factor: 10,
+        ..ProofOptions::default_test_options()
+    }
+    .with_grinding_hash(GrindingHash::Blake2s256);
+
+    let proof = Prover::prove_multi_table::<RangeCheckAIR<Stark252PrimeField>, _>(
+        &traces,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(Verifier::verify_multi_table::<
+        RangeCheckAIR<Stark252PrimeField>,
+    >(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[])
+    ));
+    assert!(!Verifier::verify_multi_table::<
+        RangeCheckAIR<Stark252PrimeField>,
+    >(
+        &proof,
+        &pub_inputs,
+        &proof_options.with_grinding_hash(GrindingHash::Keccak256),
+        StoneProverTranscript::new(&[])
+    ));
+}
+
+#[test_log::test]
+fn test_prove_fib_with_poseidon_transcript_and_grinding() {
+    type PoseidonProver = ProverWithBackends<
+        Stark252PrimeField,
+        Stark252PrimeField,
+        BatchPoseidonStark252Backend,
+        BatchPoseidonStark252Backend,
+    >;
+    type PoseidonVerifier = VerifierWithBackends<
+        Stark252PrimeField,
+        Stark252PrimeField,
+        BatchPoseidonStark252Backend,
+        BatchPoseidonStark252Backend,
+    >;
+
+    let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 8);
+    let proof_options = ProofOptions {
+        grinding_factor: 4,
+        ..ProofOptions::default_test_options()
+    }
+    .with_grinding_hash(GrindingHash::PoseidonStark252);
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let proof = PoseidonProver::prove::<FibonacciAIR<Stark252PrimeField>>(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        PoseidonTranscript::<Stark252PrimeField>::new(&[]),
+    )
+    .unwrap();
+    assert!(
+        PoseidonVerifier::verify::<FibonacciAIR<Stark252PrimeField>>(
+            &proof,
+            &pub_inputs,
+            &proof_options,
+            PoseidonTranscript::<Stark252PrimeField>::new(&[]),
+        )
+    );
+}
+
+fn fibonacci_binary_proof() -> (Vec<u8>, FibonacciPublicInputs<Stark252PrimeField>) {
+    let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 8);
+    let proof_options = ProofOptions::default_test_options();
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+    let proof = Prover::prove::<FibonacciAIR<Stark252PrimeField>>(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    let bytes = VersionedStarkProof::<Stark252PrimeField>::new(proof, proof_options).serialize();
+    (bytes, pub_inputs)
+}
+
+#[test_log::test]
+fn test_binary_proof_round_trips_and_verifies() {
+    let (bytes, pub_inputs) = fibonacci_binary_proof();
+    assert_eq!(&bytes[..MAGIC.len()], &MAGIC);
+
+    let decoded = VersionedStarkProof::<Stark252PrimeField>::deserialize(&bytes).unwrap();
+    assert_eq!(decoded.serialize(), bytes);
+    let proof_options = ProofOptions::default_test_options();
+    decoded.check_options(&proof_options).unwrap();
+    assert!(Verifier::verify::<FibonacciAIR<Stark252PrimeField>>(
+        &decoded.proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    ));
+}
+
+#[test_log::test]
+fn test_binary_proof_with_other_options_than_the_expected_ones_is_rejected() {
+    let (bytes, _) = fibonacci_binary_proof();
+
+    let decoded = VersionedStarkProof::<Stark252PrimeField>::deserialize(&bytes).unwrap();
+    let mut expected_options = ProofOptions::default_test_options();
+    expected_options.fri_number_of_queries += 1;
+    assert_eq!(
+        decoded.check_options(&expected_options),
+        Err(DeserializationError::OptionsMismatch)
+    );
+}
+
+#[test_log::test]
+fn test_binary_proof_round_trips_in_degree_4_extension_with_poseidon_commitments() {
+    type FE = FieldElement<Babybear31PrimeField>;
+    type BabybearFibonacciRAP = FibonacciRAP<Babybear31PrimeField, Degree4Babybear31ExtensionField>;
+    type PoseidonProver = ProverWithBackends<
+        Babybear31PrimeField,
+        Degree4Babybear31ExtensionField,
+        BatchPoseidonBabybearBackend,
+        BatchPoseidonBabybearBackend<Degree4Babybear31ExtensionField>,
+    >;
+    type PoseidonVerifier = VerifierWithBackends<
+        Babybear31PrimeField,
+        Degree4Babybear31ExtensionField,
+        BatchPoseidonBabybearBackend,
+        BatchPoseidonBabybearBackend<Degree4Babybear31ExtensionField>,
+    >;
+    type BinaryProof = VersionedStarkProof<
+        Babybear31PrimeField,
+        Degree4Babybear31ExtensionField,
+        BatchPoseidonBabybearBackend,
+    >;
+
+    let steps = 16;
+    let trace = fibonacci_rap_trace([FE::from(1), FE::from(1)], steps);
+    let proof_options = ProofOptions::default_test_options().with_zero_knowledge();
+    let pub_inputs = FibonacciRAPPublicInputs {
+        steps,
+        a0: FE::one(),
+        a1: FE::one(),
+    };
+
+    let proof = PoseidonProver::prove::<BabybearFibonacciRAP>(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        PoseidonTranscript::<Babybear31PrimeField>::new(&[]),
+    )
+    .unwrap();
+    let bytes = BinaryProof::new(proof, proof_options.clone()).serialize();
+
+    let decoded = BinaryProof::deserialize(&bytes).unwrap();
+    assert_eq!(decoded.serialize(), bytes);
+    decoded.check_options(&proof_options).unwrap();
+    assert!(PoseidonVerifier::verify::<BabybearFibonacciRAP>(
+        &decoded.proof,
+        &pub_inputs,
+        &proof_options,
+        PoseidonTranscript::<Babybear31PrimeField>::new(&[]),
+    ));
+}
+
+#[test_log::test]
+fn test_binary_proof_with_wrong_header_is_rejected() {
+    let (bytes, _) = fibonacci_binary_proof();
+
+    let mut wrong_magic = bytes.clone();
+    wrong_magic[0] ^= 1;
+    assert_eq!(
+        VersionedStarkProof::<Stark252PrimeField>::deserialize(&wrong_magic).err(),
+        Some(DeserializationError::InvalidMagicBytes)
+    );
+
+    let mut wrong_version = bytes.clone();
+    wrong_version[MAGIC.len()..MAGIC.len() + 2]
+        .copy_from_slice(&(FORMAT_VERSION + 1).to_be_bytes());
+    assert_eq!(
+        VersionedStarkProof::<Stark252PrimeField>::deserialize(&wrong_version).err(),
+        Some(DeserializationError::UnsupportedVersion)
+    );
+
+    type Sha2VersionedProof = VersionedStarkProof<
+        Stark252PrimeField,
+        Stark252PrimeField,
+        BatchSha2_256Backend<Stark252PrimeField>,
+    >;
+    assert_eq!(
+        VersionedStarkProof::<Babybear31PrimeField>::deserialize(&bytes).err(),
+        Some(DeserializationError::FieldMismatch)
+    );
+    assert_eq!(
+        Sha2VersionedProof::deserialize(&bytes).err(),
+        Some(DeserializationError::HashMismatch)
+    );
+}
+
+#[test_log::test]
+fn test_malformed_binary_proof_is_rejected() {
+    let (bytes, _) = fibonacci_binary_proof();
+
+    assert_eq!(
+        VersionedStarkProof::<Stark252PrimeField>::deserialize(&bytes[..bytes.len() - 1]).err(),
+        Some(DeserializationError::InvalidAmountOfBytes)
+    );
+
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    assert_eq!(
+        VersionedStarkProof::<Stark252PrimeField>::deserialize(&trailing).err(),
+        Some(DeserializationError::TrailingBytes)
+    );
+
+    // The zero-knowledge flag follows the header and the blowup factor, number of queries,
+    // coset offset and grinding factor.
+    let mut invalid_flag = bytes.clone();
+    invalid_flag[10 + 1 + 4 + 8 + 1] = 2;
+    assert_eq!(
+        VersionedStarkProof::<Stark252PrimeField>::deserialize(&invalid_flag).err(),
+        Some(DeserializationError::InvalidValue)
+    );
+}
+
+#[test_log::test]
+fn test_verify_batch_returns_the_result_of_each_proof() {
+    let proof_options = ProofOptions::default_test_options();
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+    let proofs: Vec<_> = [8, 16, 8]
+        .into_iter()
+        .map(|trace_length| {
+            let trace = simple_fibonacci::fibonacci_trace(
+                [Felt252::from(1), Felt252::from(1)],
+                trace_length,
+            );
+            Prover::prove::<FibonacciAIR<Stark252PrimeField>>(
+                &trace,
+                &pub_inputs,
+                &proof_options,
+                StoneProverTranscript::new(&[]),
+            )
+            .unwrap()
+        })
+        .collect();
+    let wrong_pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::from(2),
+    };
+    let batch_pub_inputs = [pub_inputs.clone(), pub_inputs.clone(), wrong_pub_inputs];
+
+    let results = Verifier::verify_batch::<FibonacciAIR<Stark252PrimeField>, _>(
+        &proofs,
+        &batch_pub_inputs,
+        &proof_options,
+        None,
+        vec![StoneProverTranscript::new(&[]); proofs.len()],
+    );
+    assert_eq!(results, vec![true, true, false]);
+
+    let results = Verifier::verify_batch::<FibonacciAIR<Stark252PrimeField>, _>(
+        &proofs,
+        &batch_pub_inputs[..2],
+        &proof_options,
+        None,
+        vec![StoneProverTranscript::new(&[]); proofs.len()],
+    );
+    assert_eq!(results, vec![false; proofs.len()]);
+}
+
+fn fixed_column_sum_proof(
+    proof_options: &ProofOptions,
+) -> (
+    StarkProof<Stark252PrimeField, Stark252PrimeField, Commitment>,
+    FixedColumnSumPublicInputs<Stark252PrimeField>,
+) {
+    let trace = fixed_column_sum_trace(Felt252::from(3), 32);
+    let pub_inputs = FixedColumnSumPublicInputs {
+        a0: Felt252::from(3),
+    };
+    let proof = Prover::prove::<FixedColumnSumAIR<Stark252PrimeField>>(
+        &trace,
+        &pub_inputs,
+        proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    (proof, pub_inputs)
+}
+
+fn fixed_column_sum_verification_key(
+    trace_length: usize,
+    pub_inputs: &FixedColumnSumPublicInputs<Stark252PrimeField>,
+    proof_options: &ProofOptions,
+) -> VerificationKey<Commitment> {
+    let air = FixedColumnSumAIR::new(trace_length, pub_inputs, proof_options);
+    VerificationKey::new::<
+        FixedColumnSumAIR<Stark252PrimeField>,
+        BatchedMerkleTreeBackend<Stark252PrimeField>,
+    >(&air)
+    .unwrap()
+}
+
+#[test_log::test]
+fn test_prove_fixed_column_sum() {
+    let proof_options = ProofOptions::default_test_options();
+    let (proof, pub_inputs) = fixed_column_sum_proof(&proof_options);
+    assert!(proof.lde_preprocessed_merkle_proof.is_some());
+
+    let air = FixedColumnSumAIR::new(proof.trace_length, &pub_inputs, &proof_options);
+    let verification_key = VerificationKey::new::<
+        FixedColumnSumAIR<Stark252PrimeField>,
+        BatchedMerkleTreeBackend<Stark252PrimeField>,
+    >(&air)
+    .unwrap();
+
+    assert!(Verifier::verify_with_key::<
+        FixedColumnSumAIR<Stark252PrimeField>,
+    >(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        &verification_key,
+        StoneProverTranscript::new(&[]),
+    ));
+}
+
+#[test_log::test]
+fn test_verify_batch_with_a_shared_verification_key() {
+    let proof_options = ProofOptions::default_test_options();
+    let (proof, pub_inputs) = fixed_column_sum_proof(&proof_options);
+    let (other_proof, _) = fixed_column_sum_proof(&proof_options);
+    let air = FixedColumnSumAIR::new(proof.trace_length, &pub_inputs, &proof_options);
+    let verification_key = VerificationKey::new::<
+        FixedColumnSumAIR<Stark252PrimeField>,
+        BatchedMerkleTreeBackend<Stark252PrimeField>,
+    >(&air)
+    .unwrap();
+    let proofs = vec![proof, other_proof];
+
+    let results = Verifier::verify_batch::<FixedColumnSumAIR<Stark252PrimeField>, _>(
+        &proofs,
+        &[pub_inputs.clone(), pub_inputs],
+        &proof_options,
+        Some(&verification_key),
+        vec![StoneProverTranscript::new(&[]); proofs.len()],
+    );
+    assert_eq!(results, vec![true, true]);
+}
+
+#[test_log::test]
+fn test_verify_fixed_column_sum_with_wrong_verification_key_fails() {
+    let proof_options = ProofOptions::default_test_options();
+    let (mut proof, pub_inputs) = fixed_column_sum_proof(&proof_options);
+    let air = FixedColumnSumAIR::new(proof.trace_length, &pub_inputs, &proof_options);
+    let verification_key = VerificationKey::new::<
+        FixedColumnSumAIR<Stark252PrimeField>,
+        BatchedMerkleTreeBackend<Stark252PrimeField>,
+    >(&air)
+    .unwrap();
+    let verify = |proof: &StarkProof<_, _, _>, verification_key: &VerificationKey<Commitment>| {
+        Verifier::verify_with_key::<FixedColumnSumAIR<Stark252PrimeField>>(
+            proof,
+            &pub_inputs,
+            &proof_options,
+            verification_key,
+            StoneProverTranscript::new(&[]),
+        )
+    };
+
+    let mut wrong_root = verification_key.clone();
+    wrong_root.root[0] ^= 1;
+    assert!(!verify(&proof, &wrong_root));
+
+    // A key for a different trace length commits to other columns.
+    let other_air = FixedColumnSumAIR::new(proof.trace_length * 2, &pub_inputs, &proof_options);
+    let other_key = VerificationKey::new::<
+        FixedColumnSumAIR<Stark252PrimeField>,
+        BatchedMerkleTreeBackend<Stark252PrimeField>,
+    >(&other_air)
+    .unwrap();
+    assert!(!verify(&proof, &other_key));
+
+    // The openings of the preprocessed columns are required.
+    proof.lde_preprocessed_merkle_proof = None;
+    assert!(!verify(&proof, &verification_key));
+}
+
+#[test_log::test]
+fn test_verify_fixed_column_sum_without_verification_key_fails() {
+    let proof_options = ProofOptions::default_test_options();
+    let (proof, pub_inputs) = fixed_column_sum_proof(&proof_options);
+
+    assert!(!Verifier::verify::<FixedColumnSumAIR<Stark252PrimeField>>(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    ));
+    let results = Verifier::verify_batch::<FixedColumnSumAIR<Stark252PrimeField>, _>(
+        &[proof],
+        &[pub_inputs],
+        &proof_options,
+        None,
+        vec![StoneProverTranscript::new(&[])],
+    );
+    assert_eq!(results, vec![false]);
+}
+
+#[test_log::test]
+fn test_verify_fixed_column_sum_with_tampered_preprocessed_openings_fails() {
+    let proof_options = ProofOptions::default_test_options();
+    let (mut proof, pub_inputs) = fixed_column_sum_proof(&proof_options);
+    let verification_key =
+        fixed_column_sum_verification_key(proof.trace_length, &pub_inputs, &proof_options);
+    proof.deep_poly_openings[0].lde_preprocessed_evaluations[0] += Felt252::one();
+
+    assert!(!Verifier::verify_with_key::<
+        FixedColumnSumAIR<Stark252PrimeField>,
+    >(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        &verification_key,
+        StoneProverTranscript::new(&[]),
+    ));
+}
+
+#[test_log::test]
+fn test_prove_fixed_column_sum_with_streamed_lde_gives_the_same_proof() {
+    let proof_options = ProofOptions::default_test_options();
+    let (proof, pub_inputs) = fixed_column_sum_proof(&proof_options);
+    let streamed_proof =
+        Prover::prove_with_prover_options::<FixedColumnSumAIR<Stark252PrimeField>>(
+            &fixed_column_sum_trace(Felt252::from(3), 32),
+            &pub_inputs,
+            &proof_options,
+            &ProverOptions::default().with_stream_lde(),
+            StoneProverTranscript::new(&[]),
+        )
+        .unwrap();
+
+    assert_eq!(
+        serde_json::to_string(&proof).unwrap(),
+        serde_json::to_string(&streamed_proof).unwrap()
+    );
+}
+
+#[test_log::test]
+fn test_prove_fixed_column_sum_with_zero_knowledge() {
+    let proof_options = ProofOptions::default_test_options().with_zero_knowledge();
+    let (proof, pub_inputs) = fixed_column_sum_proof(&proof_options);
+    let verification_key =
+        fixed_column_sum_verification_key(proof.trace_length, &pub_inputs, &proof_options);
+
+    assert!(Verifier::verify_with_key::<
+        FixedColumnSumAIR<Stark252PrimeField>,
+    >(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        &verification_key,
+        StoneProverTranscript::new(&[]),
+    ));
+}
+
+#[test_log::test]
+fn test_binary_proof_with_preprocessed_columns_round_trips_and_verifies() {
+    let proof_options = ProofOptions::default_test_options();
+    let (proof, pub_inputs) = fixed_column_sum_proof(&proof_options);
+    let bytes =
+        VersionedStarkProof::<Stark252PrimeField>::new(proof, proof_options.clone()).serialize();
+    assert_eq!(
+        &bytes[MAGIC.len()..MAGIC.len() + 2],
+        &FORMAT_VERSION.to_be_bytes()
+    );
+
+    let decoded = VersionedStarkProof::<Stark252PrimeField>::deserialize(&bytes).unwrap();
+    assert_eq!(decoded.serialize(), bytes);
+    decoded.check_options(&proof_options).unwrap();
+    let verification_key =
+        fixed_column_sum_verification_key(decoded.proof.trace_length, &pub_inputs, &proof_options);
+    assert!(Verifier::verify_with_key::<
+        FixedColumnSumAIR<Stark252PrimeField>,
+    >(
+        &decoded.proof,
+        &pub_inputs,
+        &proof_options,
+        &verification_key,
+        StoneProverTranscript::new(&[]),
+    ));
+}
+
+#[test_log::test]
+fn test_check_trace_reads_preprocessed_columns() {
+    let proof_options = ProofOptions::default_test_options();
+    let pub_inputs = FixedColumnSumPublicInputs {
+        a0: Felt252::from(3),
+    };
+    let air = FixedColumnSumAIR::new(32, &pub_inputs, &proof_options);
+    let trace = fixed_column_sum_trace(Felt252::from(3), 32);
+    assert!(check_trace(&air, &trace, &mut StoneProverTranscript::new(&[]), None).is_valid());
+
+    let mut columns = trace.columns();
+    columns[0][5] += Felt252::one();
+    let report = check_trace(
+        &air,
+        &TraceTable::from_columns(&columns),
+        &mut StoneProverTranscript::new(&[]),
+        None,
+    );
+    assert_eq!(report.transition_failures.len(), 2);
+}
+
+#[test_log::test]
+fn test_multi_table_prover_rejects_preprocessed_columns() {
+    let traces = [
+        fixed_column_sum_trace(Felt252::from(3), 8),
+        fixed_column_sum_trace(Felt252::from(3), 16),
+    ];
+    let pub_inputs = [
+        FixedColumnSumPublicInputs {
+            a0: Felt252::from(3),
+        },
+        FixedColumnSumPublicInputs {
+            a0: Felt252::from(3),
+        },
+    ];
+
+    let result = Prover::prove_multi_table::<FixedColumnSumAIR<Stark252PrimeField>, _>(
+        &traces,
+        &pub_inputs,
+        &ProofOptions::default_test_options(),
+        StoneProverTranscript::new(&[]),
+    );
+    assert!(matches!(result, Err(ProvingError::WrongParameter(_))));
+}
+
+#[test_log::test]
+fn test_symbolic_transitions_of_fibonacci_rap_match_its_context() {
+    let pub_inputs = FibonacciRAPPublicInputs {
+        steps: 16,
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+    let air = FibonacciRAP::<Stark252PrimeField>::new(
+        32,
+        &pub_inputs,
+        &ProofOptions::default_test_options(),
+    );
+
+    assert!(validate_symbolic_transitions(&air));
+    assert_eq!(air.context().transition_degrees, vec![1, 2]);
+}
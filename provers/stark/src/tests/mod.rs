@@ -1 +1,2 @@
 pub mod integration_tests;
+mod prover_memory;
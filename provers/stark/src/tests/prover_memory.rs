@@ -0,0 +1,106 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+use crate::{
+    examples::{
+        fibonacci_2_columns::{self, Fibonacci2ColsAIR},
+        simple_fibonacci::FibonacciPublicInputs,
+    },
+    proof::options::ProofOptions,
+    prover::{IsStarkProver, Prover, ProverOptions},
+    transcript::StoneProverTranscript,
+    Felt252,
+};
+
+/// Allocator that keeps track of the memory allocated by each thread, so that the tests
+/// running at the same time do not change the measures of each other.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATED: Cell<isize> = const { Cell::new(0) };
+    static PEAK: Cell<isize> = const { Cell::new(0) };
+}
+
+fn record(size: isize) {
+    // The thread locals may be gone when a thread frees its last values.
+    let _ = ALLOCATED.try_with(|allocated| {
+        let value = allocated.get() + size;
+        allocated.set(value);
+        let _ = PEAK.try_with(|peak| peak.set(peak.get().max(value)));
+    });
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record(layout.size() as isize);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record(layout.size() as isize);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record(-(layout.size() as isize));
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record(new_size as isize - layout.size() as isize);
+        }
+        new_ptr
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Runs `f` and returns the largest amount of memory, in bytes, that the current thread
+/// held during the call on top of what it held before.
+fn peak_memory(f: impl FnOnce()) -> usize {
+    let start = ALLOCATED.with(Cell::get);
+    PEAK.with(|peak| peak.set(start));
+    f();
+    (PEAK.with(Cell::get) - start) as usize
+}
+
+#[test]
+fn test_prove_with_streamed_lde_stays_within_the_documented_memory_bound() {
+    let trace_length = 256;
+    let blowup_factor = 8;
+    let trace = fibonacci_2_columns::compute_trace([Felt252::one(), Felt252::one()], trace_length);
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+    let proof_options = ProofOptions {
+        blowup_factor: blowup_factor as u8,
+        ..ProofOptions::default_test_options()
+    };
+
+    let peak = peak_memory(|| {
+        Prover::prove_with_prover_options::<Fibonacci2ColsAIR<_>>(
+            &trace,
+            &pub_inputs,
+            &proof_options,
+            &ProverOptions::default().with_stream_lde(),
+            StoneProverTranscript::new(&[]),
+        )
+        .unwrap();
+    });
+
+    // Field elements and Keccak256 nodes take 32 bytes each.
+    let bound = 8 * (blowup_factor + trace.n_cols()) * trace_length * 32;
+    assert!(peak <= bound, "{peak} bytes above the bound of {bound}");
+}
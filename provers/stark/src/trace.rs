@@ -2,7 +2,10 @@ use crate::table::Table;
 use lambdaworks_math::fft::errors::FFTError;
 use lambdaworks_math::fft::polynomial::FFTPoly;
 use lambdaworks_math::{
-    field::{element::FieldElement, traits::IsFFTField},
+    field::{
+        element::FieldElement,
+        traits::{IsFFTField, IsField, IsSubFieldOf},
+    },
     polynomial::Polynomial,
 };
 
@@ -13,11 +16,11 @@ use lambdaworks_math::{
 /// layer above the raw two-dimensional table, with functionality relevant to the
 /// STARK protocol.
 #[derive(Clone, Default, Debug, PartialEq, Eq)]
-pub struct TraceTable<F: IsFFTField> {
+pub struct TraceTable<F: IsField> {
     pub table: Table<F>,
 }
 
-impl<F: IsFFTField> TraceTable<F> {
+impl<F: IsField> TraceTable<F> {
     pub fn new(data: &[FieldElement<F>], n_columns: usize) -> Self {
         let table = Table::new(data, n_columns);
         Self { table }
@@ -85,14 +88,6 @@ impl<F: IsFFTField> TraceTable<F> {
         self.table.get(row, col)
     }
 
-    pub fn compute_trace_polys(&self) -> Vec<Polynomial<FieldElement<F>>> {
-        self.columns()
-            .iter()
-            .map(|col| Polynomial::interpolate_fft(col))
-            .collect::<Result<Vec<Polynomial<FieldElement<F>>>, FFTError>>()
-            .unwrap()
-    }
-
     pub fn concatenate(&self, new_cols: Vec<FieldElement<F>>, n_cols: usize) -> Self {
         let mut data = Vec::new();
         let mut i = 0;
@@ -139,6 +134,30 @@ impl<F: IsFFTField> TraceTable<F> {
             row[col_idx] = value.clone();
         }
     }
+
+    /// Returns the same table with every value embedded into the field `L` containing `F`.
+    pub fn to_extension<L: IsField>(&self) -> TraceTable<L>
+    where
+        F: IsSubFieldOf<L>,
+    {
+        let data: Vec<_> = self
+            .table
+            .data
+            .iter()
+            .map(|value| value.clone().to_extension())
+            .collect();
+        TraceTable::new(&data, self.n_cols())
+    }
+}
+
+impl<F: IsFFTField> TraceTable<F> {
+    pub fn compute_trace_polys(&self) -> Vec<Polynomial<FieldElement<F>>> {
+        self.columns()
+            .iter()
+            .map(|col| Polynomial::interpolate_fft(col))
+            .collect::<Result<Vec<Polynomial<FieldElement<F>>>, FFTError>>()
+            .unwrap()
+    }
 }
 
 #[cfg(test)]
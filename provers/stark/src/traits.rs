@@ -1,20 +1,29 @@
 use itertools::Itertools;
 use lambdaworks_math::{
-    fft::cpu::roots_of_unity::get_powers_of_primitive_root_coset,
-    field::{element::FieldElement, traits::IsFFTField},
+    fft::{cpu::roots_of_unity::get_powers_of_primitive_root_coset, polynomial::FFTPoly},
+    field::{
+        element::FieldElement,
+        traits::{IsFFTField, IsSubFieldOf},
+    },
     polynomial::Polynomial,
 };
 
 use crate::transcript::IsStarkTranscript;
 
 use super::{
-    constraints::boundary::BoundaryConstraints, context::AirContext, frame::Frame,
-    proof::options::ProofOptions, trace::TraceTable,
+    constraints::{boundary::BoundaryConstraints, builder::TransitionConstraints},
+    context::AirContext,
+    frame::Frame,
+    proof::options::ProofOptions,
+    trace::TraceTable,
 };
 
 /// AIR is a representation of the Constraints
 pub trait AIR: Clone {
-    type Field: IsFFTField;
+    type Field: IsFFTField + IsSubFieldOf<Self::FieldExtension>;
+    /// Field where the challenges, the out of domain point and every polynomial
+    /// other than the main trace live. It can be `Self::Field` itself.
+    type FieldExtension: IsFFTField;
     type RAPChallenges;
     type PublicInputs;
 
@@ -28,27 +37,118 @@ pub trait AIR: Clone {
         &self,
         main_trace: &TraceTable<Self::Field>,
         rap_challenges: &Self::RAPChallenges,
-    ) -> TraceTable<Self::Field>;
+    ) -> TraceTable<Self::FieldExtension>;
 
     fn build_rap_challenges(
         &self,
-        transcript: &mut impl IsStarkTranscript<Self::Field>,
+        transcript: &mut impl IsStarkTranscript<Self::FieldExtension>,
     ) -> Self::RAPChallenges;
 
     fn number_auxiliary_rap_columns(&self) -> usize;
 
-    fn composition_poly_degree_bound(&self) -> usize;
+    /// Bound on the degree of the composition polynomial, derived from the degrees of the
+    /// transition constraints. The quotient of a constraint of degree `d` over trace
+    /// polynomials of degree `D`, with `e` exempted rows, has degree `d D + e - n`, where
+    /// `n` is the trace length. The boundary quotients have degree less than `n`.
+    fn composition_poly_degree_bound(&self) -> usize {
+        let trace_length = self.trace_length();
+        let mut trace_poly_degree = trace_length - 1;
+        if self.options().zero_knowledge {
+            trace_poly_degree += number_of_blinding_coefficients(self);
+        }
+        self.context()
+            .transition_degrees
+            .iter()
+            .zip(&self.context().transition_exemptions)
+            .map(|(degree, exemptions)| {
+                (degree * trace_poly_degree + exemptions + 1).saturating_sub(trace_length)
+            })
+            .fold(trace_length, usize::max)
+    }
+
+    /// Number of parts the composition polynomial is broken into, so that the degree of
+    /// each of them is below the degree bound of the DEEP composition polynomial.
+    fn number_of_composition_poly_parts(&self) -> usize {
+        let degree_bound = self.deep_composition_poly_degree_bound();
+        (self.composition_poly_degree_bound() + degree_bound - 1) / degree_bound
+    }
 
     fn compute_transition(
         &self,
-        frame: &Frame<Self::Field>,
+        frame: &Frame<Self::FieldExtension>,
         rap_challenges: &Self::RAPChallenges,
-    ) -> Vec<FieldElement<Self::Field>>;
+    ) -> Vec<FieldElement<Self::FieldExtension>>;
 
     fn boundary_constraints(
         &self,
         rap_challenges: &Self::RAPChallenges,
-    ) -> BoundaryConstraints<Self::Field>;
+    ) -> BoundaryConstraints<Self::FieldExtension>;
+
+    /// Transition constraints of the AIR as expressions, if it was written with a
+    /// `ConstraintBuilder`. They must be the constraints `compute_transition` evaluates.
+    /// They let other tools read the constraints, such as verifier generators or
+    /// `debug::validate_symbolic_transitions`, which checks the context against them.
+    fn symbolic_transitions(&self) -> Option<&TransitionConstraints<Self::Field>> {
+        None
+    }
+
+    /// Values of the periodic columns over one period. These are public columns, such as
+    /// round constants or selectors, that are not committed: the prover and the verifier
+    /// compute them on their own. The period of each column must be a power of two that
+    /// divides the trace length. Their values at the first row of a frame are available
+    /// to `compute_transition` through `Frame::get_periodic_values`.
+    fn get_periodic_column_values(&self) -> Vec<Vec<FieldElement<Self::Field>>> {
+        Vec::new()
+    }
+
+    /// Polynomials that interpolate the periodic columns over the trace domain. If `q`
+    /// interpolates a column of period `p` over the roots of unity of order `p`, its
+    /// polynomial is `q(X^(n/p))`, where `n` is the trace length. Panics if a period is not
+    /// a power of two dividing the trace length, which the prover and the verifier check
+    /// beforehand with `has_valid_periodic_columns`.
+    fn get_periodic_column_polynomials(&self) -> Vec<Polynomial<FieldElement<Self::Field>>> {
+        let trace_length = self.trace_length();
+        self.get_periodic_column_values()
+            .iter()
+            .map(|values| {
+                let period = values.len();
+                assert!(
+                    is_valid_period(period, trace_length),
+                    "Periodic column of period {period} for a trace of length {trace_length}"
+                );
+                let poly = Polynomial::interpolate_fft(values).unwrap();
+                let stride = trace_length / period;
+                let mut coefficients = vec![FieldElement::zero(); trace_length];
+                for (i, coefficient) in poly.coefficients().iter().enumerate() {
+                    coefficients[i * stride] = coefficient.clone();
+                }
+                Polynomial::new(&coefficients)
+            })
+            .collect()
+    }
+
+    /// Number of preprocessed columns of the AIR. See `get_preprocessed_columns`.
+    fn number_preprocessed_columns(&self) -> usize {
+        0
+    }
+
+    /// Values over the trace domain of the preprocessed columns. These are public columns
+    /// fixed by the AIR, such as a program or the selectors of its instructions, that are
+    /// committed once in a `VerificationKey` instead of being committed by the prover in
+    /// every proof. They follow the main and auxiliary columns in the frames of
+    /// `compute_transition` and in the column indexes of boundary constraints. There must
+    /// be `number_preprocessed_columns` of them, each with `trace_length` values.
+    fn get_preprocessed_columns(&self) -> Vec<Vec<FieldElement<Self::Field>>> {
+        Vec::new()
+    }
+
+    /// Column of the trace, counting the main columns first, that accumulates this table's
+    /// terms of a cross-table lookup in a multi-table proof. Its value at the last row is
+    /// sent in the proof, and the values of all the tables must add up to zero.
+    /// See `crate::multi_table`.
+    fn cross_table_lookup_column(&self) -> Option<usize> {
+        None
+    }
 
     fn transition_exemptions(&self) -> Vec<Polynomial<FieldElement<Self::Field>>> {
         let trace_length = self.trace_length();
@@ -97,6 +197,17 @@ pub trait AIR: Clone {
         self.context().num_transition_constraints
     }
 
+    /// Degree bound checked by FRI for the trace polynomials and the parts of the
+    /// composition polynomial. In zero-knowledge mode the trace polynomials carry
+    /// random blinding terms, so the bound is twice the trace length.
+    fn deep_composition_poly_degree_bound(&self) -> usize {
+        if self.options().zero_knowledge {
+            2 * self.trace_length()
+        } else {
+            self.trace_length()
+        }
+    }
+
     fn pub_inputs(&self) -> &Self::PublicInputs;
 
     fn transition_exemptions_verifier(
@@ -121,3 +232,24 @@ pub trait AIR: Clone {
             .collect()
     }
 }
+
+/// Returns whether `period` is a power of two that divides `trace_length`.
+fn is_valid_period(period: usize, trace_length: usize) -> bool {
+    period.is_power_of_two() && trace_length % period == 0
+}
+
+/// Returns whether the period of every periodic column of `air` is a power of two that
+/// divides the trace length. The trace length of the verifier comes from the proof, so
+/// this is checked before interpolating the columns.
+pub(crate) fn has_valid_periodic_columns<A: AIR>(air: &A) -> bool {
+    air.get_periodic_column_values()
+        .iter()
+        .all(|values| is_valid_period(values.len(), air.trace_length()))
+}
+
+/// Number of random coefficients of the blinding term added to each trace polynomial in
+/// zero-knowledge mode. It is the number of evaluations of a trace polynomial revealed by
+/// a proof: two for each FRI query and one for each row of the out of domain frame.
+pub(crate) fn number_of_blinding_coefficients<A: AIR>(air: &A) -> usize {
+    2 * air.options().fri_number_of_queries + air.context().transition_offsets.len()
+}
@@ -1,11 +1,24 @@
+use core::fmt::Debug;
+use core::marker::PhantomData;
+use lambdaworks_crypto::hash::poseidon::{instances::IsPoseidonField, Poseidon};
 use lambdaworks_math::{
     field::{
         element::FieldElement,
-        fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
-        traits::{IsFFTField, IsField},
+        extensions::{
+            cubic::{CubicExtensionField, HasCubicNonResidue},
+            quadratic::{HasQuadraticNonResidue, QuadraticExtensionField},
+        },
+        fields::{
+            fft_friendly::{
+                goldilocks::{Goldilocks64Field, GOLDILOCKS_PRIME},
+                stark_252_prime_field::Stark252PrimeField,
+            },
+            montgomery_backed_prime_fields::{IsModulus, U64PrimeField},
+        },
+        traits::{IsFFTField, IsField, IsSubFieldOf},
     },
     traits::{ByteConversion, Serializable},
-    unsigned_integer::element::U256,
+    unsigned_integer::element::{U256, U64},
 };
 use sha3::{Digest, Keccak256};
 
@@ -15,18 +28,24 @@ pub trait IsStarkTranscript<F: IsField> {
     fn state(&self) -> [u8; 32];
     fn sample_field_element(&mut self) -> FieldElement<F>;
     fn sample_u64(&mut self, upper_bound: u64) -> u64;
-    fn sample_z_ood(
+    /// Samples an out of domain element. The domains may live in a subfield `S` of `F`,
+    /// in which case their elements are embedded into `F` before comparing.
+    fn sample_z_ood<S: IsSubFieldOf<F>>(
         &mut self,
-        lde_roots_of_unity_coset: &[FieldElement<F>],
-        trace_roots_of_unity: &[FieldElement<F>],
+        lde_roots_of_unity_coset: &[FieldElement<S>],
+        trace_roots_of_unity: &[FieldElement<S>],
     ) -> FieldElement<F>
     where
         FieldElement<F>: Serializable,
     {
         loop {
             let value: FieldElement<F> = self.sample_field_element();
-            if !lde_roots_of_unity_coset.iter().any(|x| x == &value)
-                && !trace_roots_of_unity.iter().any(|x| x == &value)
+            if !lde_roots_of_unity_coset
+                .iter()
+                .any(|x| x.clone().to_extension() == value)
+                && !trace_roots_of_unity
+                    .iter()
+                    .any(|x| x.clone().to_extension() == value)
             {
                 return value;
             }
@@ -34,6 +53,7 @@ pub trait IsStarkTranscript<F: IsField> {
     }
 }
 
+#[derive(Clone)]
 pub struct StoneProverTranscript {
     state: [u8; 32],
     seed_increment: U256,
@@ -42,10 +62,10 @@ pub struct StoneProverTranscript {
 }
 
 impl StoneProverTranscript {
-    const MODULUS_MAX_MULTIPLE: U256 = U256::from_hex_unchecked(
+    pub(crate) const MODULUS_MAX_MULTIPLE: U256 = U256::from_hex_unchecked(
         "f80000000000020f00000000000000000000000000000000000000000000001f",
     );
-    const R_INV: U256 = U256::from_hex_unchecked(
+    pub(crate) const R_INV: U256 = U256::from_hex_unchecked(
         "0x40000000000001100000000000012100000000000000000000000000000000",
     );
     pub fn new(public_input_data: &[u8]) -> Self {
@@ -150,6 +170,220 @@ impl IsStarkTranscript<Stark252PrimeField> for StoneProverTranscript {
     }
 }
 
+/// Fields whose elements can be sampled by a `DefaultTranscript` out of uniformly
+/// distributed 64 bit words.
+pub trait HasDefaultTranscript: IsField {
+    fn sample_field_element(sample_u64: &mut impl FnMut() -> u64) -> FieldElement<Self>;
+}
+
+impl<M> HasDefaultTranscript for U64PrimeField<M>
+where
+    M: IsModulus<U64> + Clone + Debug,
+{
+    /// Uses rejection sampling over the bits needed to represent the modulus.
+    fn sample_field_element(sample_u64: &mut impl FnMut() -> u64) -> FieldElement<Self> {
+        let modulus = M::MODULUS.limbs[0];
+        let mask = u64::MAX >> modulus.leading_zeros();
+        loop {
+            let value = sample_u64() & mask;
+            if value < modulus {
+                return FieldElement::from(value);
+            }
+        }
+    }
+}
+
+impl HasDefaultTranscript for Goldilocks64Field {
+    /// Uses rejection sampling, which rejects a word with probability about 2^-32.
+    fn sample_field_element(sample_u64: &mut impl FnMut() -> u64) -> FieldElement<Self> {
+        loop {
+            let value = sample_u64();
+            if value < GOLDILOCKS_PRIME {
+                return FieldElement::from(value);
+            }
+        }
+    }
+}
+
+impl<Q> HasDefaultTranscript for QuadraticExtensionField<Q>
+where
+    Q: Clone + Debug + HasQuadraticNonResidue,
+    Q::BaseField: HasDefaultTranscript,
+{
+    fn sample_field_element(sample_u64: &mut impl FnMut() -> u64) -> FieldElement<Self> {
+        FieldElement::new([
+            Q::BaseField::sample_field_element(sample_u64),
+            Q::BaseField::sample_field_element(sample_u64),
+        ])
+    }
+}
+
+impl<Q> HasDefaultTranscript for CubicExtensionField<Q>
+where
+    Q: Clone + Debug + HasCubicNonResidue,
+    Q::BaseField: HasDefaultTranscript,
+{
+    fn sample_field_element(sample_u64: &mut impl FnMut() -> u64) -> FieldElement<Self> {
+        FieldElement::new([
+            Q::BaseField::sample_field_element(sample_u64),
+            Q::BaseField::sample_field_element(sample_u64),
+            Q::BaseField::sample_field_element(sample_u64),
+        ])
+    }
+}
+
+/// Keccak256 based transcript for fields other than the Stark252 prime field,
+/// e.g. Babybear and its extensions.
+#[derive(Clone)]
+pub struct DefaultTranscript<F: HasDefaultTranscript> {
+    state: [u8; 32],
+    counter: u32,
+    phantom: PhantomData<F>,
+}
+
+impl<F: HasDefaultTranscript> DefaultTranscript<F> {
+    pub fn new(public_input_data: &[u8]) -> Self {
+        Self {
+            state: Self::keccak_hash(public_input_data),
+            counter: 0,
+            phantom: PhantomData,
+        }
+    }
+
+    fn keccak_hash(data: &[u8]) -> [u8; 32] {
+        let mut hasher = Keccak256::new();
+        hasher.update(data);
+        let mut result_hash = [0_u8; 32];
+        result_hash.copy_from_slice(&hasher.finalize_reset());
+        result_hash
+    }
+
+    /// Returns the first 8 bytes of `keccak(state || counter)` and increments the counter.
+    fn sample_word(&mut self) -> u64 {
+        let block = Self::keccak_hash(&[&self.state[..], &self.counter.to_be_bytes()].concat());
+        self.counter += 1;
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&block[..8]);
+        u64::from_be_bytes(bytes)
+    }
+}
+
+impl<F> IsStarkTranscript<F> for DefaultTranscript<F>
+where
+    F: HasDefaultTranscript,
+    FieldElement<F>: Serializable,
+{
+    fn append_field_element(&mut self, element: &FieldElement<F>) {
+        self.append_bytes(&element.serialize());
+    }
+
+    fn append_bytes(&mut self, new_bytes: &[u8]) {
+        self.state = Self::keccak_hash(&[&self.state[..], new_bytes].concat());
+        self.counter = 0;
+    }
+
+    fn state(&self) -> [u8; 32] {
+        self.state
+    }
+
+    fn sample_field_element(&mut self) -> FieldElement<F> {
+        F::sample_field_element(&mut || self.sample_word())
+    }
+
+    fn sample_u64(&mut self, upper_bound: u64) -> u64 {
+        self.sample_word() % upper_bound
+    }
+}
+
+/// Transcript built on the Poseidon sponge of the prime field `F`, cheap to replay inside
+/// another proof system. It can sample challenges in any extension of `F`, whose
+/// elements are absorbed and built as their coordinates over `F`.
+#[derive(Clone)]
+pub struct PoseidonTranscript<F: IsPoseidonField> {
+    poseidon: Poseidon<F>,
+    digest: Vec<FieldElement<F>>,
+    counter: u64,
+}
+
+impl<F: IsPoseidonField> PoseidonTranscript<F> {
+    pub fn new(public_input_data: &[u8]) -> Self {
+        let poseidon = F::poseidon();
+        let digest = poseidon.hash_elements(&Self::bytes_to_elements(public_input_data));
+        Self {
+            poseidon,
+            digest,
+            counter: 0,
+        }
+    }
+
+    /// Packs `bytes` into field elements, as many bytes per element as fit below the
+    /// modulus, preceded by the number of bytes.
+    pub(crate) fn bytes_to_elements(bytes: &[u8]) -> Vec<FieldElement<F>> {
+        let bytes_per_element = (F::field_bit_size() - 1) / 8;
+        let byte_base = FieldElement::<F>::from(256);
+        core::iter::once(FieldElement::from(bytes.len() as u64))
+            .chain(bytes.chunks(bytes_per_element).map(|chunk| {
+                chunk.iter().fold(FieldElement::zero(), |acc, byte| {
+                    acc * &byte_base + FieldElement::from(*byte as u64)
+                })
+            }))
+            .collect()
+    }
+
+    fn append_elements(&mut self, elements: &[FieldElement<F>]) {
+        let input = [&self.digest[..], elements].concat();
+        self.digest = self.poseidon.hash_elements(&input);
+        self.counter = 0;
+    }
+
+    /// Returns `poseidon(digest || counter)` and increments the counter.
+    fn sample_digest(&mut self) -> Vec<FieldElement<F>> {
+        let input = [&self.digest[..], &[FieldElement::from(self.counter)]].concat();
+        self.counter += 1;
+        self.poseidon.hash_elements(&input)
+    }
+}
+
+impl<F, E> IsStarkTranscript<E> for PoseidonTranscript<F>
+where
+    F: IsPoseidonField + IsSubFieldOf<E>,
+    E: IsField,
+{
+    fn append_field_element(&mut self, element: &FieldElement<E>) {
+        let coordinates: Vec<_> = F::to_coordinates(element.value())
+            .iter()
+            .map(FieldElement::from_raw)
+            .collect();
+        self.append_elements(&coordinates);
+    }
+
+    fn append_bytes(&mut self, new_bytes: &[u8]) {
+        self.append_elements(&Self::bytes_to_elements(new_bytes));
+    }
+
+    fn state(&self) -> [u8; 32] {
+        F::digest_to_bytes(&self.digest)
+    }
+
+    fn sample_field_element(&mut self) -> FieldElement<E> {
+        let degree = <F as IsSubFieldOf<E>>::EXTENSION_DEGREE;
+        let mut coordinates = Vec::with_capacity(degree);
+        while coordinates.len() < degree {
+            coordinates.extend(self.sample_digest().iter().map(|x| x.value().clone()));
+        }
+        coordinates.truncate(degree);
+        FieldElement::from_raw(&F::from_coordinates(&coordinates))
+    }
+
+    fn sample_u64(&mut self, upper_bound: u64) -> u64 {
+        let digest = self.sample_digest();
+        let bytes = F::digest_to_bytes(&digest);
+        let mut word = [0u8; 8];
+        word.copy_from_slice(&bytes[24..]);
+        u64::from_be_bytes(word) % upper_bound
+    }
+}
+
 pub fn batch_sample_challenges<F: IsFFTField>(
     size: usize,
     transcript: &mut impl IsStarkTranscript<F>,
@@ -165,10 +399,16 @@ where
 #[cfg(test)]
 mod tests {
     use lambdaworks_math::field::{
-        element::FieldElement, fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
+        element::FieldElement,
+        fields::fft_friendly::{
+            babybear::{Babybear31PrimeField, Degree4Babybear31ExtensionField},
+            stark_252_prime_field::Stark252PrimeField,
+        },
     };
 
-    use crate::transcript::{IsStarkTranscript, StoneProverTranscript};
+    use crate::transcript::{
+        DefaultTranscript, IsStarkTranscript, PoseidonTranscript, StoneProverTranscript,
+    };
 
     use std::num::ParseIntError;
 
@@ -453,4 +693,52 @@ mod tests {
         assert_eq!(transcript.sample_u64(128), 28);
         assert_eq!(transcript.sample_u64(128), 31);
     }
+
+    #[test]
+    fn default_transcript_samples_the_same_challenges_for_the_same_inputs() {
+        let mut transcript_1 = DefaultTranscript::<Degree4Babybear31ExtensionField>::new(&[1, 2]);
+        let mut transcript_2 = DefaultTranscript::<Degree4Babybear31ExtensionField>::new(&[1, 2]);
+        transcript_1.append_bytes(&[3]);
+        transcript_2.append_bytes(&[3]);
+        assert_eq!(
+            transcript_1.sample_field_element(),
+            transcript_2.sample_field_element()
+        );
+        assert_eq!(transcript_1.sample_u64(128), transcript_2.sample_u64(128));
+    }
+
+    #[test]
+    fn default_transcript_challenges_depend_on_appended_data() {
+        let mut transcript_1 = DefaultTranscript::<Babybear31PrimeField>::new(&[]);
+        let mut transcript_2 = DefaultTranscript::<Babybear31PrimeField>::new(&[]);
+        transcript_1.append_field_element(&FieldElement::from(1));
+        transcript_2.append_field_element(&FieldElement::from(2));
+        assert_ne!(
+            transcript_1.sample_field_element(),
+            transcript_2.sample_field_element()
+        );
+    }
+
+    #[test]
+    fn poseidon_transcript_samples_the_same_challenges_for_the_same_inputs() {
+        type FE4 = FieldElement<Degree4Babybear31ExtensionField>;
+        let mut transcript_1 = PoseidonTranscript::<Babybear31PrimeField>::new(&[1, 2]);
+        let mut transcript_2 = PoseidonTranscript::<Babybear31PrimeField>::new(&[1, 2]);
+        IsStarkTranscript::<Degree4Babybear31ExtensionField>::append_bytes(&mut transcript_1, &[3]);
+        IsStarkTranscript::<Degree4Babybear31ExtensionField>::append_bytes(&mut transcript_2, &[3]);
+        let challenge_1: FE4 = transcript_1.sample_field_element();
+        let challenge_2: FE4 = transcript_2.sample_field_element();
+        assert_eq!(challenge_1, challenge_2);
+    }
+
+    #[test]
+    fn poseidon_transcript_challenges_depend_on_appended_data() {
+        let mut transcript_1 = PoseidonTranscript::<Stark252PrimeField>::new(&[]);
+        let mut transcript_2 = PoseidonTranscript::<Stark252PrimeField>::new(&[]);
+        transcript_1.append_field_element(&FE::from(1));
+        transcript_2.append_field_element(&FE::from(2));
+        let challenge_1: FE = transcript_1.sample_field_element();
+        let challenge_2: FE = transcript_2.sample_field_element();
+        assert_ne!(challenge_1, challenge_2);
+    }
 }
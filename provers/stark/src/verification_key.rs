@@ -0,0 +1,74 @@
+use lambdaworks_crypto::merkle_tree::merkle::MerkleTree;
+use lambdaworks_math::fft::cpu::bit_reversing::in_place_bit_reverse_permute;
+
+use crate::{
+    config::IsStarkMerkleTreeBackend, domain::Domain, prover::evaluate_polynomial_on_lde_domain,
+    trace::TraceTable, traits::AIR,
+};
+
+/// Commitment to the preprocessed columns of an AIR. See `AIR::get_preprocessed_columns`.
+/// The columns depend on the trace length and their LDE on the blowup factor and the coset
+/// offset, so a key is only valid for proofs with the same ones. The verifier checks the
+/// openings of the preprocessed columns against the root of the key, so it does not have
+/// to trust the prover with them nor to compute them on its own.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct VerificationKey<C> {
+    pub trace_length: usize,
+    pub blowup_factor: u8,
+    pub coset_offset: u64,
+    pub number_of_columns: usize,
+    /// Root of the Merkle tree of the LDE of the preprocessed columns, which are committed
+    /// as the main trace is.
+    pub root: C,
+}
+
+impl<C> VerificationKey<C> {
+    /// Commits to the preprocessed columns of `air` with the backend `B`, which must be the
+    /// backend of the main trace of the proofs. Returns `None` if the AIR has no
+    /// preprocessed columns.
+    pub fn new<A, B>(air: &A) -> Option<Self>
+    where
+        A: AIR,
+        B: IsStarkMerkleTreeBackend<A::Field, Node = C>,
+    {
+        let columns = air.get_preprocessed_columns();
+        if columns.is_empty() {
+            return None;
+        }
+
+        let domain = Domain::new(air);
+        let lde_columns: Vec<_> = TraceTable::from_columns(&columns)
+            .compute_trace_polys()
+            .iter()
+            .map(|poly| {
+                let mut evaluations = evaluate_polynomial_on_lde_domain(
+                    poly,
+                    domain.blowup_factor,
+                    domain.interpolation_domain_size,
+                    &domain.coset_offset,
+                )
+                .unwrap();
+                in_place_bit_reverse_permute(&mut evaluations);
+                evaluations
+            })
+            .collect();
+        let tree = MerkleTree::<B>::build(&TraceTable::from_columns(&lde_columns).rows());
+
+        Some(Self {
+            trace_length: air.trace_length(),
+            blowup_factor: air.options().blowup_factor,
+            coset_offset: air.options().coset_offset,
+            number_of_columns: columns.len(),
+            root: tree.root,
+        })
+    }
+
+    /// Returns whether the key commits to the preprocessed columns of `air`, judging by
+    /// the parameters they depend on.
+    pub fn matches<A: AIR>(&self, air: &A) -> bool {
+        self.trace_length == air.trace_length()
+            && self.blowup_factor == air.options().blowup_factor
+            && self.coset_offset == air.options().coset_offset
+            && self.number_of_columns == air.number_preprocessed_columns()
+    }
+}
@@ -1,7 +1,7 @@
 #[cfg(feature = "instruments")]
 use std::time::Instant;
 
-use lambdaworks_crypto::merkle_tree::proof::Proof;
+use lambdaworks_crypto::merkle_tree::proof::BatchProof;
 //use itertools::multizip;
 #[cfg(not(feature = "test_fiat_shamir"))]
 use log::error;
@@ -9,42 +9,69 @@ use log::error;
 use lambdaworks_math::{
     fft::cpu::bit_reversing::reverse_index,
     field::{
-        element::FieldElement, fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
-        traits::IsFFTField,
+        element::FieldElement,
+        fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
+        traits::{IsFFTField, IsSubFieldOf},
     },
+    polynomial::Polynomial,
     traits::Serializable,
 };
 
-use crate::{
-    config::Commitment, proof::stark::DeepPolynomialOpening, transcript::IsStarkTranscript,
+use crate::{proof::stark::DeepPolynomialOpening, transcript::IsStarkTranscript};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::{
+    IndexedParallelIterator, IntoParallelIterator, IntoParallelRefIterator, ParallelIterator,
 };
+use std::{collections::HashMap, marker::PhantomData};
 
 use super::{
-    config::BatchedMerkleTreeBackend,
+    config::{BatchedMerkleTreeBackend, IsStarkMerkleTreeBackend},
     domain::Domain,
-    fri::fri_decommit::FriDecommitment,
+    fri::{self, fri_decommit::FriDecommitment},
     grinding,
     proof::{options::ProofOptions, stark::StarkProof},
-    traits::AIR,
+    traits::{has_valid_periodic_columns, AIR},
+    verification_key::VerificationKey,
 };
 
-pub struct Verifier {}
+/// Verifier for AIRs whose main trace lives in `F` and whose challenges, auxiliary trace
+/// and FRI run over the extension `E`. The main trace is checked against the Merkle tree
+/// backend `B` and every other polynomial against `BE`. Both backends must have the same
+/// type of nodes, which is the type of the commitments of the proof.
+pub struct VerifierWithBackends<F, E, B, BE> {
+    phantom: PhantomData<(F, E, B, BE)>,
+}
+
+/// Verifier of proofs committed with the default Keccak256 backend.
+pub type Verifier<F = Stark252PrimeField, E = F> =
+    VerifierWithBackends<F, E, BatchedMerkleTreeBackend<F>, BatchedMerkleTreeBackend<E>>;
 
-impl IsStarkVerifier for Verifier {
-    type Field = Stark252PrimeField;
+impl<F, E, B, BE> IsStarkVerifier for VerifierWithBackends<F, E, B, BE>
+where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsFFTField,
+    B: IsStarkMerkleTreeBackend<F>,
+    B::Node: AsRef<[u8]>,
+    BE: IsStarkMerkleTreeBackend<E, Node = B::Node>,
+{
+    type Field = F;
+    type FieldExtension = E;
+    type Commitment = B::Node;
+    type MerkleTreeBackend = B;
+    type MerkleTreeBackendExtension = BE;
 }
 
-pub struct Challenges<F, A>
+pub struct Challenges<A>
 where
-    F: IsFFTField,
-    A: AIR<Field = F>,
+    A: AIR,
 {
-    pub z: FieldElement<F>,
-    pub boundary_coeffs: Vec<FieldElement<F>>,
-    pub transition_coeffs: Vec<FieldElement<F>>,
-    pub trace_term_coeffs: Vec<Vec<FieldElement<F>>>,
-    pub gammas: Vec<FieldElement<F>>,
-    pub zetas: Vec<FieldElement<F>>,
+    pub z: FieldElement<A::FieldExtension>,
+    pub boundary_coeffs: Vec<FieldElement<A::FieldExtension>>,
+    pub transition_coeffs: Vec<FieldElement<A::FieldExtension>>,
+    pub trace_term_coeffs: Vec<Vec<FieldElement<A::FieldExtension>>>,
+    pub gammas: Vec<FieldElement<A::FieldExtension>>,
+    pub zetas: Vec<FieldElement<A::FieldExtension>>,
     pub iotas: Vec<usize>,
     pub rap_challenges: A::RAPChallenges,
     pub grinding_seed: [u8; 32],
@@ -53,12 +80,23 @@ where
 pub type DeepPolynomialEvaluations<F> = (Vec<FieldElement<F>>, Vec<FieldElement<F>>);
 
 pub trait IsStarkVerifier {
-    type Field: IsFFTField;
+    type Field: IsFFTField + IsSubFieldOf<Self::FieldExtension>;
+    type FieldExtension: IsFFTField;
+    /// Nodes of the Merkle trees, which are the commitments of the proof.
+    type Commitment: PartialEq + Eq + Clone + AsRef<[u8]>;
+    /// Merkle tree backend the main trace was committed with.
+    type MerkleTreeBackend: IsStarkMerkleTreeBackend<Self::Field, Node = Self::Commitment>;
+    /// Merkle tree backend the auxiliary trace, the composition polynomial and the FRI
+    /// layers were committed with.
+    type MerkleTreeBackendExtension: IsStarkMerkleTreeBackend<
+        Self::FieldExtension,
+        Node = Self::Commitment,
+    >;
 
     fn sample_query_indexes(
         number_of_queries: usize,
         domain: &Domain<Self::Field>,
-        transcript: &mut impl IsStarkTranscript<Self::Field>,
+        transcript: &mut impl IsStarkTranscript<Self::FieldExtension>,
     ) -> Vec<usize> {
         let domain_size = domain.lde_roots_of_unity_coset.len() as u64;
         (0..number_of_queries)
@@ -68,25 +106,31 @@ pub trait IsStarkVerifier {
 
     fn step_1_replay_rounds_and_recover_challenges<A>(
         air: &A,
-        proof: &StarkProof<Self::Field>,
+        proof: &StarkProof<Self::Field, Self::FieldExtension, Self::Commitment>,
         domain: &Domain<Self::Field>,
-        transcript: &mut impl IsStarkTranscript<Self::Field>,
-    ) -> Challenges<Self::Field, A>
+        preprocessed_root: Option<&Self::Commitment>,
+        transcript: &mut impl IsStarkTranscript<Self::FieldExtension>,
+    ) -> Challenges<A>
     where
-        FieldElement<Self::Field>: Serializable,
-        A: AIR<Field = Self::Field>,
+        FieldElement<Self::FieldExtension>: Serializable,
+        A: AIR<Field = Self::Field, FieldExtension = Self::FieldExtension>,
     {
         // ===================================
         // ==========|   Round 1   |==========
         // ===================================
 
+        // <<<< Receive commitment: root of the verification key
+        if let Some(root) = preprocessed_root {
+            transcript.append_bytes(root.as_ref());
+        }
+
         // <<<< Receive commitments:[tⱼ]
-        transcript.append_bytes(&proof.lde_trace_merkle_roots[0]);
+        transcript.append_bytes(proof.lde_trace_merkle_roots[0].as_ref());
 
         let rap_challenges = air.build_rap_challenges(transcript);
 
         if let Some(root) = proof.lde_trace_merkle_roots.get(1) {
-            transcript.append_bytes(root);
+            transcript.append_bytes(root.as_ref());
         }
 
         // ===================================
@@ -107,7 +151,7 @@ pub trait IsStarkVerifier {
         let boundary_coeffs = coefficients;
 
         // <<<< Receive commitments: [H₁], [H₂]
-        transcript.append_bytes(&proof.composition_poly_root);
+        transcript.append_bytes(proof.composition_poly_root.as_ref());
 
         // ===================================
         // ==========|   Round 3   |==========
@@ -135,7 +179,8 @@ pub trait IsStarkVerifier {
         // ===================================
 
         let n_terms_composition_poly = proof.composition_poly_parts_ood_evaluation.len();
-        let n_terms_trace = air.context().transition_offsets.len() * air.context().trace_columns;
+        let n_terms_trace = air.context().transition_offsets.len()
+            * (air.context().trace_columns + air.number_preprocessed_columns());
         let gamma = transcript.sample_field_element();
 
         // <<<< Receive challenges: 𝛾, 𝛾'
@@ -162,16 +207,18 @@ pub trait IsStarkVerifier {
                 // >>>> Send challenge 𝜁ₖ
                 let element = transcript.sample_field_element();
                 // <<<< Receive commitment: [pₖ] (the first one is [p₀])
-                transcript.append_bytes(root);
+                transcript.append_bytes(root.as_ref());
                 element
             })
-            .collect::<Vec<FieldElement<Self::Field>>>();
+            .collect::<Vec<FieldElement<Self::FieldExtension>>>();
 
         // >>>> Send challenge 𝜁ₙ₋₁
         zetas.push(transcript.sample_field_element());
 
-        // <<<< Receive value: pₙ
-        transcript.append_field_element(&proof.fri_last_value);
+        // <<<< Receive coefficients: pₙ
+        for coefficient in &proof.fri_remainder_coefficients {
+            transcript.append_field_element(coefficient);
+        }
 
         // Receive grinding value
         let security_bits = air.context().proof_options.grinding_factor;
@@ -201,12 +248,12 @@ pub trait IsStarkVerifier {
 
     fn step_2_verify_claimed_composition_polynomial<A>(
         air: &A,
-        proof: &StarkProof<Self::Field>,
+        proof: &StarkProof<Self::Field, Self::FieldExtension, Self::Commitment>,
         domain: &Domain<Self::Field>,
-        challenges: &Challenges<Self::Field, A>,
+        challenges: &Challenges<A>,
     ) -> bool
     where
-        A: AIR<Field = Self::Field>,
+        A: AIR<Field = Self::Field, FieldExtension = Self::FieldExtension>,
     {
         let boundary_constraints = air.boundary_constraints(&challenges.rap_challenges);
 
@@ -215,15 +262,15 @@ pub trait IsStarkVerifier {
 
         #[allow(clippy::type_complexity)]
         let (boundary_c_i_evaluations_num, mut boundary_c_i_evaluations_den): (
-            Vec<FieldElement<Self::Field>>,
-            Vec<FieldElement<Self::Field>>,
+            Vec<FieldElement<Self::FieldExtension>>,
+            Vec<FieldElement<Self::FieldExtension>>,
         ) = (0..number_of_b_constraints)
             .map(|index| {
                 let step = boundary_constraints.constraints[index].step;
-                let point = &domain.trace_primitive_root.pow(step as u64);
+                let point = domain.trace_primitive_root.pow(step as u64).to_extension();
                 let trace_idx = boundary_constraints.constraints[index].col;
                 let trace_evaluation = &proof.trace_ood_frame_evaluations.get_row(0)[trace_idx];
-                let boundary_zerofier_challenges_z_den = &challenges.z - point;
+                let boundary_zerofier_challenges_z_den = &challenges.z - &point;
 
                 let boundary_quotient_ood_evaluation_num =
                     trace_evaluation - &boundary_constraints.constraints[index].value;
@@ -239,30 +286,42 @@ pub trait IsStarkVerifier {
 
         FieldElement::inplace_batch_inverse(&mut boundary_c_i_evaluations_den).unwrap();
 
-        let boundary_quotient_ood_evaluation: FieldElement<Self::Field> =
+        let boundary_quotient_ood_evaluation: FieldElement<Self::FieldExtension> =
             boundary_c_i_evaluations_num
                 .iter()
                 .zip(&boundary_c_i_evaluations_den)
                 .zip(&challenges.boundary_coeffs)
                 .map(|((num, den), beta)| num * den * beta)
-                .fold(FieldElement::<Self::Field>::zero(), |acc, x| acc + x);
-
-        let transition_ood_frame_evaluations = air.compute_transition(
-            &proof.trace_ood_frame_evaluations,
-            &challenges.rap_challenges,
-        );
-
-        let denominator = (&challenges.z.pow(trace_length) - FieldElement::<Self::Field>::one())
-            .inv()
-            .unwrap();
+                .fold(FieldElement::<Self::FieldExtension>::zero(), |acc, x| {
+                    acc + x
+                });
+
+        // The periodic columns are not part of the proof, so their values at the out of
+        // domain point are computed here.
+        let periodic_values = air
+            .get_periodic_column_polynomials()
+            .into_iter()
+            .map(|poly| poly.to_extension().evaluate(&challenges.z))
+            .collect();
+        let ood_frame = proof
+            .trace_ood_frame_evaluations
+            .clone()
+            .with_periodic_values(periodic_values);
+        let transition_ood_frame_evaluations =
+            air.compute_transition(&ood_frame, &challenges.rap_challenges);
+
+        let denominator = (&challenges.z.pow(trace_length)
+            - FieldElement::<Self::FieldExtension>::one())
+        .inv()
+        .unwrap();
 
         let exemption = air
             .transition_exemptions_verifier(
                 domain.trace_roots_of_unity.iter().last().expect("has last"),
             )
-            .iter()
-            .map(|poly| poly.evaluate(&challenges.z))
-            .collect::<Vec<FieldElement<Self::Field>>>();
+            .into_iter()
+            .map(|poly| poly.to_extension().evaluate(&challenges.z))
+            .collect::<Vec<FieldElement<Self::FieldExtension>>>();
 
         let unity = &FieldElement::one();
         let transition_c_i_evaluations_sum = transition_ood_frame_evaluations
@@ -281,9 +340,13 @@ pub trait IsStarkVerifier {
         let composition_poly_ood_evaluation =
             &boundary_quotient_ood_evaluation + transition_c_i_evaluations_sum;
 
+        // In zero-knowledge mode the last part is a random mask that is not part of
+        // the composition polynomial.
+        let number_of_parts = air.number_of_composition_poly_parts();
         let composition_poly_claimed_ood_evaluation = proof
             .composition_poly_parts_ood_evaluation
             .iter()
+            .take(number_of_parts)
             .rev()
             .fold(FieldElement::zero(), |acc, coeff| {
                 acc * &challenges.z + coeff
@@ -293,45 +356,141 @@ pub trait IsStarkVerifier {
     }
 
     fn step_3_verify_fri<A>(
-        proof: &StarkProof<Self::Field>,
+        air: &A,
+        proof: &StarkProof<Self::Field, Self::FieldExtension, Self::Commitment>,
         domain: &Domain<Self::Field>,
-        challenges: &Challenges<Self::Field, A>,
+        challenges: &Challenges<A>,
     ) -> bool
     where
-        FieldElement<Self::Field>: Serializable,
-        A: AIR<Field = Self::Field>,
+        FieldElement<Self::FieldExtension>: Serializable,
+        A: AIR<Field = Self::Field, FieldExtension = Self::FieldExtension>,
     {
         let (deep_poly_evaluations, deep_poly_evaluations_sym) =
             Self::reconstruct_deep_composition_poly_evaluations_for_all_queries(
-                challenges, domain, proof,
+                air, challenges, domain, proof,
             );
 
-        // verify FRI
-        let mut evaluation_point_inverse = challenges
-            .iotas
+        let number_of_fri_layers =
+            air.deep_composition_poly_degree_bound().trailing_zeros() as usize;
+        let layers_folding_factors = fri::layers_folding_factors(
+            number_of_fri_layers,
+            air.options().fri_folding_factor as usize,
+            fri::remainder_degree_bound(
+                number_of_fri_layers,
+                air.options().fri_max_remainder_degree,
+            ),
+        );
+
+        Self::verify_fri_queries(
+            &proof.fri_layers_merkle_roots,
+            &proof.fri_layers_merkle_proofs,
+            &proof.fri_remainder_coefficients,
+            &proof.query_list,
+            &challenges.zetas,
+            &challenges.iotas,
+            &layers_folding_factors,
+            domain,
+            &deep_poly_evaluations,
+            &deep_poly_evaluations_sym,
+        )
+    }
+
+    /// Verifies the FRI queries: folds each of them through all the FRI layers, checks the
+    /// result against the remainder polynomial and checks the openings of each layer against its commitment with a
+    /// single batched Merkle proof.
+    /// `layers_folding_factors`: the folding factor of each FRI layer, starting from layer 1.
+    /// `domain`: the LDE domain of the DEEP composition polynomial p₀.
+    /// `deep_poly_evaluations`, `deep_poly_evaluations_sym`: precomputed values of p₀(𝜐ₛ)
+    /// and p₀(-𝜐ₛ) for each query.
+    #[allow(clippy::too_many_arguments)]
+    fn verify_fri_queries(
+        fri_layers_merkle_roots: &[Self::Commitment],
+        fri_layers_merkle_proofs: &[BatchProof<Self::Commitment>],
+        fri_remainder_coefficients: &[FieldElement<Self::FieldExtension>],
+        query_list: &[FriDecommitment<Self::FieldExtension>],
+        zetas: &[FieldElement<Self::FieldExtension>],
+        iotas: &[usize],
+        layers_folding_factors: &[usize],
+        domain: &Domain<Self::Field>,
+        deep_poly_evaluations: &[FieldElement<Self::FieldExtension>],
+        deep_poly_evaluations_sym: &[FieldElement<Self::FieldExtension>],
+    ) -> bool
+    where
+        FieldElement<Self::FieldExtension>: Serializable,
+    {
+        if query_list.len() != iotas.len()
+            || fri_layers_merkle_proofs.len() != fri_layers_merkle_roots.len()
+            || layers_folding_factors.len() != fri_layers_merkle_roots.len()
+        {
+            return false;
+        }
+
+        let evaluation_points = iotas
             .iter()
             .map(|iota| Self::query_challenge_to_evaluation_point(*iota, domain))
             .collect::<Vec<FieldElement<Self::Field>>>();
+        let mut evaluation_point_inverse = evaluation_points.clone();
         FieldElement::inplace_batch_inverse(&mut evaluation_point_inverse).unwrap();
-        proof
-            .query_list
+
+        // The remainder pₙ is evaluated at 𝜐ₛ raised to the product of all the folding factors
+        let remainder_poly = Polynomial::new(fri_remainder_coefficients);
+        let total_folding_factor = 2 * layers_folding_factors.iter().product::<usize>();
+
+        // Leaves opened in each layer by all the queries
+        let mut layers_openings =
+            vec![Vec::with_capacity(iotas.len()); fri_layers_merkle_roots.len()];
+        for (i, (((fri_decommitment, iota), eval), point)) in query_list
             .iter()
-            .zip(&challenges.iotas)
+            .zip(iotas)
             .zip(evaluation_point_inverse)
+            .zip(&evaluation_points)
             .enumerate()
-            .fold(true, |mut result, (i, ((proof_s, iota_s), eval))| {
-                // this is done in constant time
-                result &= Self::verify_query_and_sym_openings(
-                    proof,
-                    &challenges.zetas,
-                    *iota_s,
-                    proof_s,
-                    eval,
-                    &deep_poly_evaluations[i],
-                    &deep_poly_evaluations_sym[i],
-                );
-                result
-            })
+        {
+            let Some((remainder_evaluation, openings)) = Self::verify_query_and_sym_openings(
+                zetas,
+                layers_folding_factors,
+                *iota,
+                fri_decommitment,
+                eval.to_extension(),
+                &deep_poly_evaluations[i],
+                &deep_poly_evaluations_sym[i],
+            ) else {
+                return false;
+            };
+            // Check that final value is the evaluation of the remainder given by the prover
+            let remainder_point = point.pow(total_folding_factor).to_extension();
+            if remainder_evaluation != remainder_poly.evaluate(&remainder_point) {
+                return false;
+            }
+            for (layer_openings, opening) in layers_openings.iter_mut().zip(openings) {
+                layer_openings.push(opening);
+            }
+        }
+
+        // The layer pₖ has a leaf for each coset of its domain. The domain of p₁ is half the
+        // one of p₀, and the one of pₖ₊₁ is the one of pₖ shrunk by its folding factor. The
+        // openings of a query are in the leaf of its position divided by the folding factor.
+        let mut positions = iotas.to_vec();
+        let mut layer_domain_size = domain.lde_roots_of_unity_coset.len() / 2;
+        fri_layers_merkle_roots
+            .iter()
+            .zip(fri_layers_merkle_proofs)
+            .zip(layers_openings)
+            .zip(layers_folding_factors)
+            .all(
+                |(((merkle_root, merkle_proof), openings), folding_factor)| {
+                    positions
+                        .iter_mut()
+                        .for_each(|position| *position /= folding_factor);
+                    layer_domain_size /= folding_factor;
+                    merkle_proof.verify::<Self::MerkleTreeBackendExtension>(
+                        merkle_root,
+                        layer_domain_size,
+                        &positions,
+                        &openings,
+                    )
+                },
+            )
     }
 
     fn query_challenge_to_evaluation_point(
@@ -352,276 +511,317 @@ pub trait IsStarkVerifier {
         .clone()
     }
 
-    fn verify_opening(
-        proof: &Proof<Commitment>,
-        root: &Commitment,
-        index: usize,
-        value: &[FieldElement<Self::Field>],
-    ) -> bool
-    where
-        FieldElement<Self::Field>: Serializable,
-    {
-        proof.verify::<BatchedMerkleTreeBackend<Self::Field>>(root, index, &value.to_owned())
-    }
-
-    /// Verify opening Open(tⱼ(D_LDE), 𝜐) and Open(tⱼ(D_LDE), -𝜐) for all trace polynomials tⱼ,
-    /// where 𝜐 and -𝜐 are the elements corresponding to the index challenge `iota`.
+    /// Verify the openings Open(tⱼ(D_LDE), 𝜐ₛ) and Open(tⱼ(D_LDE), -𝜐ₛ) of all the queries
+    /// for all trace polynomials tⱼ, where 𝜐ₛ and -𝜐ₛ are the elements corresponding to the
+    /// index challenge `iota_s`. The main trace is checked against the first root, the
+    /// auxiliary trace, if any, against the second one and the preprocessed columns, if any,
+    /// against `preprocessed_root`.
     fn verify_trace_openings(
-        num_main_columns: usize,
-        proof: &StarkProof<Self::Field>,
-        deep_poly_openings: &DeepPolynomialOpening<Self::Field>,
-        deep_poly_openings_sym: &DeepPolynomialOpening<Self::Field>,
-        iota: usize,
+        proof: &StarkProof<Self::Field, Self::FieldExtension, Self::Commitment>,
+        domain: &Domain<Self::Field>,
+        iotas: &[usize],
+        preprocessed_root: Option<&Self::Commitment>,
     ) -> bool
     where
         FieldElement<Self::Field>: Serializable,
+        FieldElement<Self::FieldExtension>: Serializable,
     {
-        let lde_trace_evaluations = vec![
-            deep_poly_openings.lde_trace_evaluations[..num_main_columns].to_vec(),
-            deep_poly_openings.lde_trace_evaluations[num_main_columns..].to_vec(),
-        ];
-
-        let index = iota * 2;
-        let openings_are_valid = proof
-            .lde_trace_merkle_roots
-            .iter()
-            .zip(&deep_poly_openings.lde_trace_merkle_proofs)
-            .zip(lde_trace_evaluations)
-            .fold(true, |acc, ((merkle_root, merkle_proof), evaluation)| {
-                acc & Self::verify_opening(merkle_proof, merkle_root, index, &evaluation)
-            });
-
-        let lde_trace_evaluations_sym = vec![
-            deep_poly_openings_sym.lde_trace_evaluations[..num_main_columns].to_vec(),
-            deep_poly_openings_sym.lde_trace_evaluations[num_main_columns..].to_vec(),
-        ];
+        let roots = &proof.lde_trace_merkle_roots;
+        let merkle_proofs = &proof.lde_trace_merkle_proofs;
+        if merkle_proofs.len() != roots.len() {
+            return false;
+        }
 
-        let index_sym = iota * 2 + 1;
-        let openings_sym_are_valid = proof
-            .lde_trace_merkle_roots
+        // The trace is committed in bit-reversed order, so 𝜐ₛ and -𝜐ₛ are the leaves
+        // 2𝜄ₛ and 2𝜄ₛ + 1.
+        let number_of_leaves = domain.lde_roots_of_unity_coset.len();
+        let positions: Vec<_> = iotas
             .iter()
-            .zip(&deep_poly_openings_sym.lde_trace_merkle_proofs)
-            .zip(lde_trace_evaluations_sym)
-            .fold(true, |acc, ((merkle_root, merkle_proof), evaluation)| {
-                acc & Self::verify_opening(merkle_proof, merkle_root, index_sym, &evaluation)
-            });
-        openings_are_valid & openings_sym_are_valid
-    }
+            .flat_map(|iota| [iota * 2, iota * 2 + 1])
+            .collect();
+        let openings = proof
+            .deep_poly_openings
+            .iter()
+            .zip(&proof.deep_poly_openings_sym);
+
+        let main_values: Vec<_> = openings
+            .clone()
+            .flat_map(|(opening, opening_sym)| {
+                [
+                    opening.lde_trace_evaluations.clone(),
+                    opening_sym.lde_trace_evaluations.clone(),
+                ]
+            })
+            .collect();
+        let main_openings_are_valid = merkle_proofs[0].verify::<Self::MerkleTreeBackend>(
+            &roots[0],
+            number_of_leaves,
+            &positions,
+            &main_values,
+        );
 
-    /// Verify opening Open(Hᵢ(D_LDE), 𝜐) and Open(Hᵢ(D_LDE), -𝜐) for all parts Hᵢof the composition
-    /// polynomial, where 𝜐 and -𝜐 are the elements corresponding to the index challenge `iota`.
-    fn verify_composition_poly_opening(
-        deep_poly_openings: &DeepPolynomialOpening<Self::Field>,
-        deep_poly_openings_sym: &DeepPolynomialOpening<Self::Field>,
-        composition_poly_merkle_root: &Commitment,
-        iota: &usize,
-    ) -> bool
-    where
-        FieldElement<Self::Field>: Serializable,
-    {
-        let mut value = deep_poly_openings
-            .lde_composition_poly_parts_evaluation
-            .clone();
-        value.extend_from_slice(&deep_poly_openings_sym.lde_composition_poly_parts_evaluation);
+        let aux_openings_are_valid = match (roots.get(1), merkle_proofs.get(1)) {
+            (Some(root), Some(merkle_proof)) => {
+                let aux_values: Vec<_> = openings
+                    .clone()
+                    .flat_map(|(opening, opening_sym)| {
+                        [
+                            opening.lde_aux_trace_evaluations.clone(),
+                            opening_sym.lde_aux_trace_evaluations.clone(),
+                        ]
+                    })
+                    .collect();
+                merkle_proof.verify::<Self::MerkleTreeBackendExtension>(
+                    root,
+                    number_of_leaves,
+                    &positions,
+                    &aux_values,
+                )
+            }
+            _ => true,
+        };
 
-        deep_poly_openings
-            .lde_composition_poly_proof
-            .verify::<BatchedMerkleTreeBackend<Self::Field>>(
-                composition_poly_merkle_root,
-                *iota,
-                &value,
-            )
+        let preprocessed_openings_are_valid =
+            match (preprocessed_root, &proof.lde_preprocessed_merkle_proof) {
+                (Some(root), Some(merkle_proof)) => {
+                    let preprocessed_values: Vec<_> = openings
+                        .flat_map(|(opening, opening_sym)| {
+                            [
+                                opening.lde_preprocessed_evaluations.clone(),
+                                opening_sym.lde_preprocessed_evaluations.clone(),
+                            ]
+                        })
+                        .collect();
+                    merkle_proof.verify::<Self::MerkleTreeBackend>(
+                        root,
+                        number_of_leaves,
+                        &positions,
+                        &preprocessed_values,
+                    )
+                }
+                (None, None) => true,
+                _ => false,
+            };
+
+        main_openings_are_valid & aux_openings_are_valid & preprocessed_openings_are_valid
     }
 
-    fn step_4_verify_trace_and_composition_openings<F: IsFFTField, A: AIR<Field = F>>(
-        air: &A,
-        proof: &StarkProof<Self::Field>,
-        challenges: &Challenges<F, A>,
+    /// Verify the openings Open(Hᵢ(D_LDE), 𝜐ₛ) and Open(Hᵢ(D_LDE), -𝜐ₛ) of all the queries
+    /// for all parts Hᵢ of the composition polynomial. Both evaluations of a query are in
+    /// the leaf `iota_s`.
+    fn verify_composition_poly_openings(
+        proof: &StarkProof<Self::Field, Self::FieldExtension, Self::Commitment>,
+        domain: &Domain<Self::Field>,
+        iotas: &[usize],
     ) -> bool
     where
-        FieldElement<Self::Field>: Serializable,
+        FieldElement<Self::FieldExtension>: Serializable,
     {
-        challenges
-            .iotas
+        let values: Vec<_> = proof
+            .deep_poly_openings
             .iter()
-            .zip(&proof.deep_poly_openings)
             .zip(&proof.deep_poly_openings_sym)
-            .fold(
-                true,
-                |mut result, ((iota_n, deep_poly_opening), deep_poly_openings_sym)| {
-                    result &= Self::verify_composition_poly_opening(
-                        deep_poly_opening,
-                        deep_poly_openings_sym,
-                        &proof.composition_poly_root,
-                        iota_n,
-                    );
-
-                    let num_main_columns =
-                        air.context().trace_columns - air.number_auxiliary_rap_columns();
-                    result &= Self::verify_trace_openings(
-                        num_main_columns,
-                        proof,
-                        deep_poly_opening,
-                        deep_poly_openings_sym,
-                        *iota_n,
-                    );
-                    result
-                },
+            .map(|(opening, opening_sym)| {
+                let mut value = opening.lde_composition_poly_parts_evaluation.clone();
+                value.extend_from_slice(&opening_sym.lde_composition_poly_parts_evaluation);
+                value
+            })
+            .collect();
+
+        proof
+            .lde_composition_poly_proof
+            .verify::<Self::MerkleTreeBackendExtension>(
+                &proof.composition_poly_root,
+                domain.lde_roots_of_unity_coset.len() / 2,
+                iotas,
+                &values,
             )
     }
 
-    fn verify_fri_layer_openings(
-        merkle_root: &Commitment,
-        auth_path_sym: &Proof<Commitment>,
-        evaluation: &FieldElement<Self::Field>,
-        evaluation_sym: &FieldElement<Self::Field>,
-        iota: usize,
+    fn step_4_verify_trace_and_composition_openings<A>(
+        proof: &StarkProof<Self::Field, Self::FieldExtension, Self::Commitment>,
+        domain: &Domain<Self::Field>,
+        challenges: &Challenges<A>,
+        preprocessed_root: Option<&Self::Commitment>,
     ) -> bool
     where
+        A: AIR<Field = Self::Field, FieldExtension = Self::FieldExtension>,
         FieldElement<Self::Field>: Serializable,
+        FieldElement<Self::FieldExtension>: Serializable,
     {
-        let evaluations = if iota % 2 == 1 {
-            vec![evaluation_sym.clone(), evaluation.clone()]
-        } else {
-            vec![evaluation.clone(), evaluation_sym.clone()]
-        };
-
-        auth_path_sym.verify::<BatchedMerkleTreeBackend<Self::Field>>(
-            merkle_root,
-            iota >> 1,
-            &evaluations,
-        )
+        let composition_openings_are_valid =
+            Self::verify_composition_poly_openings(proof, domain, &challenges.iotas);
+        let trace_openings_are_valid =
+            Self::verify_trace_openings(proof, domain, &challenges.iotas, preprocessed_root);
+        composition_openings_are_valid & trace_openings_are_valid
     }
 
-    /// Verify a single FRI query
+    /// Folds a single FRI query through all the FRI layers. Returns the value of the remainder
+    /// pₙ at the folded query point and the openings of the query in each layer, the
+    /// evaluations of pₖ at the coset of 𝜐^(2ᵏ) in the order of the leaf of the Merkle tree of
+    /// the layer, or `None` if the prover did not send as many evaluations as the layers
+    /// need. Both are checked by `verify_fri_queries` together with the ones of all the other
+    /// queries.
     /// `zetas`: the vector of all challenges sent by the verifier to the prover at the commit
     /// phase to fold polynomials.
+    /// `layers_folding_factors`: the folding factor of each FRI layer, starting from layer 1.
     /// `iota`: the index challenge of this FRI query. This index uniquely determines two elements 𝜐 and -𝜐
     /// of the evaluation domain of FRI layer 0.
     /// `evaluation_point_inv`: precomputed value of 𝜐⁻¹.
     /// `deep_composition_evaluation`: precomputed value of p₀(𝜐), where p₀ is the deep composition polynomial.
     /// `deep_composition_evaluation_sym`: precomputed value of p₀(-𝜐), where p₀ is the deep composition polynomial.
+    #[allow(clippy::too_many_arguments, clippy::type_complexity)]
     fn verify_query_and_sym_openings(
-        proof: &StarkProof<Self::Field>,
-        zetas: &[FieldElement<Self::Field>],
+        zetas: &[FieldElement<Self::FieldExtension>],
+        layers_folding_factors: &[usize],
         iota: usize,
-        fri_decommitment: &FriDecommitment<Self::Field>,
-        evaluation_point_inv: FieldElement<Self::Field>,
-        deep_composition_evaluation: &FieldElement<Self::Field>,
-        deep_composition_evaluation_sym: &FieldElement<Self::Field>,
-    ) -> bool
-    where
-        FieldElement<Self::Field>: Serializable,
-    {
-        let fri_layers_merkle_roots = &proof.fri_layers_merkle_roots;
-        let evaluation_point_vec: Vec<FieldElement<Self::Field>> =
-            core::iter::successors(Some(evaluation_point_inv.square()), |evaluation_point| {
-                Some(evaluation_point.square())
-            })
-            .take(fri_layers_merkle_roots.len())
-            .collect();
-
+        fri_decommitment: &FriDecommitment<Self::FieldExtension>,
+        evaluation_point_inv: FieldElement<Self::FieldExtension>,
+        deep_composition_evaluation: &FieldElement<Self::FieldExtension>,
+        deep_composition_evaluation_sym: &FieldElement<Self::FieldExtension>,
+    ) -> Option<(
+        FieldElement<Self::FieldExtension>,
+        Vec<Vec<FieldElement<Self::FieldExtension>>>,
+    )> {
         let p0_eval = deep_composition_evaluation;
         let p0_eval_sym = deep_composition_evaluation_sym;
 
         // Reconstruct p₁(𝜐²)
         let mut v =
-            (p0_eval + p0_eval_sym) + &zetas[0] * (p0_eval - p0_eval_sym) * evaluation_point_inv;
+            (p0_eval + p0_eval_sym) + &zetas[0] * (p0_eval - p0_eval_sym) * &evaluation_point_inv;
         let mut index = iota;
+        let mut evaluation_point_inv = evaluation_point_inv.square();
+        let mut layers_evaluations_sym = fri_decommitment.layers_evaluations_sym.iter();
+
+        // For each FRI layer, starting from the layer 1: collect the values of pᵢ at the coset of
+        // its query point x (given by the prover) and pᵢ(x) (computed on the previous iteration by
+        // the verifier). Then use them to obtain pᵢ₊₁(xⁿ), where n is the folding factor of the layer.
+        let mut openings = Vec::with_capacity(layers_folding_factors.len());
+        for (zeta, &folding_factor) in zetas.iter().skip(1).zip(layers_folding_factors) {
+            // Opening Open(pᵢ(Dₖ), x·ω^j) for all the n-th roots of unity ω^j.
+            // `v` is pᵢ(x), the other evaluations are given by the prover.
+            let mut coset_evaluations: Vec<_> = layers_evaluations_sym
+                .by_ref()
+                .take(folding_factor - 1)
+                .cloned()
+                .collect();
+            if coset_evaluations.len() != folding_factor - 1 {
+                return None;
+            }
+            let position_in_coset = index % folding_factor;
+            coset_evaluations.insert(position_in_coset, v);
+
+            // In bit-reversed order, the leaf of x is the coset x·ω^(-rev(position))·⟨ω⟩
+            let omega: FieldElement<Self::Field> =
+                Self::Field::get_primitive_root_of_unity(folding_factor.trailing_zeros() as u64)
+                    .unwrap();
+            let coset_offset_inv = &evaluation_point_inv
+                * omega
+                    .pow(reverse_index(position_in_coset, folding_factor as u64))
+                    .to_extension();
+            let omega_inv = omega.pow(folding_factor - 1).to_extension();
+
+            // Update `v` with next value pᵢ₊₁(xⁿ).
+            v = fri::fold_coset_evaluations(
+                &coset_evaluations,
+                zeta,
+                &coset_offset_inv,
+                &omega_inv,
+            );
+            openings.push(coset_evaluations);
 
-        // For each FRI layer, starting from the layer 1: use the proof to verify the validity of values pᵢ(−𝜐^(2ⁱ)) (given by the prover) and
-        // pᵢ(𝜐^(2ⁱ)) (computed on the previous iteration by the verifier). Then use them to obtain pᵢ₊₁(𝜐^(2ⁱ⁺¹)).
-        // Finally, check that the final value coincides with the given by the prover.
-        fri_layers_merkle_roots
-            .iter()
-            .enumerate()
-            .zip(&fri_decommitment.layers_auth_paths_sym)
-            .zip(&fri_decommitment.layers_evaluations_sym)
-            .zip(evaluation_point_vec)
-            .fold(
-                true,
-                |result,
-                 (
-                    (((i, merkle_root), auth_path_sym), evaluation_sym),
-                    evaluation_point_inv,
-                )| {
-                    // Verify opening Open(pᵢ(Dₖ), −𝜐^(2ⁱ)) and Open(pᵢ(Dₖ), 𝜐^(2ⁱ)).
-                    // `v` is pᵢ(𝜐^(2ⁱ)).
-                    // `evaluation_sym` is pᵢ(−𝜐^(2ⁱ)).
-                    let openings_ok = Self::verify_fri_layer_openings(
-                        merkle_root,
-                        auth_path_sym,
-                        &v,
-                        evaluation_sym,
-                        index,
-                    );
-
-                    // Update `v` with next value pᵢ₊₁(𝜐^(2ⁱ⁺¹)).
-                    v = (&v + evaluation_sym) + &zetas[i + 1] * (&v - evaluation_sym) * evaluation_point_inv;
-
-                    // Update index for next iteration. The index of the squares in the next layer
-                    // is obtained by halving the current index. This is due to the bit-reverse
-                    // ordering of the elements in the Merkle tree.
-                    index >>= 1;
-
-                    if i < fri_decommitment.layers_evaluations_sym.len() - 1 {
-                        result & openings_ok
-                    } else {
-                        // Check that final value is the given by the prover
-                        result & (v == proof.fri_last_value) & openings_ok
-                    }
-                },
-            )
+            // Update index for next iteration. The index of xⁿ in the next layer is obtained by
+            // dividing the current index by n. This is due to the bit-reverse ordering of the
+            // elements in the Merkle tree.
+            index /= folding_factor;
+            evaluation_point_inv = evaluation_point_inv.pow(folding_factor);
+        }
+
+        if layers_evaluations_sym.next().is_some() {
+            return None;
+        }
+
+        Some((v, openings))
     }
 
     fn reconstruct_deep_composition_poly_evaluations_for_all_queries<A>(
-        challenges: &Challenges<Self::Field, A>,
+        air: &A,
+        challenges: &Challenges<A>,
         domain: &Domain<Self::Field>,
-        proof: &StarkProof<Self::Field>,
-    ) -> DeepPolynomialEvaluations<Self::Field>
+        proof: &StarkProof<Self::Field, Self::FieldExtension, Self::Commitment>,
+    ) -> DeepPolynomialEvaluations<Self::FieldExtension>
     where
-        A: AIR<Field = Self::Field>,
+        A: AIR<Field = Self::Field, FieldExtension = Self::FieldExtension>,
     {
+        let z_power = challenges.z.pow(air.number_of_composition_poly_parts());
         let mut deep_poly_evaluations = Vec::new();
         let mut deep_poly_evaluations_sym = Vec::new();
         for (i, iota) in challenges.iotas.iter().enumerate() {
             let primitive_root =
-                &Self::Field::get_primitive_root_of_unity(domain.root_order as u64).unwrap();
+                &Self::Field::get_primitive_root_of_unity::<Self::Field>(domain.root_order as u64)
+                    .unwrap()
+                    .to_extension();
 
-            let evaluation_point = Self::query_challenge_to_evaluation_point(*iota, domain);
+            let evaluation_point =
+                Self::query_challenge_to_evaluation_point(*iota, domain).to_extension();
             deep_poly_evaluations.push(Self::reconstruct_deep_composition_poly_evaluation(
                 proof,
                 &evaluation_point,
                 primitive_root,
+                &z_power,
                 challenges,
-                &proof.deep_poly_openings[i].lde_trace_evaluations,
+                &Self::merge_trace_evaluations(&proof.deep_poly_openings[i]),
                 &proof.deep_poly_openings[i].lde_composition_poly_parts_evaluation,
             ));
 
-            let evaluation_point = Self::query_challenge_to_evaluation_point_sym(*iota, domain);
+            let evaluation_point =
+                Self::query_challenge_to_evaluation_point_sym(*iota, domain).to_extension();
             deep_poly_evaluations_sym.push(Self::reconstruct_deep_composition_poly_evaluation(
                 proof,
                 &evaluation_point,
                 primitive_root,
+                &z_power,
                 challenges,
-                &proof.deep_poly_openings_sym[i].lde_trace_evaluations,
+                &Self::merge_trace_evaluations(&proof.deep_poly_openings_sym[i]),
                 &proof.deep_poly_openings_sym[i].lde_composition_poly_parts_evaluation,
             ));
         }
         (deep_poly_evaluations, deep_poly_evaluations_sym)
     }
 
-    fn reconstruct_deep_composition_poly_evaluation<A: AIR<Field = Self::Field>>(
-        proof: &StarkProof<Self::Field>,
-        evaluation_point: &FieldElement<Self::Field>,
-        primitive_root: &FieldElement<Self::Field>,
-        challenges: &Challenges<Self::Field, A>,
-        lde_trace_evaluations: &[FieldElement<Self::Field>],
-        lde_composition_poly_parts_evaluation: &[FieldElement<Self::Field>],
-    ) -> FieldElement<Self::Field> {
+    /// Returns the evaluations of the main trace, embedded into the extension, followed by
+    /// the evaluations of the auxiliary trace and of the preprocessed columns.
+    fn merge_trace_evaluations(
+        deep_poly_opening: &DeepPolynomialOpening<Self::Field, Self::FieldExtension>,
+    ) -> Vec<FieldElement<Self::FieldExtension>> {
+        deep_poly_opening
+            .lde_trace_evaluations
+            .iter()
+            .map(|evaluation| evaluation.clone().to_extension())
+            .chain(deep_poly_opening.lde_aux_trace_evaluations.iter().cloned())
+            .chain(
+                deep_poly_opening
+                    .lde_preprocessed_evaluations
+                    .iter()
+                    .map(|evaluation| evaluation.clone().to_extension()),
+            )
+            .collect()
+    }
+
+    fn reconstruct_deep_composition_poly_evaluation<
+        A: AIR<Field = Self::Field, FieldExtension = Self::FieldExtension>,
+    >(
+        proof: &StarkProof<Self::Field, Self::FieldExtension, Self::Commitment>,
+        evaluation_point: &FieldElement<Self::FieldExtension>,
+        primitive_root: &FieldElement<Self::FieldExtension>,
+        z_power: &FieldElement<Self::FieldExtension>,
+        challenges: &Challenges<A>,
+        lde_trace_evaluations: &[FieldElement<Self::FieldExtension>],
+        lde_composition_poly_parts_evaluation: &[FieldElement<Self::FieldExtension>],
+    ) -> FieldElement<Self::FieldExtension> {
         let mut denoms_trace = (0..proof.trace_ood_frame_evaluations.n_rows())
             .map(|row_idx| evaluation_point - &challenges.z * primitive_root.pow(row_idx as u64))
-            .collect::<Vec<FieldElement<Self::Field>>>();
+            .collect::<Vec<FieldElement<Self::FieldExtension>>>();
         FieldElement::inplace_batch_inverse(&mut denoms_trace).unwrap();
 
         let trace_term = (0..proof.trace_ood_frame_evaluations.n_cols())
@@ -638,10 +838,7 @@ pub trait IsStarkVerifier {
                 trace_terms + trace_i
             });
 
-        let number_of_parts = lde_composition_poly_parts_evaluation.len();
-        let z_pow = &challenges.z.pow(number_of_parts);
-
-        let denom_composition = (evaluation_point - z_pow).inv().unwrap();
+        let denom_composition = (evaluation_point - z_power).inv().unwrap();
         let mut h_terms = FieldElement::zero();
         for (j, h_i_upsilon) in lde_composition_poly_parts_evaluation.iter().enumerate() {
             let h_i_zpower = &proof.composition_poly_parts_ood_evaluation[j];
@@ -653,15 +850,123 @@ pub trait IsStarkVerifier {
         trace_term + h_terms
     }
 
+    /// Verifies a proof of an AIR without preprocessed columns. Proofs of AIRs with
+    /// preprocessed columns are rejected: they are verified with `verify_with_key`.
     fn verify<A>(
-        proof: &StarkProof<Self::Field>,
+        proof: &StarkProof<Self::Field, Self::FieldExtension, Self::Commitment>,
         pub_input: &A::PublicInputs,
         proof_options: &ProofOptions,
-        mut transcript: impl IsStarkTranscript<Self::Field>,
+        transcript: impl IsStarkTranscript<Self::FieldExtension>,
     ) -> bool
     where
-        A: AIR<Field = Self::Field>,
+        A: AIR<Field = Self::Field, FieldExtension = Self::FieldExtension>,
         FieldElement<Self::Field>: Serializable,
+        FieldElement<Self::FieldExtension>: Serializable,
+    {
+        Self::verify_with_domain::<A>(proof, pub_input, proof_options, None, transcript, None)
+    }
+
+    /// Verifies many proofs of the same AIR generated with the same options, returning
+    /// whether each proof is valid. `pub_inputs` and `transcripts` hold the public inputs
+    /// and the initial transcript of each proof, in the order of `proofs`. If the AIR has
+    /// preprocessed columns, `verification_key` is used for all the proofs, which then
+    /// need the trace length it was built for.
+    ///
+    /// The domains of the trace and of the LDE are computed once for each trace length and
+    /// shared by all the proofs of that length, which are verified in parallel with the
+    /// `parallel` feature. Every other check is done for each proof with the challenges of
+    /// its own transcript: since the queries of each proof are sampled from its own
+    /// commitments, they cannot be shared without giving up the soundness of the proofs
+    /// that are checked individually.
+    fn verify_batch<A, T>(
+        proofs: &[StarkProof<Self::Field, Self::FieldExtension, Self::Commitment>],
+        pub_inputs: &[A::PublicInputs],
+        proof_options: &ProofOptions,
+        verification_key: Option<&VerificationKey<Self::Commitment>>,
+        transcripts: Vec<T>,
+    ) -> Vec<bool>
+    where
+        A: AIR<Field = Self::Field, FieldExtension = Self::FieldExtension>,
+        A::PublicInputs: Sync,
+        T: IsStarkTranscript<Self::FieldExtension> + Send,
+        Self::Commitment: Send + Sync,
+        FieldElement<Self::Field>: Serializable + Send + Sync,
+        FieldElement<Self::FieldExtension>: Serializable + Send + Sync,
+    {
+        if pub_inputs.len() != proofs.len() || transcripts.len() != proofs.len() {
+            error!("Wrong number of public inputs or transcripts");
+            return vec![false; proofs.len()];
+        }
+
+        let mut domains = HashMap::new();
+        for (proof, pub_input) in proofs.iter().zip(pub_inputs) {
+            domains.entry(proof.trace_length).or_insert_with(|| {
+                Domain::new(&A::new(proof.trace_length, pub_input, proof_options))
+            });
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        let proofs_iter = proofs.iter().zip(pub_inputs).zip(transcripts);
+        #[cfg(feature = "parallel")]
+        let proofs_iter = proofs
+            .par_iter()
+            .zip(pub_inputs.par_iter())
+            .zip(transcripts.into_par_iter());
+
+        proofs_iter
+            .map(|((proof, pub_input), transcript)| {
+                Self::verify_with_domain::<A>(
+                    proof,
+                    pub_input,
+                    proof_options,
+                    verification_key,
+                    transcript,
+                    domains.get(&proof.trace_length),
+                )
+            })
+            .collect()
+    }
+
+    /// Verifies a proof of an AIR with preprocessed columns, checking their openings against
+    /// the root of `verification_key` instead of committing to the columns again. Fails if
+    /// the key was not built for the trace length and the options of the proof.
+    fn verify_with_key<A>(
+        proof: &StarkProof<Self::Field, Self::FieldExtension, Self::Commitment>,
+        pub_input: &A::PublicInputs,
+        proof_options: &ProofOptions,
+        verification_key: &VerificationKey<Self::Commitment>,
+        transcript: impl IsStarkTranscript<Self::FieldExtension>,
+    ) -> bool
+    where
+        A: AIR<Field = Self::Field, FieldExtension = Self::FieldExtension>,
+        FieldElement<Self::Field>: Serializable,
+        FieldElement<Self::FieldExtension>: Serializable,
+    {
+        Self::verify_with_domain::<A>(
+            proof,
+            pub_input,
+            proof_options,
+            Some(verification_key),
+            transcript,
+            None,
+        )
+    }
+
+    /// Verifies `proof` with `domain`, which must be the domain of its trace length and of
+    /// `proof_options`, or computes the domain if it is not given. Fails if the AIR has
+    /// preprocessed columns and no `verification_key` is given.
+    fn verify_with_domain<A>(
+        proof: &StarkProof<Self::Field, Self::FieldExtension, Self::Commitment>,
+        pub_input: &A::PublicInputs,
+        proof_options: &ProofOptions,
+        verification_key: Option<&VerificationKey<Self::Commitment>>,
+        mut transcript: impl IsStarkTranscript<Self::FieldExtension>,
+        domain: Option<&Domain<Self::Field>>,
+    ) -> bool
+    where
+        A: AIR<Field = Self::Field, FieldExtension = Self::FieldExtension>,
+        FieldElement<Self::Field>: Serializable,
+        FieldElement<Self::FieldExtension>: Serializable,
     {
         // Verify there are enough queries
         if proof.query_list.len() < proof_options.fri_number_of_queries {
@@ -674,19 +979,93 @@ pub trait IsStarkVerifier {
         let timer1 = Instant::now();
 
         let air = A::new(proof.trace_length, pub_input, proof_options);
-        let domain = Domain::new(&air);
+        if !has_valid_periodic_columns(&air) {
+            error!("Invalid periods of the periodic columns");
+            return false;
+        }
+
+        // The number of FRI layers is fixed by the folding factor, the degree bound of the
+        // remainder and the degree bound of the DEEP composition polynomial, which is larger
+        // in zero-knowledge mode.
+        if !ProofOptions::SUPPORTED_FRI_FOLDING_FACTORS.contains(&proof_options.fri_folding_factor)
+        {
+            error!("Unsupported FRI folding factor");
+            return false;
+        }
+        let number_of_fri_layers =
+            air.deep_composition_poly_degree_bound().trailing_zeros() as usize;
+        let remainder_degree_bound = fri::remainder_degree_bound(
+            number_of_fri_layers,
+            proof_options.fri_max_remainder_degree,
+        );
+        let layers_folding_factors = fri::layers_folding_factors(
+            number_of_fri_layers,
+            proof_options.fri_folding_factor as usize,
+            remainder_degree_bound,
+        );
+        if proof.fri_layers_merkle_roots.len() != layers_folding_factors.len() {
+            error!("Wrong number of FRI layers");
+            return false;
+        }
+        if proof.fri_remainder_coefficients.len() != remainder_degree_bound {
+            error!("Wrong number of coefficients of the FRI remainder");
+            return false;
+        }
+
+        // The number of parts is fixed by the degrees of the transition constraints. A
+        // zero-knowledge proof also carries the random mask.
+        if proof.composition_poly_parts_ood_evaluation.len()
+            != air.number_of_composition_poly_parts() + usize::from(proof_options.zero_knowledge)
+        {
+            error!("Wrong number of composition polynomial parts");
+            return false;
+        }
+
+        // The commitment to the preprocessed columns is only read from the key: computing
+        // it here would take the verifier as long as committing to the columns.
+        match verification_key {
+            Some(verification_key) if !verification_key.matches(&air) => {
+                error!("The verification key does not match the AIR");
+                return false;
+            }
+            None if air.number_preprocessed_columns() > 0 => {
+                error!("Missing verification key");
+                return false;
+            }
+            _ => {}
+        }
+        let preprocessed_root = verification_key.map(|verification_key| &verification_key.root);
+        if preprocessed_root.is_some() != proof.lde_preprocessed_merkle_proof.is_some() {
+            error!("Wrong openings of the preprocessed columns");
+            return false;
+        }
+
+        let computed_domain;
+        let domain = match domain {
+            Some(domain) => domain,
+            None => {
+                computed_domain = Domain::new(&air);
+                &computed_domain
+            }
+        };
 
         let challenges = Self::step_1_replay_rounds_and_recover_challenges(
             &air,
             proof,
-            &domain,
+            domain,
+            preprocessed_root,
             &mut transcript,
         );
 
         // verify grinding
         let security_bits = air.context().proof_options.grinding_factor;
         if security_bits > 0
-            && !grinding::is_valid_nonce(&challenges.grinding_seed, proof.nonce, security_bits)
+            && !grinding::is_valid_nonce(
+                &challenges.grinding_seed,
+                proof.nonce,
+                security_bits,
+                air.context().proof_options.grinding_hash,
+            )
         {
             error!("Grinding factor not satisfied");
             return false;
@@ -702,7 +1081,7 @@ pub trait IsStarkVerifier {
         #[cfg(feature = "instruments")]
         let timer2 = Instant::now();
 
-        if !Self::step_2_verify_claimed_composition_polynomial(&air, proof, &domain, &challenges) {
+        if !Self::step_2_verify_claimed_composition_polynomial(&air, proof, domain, &challenges) {
             error!("Composition Polynomial verification failed");
             return false;
         }
@@ -717,7 +1096,7 @@ pub trait IsStarkVerifier {
         #[cfg(feature = "instruments")]
         let timer3 = Instant::now();
 
-        if !Self::step_3_verify_fri(proof, &domain, &challenges) {
+        if !Self::step_3_verify_fri(&air, proof, domain, &challenges) {
             error!("FRI verification failed");
             return false;
         }
@@ -733,7 +1112,12 @@ pub trait IsStarkVerifier {
         let timer4 = Instant::now();
 
         #[allow(clippy::let_and_return)]
-        if !Self::step_4_verify_trace_and_composition_openings(&air, proof, &challenges) {
+        if !Self::step_4_verify_trace_and_composition_openings(
+            proof,
+            domain,
+            &challenges,
+            preprocessed_root,
+        ) {
             error!("DEEP Composition Polynomial verification failed");
             return false;
         }